@@ -1,17 +1,19 @@
 use core::fmt;
 
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 use concordium_cis2::{
     BurnEvent, Cis2Event, IsTokenAmount, IsTokenId, MintEvent, OperatorUpdate, TokenMetadataEvent,
     TransferEvent, UpdateOperatorEvent,
 };
 use concordium_protocols::concordium_cis2_security::*;
+use concordium_protocols::concordium_cis3::NonceEvent;
 use concordium_rust_sdk::base::contracts_common::{Deserial, Serial};
 use concordium_rust_sdk::base::smart_contracts::ContractEvent;
 use diesel::Connection;
 use rust_decimal::Decimal;
 use shared::db::cis2_security::{
-    Agent, Compliance, IdentityRegistry, Operator, RecoveryRecord, Token, TokenHolder,
+    Agent, Compliance, Governance, IdentityRegistry, Operator, PermitNonce, Proposal,
+    ProposalStatus, RecoveryRecord, RecoveryStatus, Snapshot, Token, TokenDocument, TokenHolder,
     TokenHolderBalanceUpdate, TokenHolderBalanceUpdateType,
 };
 use shared::db_shared::DbConn;
@@ -450,11 +452,65 @@ where
             new_account,
         }) => {
             let updated_rows = conn.transaction(|conn| {
-                RecoveryRecord::new(contract, &lost_account, &new_account).insert(conn)?;
+                RecoveryRecord {
+                    cis2_address:      contract,
+                    holder_address:    lost_account.to_string(),
+                    recovered_address: new_account.to_string(),
+                    status:            RecoveryStatus::Recovered,
+                    execute_after:     None,
+                    update_time:       block_time,
+                }
+                .upsert(conn)?;
                 TokenHolder::replace(conn, contract, &lost_account, &new_account)
             })?;
             info!("account recovery, {} token ids updated", updated_rows);
         }
+        Cis2SecurityEvent::RecoveryInitiated(RecoveryInitiatedEvent {
+            lost_account,
+            new_account,
+            execute_after,
+        }) => {
+            RecoveryRecord {
+                cis2_address:      contract,
+                holder_address:    lost_account.to_string(),
+                recovered_address: new_account.to_string(),
+                status:            RecoveryStatus::Initiated,
+                execute_after:     DateTime::from_timestamp_millis(
+                    execute_after.timestamp_millis() as i64,
+                )
+                .map(|t| t.naive_utc()),
+                update_time:       block_time,
+            }
+            .upsert(conn)?;
+            info!(
+                "account recovery initiated, {} to {}",
+                lost_account, new_account
+            );
+        }
+        Cis2SecurityEvent::RecoveryCancelled(RecoverEvent {
+            lost_account,
+            new_account,
+        }) => {
+            RecoveryRecord {
+                cis2_address:      contract,
+                holder_address:    lost_account.to_string(),
+                recovered_address: new_account.to_string(),
+                status:            RecoveryStatus::Cancelled,
+                execute_after:     None,
+                update_time:       block_time,
+            }
+            .upsert(conn)?;
+            info!(
+                "account recovery cancelled, {} to {}",
+                lost_account, new_account
+            );
+        }
+        Cis2SecurityEvent::RecoveryDelayUpdated(RecoveryDelayUpdatedEvent(recovery_delay)) => {
+            info!(
+                "account recovery delay updated to {} ms",
+                recovery_delay.millis()
+            );
+        }
         Cis2SecurityEvent::TokenFrozen(TokenFrozen {
             address,
             amount,
@@ -537,6 +593,142 @@ where
                 amount_decimal, token_id_decimal, address_str
             );
         }
+        Cis2SecurityEvent::Nonce(NonceEvent { account, nonce }) => {
+            PermitNonce {
+                cis2_address:    contract,
+                account_address: account.to_string(),
+                nonce:           Decimal::from(nonce + 1),
+                update_time:     block_time,
+            }
+            .upsert(conn)?;
+            info!("Permit nonce {} used by {}", nonce, account);
+        }
+        Cis2SecurityEvent::Snapshot(SnapshotEvent { snapshot_id }) => {
+            let balances = Snapshot {
+                cis2_address: contract,
+                snapshot_id: Decimal::from(snapshot_id),
+                block_height,
+                txn_index,
+                create_time: block_time,
+            }
+            .insert(conn)?;
+            info!(
+                "Snapshot {} taken, {} holder balances recorded",
+                snapshot_id, balances
+            );
+        }
+        Cis2SecurityEvent::GovernanceUpdated(GovernanceUpdatedEvent { governance }) => {
+            match governance {
+                Some(governance) => {
+                    Governance {
+                        cis2_address: contract,
+                        approvers:    governance
+                            .approvers
+                            .iter()
+                            .map(|a| Some(a.to_string()))
+                            .collect(),
+                        threshold:    governance.threshold as i32,
+                        delay_millis: Decimal::from(governance.delay.millis()),
+                        update_time:  block_time,
+                    }
+                    .upsert(conn)?;
+                    info!(
+                        "Governance updated, {} of {} approvers",
+                        governance.threshold,
+                        governance.approvers.len()
+                    );
+                }
+                None => {
+                    Governance::delete(conn, contract)?;
+                    info!("Governance removed");
+                }
+            }
+        }
+        Cis2SecurityEvent::ProposalCreated(ProposalCreatedEvent {
+            proposal_id,
+            proposer,
+            entrypoint,
+            execute_after,
+        }) => {
+            Proposal {
+                cis2_address:  contract,
+                proposal_id:   Decimal::from(proposal_id),
+                proposer:      proposer.to_string(),
+                entrypoint:    entrypoint.to_string(),
+                execute_after: DateTime::from_timestamp_millis(
+                    execute_after.timestamp_millis() as i64
+                )
+                .map(|t| t.naive_utc())
+                .unwrap_or(block_time),
+                approvals:     vec![],
+                status:        ProposalStatus::Pending,
+                create_time:   block_time,
+                update_time:   block_time,
+            }
+            .insert(conn)?;
+            info!("Proposal {} created to invoke {}", proposal_id, entrypoint);
+        }
+        Cis2SecurityEvent::ProposalApproved(ProposalEvent {
+            proposal_id,
+            approver,
+        }) => {
+            let mut proposal = find_proposal(conn, contract, proposal_id)?;
+            proposal.approvals.push(Some(approver.to_string()));
+            proposal.update_time = block_time;
+            proposal.update(conn)?;
+            info!("Proposal {} approved by {}", proposal_id, approver);
+        }
+        Cis2SecurityEvent::ProposalExecuted(ProposalEvent {
+            proposal_id,
+            approver,
+        }) => {
+            let mut proposal = find_proposal(conn, contract, proposal_id)?;
+            proposal.status = ProposalStatus::Executed;
+            proposal.update_time = block_time;
+            proposal.update(conn)?;
+            info!("Proposal {} executed by {}", proposal_id, approver);
+        }
+        Cis2SecurityEvent::ProposalCancelled(ProposalEvent {
+            proposal_id,
+            approver,
+        }) => {
+            let mut proposal = find_proposal(conn, contract, proposal_id)?;
+            proposal.status = ProposalStatus::Cancelled;
+            proposal.update_time = block_time;
+            proposal.update(conn)?;
+            info!("Proposal {} cancelled by {}", proposal_id, approver);
+        }
+        Cis2SecurityEvent::DocumentUpdated(DocumentUpdatedEvent {
+            token_id,
+            name,
+            document,
+        }) => {
+            let token_id = token_id.to_decimal();
+            match document {
+                Some(document) => {
+                    TokenDocument {
+                        cis2_address: contract,
+                        token_id,
+                        name: name.clone(),
+                        uri: document.uri,
+                        content_hash: hex::encode(document.content_hash),
+                        document_time: DateTime::from_timestamp_millis(
+                            document.timestamp.timestamp_millis() as i64,
+                        )
+                        .map(|t| t.naive_utc())
+                        .unwrap_or(block_time),
+                        block_height,
+                        txn_index,
+                    }
+                    .upsert(conn)?;
+                    info!("Document {} of token_id {} set", name, token_id);
+                }
+                None => {
+                    TokenDocument::delete(conn, contract, token_id, &name)?;
+                    info!("Document {} of token_id {} removed", name, token_id);
+                }
+            }
+        }
         Cis2SecurityEvent::Cis2(e) => process_events_cis2(
             conn,
             block_height,
@@ -551,6 +743,18 @@ where
     Ok(())
 }
 
+fn find_proposal(
+    conn: &mut DbConn,
+    contract: Decimal,
+    proposal_id: u64,
+) -> Result<Proposal, ProcessorError> {
+    let proposal_id = Decimal::from(proposal_id);
+    Proposal::find(conn, contract, proposal_id)?.ok_or(ProcessorError::Cis2ProposalNotFound {
+        contract,
+        proposal_id,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use concordium_cis2::{TokenAmountU64, TokenAmountU8};
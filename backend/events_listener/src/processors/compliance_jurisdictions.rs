@@ -0,0 +1,94 @@
+use chrono::NaiveDateTime;
+use concordium_rust_sdk::base::hashes::ModuleReference;
+use concordium_rust_sdk::base::smart_contracts::{OwnedContractName, WasmModule};
+use concordium_rust_sdk::types::smart_contracts::ContractEvent;
+use concordium_rust_sdk::types::ContractAddress;
+use concordium_rwa_compliance::compliance_modules::jurisdictions::types::{
+    Event, Jurisdiction, ListType,
+};
+use rust_decimal::Decimal;
+use shared::db::compliance::{
+    ComplianceJurisdiction, ComplianceJurisdictionRule, ComplianceListType,
+};
+use shared::db_shared::DbConn;
+use tracing::{info, instrument, trace};
+
+use crate::processors::cis2_utils::ContractAddressToDecimal;
+use crate::processors::ProcessorError;
+
+pub fn module_ref() -> ModuleReference {
+    WasmModule::from_slice(include_bytes!(
+        "../../../../contracts/compliance/contract.wasm.v1"
+    ))
+    .expect("Failed to parse compliance module")
+    .get_module_ref()
+}
+
+pub fn contract_name() -> OwnedContractName {
+    OwnedContractName::new_unchecked("init_rwa_compliance_module_jurisdictions".to_string())
+}
+
+/// Processes the events of the rwa-compliance-module-jurisdictions contract.
+/// Keeps the allow and block lists of countries of the module in sync.
+#[allow(clippy::too_many_arguments)]
+#[instrument(
+    name="compliance_jurisdictions",
+    skip_all,
+    fields(contract = %contract, events = events.len())
+)]
+pub fn process_events(
+    conn: &mut DbConn,
+    _block_height: Decimal,
+    block_time: NaiveDateTime,
+    _txn_index: Decimal,
+    _txn_sender: &str,
+    _txn_instigator: &str,
+    contract: &ContractAddress,
+    events: &[ContractEvent],
+) -> Result<(), ProcessorError> {
+    for event in events {
+        let parsed_event = event.parse::<Event>().expect("Failed to parse event");
+        trace!("Event details: {:#?}", parsed_event);
+
+        match parsed_event {
+            Event::AgentAdded(e) => {
+                info!("Agent: {} added", e.agent.to_string());
+            }
+            Event::AgentRemoved(e) => {
+                info!("Agent: {} removed", e.agent.to_string());
+            }
+            Event::ListUpdated(e) => {
+                let jurisdiction = match e.jurisdiction {
+                    Jurisdiction::Nationality => ComplianceJurisdiction::Nationality,
+                    Jurisdiction::CountryOfResidence => ComplianceJurisdiction::CountryOfResidence,
+                };
+                let list_type = match e.list {
+                    ListType::Allow => ComplianceListType::Allow,
+                    ListType::Block => ComplianceListType::Block,
+                };
+                for country in e.add {
+                    ComplianceJurisdictionRule {
+                        module_address: contract.to_decimal(),
+                        jurisdiction,
+                        list_type,
+                        country,
+                        create_time: block_time,
+                    }
+                    .insert(conn)?;
+                }
+                for country in e.remove {
+                    ComplianceJurisdictionRule::delete(
+                        conn,
+                        contract.to_decimal(),
+                        jurisdiction,
+                        list_type,
+                        &country,
+                    )?;
+                }
+                info!("{:?} {:?} list updated", jurisdiction, list_type);
+            }
+        }
+    }
+
+    Ok(())
+}
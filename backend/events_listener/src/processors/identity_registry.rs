@@ -1,9 +1,9 @@
 use chrono::NaiveDateTime;
 use concordium_rust_sdk::types::smart_contracts::ContractEvent;
 use concordium_rust_sdk::types::ContractAddress;
-use concordium_rwa_identity_registry::types::Event;
+use concordium_rwa_identity_registry::types::{Event, InvalidReason};
 use rust_decimal::Decimal;
-use shared::db::identity_registry::{Agent, Identity, Issuer};
+use shared::db::identity_registry::{Agent, Identity, IdentityInvalidReason, Issuer};
 use shared::db_shared::DbConn;
 use tracing::{info, instrument, trace};
 
@@ -69,6 +69,47 @@ pub fn process_events(
                 Issuer::delete(conn, contract.to_decimal(), e.issuer.to_decimal())?;
                 info!("Issuer: {} removed", e.issuer.to_string());
             }
+            Event::IdentityInvalidated(e) => {
+                let reason = match e.reason {
+                    InvalidReason::NotYetValid => IdentityInvalidReason::NotYetValid,
+                    InvalidReason::Expired => IdentityInvalidReason::Expired,
+                    InvalidReason::CredentialNotActive(_) => {
+                        IdentityInvalidReason::CredentialNotActive
+                    }
+                };
+                Identity::invalidate(conn, contract.to_decimal(), &e.address, reason, block_time)?;
+                info!(
+                    "Identity: {} invalidated, reason: {:?}",
+                    e.address.to_string(),
+                    e.reason
+                );
+            }
+            Event::IdentityRevalidated(e) => {
+                Identity::revalidate(conn, contract.to_decimal(), &e.address)?;
+                info!("Identity: {} revalidated", e.address.to_string());
+            }
+            Event::AddressLinked(e) => {
+                Identity::link(
+                    conn,
+                    contract.to_decimal(),
+                    &e.identity,
+                    &e.address,
+                    block_time,
+                )?;
+                info!(
+                    "Address: {} linked to identity: {}",
+                    e.address.to_string(),
+                    e.identity.to_string()
+                );
+            }
+            Event::AddressUnlinked(e) => {
+                Identity::delete(conn, contract.to_decimal(), &e.address)?;
+                info!(
+                    "Address: {} unlinked from identity: {}",
+                    e.address.to_string(),
+                    e.identity.to_string()
+                );
+            }
         }
     }
 
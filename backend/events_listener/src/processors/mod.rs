@@ -12,6 +12,7 @@ use diesel::Connection;
 use rust_decimal::Decimal;
 use tracing::{debug, info, instrument, trace, warn};
 pub mod cis2_utils;
+mod compliance_jurisdictions;
 mod identity_registry;
 mod nft_multi_rewarded;
 mod offchain_rewards;
@@ -92,8 +93,29 @@ pub enum ProcessorError {
     SecurityMintFundContractNotFound { contract: Decimal },
     #[error("Trade Contract not found: {contract}")]
     TradeContractNotFound { contract: Decimal },
+    #[error("Order not found: {contract}, order_id: {order_id}")]
+    OrderNotFound {
+        contract: Decimal,
+        order_id: Decimal,
+    },
     #[error("Cis2Agent not found: {contract}, agent: {agent}")]
     Cis2AgentNotFound { contract: Decimal, agent: String },
+    #[error("Cis2Proposal not found: {contract}, proposal_id: {proposal_id}")]
+    Cis2ProposalNotFound {
+        contract:    Decimal,
+        proposal_id: Decimal,
+    },
+    #[error(
+        "Yield pool not found: {contract}, token_contract: {token_contract}, token_id: \
+         {token_id}, yield_contract: {yield_contract}, yield_token_id: {yield_token_id}"
+    )]
+    YieldPoolNotFound {
+        contract:       Decimal,
+        token_contract: Decimal,
+        token_id:       Decimal,
+        yield_contract: Decimal,
+        yield_token_id: Decimal,
+    },
 }
 
 pub type ProcessorFnType = fn(
@@ -145,6 +167,12 @@ impl Processors {
             ProcessorType::IdentityRegistry,
             identity_registry::process_events as ProcessorFnType,
         );
+        processors.insert(
+            compliance_jurisdictions::module_ref(),
+            compliance_jurisdictions::contract_name(),
+            ProcessorType::ComplianceJurisdictions,
+            compliance_jurisdictions::process_events as ProcessorFnType,
+        );
         processors.insert(
             nft_multi_rewarded::module_ref(),
             nft_multi_rewarded::contract_name(),
@@ -1,16 +1,17 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 use concordium_rust_sdk::base::hashes::ModuleReference;
 use concordium_rust_sdk::base::smart_contracts::{ContractEvent, OwnedContractName, WasmModule};
 use concordium_rust_sdk::types::ContractAddress;
 use diesel::Connection;
 use rust_decimal::Decimal;
 use security_mint_fund::types::{
-    AgentRole, Event, FundAddedEvent, InvestedEvent, UpdateFundState, UpdateFundStateParams,
+    AgentRole, Event, FundAddedEvent, FundInvestors, IdentityAttributeRequirement, InvestedEvent,
+    UpdateFundAllowlistParams, UpdateFundState, UpdateFundStateParams, VestingReleasedEvent,
 };
 use shared::db::cis2_security::Agent;
 use shared::db::security_mint_fund::{
     InvestmentRecord, InvestmentRecordType, Investor, SecurityMintFund, SecurityMintFundContract,
-    SecurityMintFundState,
+    SecurityMintFundInvestorAccess, SecurityMintFundState,
 };
 use shared::db_shared::DbConn;
 use tracing::{info, instrument, warn};
@@ -104,7 +105,18 @@ pub fn process_events(
                 rate,
                 security_token,
                 token,
+                currency_token,
+                soft_cap,
+                hard_cap,
+                deadline,
+                funds_receiver: _,
+                cooling_off_period: _,
+                min_investment,
+                max_investment,
+                investors,
+                vesting,
             }) => {
+                let (investor_access, identity_attribute) = to_db_investor_access(&investors);
                 conn.transaction::<_, ProcessorError, _>(|conn| {
                     let contract = SecurityMintFundContract::find(conn, contract.to_decimal())?
                         .ok_or(ProcessorError::SecurityMintFundContractNotFound {
@@ -116,8 +128,8 @@ pub fn process_events(
                         token_contract_address: token.contract.to_decimal(),
                         investment_token_id: security_token.id.to_decimal(),
                         investment_token_contract_address: security_token.contract.to_decimal(),
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id: currency_token.id.to_decimal(),
+                        currency_token_contract_address: currency_token.contract.to_decimal(),
                         currency_amount: Decimal::ZERO,
                         token_amount: Decimal::ZERO,
                         receiver_address: None,
@@ -126,18 +138,40 @@ pub fn process_events(
                         fund_state: SecurityMintFundState::Open,
                         create_time: block_time,
                         update_time: block_time,
+                        soft_cap: Some(soft_cap.to_decimal()),
+                        hard_cap: Some(hard_cap.to_decimal()),
+                        deadline: DateTime::from_timestamp_millis(
+                            deadline.timestamp_millis() as i64
+                        )
+                        .map(|t| t.naive_utc()),
+                        min_investment: min_investment.map(|amount| amount.to_decimal()),
+                        max_investment: max_investment.map(|amount| amount.to_decimal()),
+                        investor_access,
+                        identity_registry_address: identity_attribute
+                            .map(|attribute| attribute.identity_registry.to_decimal()),
+                        identity_attribute_tag: identity_attribute
+                            .map(|attribute| attribute.tag.into()),
+                        identity_attribute_value: identity_attribute
+                            .map(|attribute| attribute.value.clone()),
+                        vesting_lock_up_millis: vesting
+                            .map(|vesting| vesting.lock_up.millis().into()),
+                        vesting_period_millis: vesting
+                            .map(|vesting| vesting.vesting_period.millis().into()),
                     }
                     .insert(conn)?;
                     Ok(())
                 })?;
 
                 info!(
-                    "Fund added: {}/{}, rate: {}, security token: {}/{}, contract: {}",
+                    "Fund added: {}/{}, rate: {}, security token: {}/{}, soft cap: {}, hard cap: \
+                     {}, contract: {}",
                     token.id.to_decimal(),
                     token.contract.to_decimal(),
                     rate.to_decimal(),
                     security_token.id.to_decimal(),
                     security_token.contract.to_decimal(),
+                    soft_cap.to_decimal(),
+                    hard_cap.to_decimal(),
                     contract.to_decimal()
                 );
             }
@@ -155,6 +189,56 @@ pub fn process_events(
                     contract.to_decimal()
                 );
             }
+            Event::FundAllowlistUpdated(UpdateFundAllowlistParams {
+                security_token,
+                add,
+                remove,
+            }) => {
+                info!(
+                    "Fund allowlist updated: {}/{}, added: {}, removed: {}, contract: {}",
+                    security_token.id.to_decimal(),
+                    security_token.contract.to_decimal(),
+                    add.len(),
+                    remove.len(),
+                    contract.to_decimal()
+                );
+            }
+            Event::VestingReleased(VestingReleasedEvent {
+                security_token,
+                investor,
+                security_amount,
+            }) => {
+                // The un frozen balance is indexed from the un freeze event of the security token contract
+                conn.transaction::<_, ProcessorError, _>(|conn| {
+                    Investor::find(
+                        conn,
+                        contract.to_decimal(),
+                        security_token.id.to_decimal(),
+                        security_token.contract.to_decimal(),
+                        &investor.to_string(),
+                    )?
+                    .map(|record| Investor {
+                        token_amount_released: record.token_amount_released
+                            + security_amount.to_decimal(),
+                        update_time: block_time,
+                        ..record
+                    })
+                    .ok_or(ProcessorError::InvestorNotFound {
+                        investor: investor.to_string(),
+                        contract: contract.to_decimal(),
+                    })?
+                    .update(conn)?;
+                    Ok(())
+                })?;
+                info!(
+                    "Vesting released: {}/{}, investor: {}, amount: {}, contract: {}",
+                    security_token.id.to_decimal(),
+                    security_token.contract.to_decimal(),
+                    investor,
+                    security_amount.to_decimal(),
+                    contract.to_decimal()
+                );
+            }
             Event::FundStateUpdated(UpdateFundStateParams {
                 security_token,
                 state: fund_state,
@@ -201,6 +285,17 @@ pub fn process_events(
                         .ok_or(ProcessorError::SecurityMintFundContractNotFound {
                             contract: contract.to_decimal(),
                         })?;
+                    let fund = SecurityMintFund::find(
+                        conn,
+                        contract.contract_address,
+                        security_token.id.to_decimal(),
+                        security_token.contract.to_decimal(),
+                    )?
+                    .ok_or(ProcessorError::FundNotFound {
+                        contract: contract.contract_address,
+                        security_token_id: security_token.id.to_decimal(),
+                        security_token_contract_address: security_token.contract.to_decimal(),
+                    })?;
                     let investor = Investor::find(
                         conn,
                         contract.contract_address,
@@ -224,11 +319,12 @@ pub fn process_events(
                         currency_amount: currency_amount.to_decimal(),
                         token_amount: security_amount.to_decimal(),
                         currency_amount_total: currency_amount.to_decimal(),
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id: fund.currency_token_id,
+                        currency_token_contract_address: fund.currency_token_contract_address,
                         token_amount_total: 0.into(),
                         create_time: block_time,
                         update_time: block_time,
+                        token_amount_released: 0.into(),
                     })
                     .upsert(conn)?;
 
@@ -239,8 +335,8 @@ pub fn process_events(
                         contract_address: contract.contract_address,
                         investment_token_id: security_token.id.to_decimal(),
                         investment_token_contract_address: security_token.contract.to_decimal(),
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id: fund.currency_token_id,
+                        currency_token_contract_address: fund.currency_token_contract_address,
                         investor: investor.investor.to_string(),
                         currency_amount: currency_amount.to_decimal(),
                         token_amount: security_amount.to_decimal(),
@@ -251,23 +347,12 @@ pub fn process_events(
                     }
                     .insert(conn)?;
 
-                    SecurityMintFund::find(
-                        conn,
-                        contract.contract_address,
-                        security_token.id.to_decimal(),
-                        security_token.contract.to_decimal(),
-                    )?
-                    .map(|mut fund| {
-                        fund.currency_amount += currency_amount.to_decimal();
-                        fund.token_amount += security_amount.to_decimal();
-                        fund.update_time = block_time;
-                        fund
-                    })
-                    .ok_or(ProcessorError::FundNotFound {
-                        contract: contract.contract_address,
-                        security_token_id: security_token.id.to_decimal(),
-                        security_token_contract_address: security_token.contract.to_decimal(),
-                    })?
+                    SecurityMintFund {
+                        currency_amount: fund.currency_amount + currency_amount.to_decimal(),
+                        token_amount: fund.token_amount + security_amount.to_decimal(),
+                        update_time: block_time,
+                        ..fund
+                    }
                     .update(conn)?;
 
                     Ok(())
@@ -320,23 +405,25 @@ pub fn process_events(
                     })?
                     .update(conn)?;
 
-                    let _ = SecurityMintFund::find(
+                    let fund = SecurityMintFund::find(
                         conn,
                         contract.contract_address,
                         security_token_id,
                         security_token_contract_address,
                     )?
-                    .map(|fund| SecurityMintFund {
-                        currency_amount: fund.currency_amount - currency_amount,
-                        token_amount: fund.token_amount - security_amount,
-                        update_time: block_time,
-                        ..fund
-                    })
                     .ok_or(ProcessorError::FundNotFound {
                         security_token_id,
                         security_token_contract_address,
                         contract: contract.contract_address,
-                    })?
+                    })?;
+                    let (currency_token_id, currency_token_contract_address) =
+                        (fund.currency_token_id, fund.currency_token_contract_address);
+                    SecurityMintFund {
+                        currency_amount: fund.currency_amount - currency_amount,
+                        token_amount: fund.token_amount - security_amount,
+                        update_time: block_time,
+                        ..fund
+                    }
                     .update(conn)?;
 
                     InvestmentRecord {
@@ -347,8 +434,8 @@ pub fn process_events(
                         investor: investor.investor.to_string(),
                         investment_token_id: security_token_id,
                         investment_token_contract_address: security_token_contract_address,
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id,
+                        currency_token_contract_address,
                         currency_amount,
                         token_amount: security_amount,
                         currency_amount_balance: investor.currency_amount,
@@ -371,7 +458,7 @@ pub fn process_events(
                     security_amount
                 );
             }
-            Event::InvestmentClaimed(InvestedEvent {
+            Event::InvestmentWithdrawn(InvestedEvent {
                 currency_amount,
                 security_amount,
                 security_token,
@@ -398,7 +485,7 @@ pub fn process_events(
                     .map(|investor| Investor {
                         currency_amount: investor.currency_amount - currency_amount,
                         token_amount: investor.token_amount - security_amount,
-                        token_amount_total: investor.token_amount_total + security_amount,
+                        currency_amount_total: investor.currency_amount_total - currency_amount,
                         update_time: block_time,
                         ..investor
                     })
@@ -408,23 +495,115 @@ pub fn process_events(
                     })?
                     .update(conn)?;
 
-                    SecurityMintFund::find(
+                    let fund = SecurityMintFund::find(
                         conn,
                         contract.contract_address,
                         security_token_id,
                         security_token_contract_address,
                     )?
-                    .map(|fund| SecurityMintFund {
+                    .ok_or(ProcessorError::FundNotFound {
+                        security_token_id,
+                        security_token_contract_address,
+                        contract: contract.contract_address,
+                    })?;
+                    let (currency_token_id, currency_token_contract_address) =
+                        (fund.currency_token_id, fund.currency_token_contract_address);
+                    SecurityMintFund {
                         currency_amount: fund.currency_amount - currency_amount,
                         token_amount: fund.token_amount - security_amount,
                         update_time: block_time,
                         ..fund
+                    }
+                    .update(conn)?;
+
+                    InvestmentRecord {
+                        id: Uuid::new_v4(),
+                        block_height,
+                        txn_index,
+                        contract_address: contract.contract_address,
+                        investor: investor.investor.to_string(),
+                        investment_token_id: security_token_id,
+                        investment_token_contract_address: security_token_contract_address,
+                        currency_token_id,
+                        currency_token_contract_address,
+                        currency_amount,
+                        token_amount: security_amount,
+                        currency_amount_balance: investor.currency_amount,
+                        token_amount_balance: investor.token_amount,
+                        investment_record_type: InvestmentRecordType::Withdrawn,
+                        create_time: block_time,
+                    }
+                    .insert(conn)?;
+
+                    Ok(())
+                })?;
+
+                info!(
+                    "Investment withdrawn: fund: {}/{}, from: {}, currency amount: {}, token \
+                     amount: {}",
+                    security_token_id,
+                    security_token_contract_address,
+                    investor.to_string(),
+                    currency_amount,
+                    security_amount
+                );
+            }
+            Event::InvestmentClaimed(InvestedEvent {
+                currency_amount,
+                security_amount,
+                security_token,
+                investor,
+            }) => {
+                let currency_amount = currency_amount.to_decimal();
+                let security_amount = security_amount.to_decimal();
+                let security_token_id = security_token.id.to_decimal();
+                let security_token_contract_address = security_token.contract.to_decimal();
+
+                conn.transaction::<_, ProcessorError, _>(|conn| {
+                    let contract = SecurityMintFundContract::find(conn, contract.to_decimal())?
+                        .ok_or(ProcessorError::SecurityMintFundContractNotFound {
+                            contract: contract.to_decimal(),
+                        })?;
+
+                    let investor = Investor::find(
+                        conn,
+                        contract.contract_address,
+                        security_token_id,
+                        security_token_contract_address,
+                        &investor.to_string(),
+                    )?
+                    .map(|investor| Investor {
+                        currency_amount: investor.currency_amount - currency_amount,
+                        token_amount: investor.token_amount - security_amount,
+                        token_amount_total: investor.token_amount_total + security_amount,
+                        update_time: block_time,
+                        ..investor
                     })
+                    .ok_or(ProcessorError::InvestorNotFound {
+                        investor: investor.to_string(),
+                        contract: contract.contract_address,
+                    })?
+                    .update(conn)?;
+
+                    let fund = SecurityMintFund::find(
+                        conn,
+                        contract.contract_address,
+                        security_token_id,
+                        security_token_contract_address,
+                    )?
                     .ok_or(ProcessorError::FundNotFound {
                         security_token_id,
                         security_token_contract_address,
                         contract: contract.contract_address,
-                    })?
+                    })?;
+                    let (currency_token_id, currency_token_contract_address) =
+                        (fund.currency_token_id, fund.currency_token_contract_address);
+                    SecurityMintFund {
+                        currency_amount: fund.currency_amount - currency_amount,
+                        token_amount: fund.token_amount - security_amount,
+                        update_time: block_time,
+                        ..fund
+                    }
                     .update(conn)?;
 
                     InvestmentRecord {
@@ -434,8 +613,8 @@ pub fn process_events(
                         contract_address: contract.contract_address,
                         investment_token_id: security_token_id,
                         investment_token_contract_address: security_token_contract_address,
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id,
+                        currency_token_contract_address,
                         investor: investor.investor.to_string(),
                         currency_amount,
                         token_amount: security_amount,
@@ -471,6 +650,7 @@ fn role_to_string(r: &AgentRole) -> String {
         AgentRole::RemoveFund => "RemoveFund".to_string(),
         AgentRole::UpdateFundState => "UpdateFundState".to_string(),
         AgentRole::Operator => "Operator".to_string(),
+        AgentRole::UpdateFundAllowlist => "UpdateFundAllowlist".to_string(),
     }
 }
 
@@ -483,3 +663,19 @@ fn to_db_fund_state(fund_state: &UpdateFundState) -> (SecurityMintFundState, Opt
         UpdateFundState::Fail => (SecurityMintFundState::Fail, None),
     }
 }
+
+fn to_db_investor_access(
+    investors: &FundInvestors,
+) -> (
+    SecurityMintFundInvestorAccess,
+    Option<&IdentityAttributeRequirement>,
+) {
+    match investors {
+        FundInvestors::Any => (SecurityMintFundInvestorAccess::Any, None),
+        FundInvestors::Allowlist => (SecurityMintFundInvestorAccess::Allowlist, None),
+        FundInvestors::IdentityAttribute(attribute) => (
+            SecurityMintFundInvestorAccess::IdentityAttribute,
+            Some(attribute),
+        ),
+    }
+}
@@ -1,13 +1,17 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 use concordium_rust_sdk::base::hashes::ModuleReference;
 use concordium_rust_sdk::base::smart_contracts::{ContractEvent, OwnedContractName, WasmModule};
 use concordium_rust_sdk::types::ContractAddress;
 use diesel::Connection;
 use rust_decimal::Decimal;
-use security_p2p_trading::{AddMarketParams, AgentRole, Event, ExchangeEvent};
+use security_p2p_trading::{
+    AddMarketParams, AgentRole, Event, ExchangeEvent, LiquidityEvent, OrderCancelledEvent,
+    OrderFilledEvent, OrderPlacedEvent,
+};
 use shared::db::cis2_security::Agent;
 use shared::db::security_p2p_trading::{
-    ExchangeRecord, Market, MarketType, P2PTradeContract, Trader,
+    ExchangeRecord, Market, MarketPriceTier, MarketType, Order, OrderFill, OrderSettlement,
+    OrderSide, OrderStatus, P2PTradeContract, Trader,
 };
 use shared::db_shared::{DbConn, DbResult};
 use tracing::{info, instrument, trace, warn};
@@ -132,13 +136,52 @@ pub fn process_events(
                     }
 
                     // Insert the new market
+                    let price_tiers = match &market {
+                        security_p2p_trading::Market::Mint(market) => market
+                            .price_tiers
+                            .iter()
+                            .enumerate()
+                            .map(|(tier_index, tier)| MarketPriceTier {
+                                contract_address:       contract.contract_address,
+                                token_contract_address: token_contract.to_decimal(),
+                                tier_index:             tier_index as i32,
+                                rate_numerator:         tier.rate.numerator.into(),
+                                rate_denominator:       tier.rate.denominator.into(),
+                                token_amount:           tier.token_amount.to_decimal(),
+                                sold_token_amount:      0.into(),
+                            })
+                            .collect(),
+                        security_p2p_trading::Market::Transfer(_)
+                        | security_p2p_trading::Market::OrderBook(_) => vec![],
+                    };
+                    // Markets without a currency are priced in the default currency of the contract
+                    let (currency_token_id, currency_token_contract_address) = match &market {
+                        security_p2p_trading::Market::Mint(security_p2p_trading::MintMarket {
+                            currency_token,
+                            ..
+                        })
+                        | security_p2p_trading::Market::Transfer(
+                            security_p2p_trading::TransferMarket { currency_token, .. },
+                        )
+                        | security_p2p_trading::Market::OrderBook(
+                            security_p2p_trading::OrderBookMarket { currency_token, .. },
+                        ) => match currency_token {
+                            Some(currency_token) => (
+                                currency_token.id.to_decimal(),
+                                currency_token.contract.to_decimal(),
+                            ),
+                            None => (
+                                contract.currency_token_id,
+                                contract.currency_token_contract_address,
+                            ),
+                        },
+                    };
                     let market = match market {
                         security_p2p_trading::Market::Mint(market) => Market {
                             market_type: MarketType::Mint,
                             contract_address: contract.contract_address,
-                            currency_token_id: contract.currency_token_id,
-                            currency_token_contract_address: contract
-                                .currency_token_contract_address,
+                            currency_token_id,
+                            currency_token_contract_address,
                             liquidity_provider: market.liquidity_provider.to_string(),
                             token_contract_address: token_contract.to_decimal(),
                             token_id: None,
@@ -161,13 +204,27 @@ pub fn process_events(
                             token_out_amount: 0.into(),
                             create_time: block_time,
                             update_time: block_time,
+                            open_time: DateTime::from_timestamp_millis(
+                                market.open_time.timestamp_millis() as i64,
+                            )
+                            .map(|t| t.naive_utc()),
+                            close_time: DateTime::from_timestamp_millis(
+                                market.close_time.timestamp_millis() as i64,
+                            )
+                            .map(|t| t.naive_utc()),
+                            max_investor_token_amount: market
+                                .max_investor_token_amount
+                                .map(|a| a.to_decimal()),
+                            fee_rate_numerator: market.fee.map(|f| f.rate.numerator.into()),
+                            fee_rate_denominator: market.fee.map(|f| f.rate.denominator.into()),
+                            fee_receiver: market.fee.map(|f| f.receiver.to_string()),
+                            settlement: OrderSettlement::Operator,
                         },
                         security_p2p_trading::Market::Transfer(market) => Market {
                             market_type: MarketType::Transfer,
                             contract_address: contract.contract_address,
-                            currency_token_id: contract.currency_token_id,
-                            currency_token_contract_address: contract
-                                .currency_token_contract_address,
+                            currency_token_id,
+                            currency_token_contract_address,
                             liquidity_provider: market.liquidity_provider.to_string(),
                             token_contract_address: token_contract.to_decimal(),
                             token_id: Some(market.token_id.to_decimal()),
@@ -186,9 +243,56 @@ pub fn process_events(
                             currency_in_amount: 0.into(),
                             token_out_amount: 0.into(),
                             currency_out_amount: 0.into(),
+                            open_time: None,
+                            close_time: None,
+                            max_investor_token_amount: None,
+                            fee_rate_numerator: market.fee.map(|f| f.rate.numerator.into()),
+                            fee_rate_denominator: market.fee.map(|f| f.rate.denominator.into()),
+                            fee_receiver: market.fee.map(|f| f.receiver.to_string()),
+                            settlement: match market.settlement {
+                                security_p2p_trading::TransferSettlement::Operator => {
+                                    OrderSettlement::Operator
+                                }
+                                security_p2p_trading::TransferSettlement::Escrow => {
+                                    OrderSettlement::Escrow
+                                }
+                            },
+                        },
+                        security_p2p_trading::Market::OrderBook(market) => Market {
+                            market_type: MarketType::OrderBook,
+                            contract_address: contract.contract_address,
+                            currency_token_id,
+                            currency_token_contract_address,
+                            // Order book markets do not have a liquidity provider.
+                            liquidity_provider: String::new(),
+                            token_contract_address: token_contract.to_decimal(),
+                            token_id: Some(market.token_id.to_decimal()),
+                            token_id_calculation_start: None,
+                            token_id_calculation_diff_millis: None,
+                            token_id_calculation_base_token_id: None,
+                            buy_rate_numerator: None,
+                            buy_rate_denominator: None,
+                            sell_rate_numerator: None,
+                            sell_rate_denominator: None,
+                            max_token_amount: 0.into(),
+                            max_currency_amount: None,
+                            create_time: block_time,
+                            update_time: block_time,
+                            token_in_amount: 0.into(),
+                            currency_in_amount: 0.into(),
+                            token_out_amount: 0.into(),
+                            currency_out_amount: 0.into(),
+                            open_time: None,
+                            close_time: None,
+                            max_investor_token_amount: None,
+                            fee_rate_numerator: market.fee.map(|f| f.rate.numerator.into()),
+                            fee_rate_denominator: market.fee.map(|f| f.rate.denominator.into()),
+                            fee_receiver: market.fee.map(|f| f.receiver.to_string()),
+                            settlement: OrderSettlement::Operator,
                         },
                     };
                     let market = market.insert(conn)?;
+                    MarketPriceTier::insert_batch(conn, &price_tiers)?;
                     Ok(market)
                 })?;
                 info!("Market added: {:?}", market);
@@ -217,6 +321,9 @@ pub fn process_events(
                 token_id,
                 currency_amount,
                 exchange_type,
+                fee_amount,
+                fee_receiver,
+                currency_token,
             }) => {
                 conn.transaction::<_, ProcessorError, _>(|conn| {
                     let contract = P2PTradeContract::find(conn, contract.to_decimal())?.ok_or(
@@ -231,28 +338,34 @@ pub fn process_events(
                                 contract:       contract.contract_address,
                                 token_contract: token_contract.to_decimal(),
                             })?;
-                    match exchange_type {
-                        security_p2p_trading::ExchangeType::Buy
-                        | security_p2p_trading::ExchangeType::Mint => {
-                            market.currency_in_amount += currency_amount.to_decimal();
-                            market.token_out_amount += token_amount.to_decimal();
-                            market.max_token_amount -= token_amount.to_decimal();
-                            market.max_currency_amount = market
-                                .max_currency_amount
-                                .map(|v| v + currency_amount.to_decimal());
-                        }
-                        security_p2p_trading::ExchangeType::Sell => {
-                            market.currency_out_amount += currency_amount.to_decimal();
-                            market.token_in_amount += token_amount.to_decimal();
-                            market.max_token_amount += token_amount.to_decimal();
-                            market.max_currency_amount = market
-                                .max_currency_amount
-                                .map(|v| v - currency_amount.to_decimal());
-                        }
-                    }
+                    update_market_liquidity(
+                        &mut market,
+                        &exchange_type,
+                        token_amount.to_decimal(),
+                        currency_amount.to_decimal(),
+                        fee_amount.to_decimal(),
+                    );
                     market.update_time = block_time;
                     market.update(conn)?;
 
+                    if let security_p2p_trading::ExchangeType::Mint = exchange_type {
+                        // A mint spanning multiple tiers logs an event for each of them, in order. Once all
+                        // tiers are sold out the market rate applies.
+                        let tier = MarketPriceTier::find_current(
+                            conn,
+                            market.contract_address,
+                            market.token_contract_address,
+                        )?;
+                        if let Some(mut tier) = tier {
+                            if tier.rate_numerator == Decimal::from(rate.numerator)
+                                && tier.rate_denominator == Decimal::from(rate.denominator)
+                            {
+                                tier.sold_token_amount += token_amount.to_decimal();
+                                tier.update(conn)?;
+                            }
+                        }
+                    }
+
                     Trader::find(
                         conn,
                         contract.contract_address,
@@ -275,8 +388,8 @@ pub fn process_events(
                         token_out_amount: token_amount.to_decimal(),
                         currency_in_amount: currency_amount.to_decimal(),
                         currency_out_amount: 0.into(),
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id: currency_token.id.to_decimal(),
+                        currency_token_contract_address: currency_token.contract.to_decimal(),
                         create_time: block_time,
                         update_time: block_time,
                     })
@@ -304,8 +417,8 @@ pub fn process_events(
                         token_out_amount: 0.into(),
                         currency_in_amount: 0.into(),
                         currency_out_amount: currency_amount.to_decimal(),
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id: currency_token.id.to_decimal(),
+                        currency_token_contract_address: currency_token.contract.to_decimal(),
                         create_time: block_time,
                         update_time: block_time,
                     })
@@ -318,14 +431,16 @@ pub fn process_events(
                         contract_address: contract.contract_address,
                         token_id: token_id.to_decimal(),
                         token_contract_address: token_contract.to_decimal(),
-                        currency_token_id: contract.currency_token_id,
-                        currency_token_contract_address: contract.currency_token_contract_address,
+                        currency_token_id: currency_token.id.to_decimal(),
+                        currency_token_contract_address: currency_token.contract.to_decimal(),
                         seller: seller.to_string(),
                         buyer: buyer.to_string(),
                         currency_amount: currency_amount.to_decimal(),
                         token_amount: token_amount.to_decimal(),
                         create_time: block_time,
                         rate: rate.to_decimal(),
+                        fee_amount: fee_amount.to_decimal(),
+                        fee_receiver: fee_receiver.map(|r| r.to_string()),
                         exchange_record_type: match exchange_type {
                             security_p2p_trading::ExchangeType::Buy => {
                                 shared::db::security_p2p_trading::ExchangeRecordType::Buy
@@ -336,6 +451,9 @@ pub fn process_events(
                             security_p2p_trading::ExchangeType::Mint => {
                                 shared::db::security_p2p_trading::ExchangeRecordType::Mint
                             }
+                            security_p2p_trading::ExchangeType::Order => {
+                                shared::db::security_p2p_trading::ExchangeRecordType::Order
+                            }
                         },
                     }
                     .insert(conn)?;
@@ -353,12 +471,210 @@ pub fn process_events(
                     )
                 );
             }
+            Event::OrderPlaced(OrderPlacedEvent { order_id, order }) => {
+                let order = conn.transaction::<_, ProcessorError, _>(|conn| {
+                    let contract = P2PTradeContract::find(conn, contract.to_decimal())?.ok_or(
+                        ProcessorError::TradeContractNotFound {
+                            contract: contract.to_decimal(),
+                        },
+                    )?;
+                    let order = Order {
+                        contract_address: contract.contract_address,
+                        order_id: order_id.into(),
+                        token_contract_address: order.token_contract.to_decimal(),
+                        token_id: order.token_id.to_decimal(),
+                        currency_token_id: order.currency_token.id.to_decimal(),
+                        currency_token_contract_address: order.currency_token.contract.to_decimal(),
+                        owner: order.owner.to_string(),
+                        side: match order.side {
+                            security_p2p_trading::OrderSide::Sell => OrderSide::Sell,
+                            security_p2p_trading::OrderSide::Buy => OrderSide::Buy,
+                        },
+                        rate_numerator: order.rate.numerator.into(),
+                        rate_denominator: order.rate.denominator.into(),
+                        token_amount: order.amount.to_decimal(),
+                        remaining_token_amount: order.amount.to_decimal(),
+                        filled_currency_amount: 0.into(),
+                        status: OrderStatus::Open,
+                        create_time: block_time,
+                        update_time: block_time,
+                        settlement: match order.settlement {
+                            security_p2p_trading::OrderSettlement::Operator => {
+                                OrderSettlement::Operator
+                            }
+                            security_p2p_trading::OrderSettlement::Escrow(_) => {
+                                OrderSettlement::Escrow
+                            }
+                        },
+                        expiry: match order.settlement {
+                            security_p2p_trading::OrderSettlement::Operator => None,
+                            security_p2p_trading::OrderSettlement::Escrow(escrow) => {
+                                DateTime::from_timestamp_millis(
+                                    escrow.expiry.timestamp_millis() as i64
+                                )
+                                .map(|expiry| expiry.naive_utc())
+                            }
+                        },
+                    }
+                    .insert(conn)?;
+                    Ok(order)
+                })?;
+                info!("Order placed: {:?}", order);
+            }
+            Event::OrderCancelled(OrderCancelledEvent { order_id, .. }) => {
+                let mut order = Order::find(conn, contract.to_decimal(), order_id.into())?.ok_or(
+                    ProcessorError::OrderNotFound {
+                        contract: contract.to_decimal(),
+                        order_id: order_id.into(),
+                    },
+                )?;
+                order.status = OrderStatus::Cancelled;
+                order.update_time = block_time;
+                let order = order.update(conn)?;
+                info!("Order cancelled: {:?}", order);
+            }
+            Event::OrderExpired(OrderCancelledEvent { order_id, .. }) => {
+                let mut order = Order::find(conn, contract.to_decimal(), order_id.into())?.ok_or(
+                    ProcessorError::OrderNotFound {
+                        contract: contract.to_decimal(),
+                        order_id: order_id.into(),
+                    },
+                )?;
+                order.status = OrderStatus::Expired;
+                order.update_time = block_time;
+                let order = order.update(conn)?;
+                info!("Order expired: {:?}", order);
+            }
+            Event::OrderFilled(OrderFilledEvent {
+                order_id,
+                taker,
+                token_amount,
+                currency_amount,
+                remaining_amount,
+                ..
+            }) => {
+                conn.transaction::<_, ProcessorError, _>(|conn| {
+                    let mut order = Order::find(conn, contract.to_decimal(), order_id.into())?
+                        .ok_or(ProcessorError::OrderNotFound {
+                            contract: contract.to_decimal(),
+                            order_id: order_id.into(),
+                        })?;
+                    order.remaining_token_amount = remaining_amount.to_decimal();
+                    order.filled_currency_amount += currency_amount.to_decimal();
+                    if order.remaining_token_amount.is_zero() {
+                        order.status = OrderStatus::Filled;
+                    }
+                    order.update_time = block_time;
+                    order.update(conn)?;
+
+                    OrderFill {
+                        id: Uuid::new_v4(),
+                        block_height,
+                        txn_index,
+                        contract_address: order.contract_address,
+                        order_id: order.order_id,
+                        taker: taker.to_string(),
+                        token_amount: token_amount.to_decimal(),
+                        currency_amount: currency_amount.to_decimal(),
+                        remaining_token_amount: remaining_amount.to_decimal(),
+                        create_time: block_time,
+                    }
+                    .insert(conn)?;
+                    Ok(())
+                })?;
+                info!(
+                    "Order: {} filled by {}, amount: {:?}",
+                    order_id,
+                    taker.to_string(),
+                    token_amount
+                );
+            }
+            Event::LiquidityDeposited(LiquidityEvent {
+                token_contract,
+                token_amount,
+                currency_amount,
+                ..
+            }) => {
+                let mut market =
+                    Market::find(conn, contract.to_decimal(), token_contract.to_decimal())?.ok_or(
+                        ProcessorError::MarketNotFound {
+                            contract:       contract.to_decimal(),
+                            token_contract: token_contract.to_decimal(),
+                        },
+                    )?;
+                market.max_token_amount += token_amount.to_decimal();
+                market.max_currency_amount = market
+                    .max_currency_amount
+                    .map(|v| v + currency_amount.to_decimal());
+                market.update_time = block_time;
+                let market = market.update(conn)?;
+                info!("Liquidity deposited: {:?}", market);
+            }
+            Event::LiquidityWithdrawn(LiquidityEvent {
+                token_contract,
+                token_amount,
+                currency_amount,
+                ..
+            }) => {
+                // The liquidity of a removed market is withdrawn before the market is removed
+                let mut market =
+                    Market::find(conn, contract.to_decimal(), token_contract.to_decimal())?.ok_or(
+                        ProcessorError::MarketNotFound {
+                            contract:       contract.to_decimal(),
+                            token_contract: token_contract.to_decimal(),
+                        },
+                    )?;
+                market.max_token_amount -= token_amount.to_decimal();
+                market.max_currency_amount = market
+                    .max_currency_amount
+                    .map(|v| v - currency_amount.to_decimal());
+                market.update_time = block_time;
+                let market = market.update(conn)?;
+                info!("Liquidity withdrawn: {:?}", market);
+            }
         }
     }
 
     Ok(())
 }
 
+/// Updates the traded amounts and the liquidity of a market for an exchange.
+fn update_market_liquidity(
+    market: &mut Market,
+    exchange_type: &security_p2p_trading::ExchangeType,
+    token_amount: Decimal,
+    currency_amount: Decimal,
+    fee_amount: Decimal,
+) {
+    match exchange_type {
+        security_p2p_trading::ExchangeType::Buy | security_p2p_trading::ExchangeType::Mint => {
+            market.currency_in_amount += currency_amount;
+            market.token_out_amount += token_amount;
+            market.max_token_amount -= token_amount;
+            // Only the part of the payment which is not taken as fee is added to the escrowed liquidity
+            let liquidity_amount = match (exchange_type, market.settlement) {
+                (security_p2p_trading::ExchangeType::Buy, OrderSettlement::Escrow) => {
+                    currency_amount - fee_amount
+                }
+                _ => currency_amount,
+            };
+            market.max_currency_amount = market.max_currency_amount.map(|v| v + liquidity_amount);
+        }
+        security_p2p_trading::ExchangeType::Sell => {
+            market.currency_out_amount += currency_amount;
+            market.token_in_amount += token_amount;
+            market.max_token_amount += token_amount;
+            market.max_currency_amount = market.max_currency_amount.map(|v| v - currency_amount);
+        }
+        security_p2p_trading::ExchangeType::Order => {
+            // Order book markets have no liquidity limits.
+            // The traded volume is tracked as tokens going out of the market.
+            market.currency_in_amount += currency_amount;
+            market.token_out_amount += token_amount;
+        }
+    }
+}
+
 fn role_to_string(agent_role: &AgentRole) -> String {
     match agent_role {
         AgentRole::AddMarket => "AddMarket".to_string(),
@@ -366,3 +682,72 @@ fn role_to_string(agent_role: &AgentRole) -> String {
         AgentRole::Operator => "Operator".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use rust_decimal::Decimal;
+    use security_p2p_trading::ExchangeType;
+    use shared::db::security_p2p_trading::{Market, MarketType, OrderSettlement};
+
+    use super::update_market_liquidity;
+
+    fn transfer_market(settlement: OrderSettlement) -> Market {
+        let now = DateTime::from_timestamp(0, 0).unwrap().naive_utc();
+        Market {
+            contract_address: 1.into(),
+            token_id: Some(0.into()),
+            token_contract_address: 2.into(),
+            currency_token_id: 0.into(),
+            currency_token_contract_address: 3.into(),
+            liquidity_provider: String::new(),
+            buy_rate_numerator: Some(1.into()),
+            buy_rate_denominator: Some(1.into()),
+            sell_rate_numerator: Some(1.into()),
+            sell_rate_denominator: Some(1.into()),
+            create_time: now,
+            update_time: now,
+            token_id_calculation_start: None,
+            token_id_calculation_diff_millis: None,
+            token_id_calculation_base_token_id: None,
+            market_type: MarketType::Transfer,
+            max_token_amount: 1000.into(),
+            max_currency_amount: Some(500.into()),
+            token_in_amount: 0.into(),
+            currency_out_amount: 0.into(),
+            token_out_amount: 0.into(),
+            currency_in_amount: 0.into(),
+            open_time: None,
+            close_time: None,
+            max_investor_token_amount: None,
+            fee_rate_numerator: Some(1.into()),
+            fee_rate_denominator: Some(10.into()),
+            fee_receiver: Some(String::new()),
+            settlement,
+        }
+    }
+
+    #[test]
+    fn escrow_buy_with_fee() {
+        let buy = |settlement| {
+            let mut market = transfer_market(settlement);
+            update_market_liquidity(
+                &mut market,
+                &ExchangeType::Buy,
+                100.into(),
+                100.into(),
+                10.into(),
+            );
+            market
+        };
+
+        // The fee is paid out of the escrowed payment of the buyer
+        let market = buy(OrderSettlement::Escrow);
+        assert_eq!(market.max_token_amount, Decimal::from(900));
+        assert_eq!(market.max_currency_amount, Some(Decimal::from(590)));
+        assert_eq!(market.currency_in_amount, Decimal::from(100));
+
+        let market = buy(OrderSettlement::Operator);
+        assert_eq!(market.max_currency_amount, Some(Decimal::from(600)));
+    }
+}
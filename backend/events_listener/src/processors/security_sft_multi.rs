@@ -69,6 +69,9 @@ impl RoleToString for AgentRole {
             AgentRole::SetIdentityRegistry => "SetIdentityRegistry".to_string(),
             AgentRole::Pause => "Pause".to_string(),
             AgentRole::UnPause => "UnPause".to_string(),
+            AgentRole::CorporateAction => "CorporateAction".to_string(),
+            AgentRole::Snapshot => "Snapshot".to_string(),
+            AgentRole::SetDocument => "SetDocument".to_string(),
         }
     }
 }
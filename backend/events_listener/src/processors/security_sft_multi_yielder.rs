@@ -1,6 +1,9 @@
-use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDateTime};
 use concordium_protocols::concordium_cis2_security::AgentWithRoles;
 use concordium_protocols::rate::Rate;
+use concordium_rust_sdk::base::contracts_common::Duration;
 use concordium_rust_sdk::base::hashes::ModuleReference;
 use concordium_rust_sdk::base::smart_contracts::{ContractEvent, OwnedContractName, WasmModule};
 use concordium_rust_sdk::types::ContractAddress;
@@ -8,10 +11,15 @@ use diesel::{Connection, QueryResult};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use security_sft_multi_yielder::{
-    AgentRole, Event, UpsertYieldParams, YieldCalculation, YieldDistributedEvent, YieldRemovedEvent,
+    AccrualPeriod, AgentRole, Event, PoolClaimedEvent, PoolDepositedEvent, PoolReturnedEvent,
+    SetClaimModeParams, SetPoolTokenParams, TokenVersionAddedEvent, UpsertYieldParams,
+    YieldCalculation, YieldClaimedEvent, YieldDistributedEvent, YieldRemovedEvent, COMPOUND_SCALE,
 };
 use shared::db::cis2_security::Agent;
-use shared::db::security_sft_multi_yielder::{Treasury, Yield, YieldDistribution, YieldType};
+use shared::db::security_sft_multi_yielder::{
+    Checkpoint, ClaimMode, PoolClaim, TokenContractClaimMode, TokenVersion, Treasury, Yield,
+    YieldDistribution, YieldPool, YieldType,
+};
 use shared::db_shared::DbConn;
 use tracing::{info, instrument};
 use uuid::Uuid;
@@ -87,6 +95,21 @@ pub fn process_events(
                             YieldCalculation::SimpleInterest(rate) => {
                                 (YieldType::SimpleIntrest, rate)
                             }
+                            YieldCalculation::CompoundInterest(rate) => {
+                                (YieldType::CompoundInterest, rate)
+                            }
+                            YieldCalculation::TimeSimpleInterest(rate, AccrualPeriod::Second) => {
+                                (YieldType::SimpleInterestPerSecond, rate)
+                            }
+                            YieldCalculation::TimeSimpleInterest(rate, AccrualPeriod::Day) => {
+                                (YieldType::SimpleInterestPerDay, rate)
+                            }
+                            YieldCalculation::TimeCompoundInterest(rate, AccrualPeriod::Second) => {
+                                (YieldType::CompoundInterestPerSecond, rate)
+                            }
+                            YieldCalculation::TimeCompoundInterest(rate, AccrualPeriod::Day) => {
+                                (YieldType::CompoundInterestPerDay, rate)
+                            }
                         };
 
                         Yield {
@@ -139,34 +162,28 @@ pub fn process_events(
                 amount,
                 to,
             }) => {
-                let yield_distributions = Yield::find_batch(
+                // Yields already claimed against a checkpoint are not paid again.
+                let accrual_from = Checkpoint::find(
                     conn,
                     contract.to_decimal(),
                     token_contract.to_decimal(),
                     from_token.to_decimal(),
-                    to_token.to_decimal(),
+                    &to.to_string(),
                 )?
-                .into_iter()
-                .map(|y| YieldDistribution {
-                    id:                     Uuid::new_v4(),
-                    contract_address:       y.contract_address,
-                    from_token_version:     from_token.to_decimal(),
-                    to_token_version:       to_token.to_decimal(),
-                    token_amount:           amount.to_decimal(),
-                    token_contract_address: y.token_contract_address,
-                    yield_contract_address: y.yield_contract_address,
-                    yield_token_id:         y.yield_token_id,
-                    to_address:             to.to_string(),
-                    yield_amount:           calculate_yield_amount(
-                        y.yield_type,
-                        y.yield_rate_numerator,
-                        y.yield_rate_denominator,
-                        amount.to_decimal(),
-                        to_token.to_decimal() - from_token.to_decimal(),
-                    ),
-                    create_time:            block_time,
-                })
-                .collect::<Vec<_>>();
+                .map(|checkpoint| checkpoint.claimed_token_id)
+                .unwrap_or(from_token.to_decimal())
+                .max(from_token.to_decimal());
+                let yield_distributions = yield_distributions(
+                    conn,
+                    contract,
+                    &token_contract,
+                    from_token.to_decimal(),
+                    accrual_from,
+                    to_token.to_decimal(),
+                    amount.to_decimal(),
+                    &to.to_string(),
+                    block_time,
+                )?;
                 YieldDistribution::insert_batch(conn, &yield_distributions)?;
                 yield_distributions.iter().for_each(|yield_distribution| {
                     info!("Yield distributed: {:?}", yield_distribution)
@@ -182,12 +199,327 @@ pub fn process_events(
                 .upsert(conn)?;
                 info!("Treasury updated: {:?}", address.to_string());
             }
+            Event::TokenVersionAdded(TokenVersionAddedEvent {
+                token_contract,
+                token_id,
+                created_at,
+            }) => {
+                TokenVersion {
+                    contract_address:       contract.to_decimal(),
+                    token_contract_address: token_contract.to_decimal(),
+                    token_id:               token_id.to_decimal(),
+                    create_time:            DateTime::from_timestamp_millis(
+                        created_at.timestamp_millis() as i64,
+                    )
+                    .map(|t| t.naive_utc())
+                    .unwrap_or(block_time),
+                }
+                .upsert(conn)?;
+                info!(
+                    "Token version added: {:?}",
+                    (contract, token_contract, token_id)
+                );
+            }
+            Event::TokenVersionRemoved(YieldRemovedEvent {
+                token_contract,
+                token_id,
+            }) => {
+                TokenVersion::delete(
+                    conn,
+                    contract.to_decimal(),
+                    token_contract.to_decimal(),
+                    token_id.to_decimal(),
+                )?;
+                info!(
+                    "Token version removed: {:?}",
+                    (contract, token_contract, token_id)
+                );
+            }
+            Event::PoolDeposited(PoolDepositedEvent {
+                token_contract,
+                token_id,
+                yield_contract,
+                yield_token_id,
+                amount,
+                supply,
+                expiry,
+                token_ids,
+                snapshot_id,
+            }) => {
+                let pool = YieldPool {
+                    contract_address:       contract.to_decimal(),
+                    token_contract_address: token_contract.to_decimal(),
+                    token_id:               token_id.to_decimal(),
+                    yield_contract_address: yield_contract.to_decimal(),
+                    yield_token_id:         yield_token_id.to_decimal(),
+                    amount:                 amount.to_decimal(),
+                    supply:                 supply.to_decimal(),
+                    claimed_amount:         Decimal::ZERO,
+                    returned_amount:        Decimal::ZERO,
+                    expiry:                 DateTime::from_timestamp_millis(
+                        expiry.timestamp_millis() as i64,
+                    )
+                    .map(|t| t.naive_utc())
+                    .unwrap_or(block_time),
+                    create_time:            block_time,
+                    update_time:            block_time,
+                    snapshot_id:            Decimal::from(snapshot_id),
+                    token_ids:              token_ids
+                        .iter()
+                        .map(|token_id| Some(token_id.to_decimal()))
+                        .collect(),
+                };
+                pool.insert(conn)?;
+                info!("Yield pool deposited: {:?}", pool);
+            }
+            Event::PoolClaimed(PoolClaimedEvent {
+                token_contract,
+                token_id,
+                yield_contract,
+                yield_token_id,
+                from_token,
+                security_amount,
+                amount,
+                to,
+            }) => {
+                let pool = find_pool(
+                    conn,
+                    contract,
+                    &token_contract,
+                    token_id.to_decimal(),
+                    &yield_contract,
+                    yield_token_id.to_decimal(),
+                )?;
+                let yield_distribution = YieldDistribution {
+                    id:                     Uuid::new_v4(),
+                    contract_address:       contract.to_decimal(),
+                    from_token_version:     from_token.to_decimal(),
+                    to_token_version:       token_id.to_decimal(),
+                    token_amount:           security_amount.to_decimal(),
+                    token_contract_address: token_contract.to_decimal(),
+                    yield_contract_address: yield_contract.to_decimal(),
+                    yield_token_id:         yield_token_id.to_decimal(),
+                    to_address:             to.to_string(),
+                    yield_amount:           amount.to_decimal(),
+                    create_time:            block_time,
+                };
+                let pool_claim = PoolClaim {
+                    contract_address:       contract.to_decimal(),
+                    token_contract_address: token_contract.to_decimal(),
+                    token_id:               token_id.to_decimal(),
+                    yield_contract_address: yield_contract.to_decimal(),
+                    yield_token_id:         yield_token_id.to_decimal(),
+                    from_token_id:          from_token.to_decimal(),
+                    holder_address:         to.to_string(),
+                    security_amount:        security_amount.to_decimal(),
+                    create_time:            block_time,
+                    update_time:            block_time,
+                };
+                conn.transaction(|conn| {
+                    YieldPool {
+                        claimed_amount: pool.claimed_amount + amount.to_decimal(),
+                        update_time: block_time,
+                        ..pool
+                    }
+                    .update(conn)?;
+                    pool_claim.upsert_add(conn)?;
+                    yield_distribution.insert(conn)?;
+                    QueryResult::Ok(())
+                })?;
+                info!("Yield pool claimed: {:?}", yield_distribution);
+            }
+            Event::ClaimModeUpdated(SetClaimModeParams {
+                token_contract,
+                mode,
+            }) => {
+                TokenContractClaimMode {
+                    contract_address:       contract.to_decimal(),
+                    token_contract_address: token_contract.to_decimal(),
+                    claim_mode:             match mode {
+                        security_sft_multi_yielder::ClaimMode::Versioned => ClaimMode::Versioned,
+                        security_sft_multi_yielder::ClaimMode::Checkpoint => ClaimMode::Checkpoint,
+                    },
+                    create_time:            block_time,
+                    update_time:            block_time,
+                }
+                .upsert(conn)?;
+                info!("Claim mode updated: {:?}", (contract, token_contract, mode));
+            }
+            Event::YieldClaimed(YieldClaimedEvent {
+                token_contract,
+                token_id,
+                from_token,
+                to_token,
+                amount,
+                to,
+            }) => {
+                let yield_distributions = yield_distributions(
+                    conn,
+                    contract,
+                    &token_contract,
+                    from_token.to_decimal(),
+                    from_token.to_decimal(),
+                    to_token.to_decimal(),
+                    amount.to_decimal(),
+                    &to.to_string(),
+                    block_time,
+                )?;
+                conn.transaction(|conn| {
+                    Checkpoint {
+                        contract_address:       contract.to_decimal(),
+                        token_contract_address: token_contract.to_decimal(),
+                        token_id:               token_id.to_decimal(),
+                        holder_address:         to.to_string(),
+                        claimed_token_id:       to_token.to_decimal(),
+                        create_time:            block_time,
+                        update_time:            block_time,
+                    }
+                    .upsert(conn)?;
+                    YieldDistribution::insert_batch(conn, &yield_distributions)
+                })?;
+                yield_distributions.iter().for_each(|yield_distribution| {
+                    info!("Yield claimed: {:?}", yield_distribution)
+                });
+            }
+            Event::PoolReturned(PoolReturnedEvent {
+                token_contract,
+                token_id,
+                yield_contract,
+                yield_token_id,
+                amount,
+                to,
+            }) => {
+                let pool = find_pool(
+                    conn,
+                    contract,
+                    &token_contract,
+                    token_id.to_decimal(),
+                    &yield_contract,
+                    yield_token_id.to_decimal(),
+                )?;
+                let pool = YieldPool {
+                    returned_amount: pool.returned_amount + amount.to_decimal(),
+                    update_time: block_time,
+                    ..pool
+                }
+                .update(conn)?;
+                info!("Yield pool returned to {}: {:?}", to.to_string(), pool);
+            }
+            Event::PoolTokenUpdated(SetPoolTokenParams {
+                contract: yield_contract,
+                allowed,
+            }) => {
+                info!(
+                    "Pool token updated: {:?}",
+                    (contract, yield_contract, allowed)
+                );
+            }
         }
     }
 
     Ok(())
 }
 
+fn find_pool(
+    conn: &mut DbConn,
+    contract: &ContractAddress,
+    token_contract: &ContractAddress,
+    token_id: Decimal,
+    yield_contract: &ContractAddress,
+    yield_token_id: Decimal,
+) -> Result<YieldPool, ProcessorError> {
+    YieldPool::find(
+        conn,
+        contract.to_decimal(),
+        token_contract.to_decimal(),
+        token_id,
+        yield_contract.to_decimal(),
+        yield_token_id,
+    )?
+    .ok_or_else(|| ProcessorError::YieldPoolNotFound {
+        contract: contract.to_decimal(),
+        token_contract: token_contract.to_decimal(),
+        token_id,
+        yield_contract: yield_contract.to_decimal(),
+        yield_token_id,
+    })
+}
+
+/// Yield distributions of the yields accruing from the token version `accrual_from` up to `to_token`.
+#[allow(clippy::too_many_arguments)]
+fn yield_distributions(
+    conn: &mut DbConn,
+    contract: &ContractAddress,
+    token_contract: &ContractAddress,
+    from_token: Decimal,
+    accrual_from: Decimal,
+    to_token: Decimal,
+    amount: Decimal,
+    to: &str,
+    block_time: NaiveDateTime,
+) -> Result<Vec<YieldDistribution>, ProcessorError> {
+    let versions = TokenVersion::list_between(
+        conn,
+        contract.to_decimal(),
+        token_contract.to_decimal(),
+        accrual_from,
+        to_token,
+    )?;
+    let mut yields = Yield::find_batch(
+        conn,
+        contract.to_decimal(),
+        token_contract.to_decimal(),
+        accrual_from,
+        to_token,
+    )?;
+    // Compound yields accrue on the compound yields of the previous token versions.
+    yields.sort_by_key(|y| y.token_id);
+    let mut factors = BTreeMap::new();
+    let yield_distributions = yields
+        .into_iter()
+        .map(|y| {
+            // Every yield accrues from the previous token version or from the token version yielded from.
+            let accrual_from_version = versions.iter().rfind(|v| v.token_id < y.token_id);
+            let accrual_to_version = versions.iter().find(|v| v.token_id == y.token_id);
+            let token_version_diff = y.token_id
+                - accrual_from_version
+                    .map(|v| v.token_id)
+                    .unwrap_or(accrual_from);
+            let elapsed = match (accrual_from_version, accrual_to_version) {
+                (Some(from), Some(to)) => (to.create_time - from.create_time)
+                    .num_milliseconds()
+                    .max(0) as u64,
+                _ => 0,
+            };
+
+            YieldDistribution {
+                id:                     Uuid::new_v4(),
+                contract_address:       y.contract_address,
+                from_token_version:     from_token,
+                to_token_version:       to_token,
+                token_amount:           amount,
+                token_contract_address: y.token_contract_address,
+                yield_contract_address: y.yield_contract_address,
+                yield_token_id:         y.yield_token_id,
+                to_address:             to.to_string(),
+                yield_amount:           calculate_yield_amount(
+                    y.yield_type,
+                    y.yield_rate_numerator,
+                    y.yield_rate_denominator,
+                    amount,
+                    token_version_diff,
+                    Duration::from_millis(elapsed),
+                    factors
+                        .entry((y.yield_contract_address, y.yield_token_id))
+                        .or_insert(COMPOUND_SCALE),
+                ),
+                create_time:            block_time,
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(yield_distributions)
+}
+
 fn role_to_string(r: &AgentRole) -> String {
     match r {
         AgentRole::AddYield => "AddYield".to_string(),
@@ -203,6 +535,8 @@ fn calculate_yield_amount(
     rate_denominator: Decimal,
     amount: Decimal,
     token_version_diff: Decimal,
+    elapsed: Duration,
+    factor: &mut u128,
 ) -> Decimal {
     let rate = Rate {
         numerator:   rate_numerator
@@ -213,16 +547,36 @@ fn calculate_yield_amount(
             .expect("Failed to convert denominator"),
     };
 
-    match yeild_type {
+    let calculation = match yeild_type {
         YieldType::Quantity => YieldCalculation::Quantity(rate),
         YieldType::SimpleIntrest => YieldCalculation::SimpleInterest(rate),
-    }
-    .calculate_amount(
-        &concordium_cis2::TokenAmountU64(amount.to_u64().expect("Failed to convert amount")),
+        YieldType::CompoundInterest => YieldCalculation::CompoundInterest(rate),
+        YieldType::SimpleInterestPerSecond => {
+            YieldCalculation::TimeSimpleInterest(rate, AccrualPeriod::Second)
+        }
+        YieldType::SimpleInterestPerDay => {
+            YieldCalculation::TimeSimpleInterest(rate, AccrualPeriod::Day)
+        }
+        YieldType::CompoundInterestPerSecond => {
+            YieldCalculation::TimeCompoundInterest(rate, AccrualPeriod::Second)
+        }
+        YieldType::CompoundInterestPerDay => {
+            YieldCalculation::TimeCompoundInterest(rate, AccrualPeriod::Day)
+        }
+    };
+    let ticks = calculation.ticks(
         token_version_diff
             .to_u64()
             .expect("Failed to convert token_version_diff"),
-    )
-    .expect("Failed to calculate yield amount")
-    .to_decimal()
+        elapsed,
+    );
+    let (amount, grown) = calculation
+        .calculate_accrued_amount(
+            &concordium_cis2::TokenAmountU64(amount.to_u64().expect("Failed to convert amount")),
+            ticks,
+            *factor,
+        )
+        .expect("Failed to calculate yield amount");
+    *factor = grown;
+    amount.to_decimal()
 }
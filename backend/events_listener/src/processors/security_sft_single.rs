@@ -64,6 +64,8 @@ impl RoleToString for AgentRole {
             AgentRole::Operator => "Operator".to_string(),
             AgentRole::SetCompliance => "SetCompliance".to_string(),
             AgentRole::SetTokenMetadata => "SetTokenMetadata".to_string(),
+            AgentRole::Snapshot => "Snapshot".to_string(),
+            AgentRole::SetDocument => "SetDocument".to_string(),
             AgentRole::SetIdentityRegistry => "SetIdentityRegistry".to_string(),
             AgentRole::Pause => "Pause".to_string(),
             AgentRole::UnPause => "UnPause".to_string(),
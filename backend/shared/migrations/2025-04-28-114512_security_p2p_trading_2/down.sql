@@ -0,0 +1,17 @@
+DROP TABLE IF EXISTS security_p2p_trading_order_fills;
+
+DROP TABLE IF EXISTS security_p2p_trading_orders;
+
+DROP TYPE IF EXISTS security_p2p_trading_order_status;
+
+DROP TYPE IF EXISTS security_p2p_trading_order_side;
+
+-- Postgres can not drop values from an enum type which is used by views.
+-- The `order_book` market type and the `order` exchange record type are left in place and only the rows using them are removed.
+DELETE FROM security_p2p_trading_markets
+WHERE
+       market_type = 'order_book';
+
+DELETE FROM security_p2p_exchange_records
+WHERE
+       exchange_record_type = 'order';
@@ -0,0 +1,45 @@
+ALTER TYPE security_p2p_trading_market_type ADD VALUE IF NOT EXISTS 'order_book';
+
+ALTER TYPE security_p2p_trading_exchange_record_type ADD VALUE IF NOT EXISTS 'order';
+
+CREATE TYPE security_p2p_trading_order_side AS ENUM('sell', 'buy');
+
+CREATE TYPE security_p2p_trading_order_status AS ENUM('open', 'filled', 'cancelled');
+
+CREATE TABLE security_p2p_trading_orders (
+       contract_address NUMERIC(20) NOT NULL REFERENCES security_p2p_trading_contracts (contract_address) ON DELETE cascade,
+       order_id NUMERIC(20) NOT NULL,
+       token_contract_address NUMERIC(20) NOT NULL REFERENCES listener_contracts (contract_address) ON DELETE cascade,
+       token_id NUMERIC(20) NOT NULL,
+       currency_token_id NUMERIC(20) NOT NULL,
+       currency_token_contract_address NUMERIC(20) NOT NULL,
+       owner VARCHAR NOT NULL,
+       side security_p2p_trading_order_side NOT NULL,
+       rate_numerator NUMERIC(78) NOT NULL,
+       rate_denominator NUMERIC(78) NOT NULL,
+       token_amount NUMERIC(78) NOT NULL,
+       remaining_token_amount NUMERIC(78) NOT NULL,
+       filled_currency_amount NUMERIC(78) NOT NULL DEFAULT 0,
+       status security_p2p_trading_order_status NOT NULL,
+       create_time TIMESTAMP NOT NULL,
+       update_time TIMESTAMP NOT NULL,
+       PRIMARY KEY (contract_address, order_id)
+);
+
+CREATE INDEX security_p2p_trading_orders_open_idx ON security_p2p_trading_orders (contract_address, token_contract_address, token_id)
+WHERE
+       status = 'open';
+
+CREATE TABLE security_p2p_trading_order_fills (
+       id uuid PRIMARY KEY NOT NULL,
+       block_height NUMERIC(20) NOT NULL,
+       txn_index NUMERIC(20) NOT NULL,
+       contract_address NUMERIC(20) NOT NULL,
+       order_id NUMERIC(20) NOT NULL,
+       taker VARCHAR NOT NULL,
+       token_amount NUMERIC(78) NOT NULL,
+       currency_amount NUMERIC(78) NOT NULL,
+       remaining_token_amount NUMERIC(78) NOT NULL,
+       create_time TIMESTAMP NOT NULL,
+       FOREIGN KEY (contract_address, order_id) REFERENCES security_p2p_trading_orders (contract_address, order_id) ON DELETE cascade
+);
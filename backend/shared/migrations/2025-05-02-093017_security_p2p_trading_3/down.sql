@@ -0,0 +1,10 @@
+ALTER TABLE security_p2p_trading_orders
+DROP COLUMN expiry,
+DROP COLUMN settlement;
+
+DROP TYPE security_p2p_trading_order_settlement;
+
+-- Enum values can not be removed from postgres enums. Orders using the value are removed instead.
+DELETE FROM security_p2p_trading_orders
+WHERE
+       status = 'expired';
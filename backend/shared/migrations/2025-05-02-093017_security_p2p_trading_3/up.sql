@@ -0,0 +1,7 @@
+ALTER TYPE security_p2p_trading_order_status ADD VALUE IF NOT EXISTS 'expired';
+
+CREATE TYPE security_p2p_trading_order_settlement AS ENUM('operator', 'escrow');
+
+ALTER TABLE security_p2p_trading_orders
+ADD COLUMN settlement security_p2p_trading_order_settlement NOT NULL DEFAULT 'operator',
+ADD COLUMN expiry TIMESTAMP;
@@ -0,0 +1,6 @@
+DROP TABLE security_p2p_trading_market_price_tiers;
+
+ALTER TABLE security_p2p_trading_markets
+DROP COLUMN max_investor_token_amount,
+DROP COLUMN close_time,
+DROP COLUMN open_time;
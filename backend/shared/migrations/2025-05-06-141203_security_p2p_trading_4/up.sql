@@ -0,0 +1,16 @@
+ALTER TABLE security_p2p_trading_markets
+ADD COLUMN open_time TIMESTAMP,
+ADD COLUMN close_time TIMESTAMP,
+ADD COLUMN max_investor_token_amount NUMERIC(78);
+
+CREATE TABLE security_p2p_trading_market_price_tiers (
+       contract_address NUMERIC(20) NOT NULL,
+       token_contract_address NUMERIC(20) NOT NULL,
+       tier_index INTEGER NOT NULL,
+       rate_numerator NUMERIC(78) NOT NULL,
+       rate_denominator NUMERIC(78) NOT NULL,
+       token_amount NUMERIC(78) NOT NULL,
+       sold_token_amount NUMERIC(78) NOT NULL DEFAULT 0,
+       PRIMARY KEY (contract_address, token_contract_address, tier_index),
+       FOREIGN KEY (contract_address, token_contract_address) REFERENCES security_p2p_trading_markets (contract_address, token_contract_address) ON DELETE cascade
+);
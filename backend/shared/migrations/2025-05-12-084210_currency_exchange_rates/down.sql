@@ -0,0 +1 @@
+DROP TABLE currency_exchange_rates;
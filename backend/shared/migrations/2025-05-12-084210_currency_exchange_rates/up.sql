@@ -0,0 +1,19 @@
+-- Rates used to value amounts of one currency token in another currency token.
+-- The rate converts the smallest unit of the currency token into the smallest unit of the target currency token.
+CREATE TABLE currency_exchange_rates (
+     currency_token_id NUMERIC(20) NOT NULL,
+     currency_token_contract_address NUMERIC(20) NOT NULL,
+     target_currency_token_id NUMERIC(20) NOT NULL,
+     target_currency_token_contract_address NUMERIC(20) NOT NULL,
+     rate NUMERIC NOT NULL,
+     rate_at TIMESTAMP NOT NULL DEFAULT NOW(),
+     created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+     updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+     PRIMARY KEY (
+          currency_token_id,
+          currency_token_contract_address,
+          target_currency_token_id,
+          target_currency_token_contract_address,
+          rate_at
+     )
+);
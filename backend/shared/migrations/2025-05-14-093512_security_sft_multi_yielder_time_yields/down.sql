@@ -0,0 +1,93 @@
+/* plpgsql-language-server:disable validation */
+DROP VIEW IF EXISTS holder_yields CASCADE;
+
+DROP FUNCTION IF EXISTS yield_compound_interest;
+
+DROP TABLE IF EXISTS security_sft_multi_yielder_token_versions;
+
+-- Enum values added to security_sft_multi_yielder_yield_type can not be removed.
+CREATE OR REPLACE VIEW holder_yields AS
+WITH
+    yields AS (
+        SELECT DISTINCT
+            ON (
+                yields.contract_address,
+                yields.token_contract_address,
+                yields.token_id,
+                yields.yield_contract_address,
+                yields.yield_token_id
+            ) yields.contract_address,
+            yields.token_contract_address,
+            yields.token_id,
+            y2.token_id AS previous_yield_token_id,
+            yields.yield_contract_address,
+            yields.yield_token_id,
+            yields.yield_rate_denominator,
+            yields.yield_rate_numerator,
+            yields.yield_type
+        FROM
+            security_sft_multi_yielder_yields AS yields
+            JOIN forest_project_token_contracts AS contracts ON yields.token_contract_address = contracts.contract_address
+            JOIN forest_projects AS projects ON contracts.forest_project_id = projects.id
+            LEFT JOIN security_sft_multi_yielder_yields AS y2 ON y2.token_contract_address = yields.token_contract_address
+            AND y2.token_id < yields.token_id
+            AND y2.token_contract_address = yields.token_contract_address
+        ORDER BY
+            yields.contract_address,
+            yields.token_contract_address,
+            yields.token_id,
+            yields.yield_contract_address,
+            yields.yield_token_id,
+            yields.yield_rate_denominator,
+            yields.yield_rate_numerator,
+            yields.yield_type,
+            y2.token_id DESC
+    )
+SELECT
+    yields.contract_address AS yielder_contract_address,
+    holder.holder_address,
+    holder.un_frozen_balance,
+    projects.id AS forest_project_id,
+    -- Yields
+    holder.cis2_address,
+    holder.token_id,
+    yields.token_id AS token_ver_to,
+    yields.previous_yield_token_id,
+    yields.yield_contract_address,
+    yields.yield_token_id,
+    yields.yield_rate_numerator,
+    yields.yield_rate_denominator,
+    yields.yield_type,
+    -- Yield Calculations
+    yields.token_id - GREATEST(
+        COALESCE(yields.previous_yield_token_id, 0),
+        holder.token_id
+    ) AS yield_period,
+    CASE
+        WHEN yields.yield_type = 'quantity' THEN holder.un_frozen_balance * (
+            yields.yield_rate_numerator::NUMERIC / yields.yield_rate_denominator::NUMERIC
+        )
+        WHEN yields.yield_type = 'simple_intrest' THEN holder.un_frozen_balance * (
+            yields.yield_rate_numerator::NUMERIC / yields.yield_rate_denominator::NUMERIC
+        ) * (
+            yields.token_id - GREATEST(
+                COALESCE(yields.previous_yield_token_id, 0),
+                holder.token_id
+            )
+        )
+        ELSE NULL
+    END AS yield_value
+FROM
+    cis2_token_holders AS holder
+    JOIN forest_project_token_contracts AS contracts ON holder.cis2_address = contracts.contract_address
+    JOIN forest_projects AS projects ON contracts.forest_project_id = projects.id
+    JOIN yields ON yields.token_contract_address = holder.cis2_address
+    AND yields.token_id > holder.token_id
+WHERE
+    holder.un_frozen_balance > 0
+ORDER BY
+    holder.cis2_address,
+    holder.token_id,
+    holder.holder_address,
+    yields.contract_address,
+    yields.token_id;
@@ -0,0 +1 @@
+DROP TABLE cis2_permit_nonces;
@@ -0,0 +1,8 @@
+-- The next CIS-3 permit nonce of each account which signed a permit on a security token contract.
+CREATE TABLE cis2_permit_nonces (
+       cis2_address NUMERIC(20) NOT NULL REFERENCES listener_contracts (contract_address) ON DELETE CASCADE,
+       account_address VARCHAR NOT NULL,
+       nonce NUMERIC(20) NOT NULL,
+       update_time TIMESTAMP NOT NULL,
+       PRIMARY KEY (cis2_address, account_address)
+);
@@ -0,0 +1,4 @@
+ALTER TABLE security_mint_funds
+DROP COLUMN deadline,
+DROP COLUMN hard_cap,
+DROP COLUMN soft_cap;
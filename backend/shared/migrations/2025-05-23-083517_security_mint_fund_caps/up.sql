@@ -0,0 +1,5 @@
+-- Funding targets of a fund. Null for funds added before caps & deadlines were introduced.
+ALTER TABLE security_mint_funds
+ADD COLUMN soft_cap NUMERIC(78),
+ADD COLUMN hard_cap NUMERIC(78),
+ADD COLUMN deadline TIMESTAMP;
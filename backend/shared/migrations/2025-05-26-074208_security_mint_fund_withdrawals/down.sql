@@ -0,0 +1,4 @@
+-- Enum values can not be removed from postgres enums. Records using the value are removed instead.
+DELETE FROM security_mint_fund_investment_records
+WHERE
+       investment_record_type = 'withdrawn';
@@ -0,0 +1,2 @@
+-- Investments withdrawn by the investor while the fund is open.
+ALTER TYPE security_mint_fund_investment_record_type ADD VALUE IF NOT EXISTS 'withdrawn';
@@ -0,0 +1,9 @@
+ALTER TABLE security_mint_funds
+DROP COLUMN identity_attribute_value,
+DROP COLUMN identity_attribute_tag,
+DROP COLUMN identity_registry_address,
+DROP COLUMN investor_access,
+DROP COLUMN max_investment,
+DROP COLUMN min_investment;
+
+DROP TYPE security_mint_fund_investor_access;
@@ -0,0 +1,11 @@
+CREATE TYPE security_mint_fund_investor_access AS ENUM('any', 'allowlist', 'identity_attribute');
+
+-- Per investor investment limits and the investors allowed to invest in a fund.
+-- The identity attribute columns are set when only investors with the identity attribute are allowed to invest.
+ALTER TABLE security_mint_funds
+ADD COLUMN min_investment NUMERIC(78),
+ADD COLUMN max_investment NUMERIC(78),
+ADD COLUMN investor_access security_mint_fund_investor_access NOT NULL DEFAULT 'any',
+ADD COLUMN identity_registry_address NUMERIC(20),
+ADD COLUMN identity_attribute_tag INTEGER,
+ADD COLUMN identity_attribute_value VARCHAR;
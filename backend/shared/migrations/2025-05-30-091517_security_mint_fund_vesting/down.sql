@@ -0,0 +1,3 @@
+ALTER TABLE security_mint_funds
+DROP COLUMN vesting_period_millis,
+DROP COLUMN vesting_lock_up_millis;
@@ -0,0 +1,5 @@
+-- Lock-up & vesting schedule of the security tokens claimed from a fund.
+-- Both columns are NULL when the claimed tokens are transferable right away.
+ALTER TABLE security_mint_funds
+ADD COLUMN vesting_lock_up_millis NUMERIC(20),
+ADD COLUMN vesting_period_millis NUMERIC(20);
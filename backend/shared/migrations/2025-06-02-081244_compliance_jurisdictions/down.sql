@@ -0,0 +1,3 @@
+DROP TABLE compliance_jurisdiction_rules;
+DROP TYPE compliance_list_type;
+DROP TYPE compliance_jurisdiction;
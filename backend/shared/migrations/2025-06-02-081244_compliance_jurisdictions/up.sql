@@ -0,0 +1,12 @@
+CREATE TYPE compliance_jurisdiction AS ENUM('nationality', 'country_of_residence');
+CREATE TYPE compliance_list_type AS ENUM('allow', 'block');
+
+-- Countries in the allow & block lists of the jurisdictions compliance modules.
+CREATE TABLE compliance_jurisdiction_rules (
+       module_address NUMERIC(20) NOT NULL REFERENCES listener_contracts (contract_address) ON DELETE CASCADE,
+       jurisdiction compliance_jurisdiction NOT NULL,
+       list_type compliance_list_type NOT NULL,
+       country VARCHAR NOT NULL,
+       create_time TIMESTAMP NOT NULL,
+       PRIMARY KEY (module_address, jurisdiction, list_type, country)
+);
@@ -0,0 +1,5 @@
+ALTER TABLE identity_registry_identities
+DROP COLUMN invalidated_at,
+DROP COLUMN invalid_reason;
+
+DROP TYPE identity_invalid_reason;
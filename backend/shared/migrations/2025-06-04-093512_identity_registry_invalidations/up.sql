@@ -0,0 +1,6 @@
+CREATE TYPE identity_invalid_reason AS ENUM('not_yet_valid', 'expired', 'credential_not_active');
+
+-- Set when a re-verification of the identity registry finds the identity no longer valid.
+ALTER TABLE identity_registry_identities
+ADD COLUMN invalidated_at TIMESTAMP,
+ADD COLUMN invalid_reason identity_invalid_reason;
@@ -0,0 +1,4 @@
+DROP INDEX identity_registry_identities_linked_identity_address_idx;
+
+ALTER TABLE identity_registry_identities
+DROP COLUMN linked_identity_address;
@@ -0,0 +1,5 @@
+-- Set for addresses linked to an identity, to the address the identity was registered with.
+ALTER TABLE identity_registry_identities
+ADD COLUMN linked_identity_address VARCHAR;
+
+CREATE INDEX identity_registry_identities_linked_identity_address_idx ON identity_registry_identities (identity_registry_address, linked_identity_address);
@@ -0,0 +1,2 @@
+DROP TABLE cis2_snapshot_balances;
+DROP TABLE cis2_snapshots;
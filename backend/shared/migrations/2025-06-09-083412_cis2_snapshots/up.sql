@@ -0,0 +1,21 @@
+-- The snapshots of the balances taken on a security token contract.
+CREATE TABLE cis2_snapshots (
+       cis2_address NUMERIC(20) NOT NULL REFERENCES listener_contracts (contract_address) ON DELETE CASCADE,
+       snapshot_id NUMERIC(20) NOT NULL,
+       block_height NUMERIC(20) NOT NULL,
+       txn_index NUMERIC(20) NOT NULL,
+       create_time TIMESTAMP NOT NULL,
+       PRIMARY KEY (cis2_address, snapshot_id)
+);
+
+-- The balances of the token holders at a snapshot, copied from `cis2_token_holders` when the snapshot is taken.
+CREATE TABLE cis2_snapshot_balances (
+       cis2_address NUMERIC(20) NOT NULL,
+       snapshot_id NUMERIC(20) NOT NULL,
+       token_id NUMERIC(20) NOT NULL,
+       holder_address VARCHAR NOT NULL,
+       frozen_balance NUMERIC(78) NOT NULL,
+       un_frozen_balance NUMERIC(78) NOT NULL,
+       PRIMARY KEY (cis2_address, snapshot_id, token_id, holder_address),
+       FOREIGN KEY (cis2_address, snapshot_id) REFERENCES cis2_snapshots (cis2_address, snapshot_id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+DROP TABLE cis2_proposals;
+DROP TYPE cis2_proposal_status;
+DROP TABLE cis2_governances;
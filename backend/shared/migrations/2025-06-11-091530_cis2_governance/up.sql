@@ -0,0 +1,26 @@
+-- The approvers governing a security token contract. Absent when the contract is not governed.
+CREATE TABLE cis2_governances (
+       cis2_address NUMERIC(20) PRIMARY KEY REFERENCES listener_contracts (contract_address) ON DELETE CASCADE,
+       approvers VARCHAR[] NOT NULL,
+       threshold INTEGER NOT NULL,
+       delay_millis NUMERIC(20) NOT NULL,
+       update_time TIMESTAMP NOT NULL
+);
+
+CREATE TYPE cis2_proposal_status AS ENUM('pending', 'executed', 'cancelled');
+
+-- The proposals to invoke an entrypoint of a governed security token contract.
+CREATE TABLE cis2_proposals (
+       cis2_address NUMERIC(20) NOT NULL REFERENCES listener_contracts (contract_address) ON DELETE CASCADE,
+       proposal_id NUMERIC(20) NOT NULL,
+       proposer VARCHAR NOT NULL,
+       entrypoint VARCHAR NOT NULL,
+       execute_after TIMESTAMP NOT NULL,
+       approvals VARCHAR[] NOT NULL,
+       status cis2_proposal_status NOT NULL,
+       create_time TIMESTAMP NOT NULL,
+       update_time TIMESTAMP NOT NULL,
+       PRIMARY KEY (cis2_address, proposal_id)
+);
+
+CREATE INDEX cis2_proposals_status_idx ON cis2_proposals (cis2_address, status);
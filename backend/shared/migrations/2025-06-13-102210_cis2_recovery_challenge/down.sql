@@ -0,0 +1,8 @@
+DELETE FROM cis2_recovery_records WHERE status <> 'recovered';
+
+ALTER TABLE cis2_recovery_records
+       DROP COLUMN update_time,
+       DROP COLUMN execute_after,
+       DROP COLUMN status;
+
+DROP TYPE cis2_recovery_status;
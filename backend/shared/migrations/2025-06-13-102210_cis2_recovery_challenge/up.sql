@@ -0,0 +1,12 @@
+CREATE TYPE cis2_recovery_status AS ENUM('initiated', 'cancelled', 'recovered');
+
+-- Recoveries are initiated and can be cancelled by the holder until they are executed after the challenge window.
+-- Records indexed before recoveries were two step have been executed.
+ALTER TABLE cis2_recovery_records
+       ADD COLUMN status cis2_recovery_status NOT NULL DEFAULT 'recovered',
+       ADD COLUMN execute_after TIMESTAMP,
+       ADD COLUMN update_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP;
+
+ALTER TABLE cis2_recovery_records
+       ALTER COLUMN status DROP DEFAULT,
+       ALTER COLUMN update_time DROP DEFAULT;
@@ -0,0 +1 @@
+DROP TABLE cis2_documents;
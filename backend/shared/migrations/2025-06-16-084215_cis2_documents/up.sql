@@ -0,0 +1,16 @@
+-- The documents of the tokens of a security token contract (ERC-1643), like offering documents & legal contracts.
+CREATE TABLE cis2_documents (
+       cis2_address NUMERIC(20) NOT NULL REFERENCES listener_contracts (contract_address) ON DELETE CASCADE,
+       token_id NUMERIC(20) NOT NULL,
+       name VARCHAR NOT NULL,
+       uri VARCHAR NOT NULL,
+       -- Hex encoded SHA-256 hash of the content of the document.
+       content_hash VARCHAR NOT NULL,
+       -- The time the document was set on chain.
+       document_time TIMESTAMP NOT NULL,
+       block_height NUMERIC(20) NOT NULL,
+       txn_index NUMERIC(20) NOT NULL,
+       PRIMARY KEY (cis2_address, token_id, name)
+);
+
+CREATE INDEX cis2_documents_name_idx ON cis2_documents (cis2_address, name);
@@ -0,0 +1,2 @@
+ALTER TABLE security_p2p_trading_markets
+DROP COLUMN settlement;
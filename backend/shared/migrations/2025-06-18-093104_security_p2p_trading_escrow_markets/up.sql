@@ -0,0 +1,3 @@
+-- Transfer markets whose liquidity is escrowed by the trading contract.
+ALTER TABLE security_p2p_trading_markets
+ADD COLUMN settlement security_p2p_trading_order_settlement NOT NULL DEFAULT 'operator';
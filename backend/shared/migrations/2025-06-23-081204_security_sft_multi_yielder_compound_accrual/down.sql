@@ -0,0 +1,206 @@
+DROP VIEW IF EXISTS holder_yields CASCADE;
+
+DROP AGGREGATE IF EXISTS yield_compound_accrual (NUMERIC, NUMERIC, NUMERIC);
+
+DROP FUNCTION IF EXISTS yield_compound_accrual_step;
+
+DROP FUNCTION IF EXISTS yield_compound_factor;
+
+-- Every yield of a token version accrues from the previous token version (or the holder's token version if it is newer)
+-- Version based yields accrue for every version in between and time based yields for every complete period elapsed.
+-- Time based yields do not accrue from a holder's token version which was never added to the yielder.
+-- Pro rata pools of unexpired token versions are shared by the holders of the entitled token versions
+-- in proportion to their unclaimed balance at the snapshot taken when the pool was deposited.
+-- Yields claimed against a checkpoint accrue from the checkpoint instead of the holder's token version.
+DROP VIEW IF EXISTS holder_yields CASCADE;
+
+CREATE VIEW holder_yields AS
+SELECT
+    yields.contract_address AS yielder_contract_address,
+    holder.holder_address,
+    holder.un_frozen_balance,
+    projects.id AS forest_project_id,
+    -- Yields
+    holder.cis2_address,
+    holder.token_id,
+    yields.token_id AS token_ver_to,
+    previous_version.token_id AS previous_yield_token_id,
+    yields.yield_contract_address,
+    yields.yield_token_id,
+    yields.yield_rate_numerator,
+    yields.yield_rate_denominator,
+    yields.yield_type::TEXT AS yield_type,
+    -- Yield Calculations
+    period.ticks AS yield_period,
+    CASE
+        WHEN yields.yield_type::TEXT = 'quantity' THEN DIV(
+            holder.un_frozen_balance * yields.yield_rate_numerator,
+            yields.yield_rate_denominator
+        )
+        WHEN yields.yield_type::TEXT IN (
+            'simple_intrest',
+            'simple_interest_per_second',
+            'simple_interest_per_day'
+        ) THEN DIV(
+            holder.un_frozen_balance * period.ticks * yields.yield_rate_numerator,
+            yields.yield_rate_denominator
+        )
+        WHEN yields.yield_type::TEXT IN (
+            'compound_interest',
+            'compound_interest_per_second',
+            'compound_interest_per_day'
+        ) THEN yield_compound_interest (
+            holder.un_frozen_balance,
+            yields.yield_rate_numerator,
+            yields.yield_rate_denominator,
+            period.ticks
+        )
+        ELSE NULL
+    END AS yield_value
+FROM
+    cis2_token_holders AS holder
+    JOIN forest_project_token_contracts AS contracts ON holder.cis2_address = contracts.contract_address
+    JOIN forest_projects AS projects ON contracts.forest_project_id = projects.id
+    JOIN security_sft_multi_yielder_yields AS yields ON yields.token_contract_address = holder.cis2_address
+    AND yields.token_id > holder.token_id
+    LEFT JOIN security_sft_multi_yielder_checkpoints AS checkpoint ON checkpoint.contract_address = yields.contract_address
+    AND checkpoint.token_contract_address = holder.cis2_address
+    AND checkpoint.token_id = holder.token_id
+    AND checkpoint.holder_address = holder.holder_address
+    CROSS JOIN LATERAL (
+        SELECT
+            GREATEST(
+                holder.token_id,
+                COALESCE(checkpoint.claimed_token_id, holder.token_id)
+            ) AS token_id
+    ) AS accrual_start
+    JOIN security_sft_multi_yielder_token_versions AS yield_version ON yield_version.contract_address = yields.contract_address
+    AND yield_version.token_contract_address = yields.token_contract_address
+    AND yield_version.token_id = yields.token_id
+    LEFT JOIN LATERAL (
+        SELECT
+            versions.token_id,
+            versions.create_time
+        FROM
+            security_sft_multi_yielder_token_versions AS versions
+        WHERE
+            versions.contract_address = yields.contract_address
+            AND versions.token_contract_address = yields.token_contract_address
+            AND versions.token_id < yields.token_id
+        ORDER BY
+            versions.token_id DESC
+        LIMIT
+            1
+    ) AS previous_version ON TRUE
+    LEFT JOIN security_sft_multi_yielder_token_versions AS holder_version ON holder_version.contract_address = yields.contract_address
+    AND holder_version.token_contract_address = holder.cis2_address
+    AND holder_version.token_id = accrual_start.token_id
+    CROSS JOIN LATERAL (
+        SELECT
+            CASE
+                WHEN previous_version.token_id >= accrual_start.token_id THEN previous_version.create_time
+                ELSE holder_version.create_time
+            END AS from_time
+    ) AS accrual
+    CROSS JOIN LATERAL (
+        SELECT
+            CASE
+                WHEN yields.yield_type::TEXT IN (
+                    'simple_interest_per_second',
+                    'compound_interest_per_second'
+                ) THEN FLOOR(
+                    GREATEST(
+                        COALESCE(
+                            EXTRACT(
+                                EPOCH
+                                FROM
+                                    yield_version.create_time - accrual.from_time
+                            ),
+                            0
+                        ),
+                        0
+                    )
+                )
+                WHEN yields.yield_type::TEXT IN (
+                    'simple_interest_per_day',
+                    'compound_interest_per_day'
+                ) THEN FLOOR(
+                    GREATEST(
+                        COALESCE(
+                            EXTRACT(
+                                EPOCH
+                                FROM
+                                    yield_version.create_time - accrual.from_time
+                            ),
+                            0
+                        ),
+                        0
+                    ) / 86400
+                )
+                ELSE yields.token_id - GREATEST(
+                    COALESCE(previous_version.token_id, 0),
+                    accrual_start.token_id
+                )
+            END AS ticks
+    ) AS period
+WHERE
+    holder.un_frozen_balance > 0
+    AND yields.token_id > accrual_start.token_id
+
+UNION ALL
+SELECT
+    pools.contract_address AS yielder_contract_address,
+    holder.holder_address,
+    holder.un_frozen_balance,
+    projects.id AS forest_project_id,
+    -- Yields
+    holder.cis2_address,
+    holder.token_id,
+    pools.token_id AS token_ver_to,
+    NULL::NUMERIC AS previous_yield_token_id,
+    pools.yield_contract_address,
+    pools.yield_token_id,
+    pools.amount AS yield_rate_numerator,
+    pools.supply AS yield_rate_denominator,
+    'pro_rata' AS yield_type,
+    -- Yield Calculations
+    0 AS yield_period,
+    LEAST(
+        DIV(entitlement.amount * pools.amount, pools.supply),
+        pools.amount - pools.claimed_amount - pools.returned_amount
+    ) AS yield_value
+FROM
+    cis2_token_holders AS holder
+    JOIN forest_project_token_contracts AS contracts ON holder.cis2_address = contracts.contract_address
+    JOIN forest_projects AS projects ON contracts.forest_project_id = projects.id
+    JOIN security_sft_multi_yielder_pools AS pools ON pools.token_contract_address = holder.cis2_address
+    AND holder.token_id = ANY (pools.token_ids)
+    JOIN cis2_snapshot_balances AS snapshot ON snapshot.cis2_address = pools.token_contract_address
+    AND snapshot.snapshot_id = pools.snapshot_id
+    AND snapshot.token_id = holder.token_id
+    AND snapshot.holder_address = holder.holder_address
+    LEFT JOIN security_sft_multi_yielder_pool_claims AS claim ON claim.contract_address = pools.contract_address
+    AND claim.token_contract_address = pools.token_contract_address
+    AND claim.token_id = pools.token_id
+    AND claim.yield_contract_address = pools.yield_contract_address
+    AND claim.yield_token_id = pools.yield_token_id
+    AND claim.from_token_id = holder.token_id
+    AND claim.holder_address = holder.holder_address
+    LEFT JOIN security_sft_multi_yielder_checkpoints AS checkpoint ON checkpoint.contract_address = pools.contract_address
+    AND checkpoint.token_contract_address = holder.cis2_address
+    AND checkpoint.token_id = holder.token_id
+    AND checkpoint.holder_address = holder.holder_address
+    CROSS JOIN LATERAL (
+        SELECT
+            snapshot.frozen_balance + snapshot.un_frozen_balance - COALESCE(claim.security_amount, 0) AS amount
+    ) AS entitlement
+WHERE
+    entitlement.amount > 0
+    AND pools.expiry > NOW()
+    AND pools.token_id > COALESCE(checkpoint.claimed_token_id, holder.token_id)
+ORDER BY
+    cis2_address,
+    token_id,
+    holder_address,
+    yielder_contract_address,
+    token_ver_to;
@@ -0,0 +1,2 @@
+ALTER TABLE security_mint_fund_investors
+DROP COLUMN token_amount_released;
@@ -0,0 +1,3 @@
+-- Claimed security tokens of an investor which have been released from the vesting schedule of the fund.
+ALTER TABLE security_mint_fund_investors
+ADD COLUMN token_amount_released NUMERIC(78) NOT NULL DEFAULT 0;
@@ -10,8 +10,10 @@ use tracing::instrument;
 
 use crate::db_shared::{DbConn, DbResult};
 use crate::schema::{
-    cis2_agents, cis2_compliances, cis2_identity_registries, cis2_operators, cis2_recovery_records,
-    cis2_token_holder_balance_updates, cis2_token_holders, cis2_tokens,
+    cis2_agents, cis2_compliances, cis2_documents, cis2_governances, cis2_identity_registries,
+    cis2_operators, cis2_permit_nonces, cis2_proposals, cis2_recovery_records,
+    cis2_snapshot_balances, cis2_snapshots, cis2_token_holder_balance_updates, cis2_token_holders,
+    cis2_tokens,
 };
 
 #[derive(
@@ -732,7 +734,30 @@ impl Operator {
     }
 }
 
-#[derive(Selectable, Queryable, Identifiable, Insertable, Debug, PartialEq)]
+#[derive(
+    diesel_derive_enum::DbEnum, Debug, PartialEq, Enum, Clone, Copy, Serialize, Deserialize, Eq,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::Cis2RecoveryStatus"]
+pub enum RecoveryStatus {
+    /// The recovery is in its challenge window and can be cancelled by the holder.
+    Initiated,
+    Cancelled,
+    /// The state of the holder has been moved to the recovered address.
+    Recovered,
+}
+
+/// The latest recovery of a holder of a security token contract.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+)]
 #[diesel(table_name = cis2_recovery_records)]
 #[diesel(primary_key(cis2_address, holder_address))]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -740,26 +765,522 @@ pub struct RecoveryRecord {
     pub cis2_address:      Decimal,
     pub holder_address:    String,
     pub recovered_address: String,
+    pub status:            RecoveryStatus,
+    /// The end of the challenge window, `None` for recoveries executed without one.
+    pub execute_after:     Option<NaiveDateTime>,
+    pub update_time:       NaiveDateTime,
 }
 impl RecoveryRecord {
-    pub fn new(
+    /// Inserts the record or replaces the previous recovery of the holder.
+    #[instrument(skip_all, fields(self))]
+    pub fn upsert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(cis2_recovery_records::table)
+            .values(self)
+            .on_conflict((
+                cis2_recovery_records::cis2_address,
+                cis2_recovery_records::holder_address,
+            ))
+            .do_update()
+            .set(self)
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find(
+        conn: &mut DbConn,
         cis2_address: Decimal,
-        holder_address: &Address,
-        recovered_address: &Address,
-    ) -> Self {
-        Self {
-            cis2_address,
-            holder_address: holder_address.to_string(),
-            recovered_address: recovered_address.to_string(),
+        holder_address: &str,
+    ) -> DbResult<Option<RecoveryRecord>> {
+        let record = cis2_recovery_records::table
+            .filter(cis2_recovery_records::cis2_address.eq(cis2_address))
+            .filter(cis2_recovery_records::holder_address.eq(holder_address))
+            .first(conn)
+            .optional()?;
+        Ok(record)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn list(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        status: Option<RecoveryStatus>,
+        page: i64,
+        page_size: i64,
+    ) -> DbResult<(Vec<RecoveryRecord>, i64)> {
+        let mut query = cis2_recovery_records::table
+            .filter(cis2_recovery_records::cis2_address.eq(cis2_address))
+            .into_boxed();
+        let mut count_query = cis2_recovery_records::table
+            .filter(cis2_recovery_records::cis2_address.eq(cis2_address))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(cis2_recovery_records::status.eq(status));
+            count_query = count_query.filter(cis2_recovery_records::status.eq(status));
         }
+        let records = query
+            .select(RecoveryRecord::as_select())
+            .order(cis2_recovery_records::update_time.desc())
+            .limit(page_size)
+            .offset(page * page_size)
+            .get_results(conn)?;
+        let total_count: i64 = count_query.count().get_result(conn)?;
+        let page_count = (total_count as f64 / page_size as f64).ceil() as i64;
+        Ok((records, page_count))
     }
+}
 
+/// The next CIS-3 permit nonce of an account on a security token contract.
+#[derive(Selectable, Queryable, Identifiable, Insertable, Debug, PartialEq)]
+#[diesel(table_name = cis2_permit_nonces)]
+#[diesel(primary_key(cis2_address, account_address))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PermitNonce {
+    pub cis2_address:    Decimal,
+    pub account_address: String,
+    pub nonce:           Decimal,
+    pub update_time:     NaiveDateTime,
+}
+
+impl PermitNonce {
     #[instrument(skip_all, fields(self))]
-    pub fn insert(&self, conn: &mut DbConn) -> DbResult<()> {
-        diesel::insert_into(cis2_recovery_records::table)
+    pub fn upsert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(cis2_permit_nonces::table)
             .values(self)
+            .on_conflict((
+                cis2_permit_nonces::cis2_address,
+                cis2_permit_nonces::account_address,
+            ))
+            .do_update()
+            .set((
+                cis2_permit_nonces::nonce.eq(&self.nonce),
+                cis2_permit_nonces::update_time.eq(&self.update_time),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        account_address: &str,
+    ) -> DbResult<Option<PermitNonce>> {
+        let nonce = cis2_permit_nonces::table
+            .filter(cis2_permit_nonces::cis2_address.eq(cis2_address))
+            .filter(cis2_permit_nonces::account_address.eq(account_address))
+            .first(conn)
+            .optional()?;
+        Ok(nonce)
+    }
+}
+
+/// A snapshot of the balances taken on a security token contract.
+#[derive(Selectable, Queryable, Identifiable, Insertable, Debug, PartialEq)]
+#[diesel(table_name = cis2_snapshots)]
+#[diesel(primary_key(cis2_address, snapshot_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Snapshot {
+    pub cis2_address: Decimal,
+    pub snapshot_id:  Decimal,
+    pub block_height: Decimal,
+    pub txn_index:    Decimal,
+    pub create_time:  NaiveDateTime,
+}
+
+impl Snapshot {
+    /// Inserts the snapshot along with the current balances of the token holders.
+    /// Returns the number of balances recorded.
+    #[instrument(skip_all, fields(self))]
+    pub fn insert(&self, conn: &mut DbConn) -> DbResult<usize> {
+        conn.transaction(|conn| {
+            diesel::insert_into(cis2_snapshots::table)
+                .values(self)
+                .execute(conn)?;
+            SnapshotBalance::insert_current(conn, self.cis2_address, self.snapshot_id)
+        })
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        snapshot_id: Decimal,
+    ) -> DbResult<Option<Snapshot>> {
+        let snapshot = cis2_snapshots::table
+            .filter(cis2_snapshots::cis2_address.eq(cis2_address))
+            .filter(cis2_snapshots::snapshot_id.eq(snapshot_id))
+            .first(conn)
+            .optional()?;
+        Ok(snapshot)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_latest(conn: &mut DbConn, cis2_address: Decimal) -> DbResult<Option<Snapshot>> {
+        let snapshot = cis2_snapshots::table
+            .filter(cis2_snapshots::cis2_address.eq(cis2_address))
+            .order(cis2_snapshots::snapshot_id.desc())
+            .first(conn)
+            .optional()?;
+        Ok(snapshot)
+    }
+}
+
+/// The balance of a token holder at a snapshot. Holders without a balance at the
+/// snapshot are not recorded.
+#[derive(Selectable, Queryable, Identifiable, Insertable, Debug, PartialEq)]
+#[diesel(table_name = cis2_snapshot_balances)]
+#[diesel(primary_key(cis2_address, snapshot_id, token_id, holder_address))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SnapshotBalance {
+    pub cis2_address:      Decimal,
+    pub snapshot_id:       Decimal,
+    pub token_id:          Decimal,
+    pub holder_address:    String,
+    pub frozen_balance:    Decimal,
+    pub un_frozen_balance: Decimal,
+}
+
+impl SnapshotBalance {
+    /// Copies the current balances of the token holders of the contract into the snapshot.
+    #[instrument(skip(conn))]
+    pub fn insert_current(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        snapshot_id: Decimal,
+    ) -> DbResult<usize> {
+        let holders = cis2_token_holders::table
+            .filter(cis2_token_holders::cis2_address.eq(cis2_address))
+            .filter(
+                cis2_token_holders::frozen_balance
+                    .gt(Decimal::ZERO)
+                    .or(cis2_token_holders::un_frozen_balance.gt(Decimal::ZERO)),
+            )
+            .select((
+                cis2_token_holders::cis2_address,
+                snapshot_id.into_sql::<diesel::sql_types::Numeric>(),
+                cis2_token_holders::token_id,
+                cis2_token_holders::holder_address,
+                cis2_token_holders::frozen_balance,
+                cis2_token_holders::un_frozen_balance,
+            ));
+        let inserted_rows = diesel::insert_into(cis2_snapshot_balances::table)
+            .values(holders)
+            .into_columns((
+                cis2_snapshot_balances::cis2_address,
+                cis2_snapshot_balances::snapshot_id,
+                cis2_snapshot_balances::token_id,
+                cis2_snapshot_balances::holder_address,
+                cis2_snapshot_balances::frozen_balance,
+                cis2_snapshot_balances::un_frozen_balance,
+            ))
             .execute(conn)?;
+        Ok(inserted_rows)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        snapshot_id: Decimal,
+        token_id: Decimal,
+        holder_address: &str,
+    ) -> DbResult<Option<SnapshotBalance>> {
+        let balance = cis2_snapshot_balances::table
+            .filter(cis2_snapshot_balances::cis2_address.eq(cis2_address))
+            .filter(cis2_snapshot_balances::snapshot_id.eq(snapshot_id))
+            .filter(cis2_snapshot_balances::token_id.eq(token_id))
+            .filter(cis2_snapshot_balances::holder_address.eq(holder_address))
+            .first(conn)
+            .optional()?;
+        Ok(balance)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn list(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        snapshot_id: Decimal,
+        token_id: Decimal,
+        page: i64,
+        page_size: i64,
+    ) -> DbResult<(Vec<SnapshotBalance>, i64)> {
+        let query = cis2_snapshot_balances::table
+            .filter(cis2_snapshot_balances::cis2_address.eq(cis2_address))
+            .filter(cis2_snapshot_balances::snapshot_id.eq(snapshot_id))
+            .filter(cis2_snapshot_balances::token_id.eq(token_id));
+        let balances = query
+            .select(SnapshotBalance::as_select())
+            .order(cis2_snapshot_balances::holder_address)
+            .offset(page * page_size)
+            .limit(page_size)
+            .get_results(conn)?;
+        let total_count: i64 = query.count().get_result(conn)?;
+        Ok((balances, total_count))
+    }
+}
 
+/// The approvers governing a security token contract.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+)]
+#[diesel(table_name = cis2_governances)]
+#[diesel(primary_key(cis2_address))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Governance {
+    pub cis2_address: Decimal,
+    pub approvers:    Vec<Option<String>>,
+    /// The number of approvals required to execute a proposal.
+    pub threshold:    i32,
+    /// The time after a proposal is created before it can be executed.
+    pub delay_millis: Decimal,
+    pub update_time:  NaiveDateTime,
+}
+
+impl Governance {
+    #[instrument(skip_all, fields(contract = self.cis2_address.to_string()))]
+    pub fn upsert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(cis2_governances::table)
+            .values(self)
+            .on_conflict(cis2_governances::cis2_address)
+            .do_update()
+            .set(self)
+            .execute(conn)?;
         Ok(())
     }
+
+    #[instrument(skip(conn))]
+    pub fn delete(conn: &mut DbConn, cis2_address: Decimal) -> DbResult<()> {
+        diesel::delete(cis2_governances::table)
+            .filter(cis2_governances::cis2_address.eq(cis2_address))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find(conn: &mut DbConn, cis2_address: Decimal) -> DbResult<Option<Governance>> {
+        let governance = cis2_governances::table
+            .filter(cis2_governances::cis2_address.eq(cis2_address))
+            .first(conn)
+            .optional()?;
+        Ok(governance)
+    }
+}
+
+#[derive(
+    diesel_derive_enum::DbEnum, Debug, PartialEq, Enum, Clone, Copy, Serialize, Deserialize, Eq,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::Cis2ProposalStatus"]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// A proposal to invoke an entrypoint of a governed security token contract.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+)]
+#[diesel(table_name = cis2_proposals)]
+#[diesel(primary_key(cis2_address, proposal_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Proposal {
+    pub cis2_address:  Decimal,
+    pub proposal_id:   Decimal,
+    pub proposer:      String,
+    pub entrypoint:    String,
+    /// The time from which the proposal can be executed.
+    pub execute_after: NaiveDateTime,
+    /// The approvers which have approved the proposal.
+    pub approvals:     Vec<Option<String>>,
+    pub status:        ProposalStatus,
+    pub create_time:   NaiveDateTime,
+    pub update_time:   NaiveDateTime,
+}
+
+impl Proposal {
+    #[instrument(skip_all, fields(contract = self.cis2_address.to_string(), proposal_id = self.proposal_id.to_string()))]
+    pub fn insert(&self, conn: &mut DbConn) -> DbResult<Self> {
+        let proposal = diesel::insert_into(cis2_proposals::table)
+            .values(self)
+            .returning(Self::as_returning())
+            .get_result(conn)?;
+        Ok(proposal)
+    }
+
+    #[instrument(skip_all, fields(contract = self.cis2_address.to_string(), proposal_id = self.proposal_id.to_string()))]
+    pub fn update(&self, conn: &mut DbConn) -> DbResult<Self> {
+        let proposal = diesel::update(cis2_proposals::table)
+            .filter(cis2_proposals::cis2_address.eq(self.cis2_address))
+            .filter(cis2_proposals::proposal_id.eq(self.proposal_id))
+            .set(self)
+            .returning(Self::as_returning())
+            .get_result(conn)?;
+        Ok(proposal)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        proposal_id: Decimal,
+    ) -> DbResult<Option<Proposal>> {
+        let proposal = cis2_proposals::table
+            .filter(cis2_proposals::cis2_address.eq(cis2_address))
+            .filter(cis2_proposals::proposal_id.eq(proposal_id))
+            .first(conn)
+            .optional()?;
+        Ok(proposal)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn list(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        status: Option<ProposalStatus>,
+        page: i64,
+        page_size: i64,
+    ) -> DbResult<(Vec<Proposal>, i64)> {
+        let mut query = cis2_proposals::table
+            .filter(cis2_proposals::cis2_address.eq(cis2_address))
+            .into_boxed();
+        let mut count_query = cis2_proposals::table
+            .filter(cis2_proposals::cis2_address.eq(cis2_address))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(cis2_proposals::status.eq(status));
+            count_query = count_query.filter(cis2_proposals::status.eq(status));
+        }
+        let proposals = query
+            .select(Proposal::as_select())
+            .order(cis2_proposals::proposal_id.desc())
+            .limit(page_size)
+            .offset(page * page_size)
+            .get_results(conn)?;
+        let total_count: i64 = count_query.count().get_result(conn)?;
+        let page_count = (total_count as f64 / page_size as f64).ceil() as i64;
+        Ok((proposals, page_count))
+    }
+}
+
+/// A document of a token of a security token contract (ERC-1643).
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+)]
+#[diesel(table_name = cis2_documents)]
+#[diesel(primary_key(cis2_address, token_id, name))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TokenDocument {
+    pub cis2_address:  Decimal,
+    pub token_id:      Decimal,
+    pub name:          String,
+    pub uri:           String,
+    /// Hex encoded SHA-256 hash of the content of the document.
+    pub content_hash:  String,
+    /// The time the document was set on chain.
+    pub document_time: NaiveDateTime,
+    pub block_height:  Decimal,
+    pub txn_index:     Decimal,
+}
+
+impl TokenDocument {
+    #[instrument(skip_all, fields(contract = self.cis2_address.to_string(), name = self.name))]
+    pub fn upsert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(cis2_documents::table)
+            .values(self)
+            .on_conflict((
+                cis2_documents::cis2_address,
+                cis2_documents::token_id,
+                cis2_documents::name,
+            ))
+            .do_update()
+            .set(self)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        token_id: Decimal,
+        name: &str,
+    ) -> DbResult<usize> {
+        let deleted_rows = diesel::delete(cis2_documents::table)
+            .filter(cis2_documents::cis2_address.eq(cis2_address))
+            .filter(cis2_documents::token_id.eq(token_id))
+            .filter(cis2_documents::name.eq(name))
+            .execute(conn)?;
+        Ok(deleted_rows)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        token_id: Decimal,
+        name: &str,
+    ) -> DbResult<Option<TokenDocument>> {
+        let document = cis2_documents::table
+            .filter(cis2_documents::cis2_address.eq(cis2_address))
+            .filter(cis2_documents::token_id.eq(token_id))
+            .filter(cis2_documents::name.eq(name))
+            .first(conn)
+            .optional()?;
+        Ok(document)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn list(
+        conn: &mut DbConn,
+        cis2_address: Decimal,
+        token_id: Option<Decimal>,
+        page: i64,
+        page_size: i64,
+    ) -> DbResult<(Vec<TokenDocument>, i64)> {
+        let mut query = cis2_documents::table
+            .filter(cis2_documents::cis2_address.eq(cis2_address))
+            .into_boxed();
+        let mut count_query = cis2_documents::table
+            .filter(cis2_documents::cis2_address.eq(cis2_address))
+            .into_boxed();
+        if let Some(token_id) = token_id {
+            query = query.filter(cis2_documents::token_id.eq(token_id));
+            count_query = count_query.filter(cis2_documents::token_id.eq(token_id));
+        }
+        let documents = query
+            .select(TokenDocument::as_select())
+            .order((cis2_documents::token_id, cis2_documents::name))
+            .limit(page_size)
+            .offset(page * page_size)
+            .get_results(conn)?;
+        let total_count: i64 = count_query.count().get_result(conn)?;
+        let page_count = (total_count as f64 / page_size as f64).ceil() as i64;
+        Ok((documents, page_count))
+    }
 }
@@ -0,0 +1,115 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use poem_openapi::{Enum, Object};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::db_shared::{DbConn, DbResult};
+use crate::schema::compliance_jurisdiction_rules;
+
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    Hash,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::ComplianceJurisdiction"]
+pub enum ComplianceJurisdiction {
+    Nationality,
+    CountryOfResidence,
+}
+
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    Hash,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::ComplianceListType"]
+pub enum ComplianceListType {
+    Allow,
+    Block,
+}
+
+/// A country on the allow or block list of a jurisdictions compliance module.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+    Deserialize,
+    Clone,
+)]
+#[diesel(table_name = compliance_jurisdiction_rules)]
+#[diesel(primary_key(module_address, jurisdiction, list_type, country))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ComplianceJurisdictionRule {
+    pub module_address: Decimal,
+    pub jurisdiction:   ComplianceJurisdiction,
+    pub list_type:      ComplianceListType,
+    pub country:        String,
+    pub create_time:    NaiveDateTime,
+}
+
+impl ComplianceJurisdictionRule {
+    #[instrument(skip(conn))]
+    pub fn list(
+        conn: &mut DbConn,
+        module_address: Decimal,
+        jurisdiction: ComplianceJurisdiction,
+        list_type: ComplianceListType,
+    ) -> DbResult<Vec<Self>> {
+        compliance_jurisdiction_rules::table
+            .filter(
+                compliance_jurisdiction_rules::module_address
+                    .eq(module_address)
+                    .and(compliance_jurisdiction_rules::jurisdiction.eq(jurisdiction))
+                    .and(compliance_jurisdiction_rules::list_type.eq(list_type)),
+            )
+            .order(compliance_jurisdiction_rules::country.asc())
+            .select(Self::as_select())
+            .get_results(conn)
+    }
+
+    #[instrument(skip_all, fields(module = self.module_address.to_string(), country = self.country))]
+    pub fn insert(&self, conn: &mut DbConn) -> DbResult<usize> {
+        diesel::insert_into(compliance_jurisdiction_rules::table)
+            .values(self)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete(
+        conn: &mut DbConn,
+        module_address: Decimal,
+        jurisdiction: ComplianceJurisdiction,
+        list_type: ComplianceListType,
+        country: &str,
+    ) -> DbResult<usize> {
+        diesel::delete(compliance_jurisdiction_rules::table.find((
+            module_address,
+            jurisdiction,
+            list_type,
+            country,
+        )))
+        .execute(conn)
+    }
+}
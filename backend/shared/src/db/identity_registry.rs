@@ -1,7 +1,8 @@
 use chrono::NaiveDateTime;
 use concordium_rust_sdk::types::Address;
-use diesel::dsl::*;
+use diesel::dsl::{exists, select};
 use diesel::prelude::*;
+use poem_openapi::Enum;
 use rust_decimal::Decimal;
 use tracing::instrument;
 
@@ -10,6 +11,23 @@ use crate::schema::{
     identity_registry_agents, identity_registry_identities, identity_registry_issuers,
 };
 
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::IdentityInvalidReason"]
+pub enum IdentityInvalidReason {
+    NotYetValid,
+    Expired,
+    CredentialNotActive,
+}
+
 #[derive(Selectable, Queryable, Identifiable, Insertable, Debug, PartialEq)]
 #[diesel(table_name = identity_registry_identities)]
 #[diesel(primary_key(identity_registry_address, identity_address))]
@@ -18,6 +36,10 @@ pub struct Identity {
     identity_registry_address: Decimal,
     identity_address:          String,
     create_time:               NaiveDateTime,
+    invalidated_at:            Option<NaiveDateTime>,
+    invalid_reason:            Option<IdentityInvalidReason>,
+    /// The address of the identity this address is linked to. `None` for registered identities.
+    linked_identity_address:   Option<String>,
 }
 
 impl Identity {
@@ -30,9 +52,22 @@ impl Identity {
             identity_address: identity_address.to_string(),
             create_time: time,
             identity_registry_address,
+            invalidated_at: None,
+            invalid_reason: None,
+            linked_identity_address: None,
         }
     }
 
+    /// Returns the address of the identity this address is linked to.
+    pub fn linked_identity_address(&self) -> Option<&str> {
+        self.linked_identity_address.as_deref()
+    }
+
+    /// Returns false if the last re-verification of the identity found it not valid.
+    pub fn is_valid(&self) -> bool { self.invalidated_at.is_none() }
+
+    pub fn invalid_reason(&self) -> Option<IdentityInvalidReason> { self.invalid_reason }
+
     #[allow(dead_code)]
     #[instrument(skip_all)]
     pub fn list(
@@ -92,6 +127,26 @@ impl Identity {
         Ok(res)
     }
 
+    /// Returns true if the address has a registered identity which has not been invalidated.
+    #[instrument(skip_all)]
+    pub fn exists_valid(
+        conn: &mut DbConn,
+        identity_registry_address: Decimal,
+        address: &str,
+    ) -> DbResult<bool> {
+        let address = address.to_string();
+        let res: bool = select(exists(
+            identity_registry_identities::table.filter(
+                identity_registry_identities::identity_registry_address
+                    .eq(identity_registry_address)
+                    .and(identity_registry_identities::identity_address.eq(address))
+                    .and(identity_registry_identities::invalidated_at.is_null()),
+            ),
+        ))
+        .get_result(conn)?;
+        Ok(res)
+    }
+
     #[instrument(skip_all)]
     pub fn exists_batch(
         conn: &mut DbConn,
@@ -118,10 +173,120 @@ impl Identity {
         skip_all,
         fields(identity_registry = self.identity_registry_address.to_string(), address = self.identity_address.to_string())
     )]
+    /// Inserts the identity. Registering an existing identity again clears its invalidation and
+    /// the invalidation of its linked addresses.
     pub fn insert(&self, conn: &mut DbConn) -> DbResult<usize> {
-        diesel::insert_into(identity_registry_identities::table)
+        let inserted = diesel::insert_into(identity_registry_identities::table)
             .values(self)
-            .on_conflict_do_nothing()
+            .on_conflict((
+                identity_registry_identities::identity_registry_address,
+                identity_registry_identities::identity_address,
+            ))
+            .do_update()
+            .set((
+                identity_registry_identities::invalidated_at.eq(None::<NaiveDateTime>),
+                identity_registry_identities::invalid_reason.eq(None::<IdentityInvalidReason>),
+            ))
+            .execute(conn)?;
+        Self::update_invalidation(
+            conn,
+            self.identity_registry_address,
+            &self.identity_address,
+            None,
+            None,
+        )?;
+        Ok(inserted)
+    }
+
+    /// Links the address to the identity. The linked address shares the invalidation of the
+    /// identity.
+    #[instrument(skip(conn))]
+    pub fn link(
+        conn: &mut DbConn,
+        identity_registry_address: Decimal,
+        identity_address: &Address,
+        address: &Address,
+        time: NaiveDateTime,
+    ) -> DbResult<usize> {
+        let identity = Self::find(conn, identity_registry_address, identity_address)?;
+        let linked = Self {
+            identity_registry_address,
+            identity_address: address.to_string(),
+            create_time: time,
+            invalidated_at: identity.as_ref().and_then(|i| i.invalidated_at),
+            invalid_reason: identity.as_ref().and_then(|i| i.invalid_reason),
+            linked_identity_address: Some(identity_address.to_string()),
+        };
+        diesel::insert_into(identity_registry_identities::table)
+            .values(&linked)
+            .on_conflict((
+                identity_registry_identities::identity_registry_address,
+                identity_registry_identities::identity_address,
+            ))
+            .do_update()
+            .set((
+                identity_registry_identities::invalidated_at.eq(linked.invalidated_at),
+                identity_registry_identities::invalid_reason.eq(linked.invalid_reason),
+                identity_registry_identities::linked_identity_address
+                    .eq(&linked.linked_identity_address),
+            ))
+            .execute(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn invalidate(
+        conn: &mut DbConn,
+        identity_registry_address: Decimal,
+        address: &Address,
+        reason: IdentityInvalidReason,
+        time: NaiveDateTime,
+    ) -> DbResult<usize> {
+        Self::update_invalidation(
+            conn,
+            identity_registry_address,
+            &address.to_string(),
+            Some(time),
+            Some(reason),
+        )
+    }
+
+    #[instrument(skip(conn))]
+    pub fn revalidate(
+        conn: &mut DbConn,
+        identity_registry_address: Decimal,
+        address: &Address,
+    ) -> DbResult<usize> {
+        Self::update_invalidation(
+            conn,
+            identity_registry_address,
+            &address.to_string(),
+            None,
+            None,
+        )
+    }
+
+    /// Updates the invalidation of the identity and of its linked addresses.
+    fn update_invalidation(
+        conn: &mut DbConn,
+        identity_registry_address: Decimal,
+        address: &str,
+        invalidated_at: Option<NaiveDateTime>,
+        invalid_reason: Option<IdentityInvalidReason>,
+    ) -> DbResult<usize> {
+        diesel::update(identity_registry_identities::table)
+            .filter(
+                identity_registry_identities::identity_registry_address
+                    .eq(identity_registry_address)
+                    .and(
+                        identity_registry_identities::identity_address
+                            .eq(address)
+                            .or(identity_registry_identities::linked_identity_address.eq(address)),
+                    ),
+            )
+            .set((
+                identity_registry_identities::invalidated_at.eq(invalidated_at),
+                identity_registry_identities::invalid_reason.eq(invalid_reason),
+            ))
             .execute(conn)
     }
 
@@ -1,4 +1,5 @@
 pub mod cis2_security;
+pub mod compliance;
 pub mod identity_registry;
 pub mod nft_multi_rewarded;
 pub mod offchain_rewards;
@@ -29,6 +29,23 @@ pub enum SecurityMintFundState {
     Fail,
 }
 
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::SecurityMintFundInvestorAccess"]
+pub enum SecurityMintFundInvestorAccess {
+    Any,
+    Allowlist,
+    IdentityAttribute,
+}
+
 #[derive(
     Selectable,
     Queryable,
@@ -107,6 +124,19 @@ pub struct SecurityMintFund {
     pub fund_state: SecurityMintFundState,
     pub create_time: NaiveDateTime,
     pub update_time: NaiveDateTime,
+    pub soft_cap: Option<Decimal>,
+    pub hard_cap: Option<Decimal>,
+    pub deadline: Option<NaiveDateTime>,
+    pub min_investment: Option<Decimal>,
+    pub max_investment: Option<Decimal>,
+    pub investor_access: SecurityMintFundInvestorAccess,
+    pub identity_registry_address: Option<Decimal>,
+    pub identity_attribute_tag: Option<i32>,
+    pub identity_attribute_value: Option<String>,
+    /// Lock-up of the claimed security tokens, starting when the fund succeeds.
+    pub vesting_lock_up_millis: Option<Decimal>,
+    /// Period after the lock-up over which the claimed security tokens vest linearly.
+    pub vesting_period_millis: Option<Decimal>,
 }
 
 impl SecurityMintFund {
@@ -240,6 +270,8 @@ pub struct Investor {
     pub token_amount_total: Decimal,
     pub create_time: NaiveDateTime,
     pub update_time: NaiveDateTime,
+    /// Claimed security tokens which have been released from the vesting schedule of the fund.
+    pub token_amount_released: Decimal,
 }
 
 impl Investor {
@@ -281,6 +313,44 @@ impl Investor {
         Ok(investor_record)
     }
 
+    /// Investors of the successful funds with a vesting schedule whose claimed security tokens
+    /// have not all been released yet, ordered by fund.
+    #[instrument(skip_all)]
+    pub fn list_vesting(conn: &mut DbConn, contract_address: Decimal) -> QueryResult<Vec<Self>> {
+        let investors = security_mint_fund_investors::table
+            .inner_join(
+                security_mint_funds::table.on(security_mint_funds::contract_address
+                    .eq(security_mint_fund_investors::contract_address)
+                    .and(
+                        security_mint_funds::investment_token_id
+                            .eq(security_mint_fund_investors::investment_token_id),
+                    )
+                    .and(
+                        security_mint_funds::investment_token_contract_address
+                            .eq(security_mint_fund_investors::investment_token_contract_address),
+                    )),
+            )
+            .filter(security_mint_fund_investors::contract_address.eq(contract_address))
+            .filter(security_mint_funds::fund_state.eq(SecurityMintFundState::Success))
+            .filter(
+                security_mint_funds::vesting_lock_up_millis
+                    .is_not_null()
+                    .or(security_mint_funds::vesting_period_millis.is_not_null()),
+            )
+            .filter(
+                security_mint_fund_investors::token_amount_released
+                    .lt(security_mint_fund_investors::token_amount_total),
+            )
+            .order((
+                security_mint_fund_investors::investment_token_contract_address,
+                security_mint_fund_investors::investment_token_id,
+                security_mint_fund_investors::investor,
+            ))
+            .select(Self::as_select())
+            .load(conn)?;
+        Ok(investors)
+    }
+
     #[instrument(skip_all)]
     pub fn update(&self, conn: &mut DbConn) -> QueryResult<Self> {
         let investor = diesel::update(security_mint_fund_investors::table)
@@ -416,4 +486,5 @@ pub enum InvestmentRecordType {
     Invested,
     Cancelled,
     Claimed,
+    Withdrawn,
 }
@@ -8,7 +8,9 @@ use uuid::Uuid;
 
 use crate::db_shared::{DbConn, DbResult};
 use crate::schema::{
-    security_p2p_exchange_records, security_p2p_trading_contracts, security_p2p_trading_markets,
+    security_p2p_exchange_records, security_p2p_trading_contracts,
+    security_p2p_trading_market_price_tiers, security_p2p_trading_markets,
+    security_p2p_trading_order_fills, security_p2p_trading_orders,
 };
 
 /// Represents a contract in the security P2P trading system.
@@ -97,6 +99,7 @@ impl P2PTradeContract {
 pub enum MarketType {
     Mint,
     Transfer,
+    OrderBook,
 }
 
 #[derive(
@@ -152,6 +155,19 @@ pub struct Market {
     pub token_out_amount: Decimal,
     /// Total amount of currency units which the market has received / users have bought tokens.
     pub currency_in_amount: Decimal,
+    /// In case of mint market this is the time from which tokens can be minted.
+    pub open_time: Option<NaiveDateTime>,
+    /// In case of mint market this is the time after which tokens can not be minted anymore.
+    pub close_time: Option<NaiveDateTime>,
+    /// In case of mint market this is the maximum amount of tokens a single investor can mint.
+    pub max_investor_token_amount: Option<Decimal>,
+    /// Fraction of the currency amount of each exchange which is taken as platform fee.
+    pub fee_rate_numerator: Option<Decimal>,
+    pub fee_rate_denominator: Option<Decimal>,
+    pub fee_receiver: Option<String>,
+    /// In case of transfer market whether the liquidity is escrowed by the trading contract.
+    /// For escrowed markets `max_token_amount` and `max_currency_amount` are the escrowed liquidity.
+    pub settlement: OrderSettlement,
 }
 
 impl Market {
@@ -255,6 +271,102 @@ impl Market {
     }
 }
 
+/// A price tier of a mint market. Tiers are sold in the order of `tier_index` before the market rate applies.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+    Deserialize,
+    Clone,
+)]
+#[diesel(table_name = security_p2p_trading_market_price_tiers)]
+#[diesel(primary_key(contract_address, token_contract_address, tier_index))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MarketPriceTier {
+    pub contract_address:       Decimal,
+    pub token_contract_address: Decimal,
+    pub tier_index:             i32,
+    pub rate_numerator:         Decimal,
+    pub rate_denominator:       Decimal,
+    /// Amount of tokens available at the rate of this tier when the market was added.
+    pub token_amount:           Decimal,
+    /// Amount of tokens minted at the rate of this tier.
+    pub sold_token_amount:      Decimal,
+}
+
+impl MarketPriceTier {
+    #[instrument(skip_all)]
+    pub fn insert_batch(conn: &mut DbConn, tiers: &[Self]) -> DbResult<()> {
+        diesel::insert_into(security_p2p_trading_market_price_tiers::table)
+            .values(tiers)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn list(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        token_contract_address: Decimal,
+    ) -> DbResult<Vec<Self>> {
+        let tiers = security_p2p_trading_market_price_tiers::table
+            .filter(security_p2p_trading_market_price_tiers::contract_address.eq(contract_address))
+            .filter(
+                security_p2p_trading_market_price_tiers::token_contract_address
+                    .eq(token_contract_address),
+            )
+            .order_by(security_p2p_trading_market_price_tiers::tier_index.asc())
+            .load(conn)?;
+        Ok(tiers)
+    }
+
+    /// Finds the first tier of the market which is not sold out.
+    #[instrument(skip_all)]
+    pub fn find_current(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        token_contract_address: Decimal,
+    ) -> DbResult<Option<Self>> {
+        let tier = security_p2p_trading_market_price_tiers::table
+            .filter(security_p2p_trading_market_price_tiers::contract_address.eq(contract_address))
+            .filter(
+                security_p2p_trading_market_price_tiers::token_contract_address
+                    .eq(token_contract_address),
+            )
+            .filter(
+                security_p2p_trading_market_price_tiers::sold_token_amount
+                    .lt(security_p2p_trading_market_price_tiers::token_amount),
+            )
+            .order_by(security_p2p_trading_market_price_tiers::tier_index.asc())
+            .first(conn)
+            .optional()?;
+        Ok(tier)
+    }
+
+    #[instrument(skip_all)]
+    pub fn update(&self, conn: &mut DbConn) -> DbResult<Self> {
+        let tier = diesel::update(security_p2p_trading_market_price_tiers::table)
+            .filter(
+                security_p2p_trading_market_price_tiers::contract_address.eq(self.contract_address),
+            )
+            .filter(
+                security_p2p_trading_market_price_tiers::token_contract_address
+                    .eq(self.token_contract_address),
+            )
+            .filter(security_p2p_trading_market_price_tiers::tier_index.eq(self.tier_index))
+            .set(self)
+            .returning(Self::as_returning())
+            .get_result(conn)?;
+        Ok(tier)
+    }
+}
+
 #[derive(
     Selectable,
     Queryable,
@@ -384,6 +496,9 @@ pub struct ExchangeRecord {
     pub rate: Decimal,
     pub create_time: NaiveDateTime,
     pub exchange_record_type: ExchangeRecordType,
+    /// The part of the `currency_amount` which was transferred to the `fee_receiver` instead of the seller.
+    pub fee_amount: Decimal,
+    pub fee_receiver: Option<String>,
 }
 
 impl ExchangeRecord {
@@ -475,4 +590,255 @@ pub enum ExchangeRecordType {
     Sell,
     /// Represents a mint transaction.
     Mint,
+    /// Represents a fill of an order book order.
+    Order,
+}
+
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    std::cmp::Eq,
+    std::hash::Hash,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::SecurityP2pTradingOrderSide"]
+pub enum OrderSide {
+    /// The order owner is selling tokens.
+    Sell,
+    /// The order owner is buying tokens.
+    Buy,
+}
+
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    std::cmp::Eq,
+    std::hash::Hash,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::SecurityP2pTradingOrderStatus"]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+    /// The escrowed order expired and the escrow was refunded to the owner.
+    Expired,
+}
+
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    std::cmp::Eq,
+    std::hash::Hash,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::SecurityP2pTradingOrderSettlement"]
+pub enum OrderSettlement {
+    /// Trades are settled using the operator rights of the trading contract.
+    Operator,
+    /// The owner leg of the order or the liquidity of the market is held in escrow by the trading contract.
+    Escrow,
+}
+
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+    Deserialize,
+    Clone,
+)]
+#[diesel(table_name = security_p2p_trading_orders)]
+#[diesel(primary_key(contract_address, order_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Order {
+    pub contract_address: Decimal,
+    pub order_id: Decimal,
+    pub token_contract_address: Decimal,
+    pub token_id: Decimal,
+    pub currency_token_id: Decimal,
+    pub currency_token_contract_address: Decimal,
+    pub owner: String,
+    pub side: OrderSide,
+    pub rate_numerator: Decimal,
+    pub rate_denominator: Decimal,
+    /// Amount of tokens with which the order was placed.
+    pub token_amount: Decimal,
+    /// Amount of tokens which are still open to be filled.
+    pub remaining_token_amount: Decimal,
+    /// Total amount of currency units exchanged by the fills of this order.
+    pub filled_currency_amount: Decimal,
+    pub status: OrderStatus,
+    pub create_time: NaiveDateTime,
+    pub update_time: NaiveDateTime,
+    pub settlement: OrderSettlement,
+    /// Time after which an escrowed order can not be filled anymore.
+    pub expiry: Option<NaiveDateTime>,
+}
+
+impl Order {
+    #[instrument(skip_all)]
+    pub fn insert(&self, conn: &mut DbConn) -> DbResult<Self> {
+        let order = diesel::insert_into(security_p2p_trading_orders::table)
+            .values(self)
+            .returning(Self::as_returning())
+            .get_result(conn)?;
+        Ok(order)
+    }
+
+    #[instrument(skip_all)]
+    pub fn find(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        order_id: Decimal,
+    ) -> DbResult<Option<Self>> {
+        let order = security_p2p_trading_orders::table
+            .filter(security_p2p_trading_orders::contract_address.eq(contract_address))
+            .filter(security_p2p_trading_orders::order_id.eq(order_id))
+            .first(conn)
+            .optional()?;
+        Ok(order)
+    }
+
+    #[instrument(skip_all)]
+    pub fn update(&self, conn: &mut DbConn) -> DbResult<Self> {
+        let order = diesel::update(security_p2p_trading_orders::table)
+            .filter(security_p2p_trading_orders::contract_address.eq(self.contract_address))
+            .filter(security_p2p_trading_orders::order_id.eq(self.order_id))
+            .set(self)
+            .returning(Self::as_returning())
+            .get_result(conn)?;
+        Ok(order)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all)]
+    pub fn list(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        token_contract_address: Option<Decimal>,
+        token_id: Option<Decimal>,
+        owner: Option<&str>,
+        status: Option<OrderStatus>,
+        page: i64,
+        page_size: i64,
+    ) -> DbResult<(Vec<Self>, i64)> {
+        let mut query = security_p2p_trading_orders::table
+            .filter(security_p2p_trading_orders::contract_address.eq(contract_address))
+            .into_boxed();
+        let mut count_query = security_p2p_trading_orders::table
+            .filter(security_p2p_trading_orders::contract_address.eq(contract_address))
+            .into_boxed();
+        if let Some(token_contract_address) = token_contract_address {
+            query = query.filter(
+                security_p2p_trading_orders::token_contract_address.eq(token_contract_address),
+            );
+            count_query = count_query.filter(
+                security_p2p_trading_orders::token_contract_address.eq(token_contract_address),
+            );
+        }
+        if let Some(token_id) = token_id {
+            query = query.filter(security_p2p_trading_orders::token_id.eq(token_id));
+            count_query = count_query.filter(security_p2p_trading_orders::token_id.eq(token_id));
+        }
+        if let Some(owner) = owner {
+            query = query.filter(security_p2p_trading_orders::owner.eq(owner));
+            count_query = count_query.filter(security_p2p_trading_orders::owner.eq(owner));
+        }
+        if let Some(status) = status {
+            query = query.filter(security_p2p_trading_orders::status.eq(status));
+            count_query = count_query.filter(security_p2p_trading_orders::status.eq(status));
+        }
+        query = query.order_by(security_p2p_trading_orders::create_time.desc());
+
+        let orders = query
+            .limit(page_size)
+            .offset(page * page_size)
+            .load::<Order>(conn)?;
+        let total_count: i64 = count_query.count().get_result(conn)?;
+        let page_count = std::cmp::max((total_count as f64 / page_size as f64).ceil() as i64, 1);
+
+        Ok((orders, page_count))
+    }
+}
+
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+    Deserialize,
+    Clone,
+)]
+#[diesel(table_name = security_p2p_trading_order_fills)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OrderFill {
+    pub id:                     Uuid,
+    pub block_height:           Decimal,
+    pub txn_index:              Decimal,
+    pub contract_address:       Decimal,
+    pub order_id:               Decimal,
+    pub taker:                  String,
+    pub token_amount:           Decimal,
+    pub currency_amount:        Decimal,
+    /// Amount of tokens of the order which were still open after this fill.
+    pub remaining_token_amount: Decimal,
+    pub create_time:            NaiveDateTime,
+}
+
+impl OrderFill {
+    #[instrument(skip_all)]
+    pub fn insert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(security_p2p_trading_order_fills::table)
+            .values(self)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn list(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        order_id: Decimal,
+        page: i64,
+        page_size: i64,
+    ) -> DbResult<(Vec<Self>, i64)> {
+        let query = security_p2p_trading_order_fills::table
+            .filter(security_p2p_trading_order_fills::contract_address.eq(contract_address))
+            .filter(security_p2p_trading_order_fills::order_id.eq(order_id));
+        let fills = query
+            .order_by(security_p2p_trading_order_fills::create_time.desc())
+            .limit(page_size)
+            .offset(page * page_size)
+            .load::<OrderFill>(conn)?;
+        let total_count: i64 = query.count().get_result(conn)?;
+        let page_count = std::cmp::max((total_count as f64 / page_size as f64).ceil() as i64, 1);
+
+        Ok((fills, page_count))
+    }
 }
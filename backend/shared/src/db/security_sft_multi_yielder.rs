@@ -8,8 +8,10 @@ use tracing::instrument;
 use super::cis2_security::Token;
 use crate::db_shared::{DbConn, DbResult};
 use crate::schema::{
-    security_sft_multi_yielder_treasuries, security_sft_multi_yielder_yeild_distributions,
-    security_sft_multi_yielder_yields,
+    security_sft_multi_yielder_checkpoints, security_sft_multi_yielder_claim_modes,
+    security_sft_multi_yielder_pool_claims, security_sft_multi_yielder_pools,
+    security_sft_multi_yielder_token_versions, security_sft_multi_yielder_treasuries,
+    security_sft_multi_yielder_yeild_distributions, security_sft_multi_yielder_yields,
 };
 
 #[derive(
@@ -29,6 +31,29 @@ pub enum YieldType {
     Quantity,
     #[db_rename = "simple_intrest"]
     SimpleIntrest,
+    CompoundInterest,
+    SimpleInterestPerSecond,
+    SimpleInterestPerDay,
+    CompoundInterestPerSecond,
+    CompoundInterestPerDay,
+}
+
+#[derive(
+    diesel_derive_enum::DbEnum,
+    Debug,
+    PartialEq,
+    Enum,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    std::cmp::Eq,
+    std::hash::Hash,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::SecuritySftMultiYielderClaimMode"]
+pub enum ClaimMode {
+    Versioned,
+    Checkpoint,
 }
 
 #[derive(
@@ -410,6 +435,368 @@ impl YieldDistribution {
     }
 }
 
+/// A security token version added to the yielder.
+/// Time based yields accrue between the creation times of consecutive token versions.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    Object,
+    Serialize,
+    Deserialize,
+    Clone,
+)]
+#[diesel(table_name = security_sft_multi_yielder_token_versions)]
+#[diesel(primary_key(contract_address, token_contract_address, token_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TokenVersion {
+    pub contract_address:       Decimal,
+    pub token_contract_address: Decimal,
+    pub token_id:               Decimal,
+    pub create_time:            NaiveDateTime,
+}
+
+impl TokenVersion {
+    #[instrument(skip_all)]
+    pub fn upsert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(security_sft_multi_yielder_token_versions::table)
+            .values(self)
+            .on_conflict((
+                security_sft_multi_yielder_token_versions::contract_address,
+                security_sft_multi_yielder_token_versions::token_contract_address,
+                security_sft_multi_yielder_token_versions::token_id,
+            ))
+            .do_update()
+            .set(security_sft_multi_yielder_token_versions::create_time.eq(self.create_time))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn delete(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        token_contract_address: Decimal,
+        token_id: Decimal,
+    ) -> DbResult<()> {
+        diesel::delete(security_sft_multi_yielder_token_versions::table)
+            .filter(
+                security_sft_multi_yielder_token_versions::contract_address.eq(contract_address),
+            )
+            .filter(
+                security_sft_multi_yielder_token_versions::token_contract_address
+                    .eq(token_contract_address),
+            )
+            .filter(security_sft_multi_yielder_token_versions::token_id.eq(token_id))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Lists the token versions from `from_token_id` to `to_token_id` (both inclusive) in ascending order.
+    #[instrument(skip_all)]
+    pub fn list_between(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        token_contract_address: Decimal,
+        from_token_id: Decimal,
+        to_token_id: Decimal,
+    ) -> DbResult<Vec<Self>> {
+        let versions = security_sft_multi_yielder_token_versions::table
+            .filter(
+                security_sft_multi_yielder_token_versions::contract_address.eq(contract_address),
+            )
+            .filter(
+                security_sft_multi_yielder_token_versions::token_contract_address
+                    .eq(token_contract_address),
+            )
+            .filter(security_sft_multi_yielder_token_versions::token_id.ge(from_token_id))
+            .filter(security_sft_multi_yielder_token_versions::token_id.le(to_token_id))
+            .order(security_sft_multi_yielder_token_versions::token_id.asc())
+            .load(conn)?;
+        Ok(versions)
+    }
+}
+
+/// The mode in which the holders of a security token contract claim their yields.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    AsChangeset,
+    Object,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(table_name = security_sft_multi_yielder_claim_modes)]
+#[diesel(primary_key(contract_address, token_contract_address))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TokenContractClaimMode {
+    pub contract_address:       Decimal,
+    pub token_contract_address: Decimal,
+    pub claim_mode:             ClaimMode,
+    pub create_time:            NaiveDateTime,
+    pub update_time:            NaiveDateTime,
+}
+
+impl TokenContractClaimMode {
+    #[instrument(skip_all)]
+    pub fn upsert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(security_sft_multi_yielder_claim_modes::table)
+            .values(self)
+            .on_conflict((
+                security_sft_multi_yielder_claim_modes::contract_address,
+                security_sft_multi_yielder_claim_modes::token_contract_address,
+            ))
+            .do_update()
+            .set((
+                security_sft_multi_yielder_claim_modes::claim_mode.eq(self.claim_mode),
+                security_sft_multi_yielder_claim_modes::update_time.eq(self.update_time),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn list(conn: &mut DbConn, contract_address: Decimal) -> DbResult<Vec<Self>> {
+        let claim_modes = security_sft_multi_yielder_claim_modes::table
+            .filter(security_sft_multi_yielder_claim_modes::contract_address.eq(contract_address))
+            .load(conn)?;
+        Ok(claim_modes)
+    }
+}
+
+/// The last token version up to which a holder has claimed the yields of a held token version.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    AsChangeset,
+    Object,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(table_name = security_sft_multi_yielder_checkpoints)]
+#[diesel(primary_key(contract_address, token_contract_address, token_id, holder_address))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Checkpoint {
+    pub contract_address:       Decimal,
+    pub token_contract_address: Decimal,
+    pub token_id:               Decimal,
+    pub holder_address:         String,
+    pub claimed_token_id:       Decimal,
+    pub create_time:            NaiveDateTime,
+    pub update_time:            NaiveDateTime,
+}
+
+impl Checkpoint {
+    #[instrument(skip_all)]
+    pub fn upsert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(security_sft_multi_yielder_checkpoints::table)
+            .values(self)
+            .on_conflict((
+                security_sft_multi_yielder_checkpoints::contract_address,
+                security_sft_multi_yielder_checkpoints::token_contract_address,
+                security_sft_multi_yielder_checkpoints::token_id,
+                security_sft_multi_yielder_checkpoints::holder_address,
+            ))
+            .do_update()
+            .set((
+                security_sft_multi_yielder_checkpoints::claimed_token_id.eq(self.claimed_token_id),
+                security_sft_multi_yielder_checkpoints::update_time.eq(self.update_time),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn find(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        token_contract_address: Decimal,
+        token_id: Decimal,
+        holder_address: &str,
+    ) -> DbResult<Option<Self>> {
+        let checkpoint = security_sft_multi_yielder_checkpoints::table
+            .find((
+                contract_address,
+                token_contract_address,
+                token_id,
+                holder_address,
+            ))
+            .first(conn)
+            .optional()?;
+        Ok(checkpoint)
+    }
+}
+
+/// A pro rata yield pool deposited for a security token version.
+/// Holders of the entitled token versions claim their share of the pool, for their balance at the snapshot taken
+/// when the pool was deposited, until it expires.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    AsChangeset,
+    Object,
+    Serialize,
+    Deserialize,
+    Clone,
+)]
+#[diesel(table_name = security_sft_multi_yielder_pools)]
+#[diesel(primary_key(
+    contract_address,
+    token_contract_address,
+    token_id,
+    yield_contract_address,
+    yield_token_id
+))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct YieldPool {
+    pub contract_address:       Decimal,
+    pub token_contract_address: Decimal,
+    pub token_id:               Decimal,
+    pub yield_contract_address: Decimal,
+    pub yield_token_id:         Decimal,
+    pub amount:                 Decimal,
+    pub supply:                 Decimal,
+    pub claimed_amount:         Decimal,
+    pub returned_amount:        Decimal,
+    pub expiry:                 NaiveDateTime,
+    pub create_time:            NaiveDateTime,
+    pub update_time:            NaiveDateTime,
+    pub snapshot_id:            Decimal,
+    pub token_ids:              Vec<Option<Decimal>>,
+}
+
+impl YieldPool {
+    #[instrument(skip_all)]
+    pub fn insert(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(security_sft_multi_yielder_pools::table)
+            .values(self)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub fn find(
+        conn: &mut DbConn,
+        contract_address: Decimal,
+        token_contract_address: Decimal,
+        token_id: Decimal,
+        yield_contract_address: Decimal,
+        yield_token_id: Decimal,
+    ) -> DbResult<Option<Self>> {
+        let pool = security_sft_multi_yielder_pools::table
+            .find((
+                contract_address,
+                token_contract_address,
+                token_id,
+                yield_contract_address,
+                yield_token_id,
+            ))
+            .first(conn)
+            .optional()?;
+        Ok(pool)
+    }
+
+    #[instrument(skip_all)]
+    pub fn update(&self, conn: &mut DbConn) -> DbResult<Self> {
+        let pool = diesel::update(security_sft_multi_yielder_pools::table)
+            .filter(security_sft_multi_yielder_pools::contract_address.eq(self.contract_address))
+            .filter(
+                security_sft_multi_yielder_pools::token_contract_address
+                    .eq(self.token_contract_address),
+            )
+            .filter(security_sft_multi_yielder_pools::token_id.eq(self.token_id))
+            .filter(
+                security_sft_multi_yielder_pools::yield_contract_address
+                    .eq(self.yield_contract_address),
+            )
+            .filter(security_sft_multi_yielder_pools::yield_token_id.eq(self.yield_token_id))
+            .set(self)
+            .get_result(conn)?;
+        Ok(pool)
+    }
+}
+
+/// Security token amount of a held token version for which a holder has claimed a share of a pool.
+#[derive(
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    AsChangeset,
+    Object,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(table_name = security_sft_multi_yielder_pool_claims)]
+#[diesel(primary_key(
+    contract_address,
+    token_contract_address,
+    token_id,
+    yield_contract_address,
+    yield_token_id,
+    from_token_id,
+    holder_address
+))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PoolClaim {
+    pub contract_address:       Decimal,
+    pub token_contract_address: Decimal,
+    /// Token version of the pool.
+    pub token_id:               Decimal,
+    pub yield_contract_address: Decimal,
+    pub yield_token_id:         Decimal,
+    /// Token version held by the holder.
+    pub from_token_id:          Decimal,
+    pub holder_address:         String,
+    pub security_amount:        Decimal,
+    pub create_time:            NaiveDateTime,
+    pub update_time:            NaiveDateTime,
+}
+
+impl PoolClaim {
+    /// Inserts the claim or adds its security token amount to the amount already claimed.
+    #[instrument(skip_all)]
+    pub fn upsert_add(&self, conn: &mut DbConn) -> DbResult<()> {
+        diesel::insert_into(security_sft_multi_yielder_pool_claims::table)
+            .values(self)
+            .on_conflict((
+                security_sft_multi_yielder_pool_claims::contract_address,
+                security_sft_multi_yielder_pool_claims::token_contract_address,
+                security_sft_multi_yielder_pool_claims::token_id,
+                security_sft_multi_yielder_pool_claims::yield_contract_address,
+                security_sft_multi_yielder_pool_claims::yield_token_id,
+                security_sft_multi_yielder_pool_claims::from_token_id,
+                security_sft_multi_yielder_pool_claims::holder_address,
+            ))
+            .do_update()
+            .set((
+                security_sft_multi_yielder_pool_claims::security_amount
+                    .eq(security_sft_multi_yielder_pool_claims::security_amount
+                        + self.security_amount),
+                security_sft_multi_yielder_pool_claims::update_time.eq(self.update_time),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+}
+
 #[derive(
     Selectable,
     Queryable,
@@ -215,6 +215,7 @@ pub enum ProcessorType {
     OffchainRewards    = 7,
     SecuritySftMulti   = 8,
     SecuritySftMultiYielder = 9,
+    ComplianceJurisdictions = 10,
 }
 
 impl Display for ProcessorType {
@@ -229,6 +230,7 @@ impl Display for ProcessorType {
             ProcessorType::OffchainRewards => write!(f, "OffchainRewards"),
             ProcessorType::SecuritySftMulti => write!(f, "SecuritySftMulti"),
             ProcessorType::SecuritySftMultiYielder => write!(f, "SecuritySftMultiYielder"),
+            ProcessorType::ComplianceJurisdictions => write!(f, "ComplianceJurisdictions"),
         }
     }
 }
@@ -246,6 +248,7 @@ impl FromSql<Integer, diesel::pg::Pg> for ProcessorType {
             7 => ProcessorType::OffchainRewards,
             8 => ProcessorType::SecuritySftMulti,
             9 => ProcessorType::SecuritySftMultiYielder,
+            10 => ProcessorType::ComplianceJurisdictions,
             _ => return Err(format!("Invalid processor type: {}", value).into()),
         })
     }
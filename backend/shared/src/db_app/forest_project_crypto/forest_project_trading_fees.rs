@@ -0,0 +1,48 @@
+use diesel::prelude::*;
+use poem_openapi::Object;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db_shared::DbConn;
+
+/// Platform fees collected by the trading contract on the exchanges of the tokens of a forest project.
+#[derive(
+    Object, Selectable, Queryable, Identifiable, Debug, PartialEq, Serialize, Deserialize, Clone,
+)]
+#[diesel(table_name = crate::schema_manual::forest_project_trading_fees)]
+#[diesel(primary_key(
+    forest_project_id,
+    contract_address,
+    currency_token_id,
+    currency_token_contract_address,
+    fee_receiver
+))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ForestProjectTradingFee {
+    pub forest_project_id: Uuid,
+    /// The trading contract which collected the fees.
+    pub contract_address: Decimal,
+    pub currency_token_id: Decimal,
+    pub currency_token_contract_address: Decimal,
+    pub fee_receiver: String,
+    pub fee_amount: Decimal,
+    /// Total currency amount of the exchanges on which the fees were collected.
+    pub currency_amount: Decimal,
+    pub exchange_count: i64,
+}
+
+impl ForestProjectTradingFee {
+    pub fn list_by_forest_project_id(
+        conn: &mut DbConn,
+        project_id: Uuid,
+    ) -> QueryResult<Vec<Self>> {
+        use crate::schema_manual::forest_project_trading_fees::dsl::*;
+
+        let records = forest_project_trading_fees
+            .filter(forest_project_id.eq(project_id))
+            .load::<Self>(conn)?;
+
+        Ok(records)
+    }
+}
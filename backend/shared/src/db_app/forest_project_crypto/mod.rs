@@ -2,6 +2,7 @@ pub mod affiliates;
 pub mod forest_project_supply;
 pub mod forest_project_token_contract;
 pub mod forest_project_token_contract_user_balance_agg;
+pub mod forest_project_trading_fees;
 pub mod forest_project_user_balance_aggregate;
 pub mod security_token_contract_type;
 pub mod yields;
@@ -10,6 +11,7 @@ pub mod prelude {
     pub use super::forest_project_supply::*;
     pub use super::forest_project_token_contract::*;
     pub use super::forest_project_token_contract_user_balance_agg::*;
+    pub use super::forest_project_trading_fees::*;
     pub use super::forest_project_user_balance_aggregate::*;
     pub use super::security_token_contract_type::*;
     pub use super::yields::*;
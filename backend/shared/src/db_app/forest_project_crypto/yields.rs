@@ -79,6 +79,45 @@ impl YieldClaim {
     }
 }
 
+/// A security token version held by a holder with yields to be distributed, across all the holders of a yielder.
+#[derive(Object, Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct HolderYieldClaim {
+    pub holder_address:         String,
+    pub token_contract_address: Decimal,
+    pub token_id:               Decimal,
+    pub token_balance:          Decimal,
+    pub max_token_id:           Decimal,
+}
+
+impl HolderYieldClaim {
+    /// Lists the held token versions with a positive yield, ordered by holder.
+    pub fn list(conn: &mut DbConn, yielder_contract_address_: Decimal) -> QueryResult<Vec<Self>> {
+        use crate::schema_manual::holder_yields::dsl::*;
+
+        let ret = holder_yields
+            .filter(yielder_contract_address.eq(yielder_contract_address_))
+            .filter(yield_value.gt(Decimal::ZERO))
+            .order_by((
+                holder_address.asc(),
+                cis2_address.asc(),
+                token_id.asc(),
+                token_ver_to.desc(),
+            ))
+            .distinct_on((holder_address, cis2_address, token_id))
+            .load::<HolderYield>(conn)?
+            .into_iter()
+            .map(|holder_yield| HolderYieldClaim {
+                holder_address:         holder_yield.holder_address,
+                token_contract_address: holder_yield.cis2_address,
+                token_id:               holder_yield.token_id,
+                token_balance:          holder_yield.un_frozen_balance,
+                max_token_id:           holder_yield.token_ver_to,
+            })
+            .collect::<Vec<Self>>();
+        Ok(ret)
+    }
+}
+
 #[derive(Object, Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ForestProjectTokenContractUserYields {
     pub forest_project_id:      Uuid,
@@ -11,10 +11,10 @@ use super::forest_project::ForestProjectState;
 use super::forest_project_crypto::prelude::SecurityTokenContractType;
 use crate::db::cis2_security::TokenHolderBalanceUpdate;
 use crate::db::security_mint_fund::InvestmentRecordType;
-use crate::db_shared::DbConn;
+use crate::db_shared::{DbConn, DbResult};
 use crate::schema::{
-    forest_project_token_contracts, forest_projects, security_mint_fund_investment_records,
-    security_p2p_exchange_records,
+    currency_exchange_rates, forest_project_token_contracts, forest_projects,
+    security_mint_fund_investment_records, security_p2p_exchange_records,
 };
 
 #[derive(
@@ -24,15 +24,17 @@ use crate::schema::{
 #[diesel(primary_key(transaction_hash))]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct UserTransaction {
-    pub transaction_hash:                String,
-    pub block_height:                    Decimal,
-    pub forest_project_id:               uuid::Uuid,
-    pub currency_token_id:               Decimal,
+    pub transaction_hash: String,
+    pub block_height: Decimal,
+    pub forest_project_id: uuid::Uuid,
+    pub currency_token_id: Decimal,
     pub currency_token_contract_address: Decimal,
-    pub currency_amount:                 Decimal,
-    pub cognito_user_id:                 String,
-    pub transaction_type:                String,
-    pub account_address:                 String,
+    pub currency_amount: Decimal,
+    pub cognito_user_id: String,
+    pub transaction_type: String,
+    pub account_address: String,
+    /// The platform fee deducted from the currency amount. Only sellers pay fees.
+    pub fee_amount: Decimal,
 }
 
 impl UserTransaction {
@@ -58,6 +60,149 @@ impl UserTransaction {
     }
 }
 
+/// Rate at which amounts of a currency token are valued in a target currency token.
+/// The rate converts the smallest unit of the currency token into the smallest unit of the target currency token.
+#[derive(
+    Object,
+    Selectable,
+    Queryable,
+    Identifiable,
+    Insertable,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Clone,
+)]
+#[diesel(table_name = currency_exchange_rates)]
+#[diesel(primary_key(
+    currency_token_id,
+    currency_token_contract_address,
+    target_currency_token_id,
+    target_currency_token_contract_address,
+    rate_at
+))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CurrencyExchangeRate {
+    pub currency_token_id: Decimal,
+    pub currency_token_contract_address: Decimal,
+    pub target_currency_token_id: Decimal,
+    pub target_currency_token_contract_address: Decimal,
+    pub rate: Decimal,
+    pub rate_at: NaiveDateTime,
+}
+
+impl CurrencyExchangeRate {
+    pub fn insert(&self, conn: &mut DbConn) -> DbResult<CurrencyExchangeRate> {
+        diesel::insert_into(currency_exchange_rates::table)
+            .values(self)
+            .returning(CurrencyExchangeRate::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn list(
+        conn: &mut DbConn,
+        target_currency_token_id: Decimal,
+        target_currency_token_contract_address: Decimal,
+        page: i64,
+        page_size: i64,
+    ) -> DbResult<(Vec<Self>, i64)> {
+        let filter = currency_exchange_rates::target_currency_token_id
+            .eq(target_currency_token_id)
+            .and(
+                currency_exchange_rates::target_currency_token_contract_address
+                    .eq(target_currency_token_contract_address),
+            );
+        let rates = currency_exchange_rates::table
+            .filter(filter)
+            .select(CurrencyExchangeRate::as_select())
+            .order(currency_exchange_rates::rate_at.desc())
+            .limit(page_size)
+            .offset(page * page_size)
+            .get_results(conn)?;
+        let total_count = currency_exchange_rates::table
+            .filter(filter)
+            .count()
+            .get_result::<i64>(conn)?;
+        let page_count = (total_count as f64 / page_size as f64).ceil() as i64;
+
+        Ok((rates, page_count))
+    }
+
+    /// Returns the latest rate of every currency token into the target currency token at the given time.
+    pub fn latest_rates_at(
+        conn: &mut DbConn,
+        target_currency_token_id: Decimal,
+        target_currency_token_contract_address: Decimal,
+        at: NaiveDateTime,
+    ) -> DbResult<CurrencyRates> {
+        let rates = currency_exchange_rates::table
+            .filter(
+                currency_exchange_rates::target_currency_token_id
+                    .eq(target_currency_token_id)
+                    .and(
+                        currency_exchange_rates::target_currency_token_contract_address
+                            .eq(target_currency_token_contract_address),
+                    )
+                    .and(currency_exchange_rates::rate_at.le(at)),
+            )
+            .distinct_on((
+                currency_exchange_rates::currency_token_id,
+                currency_exchange_rates::currency_token_contract_address,
+            ))
+            .order_by((
+                currency_exchange_rates::currency_token_id,
+                currency_exchange_rates::currency_token_contract_address,
+                currency_exchange_rates::rate_at.desc(),
+            ))
+            .select((
+                currency_exchange_rates::currency_token_id,
+                currency_exchange_rates::currency_token_contract_address,
+                currency_exchange_rates::rate,
+            ))
+            .load::<(Decimal, Decimal, Decimal)>(conn)?
+            .into_iter()
+            .map(|(id, contract, rate)| ((id, contract), rate))
+            .collect();
+
+        Ok(CurrencyRates {
+            target: (
+                target_currency_token_id,
+                target_currency_token_contract_address,
+            ),
+            rates,
+        })
+    }
+}
+
+/// Rates of currency tokens, keyed by (token id, contract address), into a single target currency token.
+pub struct CurrencyRates {
+    pub target: (Decimal, Decimal),
+    pub rates:  BTreeMap<(Decimal, Decimal), Decimal>,
+}
+
+impl CurrencyRates {
+    /// Converts an amount of the currency token into the target currency token.
+    /// Returns `None` if there is no rate for the currency token.
+    pub fn convert(&self, currency: (Decimal, Decimal), amount: Decimal) -> Option<Decimal> {
+        if currency == self.target {
+            return Some(amount);
+        }
+        self.rates.get(&currency).map(|rate| amount * rate)
+    }
+
+    /// Sums the amounts of all the currency tokens in the target currency token.
+    /// Amounts of currency tokens without a rate are ignored.
+    pub fn convert_sum(&self, amounts: Vec<(Decimal, Decimal, Option<Decimal>)>) -> Decimal {
+        amounts
+            .into_iter()
+            .filter_map(|(id, contract, amount)| self.convert((id, contract), amount?))
+            .sum()
+    }
+}
+
+/// Values the balances of the account at the given time in the currency token.
+/// Projects priced in a different currency token are converted using the exchange rate at that time.
 pub fn portfolio_value_at(
     conn: &mut DbConn,
     account_address: &str,
@@ -103,33 +248,55 @@ pub fn portfolio_value_at(
         ))
         .load::<(TokenHolderBalanceUpdate, uuid::Uuid)>(conn)?;
 
-    let prices = forest_project_prices::table
-        .filter(
-            forest_project_prices::currency_token_id
-                .eq(curr_token_id)
-                .and(
-                    forest_project_prices::currency_token_contract_address
-                        .eq(curr_token_contract_address),
-                ),
-        )
+    let rates = CurrencyExchangeRate::latest_rates_at(
+        conn,
+        curr_token_id,
+        curr_token_contract_address,
+        at,
+    )?;
+    // The latest price of each project which can be valued in the currency token
+    let mut prices: BTreeMap<uuid::Uuid, (NaiveDateTime, Decimal)> = BTreeMap::new();
+    let project_prices = forest_project_prices::table
         .filter(forest_project_prices::price_at.le(at))
-        .distinct_on(forest_project_prices::project_id)
+        .distinct_on((
+            forest_project_prices::project_id,
+            forest_project_prices::currency_token_id,
+            forest_project_prices::currency_token_contract_address,
+        ))
         .order_by((
             forest_project_prices::project_id,
+            forest_project_prices::currency_token_id,
+            forest_project_prices::currency_token_contract_address,
             forest_project_prices::price_at.desc(),
         ))
         .select((
             forest_project_prices::project_id,
             forest_project_prices::price,
+            forest_project_prices::price_at,
+            forest_project_prices::currency_token_id,
+            forest_project_prices::currency_token_contract_address,
         ))
-        .load::<(uuid::Uuid, Decimal)>(conn)?
-        .into_iter()
-        .collect::<BTreeMap<uuid::Uuid, Decimal>>();
+        .load::<(uuid::Uuid, Decimal, NaiveDateTime, Decimal, Decimal)>(conn)?;
+    for (project_id, price, price_at, currency_token_id, currency_token_contract_address) in
+        project_prices
+    {
+        let price = match rates.convert((currency_token_id, currency_token_contract_address), price)
+        {
+            Some(price) => price,
+            None => continue,
+        };
+        match prices.get(&project_id) {
+            Some((latest_price_at, _)) if latest_price_at.ge(&price_at) => {}
+            _ => {
+                prices.insert(project_id, (price_at, price));
+            }
+        }
+    }
 
     let mut total_value_un_frozen = Decimal::ZERO;
     let mut total_value_frozen = Decimal::ZERO;
     for (balance_update, project_id) in balance_updates {
-        if let Some(price) = prices.get(&project_id) {
+        if let Some((_, price)) = prices.get(&project_id) {
             total_value_un_frozen += price * balance_update.un_frozen_balance;
             total_value_frozen += price * balance_update.frozen_balance;
         }
@@ -138,6 +305,8 @@ pub fn portfolio_value_at(
     Ok((total_value_un_frozen, total_value_frozen))
 }
 
+/// Total value invested by the account till the given time in the currency token.
+/// Investments made in a different currency token are converted using the exchange rate at that time.
 pub fn total_invested_value_till(
     conn: &mut DbConn,
     account_address: &str,
@@ -147,11 +316,16 @@ pub fn total_invested_value_till(
     token_contract_types: &[SecurityTokenContractType],
     at: NaiveDateTime,
 ) -> QueryResult<(Decimal, Decimal)> {
-    let (funds_invested, funds_locked) = total_invested_value_via_funds(
+    let rates = CurrencyExchangeRate::latest_rates_at(
         conn,
-        account_address,
         curr_token_id,
         curr_token_contract_address,
+        at,
+    )?;
+    let (funds_invested, funds_locked) = total_invested_value_via_funds(
+        conn,
+        account_address,
+        &rates,
         forest_project_states,
         token_contract_types,
         at,
@@ -159,8 +333,7 @@ pub fn total_invested_value_till(
     let market_invested = total_invested_value_via_markets(
         conn,
         account_address,
-        curr_token_id,
-        curr_token_contract_address,
+        &rates,
         forest_project_states,
         token_contract_types,
         at,
@@ -172,8 +345,7 @@ pub fn total_invested_value_till(
 fn total_invested_value_via_markets(
     conn: &mut r2d2::PooledConnection<diesel::r2d2::ConnectionManager<PgConnection>>,
     account_address: &str,
-    curr_token_id: Decimal,
-    curr_token_contract_address: Decimal,
+    rates: &CurrencyRates,
     forest_project_states: &[ForestProjectState],
     token_contract_types: &[SecurityTokenContractType],
     at: NaiveDateTime,
@@ -191,11 +363,6 @@ fn total_invested_value_via_markets(
         .filter(
             security_p2p_exchange_records::buyer
                 .eq(account_address)
-                .and(security_p2p_exchange_records::currency_token_id.eq(curr_token_id))
-                .and(
-                    security_p2p_exchange_records::currency_token_contract_address
-                        .eq(curr_token_contract_address),
-                )
                 .and(security_p2p_exchange_records::create_time.le(at)),
         )
         .filter(
@@ -203,9 +370,17 @@ fn total_invested_value_via_markets(
                 .eq_any(forest_project_states)
                 .and(forest_project_token_contracts::contract_type.eq_any(token_contract_types)),
         )
-        .select(sum(security_p2p_exchange_records::currency_amount))
-        .first::<Option<Decimal>>(conn)?
-        .unwrap_or(Decimal::ZERO);
+        .group_by((
+            security_p2p_exchange_records::currency_token_id,
+            security_p2p_exchange_records::currency_token_contract_address,
+        ))
+        .select((
+            security_p2p_exchange_records::currency_token_id,
+            security_p2p_exchange_records::currency_token_contract_address,
+            sum(security_p2p_exchange_records::currency_amount),
+        ))
+        .load::<(Decimal, Decimal, Option<Decimal>)>(conn)?;
+    let buy_value = rates.convert_sum(buy_value);
     let sell_value = security_p2p_exchange_records::table
         .inner_join(
             forest_project_token_contracts::table
@@ -219,11 +394,6 @@ fn total_invested_value_via_markets(
         .filter(
             security_p2p_exchange_records::seller
                 .eq(account_address)
-                .and(security_p2p_exchange_records::currency_token_id.eq(curr_token_id))
-                .and(
-                    security_p2p_exchange_records::currency_token_contract_address
-                        .eq(curr_token_contract_address),
-                )
                 .and(security_p2p_exchange_records::create_time.le(at)),
         )
         .filter(
@@ -231,9 +401,17 @@ fn total_invested_value_via_markets(
                 .eq_any(forest_project_states)
                 .and(forest_project_token_contracts::contract_type.eq_any(token_contract_types)),
         )
-        .select(sum(security_p2p_exchange_records::currency_amount))
-        .first::<Option<Decimal>>(conn)?
-        .unwrap_or(Decimal::ZERO);
+        .group_by((
+            security_p2p_exchange_records::currency_token_id,
+            security_p2p_exchange_records::currency_token_contract_address,
+        ))
+        .select((
+            security_p2p_exchange_records::currency_token_id,
+            security_p2p_exchange_records::currency_token_contract_address,
+            sum(security_p2p_exchange_records::currency_amount),
+        ))
+        .load::<(Decimal, Decimal, Option<Decimal>)>(conn)?;
+    let sell_value = rates.convert_sum(sell_value);
 
     Ok(buy_value - sell_value)
 }
@@ -241,8 +419,7 @@ fn total_invested_value_via_markets(
 fn total_invested_value_via_funds(
     conn: &mut r2d2::PooledConnection<diesel::r2d2::ConnectionManager<PgConnection>>,
     account_address: &str,
-    curr_token_id: Decimal,
-    curr_token_contract_address: Decimal,
+    rates: &CurrencyRates,
     forest_project_states: &[ForestProjectState],
     token_contract_types: &[SecurityTokenContractType],
     at: NaiveDateTime,
@@ -264,11 +441,6 @@ fn total_invested_value_via_funds(
         .filter(
             security_mint_fund_investment_records::investor
                 .eq(account_address)
-                .and(security_mint_fund_investment_records::currency_token_id.eq(curr_token_id))
-                .and(
-                    security_mint_fund_investment_records::currency_token_contract_address
-                        .eq(curr_token_contract_address),
-                )
                 .and(security_mint_fund_investment_records::create_time.le(at)),
         )
         .filter(
@@ -276,9 +448,17 @@ fn total_invested_value_via_funds(
                 .eq_any(forest_project_states)
                 .and(forest_project_token_contracts::contract_type.eq_any(token_contract_types)),
         )
-        .select(sum(security_mint_fund_investment_records::currency_amount))
-        .first::<Option<Decimal>>(conn)?
-        .unwrap_or(Decimal::ZERO);
+        .group_by((
+            security_mint_fund_investment_records::currency_token_id,
+            security_mint_fund_investment_records::currency_token_contract_address,
+        ))
+        .select((
+            security_mint_fund_investment_records::currency_token_id,
+            security_mint_fund_investment_records::currency_token_contract_address,
+            sum(security_mint_fund_investment_records::currency_amount),
+        ))
+        .load::<(Decimal, Decimal, Option<Decimal>)>(conn)?;
+    let invested = rates.convert_sum(invested);
     let claimed = security_mint_fund_investment_records::table
         .inner_join(
             forest_project_token_contracts::table
@@ -296,11 +476,6 @@ fn total_invested_value_via_funds(
         .filter(
             security_mint_fund_investment_records::investor
                 .eq(account_address)
-                .and(security_mint_fund_investment_records::currency_token_id.eq(curr_token_id))
-                .and(
-                    security_mint_fund_investment_records::currency_token_contract_address
-                        .eq(curr_token_contract_address),
-                )
                 .and(security_mint_fund_investment_records::create_time.le(at)),
         )
         .filter(
@@ -308,9 +483,17 @@ fn total_invested_value_via_funds(
                 .eq_any(forest_project_states)
                 .and(forest_project_token_contracts::contract_type.eq_any(token_contract_types)),
         )
-        .select(sum(security_mint_fund_investment_records::currency_amount))
-        .first::<Option<Decimal>>(conn)?
-        .unwrap_or(Decimal::ZERO);
+        .group_by((
+            security_mint_fund_investment_records::currency_token_id,
+            security_mint_fund_investment_records::currency_token_contract_address,
+        ))
+        .select((
+            security_mint_fund_investment_records::currency_token_id,
+            security_mint_fund_investment_records::currency_token_contract_address,
+            sum(security_mint_fund_investment_records::currency_amount),
+        ))
+        .load::<(Decimal, Decimal, Option<Decimal>)>(conn)?;
+    let claimed = rates.convert_sum(claimed);
     let cancelled = security_mint_fund_investment_records::table
         .inner_join(
             forest_project_token_contracts::table
@@ -322,17 +505,14 @@ fn total_invested_value_via_funds(
                 .on(forest_projects::id.eq(forest_project_token_contracts::forest_project_id)),
         )
         .filter(
-            security_mint_fund_investment_records::investment_record_type
-                .eq(InvestmentRecordType::Cancelled),
+            security_mint_fund_investment_records::investment_record_type.eq_any([
+                InvestmentRecordType::Cancelled,
+                InvestmentRecordType::Withdrawn,
+            ]),
         )
         .filter(
             security_mint_fund_investment_records::investor
                 .eq(account_address)
-                .and(security_mint_fund_investment_records::currency_token_id.eq(curr_token_id))
-                .and(
-                    security_mint_fund_investment_records::currency_token_contract_address
-                        .eq(curr_token_contract_address),
-                )
                 .and(security_mint_fund_investment_records::create_time.le(at)),
         )
         .filter(
@@ -340,9 +520,17 @@ fn total_invested_value_via_funds(
                 .eq_any(forest_project_states)
                 .and(forest_project_token_contracts::contract_type.eq_any(token_contract_types)),
         )
-        .select(sum(security_mint_fund_investment_records::currency_amount))
-        .first::<Option<Decimal>>(conn)?
-        .unwrap_or(Decimal::ZERO);
+        .group_by((
+            security_mint_fund_investment_records::currency_token_id,
+            security_mint_fund_investment_records::currency_token_contract_address,
+        ))
+        .select((
+            security_mint_fund_investment_records::currency_token_id,
+            security_mint_fund_investment_records::currency_token_contract_address,
+            sum(security_mint_fund_investment_records::currency_amount),
+        ))
+        .load::<(Decimal, Decimal, Option<Decimal>)>(conn)?;
+    let cancelled = rates.convert_sum(cancelled);
 
     let invested = invested - cancelled;
     let locked = invested - claimed;
@@ -64,6 +64,13 @@ impl User {
             .optional()
     }
 
+    pub fn list_by_emails(conn: &mut DbConn, emails: &[String]) -> DbResult<Vec<User>> {
+        users::table
+            .filter(users::email.eq_any(emails))
+            .select(User::as_select())
+            .get_results(conn)
+    }
+
     pub fn find_by_account_address(
         conn: &mut DbConn,
         account_address: &str,
@@ -179,7 +186,9 @@ impl UserKYCModel {
             .filter(identity_registry_address.eq(identity_registry_contract_index))
             .first(conn)
             .optional()?
-            .map(|(user, identity)| UserKYCModel::new(user, identity.is_some()));
+            .map(|(user, identity)| {
+                UserKYCModel::new(user, identity.is_some_and(|i| i.is_valid()))
+            });
         Ok(res)
     }
 
@@ -198,7 +207,9 @@ impl UserKYCModel {
             .filter(identity_registry_address.eq(identity_registry_contract_index))
             .first(conn)
             .optional()?
-            .map(|(user, identity)| UserKYCModel::new(user, identity.is_some()));
+            .map(|(user, identity)| {
+                UserKYCModel::new(user, identity.is_some_and(|i| i.is_valid()))
+            });
         Ok(res)
     }
 
@@ -225,7 +236,9 @@ impl UserKYCModel {
             .offset(page * page_size)
             .get_results(conn)?
             .into_iter()
-            .map(|(user, identity)| UserKYCModel::new(user, identity.is_some()));
+            .map(|(user, identity)| {
+                UserKYCModel::new(user, identity.is_some_and(|i| i.is_valid()))
+            });
 
         let count: i64 = query.count().get_result(conn)?;
         let page_count = (count as f64 / page_size as f64).ceil() as i64;
@@ -256,7 +269,9 @@ impl UserKYCModel {
             .offset(page * page_size)
             .get_results(conn)?
             .into_iter()
-            .map(|(user, identity)| UserKYCModel::new(user, identity.is_some()));
+            .map(|(user, identity)| {
+                UserKYCModel::new(user, identity.is_some_and(|i| i.is_valid()))
+            });
 
         let count: i64 = query.count().get_result(conn)?;
         let page_count = (count as f64 / page_size as f64).ceil() as i64;
@@ -370,6 +385,16 @@ impl UserRegistrationRequest {
             .optional()
     }
 
+    /// Lists the accepted requests among the given request ids.
+    pub fn list_accepted(conn: &mut DbConn, request_ids: &[Uuid]) -> DbResult<Vec<Self>> {
+        use crate::schema::user_registration_requests::dsl::*;
+        user_registration_requests
+            .filter(id.eq_any(request_ids))
+            .filter(is_accepted.eq(true))
+            .select(Self::as_select())
+            .get_results(conn)
+    }
+
     pub fn find_by_email(conn: &mut DbConn, user_email: &str) -> DbResult<Option<Self>> {
         use crate::schema::user_registration_requests::dsl::*;
         user_registration_requests
@@ -396,6 +421,11 @@ impl UserRegistrationRequest {
         use crate::schema::user_registration_requests::dsl::*;
         diesel::delete(user_registration_requests.filter(id.eq(request_id))).execute(conn)
     }
+
+    pub fn delete_many(conn: &mut DbConn, request_ids: &[Uuid]) -> DbResult<usize> {
+        use crate::schema::user_registration_requests::dsl::*;
+        diesel::delete(user_registration_requests.filter(id.eq_any(request_ids))).execute(conn)
+    }
 }
 
 #[derive(
@@ -1,10 +1,26 @@
 // @generated automatically by Diesel CLI.
 
 pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "cis2_proposal_status"))]
+    pub struct Cis2ProposalStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "cis2_recovery_status"))]
+    pub struct Cis2RecoveryStatus;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "cis2_token_holder_balance_update_type"))]
     pub struct Cis2TokenHolderBalanceUpdateType;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "compliance_jurisdiction"))]
+    pub struct ComplianceJurisdiction;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "compliance_list_type"))]
+    pub struct ComplianceListType;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "forest_project_security_token_contract_type"))]
     pub struct ForestProjectSecurityTokenContractType;
@@ -13,10 +29,18 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "forest_project_state"))]
     pub struct ForestProjectState;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "identity_invalid_reason"))]
+    pub struct IdentityInvalidReason;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "security_mint_fund_investment_record_type"))]
     pub struct SecurityMintFundInvestmentRecordType;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "security_mint_fund_investor_access"))]
+    pub struct SecurityMintFundInvestorAccess;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "security_mint_fund_state"))]
     pub struct SecurityMintFundState;
@@ -29,6 +53,22 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "security_p2p_trading_market_type"))]
     pub struct SecurityP2pTradingMarketType;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "security_p2p_trading_order_settlement"))]
+    pub struct SecurityP2pTradingOrderSettlement;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "security_p2p_trading_order_side"))]
+    pub struct SecurityP2pTradingOrderSide;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "security_p2p_trading_order_status"))]
+    pub struct SecurityP2pTradingOrderStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "security_sft_multi_yielder_claim_mode"))]
+    pub struct SecuritySftMultiYielderClaimMode;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "security_sft_multi_yielder_yield_type"))]
     pub struct SecuritySftMultiYielderYieldType;
@@ -51,6 +91,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    cis2_documents (cis2_address, token_id, name) {
+        cis2_address -> Numeric,
+        token_id -> Numeric,
+        name -> Varchar,
+        uri -> Varchar,
+        content_hash -> Varchar,
+        document_time -> Timestamp,
+        block_height -> Numeric,
+        txn_index -> Numeric,
+    }
+}
+
+diesel::table! {
+    cis2_governances (cis2_address) {
+        cis2_address -> Numeric,
+        approvers -> Array<Nullable<Varchar>>,
+        threshold -> Int4,
+        delay_millis -> Numeric,
+        update_time -> Timestamp,
+    }
+}
+
 diesel::table! {
     cis2_identity_registries (cis2_address, identity_registry_address) {
         cis2_address -> Numeric,
@@ -67,10 +130,63 @@ diesel::table! {
 }
 
 diesel::table! {
+    cis2_permit_nonces (cis2_address, account_address) {
+        cis2_address -> Numeric,
+        account_address -> Varchar,
+        nonce -> Numeric,
+        update_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Cis2ProposalStatus;
+
+    cis2_proposals (cis2_address, proposal_id) {
+        cis2_address -> Numeric,
+        proposal_id -> Numeric,
+        proposer -> Varchar,
+        entrypoint -> Varchar,
+        execute_after -> Timestamp,
+        approvals -> Array<Nullable<Varchar>>,
+        status -> Cis2ProposalStatus,
+        create_time -> Timestamp,
+        update_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Cis2RecoveryStatus;
+
     cis2_recovery_records (cis2_address, holder_address) {
         cis2_address -> Numeric,
         holder_address -> Varchar,
         recovered_address -> Varchar,
+        status -> Cis2RecoveryStatus,
+        execute_after -> Nullable<Timestamp>,
+        update_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    cis2_snapshot_balances (cis2_address, snapshot_id, token_id, holder_address) {
+        cis2_address -> Numeric,
+        snapshot_id -> Numeric,
+        token_id -> Numeric,
+        holder_address -> Varchar,
+        frozen_balance -> Numeric,
+        un_frozen_balance -> Numeric,
+    }
+}
+
+diesel::table! {
+    cis2_snapshots (cis2_address, snapshot_id) {
+        cis2_address -> Numeric,
+        snapshot_id -> Numeric,
+        block_height -> Numeric,
+        txn_index -> Numeric,
+        create_time -> Timestamp,
     }
 }
 
@@ -149,6 +265,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ComplianceJurisdiction;
+    use super::sql_types::ComplianceListType;
+
+    compliance_jurisdiction_rules (module_address, jurisdiction, list_type, country) {
+        module_address -> Numeric,
+        jurisdiction -> ComplianceJurisdiction,
+        list_type -> ComplianceListType,
+        country -> Varchar,
+        create_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    currency_exchange_rates (currency_token_id, currency_token_contract_address, target_currency_token_id, target_currency_token_contract_address, rate_at) {
+        currency_token_id -> Numeric,
+        currency_token_contract_address -> Numeric,
+        target_currency_token_id -> Numeric,
+        target_currency_token_contract_address -> Numeric,
+        rate -> Numeric,
+        rate_at -> Timestamp,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     forest_project_legal_contract_user_signatures (project_id, cognito_user_id) {
         project_id -> Uuid,
@@ -280,10 +423,16 @@ diesel::table! {
 }
 
 diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::IdentityInvalidReason;
+
     identity_registry_identities (identity_registry_address, identity_address) {
         identity_registry_address -> Numeric,
         identity_address -> Varchar,
         create_time -> Timestamp,
+        invalidated_at -> Nullable<Timestamp>,
+        invalid_reason -> Nullable<IdentityInvalidReason>,
+        linked_identity_address -> Nullable<Varchar>,
     }
 }
 
@@ -480,12 +629,14 @@ diesel::table! {
         token_amount_total -> Numeric,
         create_time -> Timestamp,
         update_time -> Timestamp,
+        token_amount_released -> Numeric,
     }
 }
 
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::SecurityMintFundState;
+    use super::sql_types::SecurityMintFundInvestorAccess;
 
     security_mint_funds (contract_address, investment_token_id, investment_token_contract_address) {
         contract_address -> Numeric,
@@ -503,6 +654,17 @@ diesel::table! {
         fund_state -> SecurityMintFundState,
         create_time -> Timestamp,
         update_time -> Timestamp,
+        soft_cap -> Nullable<Numeric>,
+        hard_cap -> Nullable<Numeric>,
+        deadline -> Nullable<Timestamp>,
+        min_investment -> Nullable<Numeric>,
+        max_investment -> Nullable<Numeric>,
+        investor_access -> SecurityMintFundInvestorAccess,
+        identity_registry_address -> Nullable<Numeric>,
+        identity_attribute_tag -> Nullable<Int4>,
+        identity_attribute_value -> Nullable<Varchar>,
+        vesting_lock_up_millis -> Nullable<Numeric>,
+        vesting_period_millis -> Nullable<Numeric>,
     }
 }
 
@@ -526,6 +688,8 @@ diesel::table! {
         rate -> Numeric,
         create_time -> Timestamp,
         exchange_record_type -> SecurityP2pTradingExchangeRecordType,
+        fee_amount -> Numeric,
+        fee_receiver -> Nullable<Varchar>,
     }
 }
 
@@ -539,9 +703,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    security_p2p_trading_market_price_tiers (contract_address, token_contract_address, tier_index) {
+        contract_address -> Numeric,
+        token_contract_address -> Numeric,
+        tier_index -> Int4,
+        rate_numerator -> Numeric,
+        rate_denominator -> Numeric,
+        token_amount -> Numeric,
+        sold_token_amount -> Numeric,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::SecurityP2pTradingMarketType;
+    use super::sql_types::SecurityP2pTradingOrderSettlement;
 
     security_p2p_trading_markets (contract_address, token_contract_address) {
         contract_address -> Numeric,
@@ -566,6 +743,56 @@ diesel::table! {
         currency_out_amount -> Numeric,
         token_out_amount -> Numeric,
         currency_in_amount -> Numeric,
+        open_time -> Nullable<Timestamp>,
+        close_time -> Nullable<Timestamp>,
+        max_investor_token_amount -> Nullable<Numeric>,
+        fee_rate_numerator -> Nullable<Numeric>,
+        fee_rate_denominator -> Nullable<Numeric>,
+        fee_receiver -> Nullable<Varchar>,
+        settlement -> SecurityP2pTradingOrderSettlement,
+    }
+}
+
+diesel::table! {
+    security_p2p_trading_order_fills (id) {
+        id -> Uuid,
+        block_height -> Numeric,
+        txn_index -> Numeric,
+        contract_address -> Numeric,
+        order_id -> Numeric,
+        taker -> Varchar,
+        token_amount -> Numeric,
+        currency_amount -> Numeric,
+        remaining_token_amount -> Numeric,
+        create_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::SecurityP2pTradingOrderSide;
+    use super::sql_types::SecurityP2pTradingOrderStatus;
+    use super::sql_types::SecurityP2pTradingOrderSettlement;
+
+    security_p2p_trading_orders (contract_address, order_id) {
+        contract_address -> Numeric,
+        order_id -> Numeric,
+        token_contract_address -> Numeric,
+        token_id -> Numeric,
+        currency_token_id -> Numeric,
+        currency_token_contract_address -> Numeric,
+        owner -> Varchar,
+        side -> SecurityP2pTradingOrderSide,
+        rate_numerator -> Numeric,
+        rate_denominator -> Numeric,
+        token_amount -> Numeric,
+        remaining_token_amount -> Numeric,
+        filled_currency_amount -> Numeric,
+        status -> SecurityP2pTradingOrderStatus,
+        create_time -> Timestamp,
+        update_time -> Timestamp,
+        settlement -> SecurityP2pTradingOrderSettlement,
+        expiry -> Nullable<Timestamp>,
     }
 }
 
@@ -586,6 +813,74 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    security_sft_multi_yielder_checkpoints (contract_address, token_contract_address, token_id, holder_address) {
+        contract_address -> Numeric,
+        token_contract_address -> Numeric,
+        token_id -> Numeric,
+        holder_address -> Varchar,
+        claimed_token_id -> Numeric,
+        create_time -> Timestamp,
+        update_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::SecuritySftMultiYielderClaimMode;
+
+    security_sft_multi_yielder_claim_modes (contract_address, token_contract_address) {
+        contract_address -> Numeric,
+        token_contract_address -> Numeric,
+        claim_mode -> SecuritySftMultiYielderClaimMode,
+        create_time -> Timestamp,
+        update_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    security_sft_multi_yielder_pool_claims (contract_address, token_contract_address, token_id, yield_contract_address, yield_token_id, from_token_id, holder_address) {
+        contract_address -> Numeric,
+        token_contract_address -> Numeric,
+        token_id -> Numeric,
+        yield_contract_address -> Numeric,
+        yield_token_id -> Numeric,
+        from_token_id -> Numeric,
+        holder_address -> Varchar,
+        security_amount -> Numeric,
+        create_time -> Timestamp,
+        update_time -> Timestamp,
+    }
+}
+
+diesel::table! {
+    security_sft_multi_yielder_pools (contract_address, token_contract_address, token_id, yield_contract_address, yield_token_id) {
+        contract_address -> Numeric,
+        token_contract_address -> Numeric,
+        token_id -> Numeric,
+        yield_contract_address -> Numeric,
+        yield_token_id -> Numeric,
+        amount -> Numeric,
+        supply -> Numeric,
+        claimed_amount -> Numeric,
+        returned_amount -> Numeric,
+        expiry -> Timestamp,
+        create_time -> Timestamp,
+        update_time -> Timestamp,
+        snapshot_id -> Numeric,
+        token_ids -> Array<Nullable<Numeric>>,
+    }
+}
+
+diesel::table! {
+    security_sft_multi_yielder_token_versions (contract_address, token_contract_address, token_id) {
+        contract_address -> Numeric,
+        token_contract_address -> Numeric,
+        token_id -> Numeric,
+        create_time -> Timestamp,
+    }
+}
+
 diesel::table! {
     security_sft_multi_yielder_treasuries (contract_address) {
         contract_address -> Numeric,
@@ -699,13 +994,19 @@ diesel::table! {
 
 diesel::joinable!(cis2_agents -> listener_contracts (cis2_address));
 diesel::joinable!(cis2_compliances -> listener_contracts (cis2_address));
+diesel::joinable!(cis2_documents -> listener_contracts (cis2_address));
+diesel::joinable!(cis2_governances -> listener_contracts (cis2_address));
 diesel::joinable!(cis2_operators -> listener_contracts (cis2_address));
+diesel::joinable!(cis2_permit_nonces -> listener_contracts (cis2_address));
+diesel::joinable!(cis2_proposals -> listener_contracts (cis2_address));
 diesel::joinable!(cis2_recovery_records -> listener_contracts (cis2_address));
+diesel::joinable!(cis2_snapshots -> listener_contracts (cis2_address));
 diesel::joinable!(cis2_token_holder_balance_updates -> listener_contracts (cis2_address));
 diesel::joinable!(cis2_token_holders -> listener_contracts (cis2_address));
 diesel::joinable!(cis2_tokens -> listener_contracts (cis2_address));
 diesel::joinable!(company_invitations -> companies (company_id));
 diesel::joinable!(company_invitations -> users (created_by));
+diesel::joinable!(compliance_jurisdiction_rules -> listener_contracts (module_address));
 diesel::joinable!(forest_project_legal_contract_user_signatures -> forest_projects (project_id));
 diesel::joinable!(forest_project_legal_contract_user_signatures -> users (cognito_user_id));
 diesel::joinable!(forest_project_legal_contracts -> forest_projects (project_id));
@@ -733,6 +1034,8 @@ diesel::joinable!(security_p2p_exchange_records -> security_p2p_trading_contract
 diesel::joinable!(security_p2p_trading_contracts -> listener_contracts (contract_address));
 diesel::joinable!(security_p2p_trading_markets -> listener_contracts (token_contract_address));
 diesel::joinable!(security_p2p_trading_markets -> security_p2p_trading_contracts (contract_address));
+diesel::joinable!(security_p2p_trading_orders -> listener_contracts (token_contract_address));
+diesel::joinable!(security_p2p_trading_orders -> security_p2p_trading_contracts (contract_address));
 diesel::joinable!(security_p2p_trading_traders -> listener_contracts (token_contract_address));
 diesel::joinable!(security_p2p_trading_traders -> security_p2p_trading_contracts (contract_address));
 diesel::joinable!(support_questions -> users (cognito_user_id));
@@ -741,14 +1044,22 @@ diesel::joinable!(users -> companies (company_id));
 diesel::allow_tables_to_appear_in_same_query!(
     cis2_agents,
     cis2_compliances,
+    cis2_documents,
+    cis2_governances,
     cis2_identity_registries,
     cis2_operators,
+    cis2_permit_nonces,
+    cis2_proposals,
     cis2_recovery_records,
+    cis2_snapshot_balances,
+    cis2_snapshots,
     cis2_token_holder_balance_updates,
     cis2_token_holders,
     cis2_tokens,
     companies,
     company_invitations,
+    compliance_jurisdiction_rules,
+    currency_exchange_rates,
     forest_project_legal_contract_user_signatures,
     forest_project_legal_contracts,
     forest_project_notifications,
@@ -779,8 +1090,16 @@ diesel::allow_tables_to_appear_in_same_query!(
     security_mint_funds,
     security_p2p_exchange_records,
     security_p2p_trading_contracts,
+    security_p2p_trading_market_price_tiers,
     security_p2p_trading_markets,
+    security_p2p_trading_order_fills,
+    security_p2p_trading_orders,
     security_p2p_trading_traders,
+    security_sft_multi_yielder_checkpoints,
+    security_sft_multi_yielder_claim_modes,
+    security_sft_multi_yielder_pool_claims,
+    security_sft_multi_yielder_pools,
+    security_sft_multi_yielder_token_versions,
     security_sft_multi_yielder_treasuries,
     security_sft_multi_yielder_yeild_distributions,
     security_sft_multi_yielder_yields,
@@ -11,6 +11,7 @@ diesel::table! {
         cognito_user_id -> Varchar,
         transaction_type -> Varchar,
         account_address -> Varchar,
+        fee_amount -> Numeric,
     }
 }
 
@@ -86,4 +87,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    forest_project_trading_fees (forest_project_id, contract_address, currency_token_id, currency_token_contract_address, fee_receiver) {
+        forest_project_id -> Uuid,
+        contract_address -> Numeric,
+        currency_token_id -> Numeric,
+        currency_token_contract_address -> Numeric,
+        fee_receiver -> Varchar,
+        fee_amount -> Numeric,
+        currency_amount -> Numeric,
+        exchange_count -> BigInt,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(holder_yields, forest_project_token_contracts,);
@@ -6,6 +6,7 @@ use poem_openapi::param::{Path, Query};
 use poem_openapi::payload::{Attachment, AttachmentType};
 use poem_openapi::OpenApi;
 use shared::api::PagedResponse;
+use shared::db::cis2_security::{PermitNonce, TokenDocument};
 use shared::db::security_mint_fund::SecurityMintFund;
 use shared::db::security_p2p_trading::Market;
 use shared::db_app::forest_project::{
@@ -26,6 +27,12 @@ const FOREST_PROJECT_STATES: &[ForestProjectState; 3] = &[
     ForestProjectState::Bond,
     ForestProjectState::Funded,
 ];
+/// The name of the document of the property token contract which registers the legal contract.
+pub const LEGAL_CONTRACT_DOCUMENT_NAME: &str = "legal_contract";
+/// Maximum size of a legal contract document downloaded for verification.
+const LEGAL_CONTRACT_MAX_SIZE: usize = 20 * 1024 * 1024;
+/// Timeout of downloading a legal contract document for verification.
+const LEGAL_CONTRACT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[OpenApi]
 impl ForestProjectApi {
@@ -360,6 +367,32 @@ impl ForestProjectApi {
         Ok(Json(contracts))
     }
 
+    /// Retrieves the next CIS-3 permit nonce of the authenticated account on the token contract.
+    /// A permit signed with this nonce can be submitted on behalf of the account by a sponsor.
+    #[oai(
+        path = "/forest_projects/contract/:contract_address/self_nonce",
+        method = "get",
+        tag = "ApiTags::ForestProject"
+    )]
+    pub async fn forest_project_token_contract_self_nonce(
+        &self,
+        BearerAuthorization(claims): BearerAuthorization,
+        Data(db_pool): Data<&DbPool>,
+        Path(contract_address): Path<Decimal>,
+    ) -> JsonResult<u64> {
+        let conn = &mut db_pool.get()?;
+        let account = ensure_account_registered(&claims)?;
+        let nonce = PermitNonce::find(conn, contract_address, &account.to_string())?
+            .map(|nonce| {
+                nonce
+                    .nonce
+                    .to_u64()
+                    .expect("unable to convert nonce to u64")
+            })
+            .unwrap_or(0);
+        Ok(Json(nonce))
+    }
+
     #[oai(
         path = "/forest_projects/yields/total",
         method = "get",
@@ -512,6 +545,123 @@ impl ForestProjectApi {
         let contract = LegalContractUserModel::find(conn, project_id, &claims.sub, &account)?;
         Ok(Json(contract))
     }
+
+    /// Verifies that the hash of the legal contract document served for the project matches
+    /// the `legal_contract` document registered on chain for the fund token of the property token
+    /// contract.
+    #[oai(
+        path = "/forest_projects/:project_id/legal_contract/verify",
+        method = "get",
+        tag = "ApiTags::ForestProject"
+    )]
+    pub async fn forest_project_legal_contract_verify(
+        &self,
+        BearerAuthorization(claims): BearerAuthorization,
+        Data(db_pool): Data<&DbPool>,
+        Path(project_id): Path<uuid::Uuid>,
+    ) -> JsonResult<LegalContractVerification> {
+        ensure_account_registered(&claims)?;
+        let (legal_contract, on_chain_document) = {
+            let conn = &mut db_pool.get()?;
+            let legal_contract = LegalContract::find(conn, project_id)?.ok_or(Error::NotFound(
+                PlainText("Legal contract not found".to_string()),
+            ))?;
+            let on_chain_document = match ForestProjectTokenContract::find_by_type(
+                conn,
+                project_id,
+                SecurityTokenContractType::Property,
+            )? {
+                Some(ForestProjectTokenContract {
+                    contract_address,
+                    fund_token_id: Some(token_id),
+                    ..
+                }) => TokenDocument::find(
+                    conn,
+                    contract_address,
+                    token_id,
+                    LEGAL_CONTRACT_DOCUMENT_NAME,
+                )?,
+                _ => None,
+            };
+            (legal_contract, on_chain_document)
+        };
+
+        let content = download_legal_contract(&legal_contract.pdf_url).await?;
+        let content_hash = hex::encode(hasher(content));
+        let verified = on_chain_document
+            .as_ref()
+            .is_some_and(|document| document.content_hash == content_hash);
+        if !verified {
+            info!(
+                "Legal contract of project {} does not match the on chain document",
+                project_id
+            );
+        }
+        Ok(Json(LegalContractVerification {
+            project_id,
+            document_url: legal_contract.pdf_url,
+            content_hash,
+            on_chain_document,
+            verified,
+        }))
+    }
+}
+
+/// Downloads the legal contract document.
+/// Fails if the document is larger than `LEGAL_CONTRACT_MAX_SIZE` or the download takes longer
+/// than `LEGAL_CONTRACT_DOWNLOAD_TIMEOUT`.
+async fn download_legal_contract(url: &str) -> Result<Vec<u8>> {
+    let fetch_error = |e: reqwest::Error| {
+        error!("Failed to fetch legal contract document: {}", e);
+        Error::InternalServer(PlainText(format!(
+            "Failed to fetch legal contract document: {}",
+            e
+        )))
+    };
+    let too_large = || {
+        error!("Legal contract document {} is too large", url);
+        Error::InternalServer(PlainText(format!(
+            "Legal contract document is larger than {} bytes",
+            LEGAL_CONTRACT_MAX_SIZE
+        )))
+    };
+
+    let mut res = reqwest::Client::builder()
+        .timeout(LEGAL_CONTRACT_DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(fetch_error)?
+        .get(url)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(fetch_error)?;
+    if res
+        .content_length()
+        .is_some_and(|length| length > LEGAL_CONTRACT_MAX_SIZE as u64)
+    {
+        return Err(too_large());
+    }
+    let mut content = Vec::new();
+    while let Some(chunk) = res.chunk().await.map_err(fetch_error)? {
+        if content.len() + chunk.len() > LEGAL_CONTRACT_MAX_SIZE {
+            return Err(too_large());
+        }
+        content.extend_from_slice(&chunk);
+    }
+    Ok(content)
+}
+
+#[derive(Object, serde::Serialize)]
+pub struct LegalContractVerification {
+    pub project_id:        Uuid,
+    /// The url of the legal contract document which was verified.
+    pub document_url:      String,
+    /// Hex encoded SHA-256 hash of the served legal contract document.
+    pub content_hash:      String,
+    /// The legal contract document registered on the property token contract.
+    pub on_chain_document: Option<TokenDocument>,
+    /// True if the hash of the served document matches the on chain document.
+    pub verified:          bool,
 }
 
 #[derive(Object, serde::Serialize, serde::Deserialize)]
@@ -963,6 +1113,25 @@ impl ForestProjectAdminApi {
         }))
     }
 
+    /// Lists the platform fees collected on the exchanges of the tokens of the forest project.
+    /// Fees are grouped by trading contract, currency token and fee receiver.
+    #[oai(
+        path = "/admin/forest_projects/:project_id/trading_fees",
+        method = "get",
+        tag = "ApiTags::ForestProject"
+    )]
+    pub async fn admin_forest_project_trading_fees(
+        &self,
+        BearerAuthorization(claims): BearerAuthorization,
+        Data(db_pool): Data<&DbPool>,
+        Path(project_id): Path<uuid::Uuid>,
+    ) -> JsonResult<Vec<ForestProjectTradingFee>> {
+        ensure_is_admin(&claims)?;
+        let conn = &mut db_pool.get()?;
+        let fees = ForestProjectTradingFee::list_by_forest_project_id(conn, project_id)?;
+        Ok(Json(fees))
+    }
+
     #[oai(
         path = "/admin/forest_projects/:project_id/price",
         method = "post",
@@ -1,8 +1,22 @@
+use std::collections::BTreeMap;
+
+use concordium_rust_sdk::base::contracts_common::attributes::NATIONALITY;
+use concordium_rust_sdk::base::contracts_common::{
+    Address, Amount, ExceedsParameterSize, NewReceiveNameError,
+};
+use concordium_rust_sdk::contract_client::{ContractClient, ContractUpdateError};
+use concordium_rust_sdk::types::RejectReason;
+use concordium_rwa_identity_registry::types::{
+    Identity, IdentityAttribute, RegisterIdentityParams,
+};
+use itertools::Itertools;
 use poem::web::Data;
 use poem_openapi::payload::{Json, PlainText};
 use poem_openapi::OpenApi;
 use shared::db::txn_listener::ListenerContract;
+use shared::db_app::users::{User, UserRegistrationRequest};
 use shared::db_shared::DbPool;
+use tracing::{info, warn};
 
 use super::*;
 
@@ -39,4 +53,178 @@ impl Api {
                 .ok_or(Error::NotFound(PlainText("Contract not found".to_string())))?;
         Ok(Json(contract))
     }
+
+    /// Registers the identities of the users of accepted registration requests in the identity
+    /// registry contract.
+    ///
+    /// The identities are registered in batches of `identity_registration_batch_size`, each batch
+    /// in a single `registerIdentities` transaction signed by the identity registry agent. The
+    /// requests of the registered identities are deleted. Requests of users who have not
+    /// completed their registration yet are kept and returned as pending. A failing batch is
+    /// logged and its requests are kept so that they can be submitted again. Fails when the
+    /// identity registry agent wallet is not configured.
+    ///
+    /// # Arguments
+    /// * `db_pool` - A reference to the database connection pool.
+    /// * `claims` - The bearer authorization claims of the authenticated user.
+    /// * `contracts` - The system contracts configuration.
+    /// * `concordium_client` - The concordium node client.
+    /// * `config` - The identity registry agent and batch size.
+    /// * `request_ids` - The ids of the accepted registration requests.
+    ///
+    /// # Returns
+    /// A JSON result containing the registered, pending and failed emails and the transaction
+    /// hashes of the batches.
+    #[oai(
+        path = "/admin/identity_registry/register_requests",
+        method = "post",
+        tag = "ApiTags::IdentityRegistry"
+    )]
+    pub async fn register_requests(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Data(contracts): Data<&SystemContractsConfig>,
+        Data(concordium_client): Data<&v2::Client>,
+        Data(config): Data<&IdentityRegistryConfig>,
+        Json(request_ids): Json<Vec<uuid::Uuid>>,
+    ) -> JsonResult<IdentityRegistrationResult> {
+        ensure_is_admin(&claims)?;
+        let agent = config
+            .agent
+            .as_ref()
+            .ok_or(Error::InternalServer(PlainText(
+                "Identity registry agent wallet is not configured".to_string(),
+            )))?;
+        let mut conn = db_pool.get()?;
+        let requests = UserRegistrationRequest::list_accepted(&mut conn, &request_ids)?;
+        if requests.len() != request_ids.len() {
+            return Err(Error::BadRequest(PlainText(
+                "Only accepted registration requests can be registered".to_string(),
+            )));
+        }
+        let emails: Vec<String> = requests.iter().map(|r| r.email.clone()).collect();
+        let users: BTreeMap<String, User> = User::list_by_emails(&mut conn, &emails)?
+            .into_iter()
+            .map(|user| (user.email.clone(), user))
+            .collect();
+
+        let mut result = IdentityRegistrationResult::default();
+        let mut registrations = Vec::with_capacity(requests.len());
+        for request in requests {
+            match users.get(&request.email) {
+                Some(user) => registrations.push((request, register_identity_params(user))),
+                None => result.pending.push(request.email),
+            }
+        }
+
+        if registrations.is_empty() {
+            return Ok(Json(result));
+        }
+
+        let mut contract: ContractClient<IdentityRegistryContract> =
+            ContractClient::create(concordium_client.clone(), contracts.identity_registry())
+                .await
+                .map_err(|e| Error::InternalServer(PlainText(e.to_string())))?;
+        let batches: Vec<Vec<_>> = registrations
+            .into_iter()
+            .chunks(config.batch_size.max(1))
+            .into_iter()
+            .map(|batch| batch.collect())
+            .collect();
+        for batch in batches {
+            let (requests, params): (Vec<UserRegistrationRequest>, Vec<_>) =
+                batch.into_iter().unzip();
+            let (ids, emails): (Vec<_>, Vec<_>) =
+                requests.into_iter().map(|r| (r.id, r.email)).unzip();
+            match register_identities(&mut contract, agent, &params).await {
+                Ok(transaction_hash) => {
+                    UserRegistrationRequest::delete_many(&mut conn, &ids)?;
+                    result.registered.extend(emails);
+                    result.transaction_hashes.push(transaction_hash);
+                }
+                Err(error) => {
+                    warn!("Identity registration batch {:?} failed: {}", emails, error);
+                    result.failed.extend(emails);
+                }
+            }
+        }
+        Ok(Json(result))
+    }
+}
+
+fn register_identity_params(user: &User) -> RegisterIdentityParams {
+    RegisterIdentityParams {
+        address:  Address::Account(user.account_address()),
+        identity: Identity {
+            attributes:  vec![IdentityAttribute {
+                tag:   NATIONALITY.0,
+                value: user.nationality.clone(),
+            }],
+            credentials: vec![],
+        },
+        validity: None,
+    }
+}
+
+/// Dry runs the batch, sends it and waits for it to be finalized.
+/// Returns the hash of the finalized transaction.
+async fn register_identities(
+    contract: &mut ContractClient<IdentityRegistryContract>,
+    agent: &WalletAccount,
+    params: &Vec<RegisterIdentityParams>,
+) -> std::result::Result<String, RegistrationError> {
+    let builder = contract
+        .dry_run_update::<_, RegistrationError>(
+            "registerIdentities",
+            Amount::zero(),
+            agent.address,
+            params,
+        )
+        .await?;
+    let handle = builder.send(agent).await?;
+    info!("Identity registration batch sent: {}", handle);
+    let update = handle.wait_for_finalization().await?;
+    info!(
+        "Identity registration batch finalized: {}, energy: {}",
+        update.transaction_hash, update.energy_cost.energy
+    );
+    Ok(update.transaction_hash.to_string())
+}
+
+pub struct IdentityRegistryContract;
+
+#[derive(Clone)]
+pub struct IdentityRegistryConfig {
+    pub agent:      Option<Arc<WalletAccount>>,
+    pub batch_size: usize,
+}
+
+#[derive(Object, Default, Debug)]
+pub struct IdentityRegistrationResult {
+    /// Emails of the users whose identities were registered
+    pub registered:         Vec<String>,
+    /// Emails of the users who have not completed their registration yet
+    pub pending:            Vec<String>,
+    /// Emails of the users whose registration batch failed
+    pub failed:             Vec<String>,
+    pub transaction_hashes: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum RegistrationError {
+    #[error("Query error: {0}")]
+    Query(#[from] v2::QueryError),
+    #[error("Contract update rejected: {0:?}")]
+    Rejected(RejectReason),
+    #[error("Contract update failed: {0}")]
+    Update(#[from] ContractUpdateError),
+    #[error("Invalid receive name: {0}")]
+    ReceiveName(#[from] NewReceiveNameError),
+    #[error("Batch parameter exceeds the parameter size")]
+    ParameterSize(#[from] ExceedsParameterSize),
+}
+
+impl From<RejectReason> for RegistrationError {
+    fn from(reason: RejectReason) -> Self { RegistrationError::Rejected(reason) }
 }
@@ -4,9 +4,14 @@ use poem_openapi::payload::Json;
 use poem_openapi::OpenApi;
 use rust_decimal::Decimal;
 use shared::api::PagedResponse;
-use shared::db::cis2_security::{Agent, Token, TokenHolderBalanceUpdateType};
+use shared::db::cis2_security::{
+    Agent, Governance, Proposal, ProposalStatus, RecoveryRecord, RecoveryStatus, Token,
+    TokenDocument, TokenHolderBalanceUpdateType,
+};
 use shared::db::security_mint_fund::{InvestmentRecord, SecurityMintFund};
-use shared::db::security_p2p_trading::{ExchangeRecord, Market};
+use shared::db::security_p2p_trading::{
+    ExchangeRecord, Market, MarketPriceTier, Order, OrderFill, OrderStatus,
+};
 use shared::db::security_sft_multi_yielder::{Treasury, Yield, YieldType};
 use shared::db::txn_listener::{ListenerBlock, ListenerContract};
 use shared::db_app::forest_project_crypto::prelude::SecurityTokenContractType;
@@ -131,6 +136,86 @@ impl Api {
         Ok(Json(PagedResponse::new(agents, page, page_count)))
     }
 
+    #[oai(
+        path = "/admin/indexer/governance",
+        method = "get",
+        tag = "ApiTags::Indexer"
+    )]
+    pub async fn admin_indexer_governance(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Query(contract_address): Query<Decimal>,
+    ) -> JsonResult<Option<Governance>> {
+        ensure_is_admin(&claims)?;
+        let mut conn = db_pool.get()?;
+        let governance = Governance::find(&mut conn, contract_address)?;
+        Ok(Json(governance))
+    }
+
+    #[oai(
+        path = "/admin/indexer/proposals",
+        method = "get",
+        tag = "ApiTags::Indexer"
+    )]
+    pub async fn admin_indexer_proposals(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Query(contract_address): Query<Decimal>,
+        Query(status): Query<Option<ProposalStatus>>,
+        Query(page): Query<i64>,
+        Query(page_size): Query<i64>,
+    ) -> JsonResult<PagedResponse<Proposal>> {
+        ensure_is_admin(&claims)?;
+        let mut conn = db_pool.get()?;
+        let (proposals, page_count) =
+            Proposal::list(&mut conn, contract_address, status, page, page_size)?;
+        Ok(Json(PagedResponse::new(proposals, page, page_count)))
+    }
+
+    #[oai(
+        path = "/admin/indexer/recovery-records",
+        method = "get",
+        tag = "ApiTags::Indexer"
+    )]
+    pub async fn admin_indexer_recovery_records(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Query(contract_address): Query<Decimal>,
+        Query(status): Query<Option<RecoveryStatus>>,
+        Query(page): Query<i64>,
+        Query(page_size): Query<i64>,
+    ) -> JsonResult<PagedResponse<RecoveryRecord>> {
+        ensure_is_admin(&claims)?;
+        let mut conn = db_pool.get()?;
+        let (records, page_count) =
+            RecoveryRecord::list(&mut conn, contract_address, status, page, page_size)?;
+        Ok(Json(PagedResponse::new(records, page, page_count)))
+    }
+
+    #[oai(
+        path = "/admin/indexer/documents",
+        method = "get",
+        tag = "ApiTags::Indexer"
+    )]
+    pub async fn admin_indexer_documents(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Query(contract_address): Query<Decimal>,
+        Query(token_id): Query<Option<Decimal>>,
+        Query(page): Query<i64>,
+        Query(page_size): Query<i64>,
+    ) -> JsonResult<PagedResponse<TokenDocument>> {
+        ensure_is_admin(&claims)?;
+        let mut conn = db_pool.get()?;
+        let (documents, page_count) =
+            TokenDocument::list(&mut conn, contract_address, token_id, page, page_size)?;
+        Ok(Json(PagedResponse::new(documents, page, page_count)))
+    }
+
     #[oai(
         path = "/admin/indexer/tokens",
         method = "get",
@@ -194,6 +279,28 @@ impl Api {
         Ok(Json(market))
     }
 
+    #[oai(
+        path = "/admin/indexer/market/price-tiers",
+        method = "get",
+        tag = "ApiTags::Indexer"
+    )]
+    pub async fn admin_indexer_market_price_tiers(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        Data(contracts): Data<&SystemContractsConfig>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Query(contract_address): Query<Decimal>,
+    ) -> JsonResult<Vec<MarketPriceTier>> {
+        ensure_is_admin(&claims)?;
+        let mut conn = db_pool.get()?;
+        let tiers = MarketPriceTier::list(
+            &mut conn,
+            contracts.trading_contract_index,
+            contract_address,
+        )?;
+        Ok(Json(tiers))
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[oai(
         path = "/admin/indexer/markets",
@@ -340,6 +447,73 @@ impl Api {
         }))
     }
 
+    #[allow(clippy::too_many_arguments)]
+    #[oai(
+        path = "/admin/indexer/orders",
+        method = "get",
+        tag = "ApiTags::Indexer"
+    )]
+    pub async fn admin_indexer_orders(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Data(contracts): Data<&SystemContractsConfig>,
+        Query(token_contract_address): Query<Option<Decimal>>,
+        Query(token_id): Query<Option<Decimal>>,
+        Query(owner): Query<Option<String>>,
+        Query(status): Query<Option<OrderStatus>>,
+        Query(page): Query<i64>,
+        Query(page_size): Query<i64>,
+    ) -> JsonResult<PagedResponse<Order>> {
+        ensure_is_admin(&claims)?;
+        let mut conn = db_pool.get()?;
+        let (orders, page_count) = Order::list(
+            &mut conn,
+            contracts.trading_contract_index,
+            token_contract_address,
+            token_id,
+            owner.as_deref(),
+            status,
+            page,
+            page_size,
+        )?;
+        Ok(Json(PagedResponse {
+            data: orders,
+            page_count,
+            page,
+        }))
+    }
+
+    #[oai(
+        path = "/admin/indexer/order-fills",
+        method = "get",
+        tag = "ApiTags::Indexer"
+    )]
+    pub async fn admin_indexer_order_fills(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Data(contracts): Data<&SystemContractsConfig>,
+        Query(order_id): Query<Decimal>,
+        Query(page): Query<i64>,
+        Query(page_size): Query<i64>,
+    ) -> JsonResult<PagedResponse<OrderFill>> {
+        ensure_is_admin(&claims)?;
+        let mut conn = db_pool.get()?;
+        let (fills, page_count) = OrderFill::list(
+            &mut conn,
+            contracts.trading_contract_index,
+            order_id,
+            page,
+            page_size,
+        )?;
+        Ok(Json(PagedResponse {
+            data: fills,
+            page_count,
+            page,
+        }))
+    }
+
     #[oai(
         path = "/admin/indexer/agent",
         method = "get",
@@ -5,13 +5,19 @@ use poem_openapi::payload::Json;
 use poem_openapi::{Object, OpenApi};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use shared::api::PagedResponse;
 use shared::db::cis2_security::Token;
 use shared::db_app::forest_project::ForestProjectState;
 use shared::db_app::forest_project_crypto::prelude::SecurityTokenContractType;
-use shared::db_app::portfolio::{portfolio_value_at, total_invested_value_till};
+use shared::db_app::portfolio::{
+    portfolio_value_at, total_invested_value_till, CurrencyExchangeRate,
+};
 use shared::db_shared::DbPool;
 
-use super::{ensure_account_registered, BearerAuthorization, JsonResult, SystemContractsConfig};
+use super::{
+    ensure_account_registered, ensure_is_admin, BearerAuthorization, JsonResult,
+    SystemContractsConfig, PAGE_SIZE,
+};
 use crate::api::ApiTags;
 const FOREST_PROJECT_STATES: [ForestProjectState; 3] = [
     ForestProjectState::Active,
@@ -71,7 +77,7 @@ impl Api {
         let year_ago = now
             .checked_sub_months(Months::new(12))
             .expect("Failed to calculate year ago date");
-        let (portfolio_value_now, _) = portfolio_value_at(
+        let (portfolio_value_now, locked_portfolio_value) = portfolio_value_at(
             conn,
             &account,
             contracts.euro_e_token_id,
@@ -115,6 +121,7 @@ impl Api {
         let ret = InvestmentPortfolioUserAggregate {
             carbon_tons_offset,
             current_portfolio_value: portfolio_value_now,
+            locked_portfolio_value,
             invested_value,
             locked_mint_fund_euro_e_amount: locked_invested_value,
             yearly_return: yearly_returns,
@@ -158,6 +165,56 @@ impl Api {
         }
         Ok(Json(ret))
     }
+
+    /// Adds the rate at which a currency token is valued in another currency token.
+    /// Portfolio values of projects priced, and investments made, in other currencies are converted using these rates.
+    #[oai(
+        path = "/admin/currency_exchange_rates",
+        method = "post",
+        tag = "ApiTags::InvestmentPortfolio"
+    )]
+    async fn admin_currency_exchange_rate_create(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Json(rate): Json<CurrencyExchangeRate>,
+    ) -> JsonResult<CurrencyExchangeRate> {
+        ensure_is_admin(&claims)?;
+        let conn = &mut db_pool.get()?;
+        let rate = rate.insert(conn)?;
+        Ok(Json(rate))
+    }
+
+    /// Lists the rates of the currency tokens into the EuroE token, which is the currency portfolios are valued in.
+    #[oai(
+        path = "/admin/currency_exchange_rates/list",
+        method = "get",
+        tag = "ApiTags::InvestmentPortfolio"
+    )]
+    async fn admin_currency_exchange_rate_list(
+        &self,
+        Data(db_pool): Data<&DbPool>,
+        BearerAuthorization(claims): BearerAuthorization,
+        Data(contracts): Data<&SystemContractsConfig>,
+        Query(page): Query<Option<i64>>,
+        Query(page_size): Query<Option<i64>>,
+    ) -> JsonResult<PagedResponse<CurrencyExchangeRate>> {
+        ensure_is_admin(&claims)?;
+        let page = page.unwrap_or_default();
+        let conn = &mut db_pool.get()?;
+        let (rates, page_count) = CurrencyExchangeRate::list(
+            conn,
+            contracts.euro_e_token_id,
+            contracts.euro_e_contract_index,
+            page,
+            page_size.unwrap_or(PAGE_SIZE),
+        )?;
+        Ok(Json(PagedResponse {
+            data: rates,
+            page_count,
+            page,
+        }))
+    }
 }
 
 #[derive(Serialize, Deserialize, Object, Eq, PartialEq, Debug)]
@@ -166,8 +223,11 @@ pub struct InvestmentPortfolioUserAggregate {
     pub locked_mint_fund_euro_e_amount: Decimal,
     /// Sum of the amount invested in the mint funds and the amount bought in the P2P trading
     pub invested_value:                 Decimal,
-    /// Sum Of(Balance of each Forest Project Token * the current price of the token)
+    /// Sum Of(Un frozen Balance of each Forest Project Token * the current price of the token)
     pub current_portfolio_value:        Decimal,
+    /// Sum Of(Frozen Balance of each Forest Project Token * the current price of the token).
+    /// Includes the tokens claimed from mint funds which are still in lock-up or vesting.
+    pub locked_portfolio_value:         Decimal,
     /// Current portfolio value - Portfolio value at the beginning of the year - Amount invested in the year + Amount withdrawn in the year
     pub yearly_return:                  Decimal,
     /// (Current portfolio value - Amount withdrawn) / Total amount invested
@@ -78,6 +78,10 @@ pub struct Config {
     pub trading_contract_index: Decimal,
     pub yielder_contract_index: Decimal,
     pub offchain_rewards_agent_wallet_json_str: String,
+    /// Identities can not be registered using the api when the agent wallet is not configured
+    pub identity_registry_agent_wallet_json_str: Option<String>,
+    /// Number of identities registered in a single identity registry transaction
+    pub identity_registration_batch_size: usize,
     pub files_bucket_name: String,
     pub files_presigned_url_expiry_secs: u64,
     pub filebase_s3_endpoint_url: String,
@@ -195,6 +199,22 @@ impl Config {
         }
     }
 
+    pub fn identity_registry_agent_wallet(&self) -> Option<WalletAccount> {
+        self.identity_registry_agent_wallet_json_str
+            .as_ref()
+            .map(|json| {
+                WalletAccount::from_json_str(json)
+                    .expect("Failed to parse Identity Registry Agent Wallet JSON")
+            })
+    }
+
+    pub fn identity_registry_config(&self) -> identity_registry::IdentityRegistryConfig {
+        identity_registry::IdentityRegistryConfig {
+            agent:      self.identity_registry_agent_wallet().map(Arc::new),
+            batch_size: self.identity_registration_batch_size,
+        }
+    }
+
     pub fn ipfs_files_bucket(&self) -> ipfs::filebase::FilesBucket {
         ipfs::filebase::FilesBucket::new(
             self.filebase_s3_endpoint_url.clone(),
@@ -257,7 +277,9 @@ pub async fn create_web_app(config: Config) -> Route {
         .with(AddData::new(config.emailer(&sdk_config)))
         .with(AddData::new(config.files_bucket(&sdk_config)))
         .with(AddData::new(config.ipfs_files_bucket()))
-        .with(AddData::new(config.aws_cognito_user_pool(&sdk_config).await))
+        .with(AddData::new(
+            config.aws_cognito_user_pool(&sdk_config).await,
+        ))
         .with(AddData::new(global_context))
         // Enhancements : Make an Object Pool for Concordium Client. So that connections to the node can be tracked
         .with(AddData::new(concordium_client))
@@ -265,6 +287,7 @@ pub async fn create_web_app(config: Config) -> Route {
         .with(AddData::new(config.contracts_config()))
         .with(AddData::new(config.tree_nft_config()))
         .with(AddData::new(config.offchain_rewards_config()))
+        .with(AddData::new(config.identity_registry_config()))
         .with(AddData::new(config.affiliate_commission()))
         .with(Cors::new())
         .after(|f| async move {
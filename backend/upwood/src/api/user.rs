@@ -117,7 +117,9 @@ impl UserApi {
     }
 
     /// Accept or reject a user registration request.
-    /// If the request is accepted, the user is added to the Cognito user pool.
+    /// If the request is accepted, the user is added to the Cognito user pool and the request is
+    /// kept as accepted until the identity of the user is registered in the identity registry.
+    /// Rejected requests are deleted.
     #[oai(
         path = "/admin/registration-request/:id/accept/:is_accepted",
         method = "put",
@@ -135,16 +137,28 @@ impl UserApi {
         let mut conn = db_pool.get()?;
         let request = UserRegistrationRequest::find(&mut conn, id)?
             .ok_or_else(|| Error::NotFound(PlainText("Request not found".to_string())))?;
-        if is_accepted {
-            admin_create_temp_user(
-                user_pool,
-                &request.email,
-                request.affiliate_account_address.as_deref(),
-            )
-            .await?;
+        if !is_accepted {
+            UserRegistrationRequest::delete(&mut conn, id)?;
+            return Ok(());
+        }
+        if request.is_accepted {
+            return Err(Error::BadRequest(PlainText(
+                "Request already accepted".to_string(),
+            )));
         }
 
-        UserRegistrationRequest::delete(&mut conn, id)?;
+        admin_create_temp_user(
+            user_pool,
+            &request.email,
+            request.affiliate_account_address.as_deref(),
+        )
+        .await?;
+        UserRegistrationRequest {
+            is_accepted: true,
+            updated_at: chrono::Utc::now().naive_utc(),
+            ..request
+        }
+        .update(&mut conn)?;
         Ok(())
     }
 
@@ -301,7 +315,7 @@ impl UserApi {
             company_id: None,
         }
         .upsert(conn)?;
-        let kyc_verified = Identity::exists(
+        let kyc_verified = Identity::exists_valid(
             conn,
             contracts.identity_registry_contract_index,
             &user.account_address,
@@ -368,7 +382,7 @@ impl UserApi {
             company_id:                None,
         }
         .upsert(&mut conn)?;
-        let kyc_verified = Identity::exists(
+        let kyc_verified = Identity::exists_valid(
             &mut conn,
             contracts.identity_registry_contract_index,
             &user.account_address,
@@ -407,7 +421,7 @@ impl UserApi {
             Some(user) => user,
             None => {
                 let account_address = claims.account.unwrap_or_default();
-                let kyc_verified = Identity::exists(
+                let kyc_verified = Identity::exists_valid(
                     &mut conn,
                     contracts.identity_registry_contract_index,
                     &account_address,
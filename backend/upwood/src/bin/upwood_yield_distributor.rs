@@ -0,0 +1,127 @@
+use std::path::Path;
+use std::time::Duration;
+
+use clap::Parser;
+use concordium_rust_sdk::types::WalletAccount;
+use concordium_rust_sdk::v2;
+use diesel::r2d2::ConnectionManager;
+use r2d2::Pool;
+use rust_decimal::Decimal;
+use secure_string::SecureString;
+use shared::db_setup;
+use shared::db_shared::DbPool;
+use tracing::{debug, error, info};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::util::TryInitError;
+use upwood::yield_distributor::{self, YieldDistributor};
+
+#[derive(Parser, Debug, Clone)]
+pub struct Config {
+    #[clap(env, long)]
+    pub postgres_user: String,
+    #[clap(env, long)]
+    pub postgres_password: SecureString,
+    #[clap(env, long)]
+    pub postgres_host: String,
+    #[clap(env, long)]
+    pub postgres_port: u16,
+    #[clap(env, long)]
+    pub postgres_db: String,
+    #[clap(env, long)]
+    pub db_pool_max_size: u32,
+    /// The Concordium node URI.
+    #[clap(env, long)]
+    pub concordium_node_uri: String,
+    #[clap(env, long)]
+    pub yielder_contract_index: Decimal,
+    /// Wallet of an agent with the `Operator` role on the yielder contract.
+    #[clap(env, long)]
+    pub yielder_agent_wallet_json_str: SecureString,
+    /// Maximum number of holders yielded in a single transaction.
+    #[clap(env, long, default_value = "50")]
+    pub yield_batch_size: usize,
+    /// Time between yield distributions.
+    #[clap(env, long, default_value = "86400")]
+    pub yield_distribution_interval_secs: u64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Invalid node URI: {0}")]
+    InvalidNodeUri(#[from] concordium_rust_sdk::endpoints::Error),
+    #[error("Yield distributor error: {0}")]
+    YieldDistributor(#[from] yield_distributor::Error),
+    #[error("Tracing subscriber error: {0}")]
+    TracingSubscriberError(#[from] TryInitError),
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    dotenvy::from_filename(Path::new(env!("CARGO_MANIFEST_DIR")).join(".env")).ok();
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new("INFO"))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .flatten_event(false)
+                .with_current_span(false)
+                .with_span_list(true)
+                .with_target(false),
+        )
+        .try_init()?;
+
+    let config = Config::parse();
+    debug!("{:#?}", config);
+    let database_url = format!(
+        "postgres://{}:{}@{}:{}/{}",
+        config.postgres_user,
+        config.postgres_password.unsecure(),
+        config.postgres_host,
+        config.postgres_port,
+        config.postgres_db
+    );
+    db_setup::run_migrations(&database_url);
+    let db_pool: DbPool = Pool::builder()
+        .max_size(config.db_pool_max_size)
+        .build(ConnectionManager::new(database_url))
+        .expect("Failed to create database connection pool");
+
+    let endpoint = config.concordium_node_uri.parse::<v2::Endpoint>()?;
+    let concordium_client = v2::Client::new(endpoint).await.unwrap_or_else(|_| {
+        panic!(
+            "Failed to connect to Concordium node at {}",
+            config.concordium_node_uri
+        )
+    });
+    let agent = WalletAccount::from_json_str(config.yielder_agent_wallet_json_str.unsecure())
+        .expect("Failed to parse Yielder Agent Wallet JSON");
+    let mut distributor =
+        YieldDistributor::new(concordium_client, config.yielder_contract_index, agent).await?;
+
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.yield_distribution_interval_secs));
+    loop {
+        interval.tick().await;
+        let mut conn = match db_pool.get() {
+            Ok(conn) => conn,
+            Err(error) => {
+                error!(
+                    "Yield distribution failed to get a database connection: {}",
+                    error
+                );
+                continue;
+            }
+        };
+        match distributor
+            .run(
+                &mut conn,
+                config.yielder_contract_index,
+                config.yield_batch_size,
+            )
+            .await
+        {
+            Ok(()) => info!("Yield distribution completed"),
+            Err(error) => error!("Yield distribution failed: {}", error),
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod api;
 pub mod utils;
+pub mod vesting_releaser;
+pub mod yield_distributor;
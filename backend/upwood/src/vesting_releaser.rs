@@ -0,0 +1,160 @@
+//! Releases the vested security tokens of the investors of the funds of the mint fund contract.
+//! The contract only un freezes vested tokens when `releaseVested` is called,
+//! until then the vested tokens of an investor are still reported as frozen by the security token contract.
+
+use concordium_cis2::TokenIdU64;
+use concordium_rust_sdk::base::contracts_common::{
+    AccountAddress, Amount, ContractAddress, ExceedsParameterSize, NewReceiveNameError,
+};
+use concordium_rust_sdk::contract_client::{ContractClient, ContractUpdateError};
+use concordium_rust_sdk::types::{RejectReason, WalletAccount};
+use concordium_rust_sdk::v2;
+use itertools::Itertools;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use security_mint_fund::types::{ReleaseVestedParams, SecurityTokenUId};
+use shared::db::security_mint_fund::Investor;
+use shared::db_shared::DbConn;
+use tracing::{info, warn};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    Database(#[from] diesel::result::Error),
+    #[error("Query error: {0}")]
+    Query(Box<v2::QueryError>),
+    #[error("Contract update rejected: {0:?}")]
+    Rejected(RejectReason),
+    #[error("Contract update failed: {0}")]
+    Update(Box<ContractUpdateError>),
+    #[error("Invalid receive name: {0}")]
+    ReceiveName(#[from] NewReceiveNameError),
+    #[error("Batch parameter exceeds the parameter size")]
+    ParameterSize(#[from] ExceedsParameterSize),
+    #[error("Invalid {0}: {1}")]
+    Conversion(&'static str, Decimal),
+}
+
+impl From<RejectReason> for Error {
+    fn from(reason: RejectReason) -> Self { Error::Rejected(reason) }
+}
+
+impl From<v2::QueryError> for Error {
+    fn from(error: v2::QueryError) -> Self { Error::Query(Box::new(error)) }
+}
+
+impl From<ContractUpdateError> for Error {
+    fn from(error: ContractUpdateError) -> Self { Error::Update(Box::new(error)) }
+}
+
+pub struct MintFundContract;
+
+/// Builds the batches of at most `batch_size` investors of a single fund
+/// whose claimed security tokens have not all been released yet.
+/// Investors which are not accounts are skipped.
+pub fn release_batches(
+    conn: &mut DbConn,
+    mint_fund_contract: Decimal,
+    batch_size: usize,
+) -> Result<Vec<ReleaseVestedParams>, Error> {
+    let investors = Investor::list_vesting(conn, mint_fund_contract)?;
+    let mut batches = Vec::new();
+    for ((token_contract, token_id), investors) in investors
+        .into_iter()
+        .chunk_by(|investor| {
+            (
+                investor.investment_token_contract_address,
+                investor.investment_token_id,
+            )
+        })
+        .into_iter()
+    {
+        let security_token = SecurityTokenUId {
+            contract: to_contract_address(token_contract)?,
+            id:       TokenIdU64(to_u64("token id", token_id)?),
+        };
+        let investors = investors
+            .filter_map(
+                |investor| match investor.investor.parse::<AccountAddress>() {
+                    Ok(address) => Some(address),
+                    Err(_) => {
+                        warn!(
+                            "Skipping vesting of non account investor: {}",
+                            investor.investor
+                        );
+                        None
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+        for chunk in investors.chunks(batch_size) {
+            batches.push(ReleaseVestedParams {
+                security_token,
+                investors: chunk.to_vec(),
+            });
+        }
+    }
+    Ok(batches)
+}
+
+pub struct VestingReleaser {
+    pub contract: ContractClient<MintFundContract>,
+    pub agent:    WalletAccount,
+}
+
+impl VestingReleaser {
+    pub async fn new(
+        client: v2::Client,
+        mint_fund_contract: Decimal,
+        agent: WalletAccount,
+    ) -> Result<Self, Error> {
+        let contract =
+            ContractClient::create(client, to_contract_address(mint_fund_contract)?).await?;
+        Ok(Self { contract, agent })
+    }
+
+    /// Dry runs the batch, sends it and waits for it to be finalized.
+    pub async fn release(&mut self, batch: &ReleaseVestedParams) -> Result<(), Error> {
+        let builder = self
+            .contract
+            .dry_run_update::<_, Error>("releaseVested", Amount::zero(), self.agent.address, batch)
+            .await?;
+        let handle = builder.send(&self.agent).await?;
+        info!("Vesting release batch sent: {}", handle);
+        let update = handle.wait_for_finalization().await?;
+        info!(
+            "Vesting release batch finalized: {}, energy: {}",
+            update.transaction_hash, update.energy_cost.energy
+        );
+        Ok(())
+    }
+
+    /// Releases the vested security tokens of all the investors with tokens under vesting, one batch at a time.
+    /// A failing batch is logged and skipped so that the remaining batches are still released.
+    pub async fn run(
+        &mut self,
+        conn: &mut DbConn,
+        mint_fund_contract: Decimal,
+        batch_size: usize,
+    ) -> Result<(), Error> {
+        let batches = release_batches(conn, mint_fund_contract, batch_size)?;
+        info!("Releasing vested tokens in {} batches", batches.len());
+        for batch in batches {
+            if let Err(error) = self.release(&batch).await {
+                warn!(
+                    "Vesting release batch of fund {:?} for investors {:?} failed: {}",
+                    batch.security_token, batch.investors, error
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_u64(name: &'static str, value: Decimal) -> Result<u64, Error> {
+    value.to_u64().ok_or(Error::Conversion(name, value))
+}
+
+fn to_contract_address(contract: Decimal) -> Result<ContractAddress, Error> {
+    to_u64("contract address", contract).map(|index| ContractAddress::new(index, 0))
+}
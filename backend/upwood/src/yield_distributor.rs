@@ -0,0 +1,257 @@
+//! Distributes the yields of the holders of the yielder contract in batches,
+//! signed by an agent with the `Operator` role on the yielder.
+
+use std::collections::BTreeSet;
+
+use concordium_cis2::{TokenAmountU64, TokenIdU64};
+use concordium_rust_sdk::base::contracts_common::{
+    from_bytes, AccountAddress, Amount, ContractAddress, ExceedsParameterSize, NewReceiveNameError,
+};
+use concordium_rust_sdk::contract_client::{ContractClient, ContractUpdateError};
+use concordium_rust_sdk::types::{RejectReason, WalletAccount};
+use concordium_rust_sdk::v2;
+use itertools::Itertools;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use security_sft_multi_yielder::{
+    BatchYieldResult, ClaimParam, ClaimParams, ClaimYieldsBatchParams, YieldForBatchParams,
+    YieldParam, YieldParams,
+};
+use shared::db::security_sft_multi_yielder::{ClaimMode, TokenContractClaimMode};
+use shared::db_app::forest_project_crypto::prelude::HolderYieldClaim;
+use shared::db_shared::DbConn;
+use tracing::{info, warn};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Database error: {0}")]
+    Database(#[from] diesel::result::Error),
+    #[error("Query error: {0}")]
+    Query(Box<v2::QueryError>),
+    #[error("Contract update rejected: {0:?}")]
+    Rejected(RejectReason),
+    #[error("Contract update failed: {0}")]
+    Update(Box<ContractUpdateError>),
+    #[error("Invalid receive name: {0}")]
+    ReceiveName(#[from] NewReceiveNameError),
+    #[error("Batch parameter exceeds the parameter size")]
+    ParameterSize(#[from] ExceedsParameterSize),
+    #[error("Invalid {0}: {1}")]
+    Conversion(&'static str, Decimal),
+}
+
+impl From<RejectReason> for Error {
+    fn from(reason: RejectReason) -> Self { Error::Rejected(reason) }
+}
+
+impl From<v2::QueryError> for Error {
+    fn from(error: v2::QueryError) -> Self { Error::Query(Box::new(error)) }
+}
+
+impl From<ContractUpdateError> for Error {
+    fn from(error: ContractUpdateError) -> Self { Error::Update(Box::new(error)) }
+}
+
+pub struct YielderContract;
+
+/// A batch of holders whose yields are distributed in a single transaction.
+pub enum YieldBatch {
+    /// Holders of security token contracts yielded by burning and minting token versions.
+    YieldFor(YieldForBatchParams),
+    /// Holders of security token contracts yielded against checkpoints.
+    Claim(ClaimYieldsBatchParams),
+}
+
+impl YieldBatch {
+    pub fn entrypoint(&self) -> &'static str {
+        match self {
+            YieldBatch::YieldFor(_) => "yieldForBatch",
+            YieldBatch::Claim(_) => "claimYieldsBatch",
+        }
+    }
+
+    pub fn owners(&self) -> Vec<AccountAddress> {
+        match self {
+            YieldBatch::YieldFor(params) => params.owners.iter().map(|p| p.owner).collect(),
+            YieldBatch::Claim(params) => params.owners.iter().map(|p| p.owner).collect(),
+        }
+    }
+}
+
+/// Builds the batches of at most `batch_size` holders from the `holder_yields` view.
+pub fn yield_batches(
+    conn: &mut DbConn,
+    yielder_contract: Decimal,
+    batch_size: usize,
+) -> Result<Vec<YieldBatch>, Error> {
+    let checkpoint_contracts = TokenContractClaimMode::list(conn, yielder_contract)?
+        .into_iter()
+        .filter(|mode| mode.claim_mode == ClaimMode::Checkpoint)
+        .map(|mode| mode.token_contract_address)
+        .collect::<BTreeSet<_>>();
+    let claims = HolderYieldClaim::list(conn, yielder_contract)?;
+    let (checkpoint_claims, versioned_claims): (Vec<_>, Vec<_>) = claims
+        .into_iter()
+        .partition(|claim| checkpoint_contracts.contains(&claim.token_contract_address));
+
+    let mut batches = Vec::new();
+    for chunk in group_by_holder(versioned_claims).chunks(batch_size) {
+        let mut owners = Vec::new();
+        for (owner, claims) in chunk {
+            owners.push(YieldParams {
+                owner:  *owner,
+                yields: claims
+                    .iter()
+                    .map(|claim| {
+                        Ok(YieldParam {
+                            token_ver_from: to_token_id(claim.token_id)?,
+                            token_ver_to:   to_token_id(claim.max_token_id)?,
+                            token_contract: to_contract_address(claim.token_contract_address)?,
+                            amount:         TokenAmountU64(to_u64(
+                                "token balance",
+                                claim.token_balance,
+                            )?),
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?,
+            });
+        }
+        batches.push(YieldBatch::YieldFor(YieldForBatchParams { owners }));
+    }
+    for chunk in group_by_holder(checkpoint_claims).chunks(batch_size) {
+        let mut owners = Vec::new();
+        for (owner, claims) in chunk {
+            owners.push(ClaimParams {
+                owner:  *owner,
+                claims: claims
+                    .iter()
+                    .map(|claim| {
+                        Ok(ClaimParam {
+                            token_contract: to_contract_address(claim.token_contract_address)?,
+                            token_id:       to_token_id(claim.token_id)?,
+                            token_ver_to:   to_token_id(claim.max_token_id)?,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?,
+            });
+        }
+        batches.push(YieldBatch::Claim(ClaimYieldsBatchParams { owners }));
+    }
+    Ok(batches)
+}
+
+pub struct YieldDistributor {
+    pub contract: ContractClient<YielderContract>,
+    pub agent:    WalletAccount,
+}
+
+impl YieldDistributor {
+    pub async fn new(
+        client: v2::Client,
+        yielder_contract: Decimal,
+        agent: WalletAccount,
+    ) -> Result<Self, Error> {
+        let contract =
+            ContractClient::create(client, to_contract_address(yielder_contract)?).await?;
+        Ok(Self { contract, agent })
+    }
+
+    /// Dry runs the batch, sends it and waits for it to be finalized.
+    /// Returns the result of every holder of the batch.
+    pub async fn distribute(&mut self, batch: &YieldBatch) -> Result<Vec<BatchYieldResult>, Error> {
+        let builder = match batch {
+            YieldBatch::YieldFor(params) => {
+                self.contract
+                    .dry_run_update::<_, Error>(
+                        batch.entrypoint(),
+                        Amount::zero(),
+                        self.agent.address,
+                        params,
+                    )
+                    .await?
+            }
+            YieldBatch::Claim(params) => {
+                self.contract
+                    .dry_run_update::<_, Error>(
+                        batch.entrypoint(),
+                        Amount::zero(),
+                        self.agent.address,
+                        params,
+                    )
+                    .await?
+            }
+        };
+        let results: Vec<BatchYieldResult> = builder
+            .return_value()
+            .and_then(|value| from_bytes(&value.value).ok())
+            .unwrap_or_default();
+        let handle = builder.send(&self.agent).await?;
+        info!("Yield batch {} sent: {}", batch.entrypoint(), handle);
+        let update = handle.wait_for_finalization().await?;
+        info!(
+            "Yield batch finalized: {}, energy: {}",
+            update.transaction_hash, update.energy_cost.energy
+        );
+        Ok(results)
+    }
+
+    /// Distributes the yields of all the holders with yields, one batch at a time.
+    /// A failing batch is logged and skipped so that the remaining batches are still distributed.
+    pub async fn run(
+        &mut self,
+        conn: &mut DbConn,
+        yielder_contract: Decimal,
+        batch_size: usize,
+    ) -> Result<(), Error> {
+        let batches = yield_batches(conn, yielder_contract, batch_size)?;
+        info!("Distributing yields in {} batches", batches.len());
+        for batch in batches {
+            let owners = batch.owners();
+            match self.distribute(&batch).await {
+                Ok(results) => owners
+                    .iter()
+                    .zip(results)
+                    .filter(|(_, result)| *result == BatchYieldResult::NoYield)
+                    .for_each(|(owner, _)| info!("No yields for holder: {}", owner)),
+                Err(error) => warn!(
+                    "Yield batch {} for holders {:?} failed: {}",
+                    batch.entrypoint(),
+                    owners,
+                    error
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Groups the claims, which are ordered by holder, by holder.
+/// Yields can only be distributed to accounts, the claims of contract holders are skipped.
+fn group_by_holder(claims: Vec<HolderYieldClaim>) -> Vec<(AccountAddress, Vec<HolderYieldClaim>)> {
+    claims
+        .into_iter()
+        .chunk_by(|claim| claim.holder_address.clone())
+        .into_iter()
+        .filter_map(
+            |(holder_address, claims)| match holder_address.parse::<AccountAddress>() {
+                Ok(holder) => Some((holder, claims.collect())),
+                Err(_) => {
+                    warn!("Skipping yields of non account holder: {}", holder_address);
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+fn to_u64(name: &'static str, value: Decimal) -> Result<u64, Error> {
+    value.to_u64().ok_or(Error::Conversion(name, value))
+}
+
+fn to_token_id(token_id: Decimal) -> Result<TokenIdU64, Error> {
+    to_u64("token id", token_id).map(TokenIdU64)
+}
+
+fn to_contract_address(contract: Decimal) -> Result<ContractAddress, Error> {
+    to_u64("contract address", contract).map(|index| ContractAddress::new(index, 0))
+}
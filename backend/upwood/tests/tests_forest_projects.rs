@@ -35,9 +35,11 @@ use nft_multi_rewarded::types::ContractMetadataUrl;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use security_mint_fund::types::{
-    AddFundParams, TransferInvestParams, UpdateFundState, UpdateFundStateParams,
+    AddFundParams, FundInvestors, TransferInvestParams, UpdateFundState, UpdateFundStateParams,
+};
+use security_p2p_trading::{
+    AddMarketParams, ExchangeParams, Market, TransferMarket, TransferSettlement,
 };
-use security_p2p_trading::{AddMarketParams, ExchangeParams, Market, TransferMarket};
 use security_sft_multi_yielder::{
     UpsertYieldParams, YieldCalculation, YieldParam, YieldParams, YieldState,
 };
@@ -333,6 +335,7 @@ pub async fn test_forest_projects() {
                         }],
                         credentials: vec![],
                     },
+                    validity: None,
                 }),
             )
         })
@@ -403,15 +406,33 @@ pub async fn test_forest_projects() {
                 chain.update(
                     sender,
                     mint_fund_contract.add_fund_payload(&AddFundParams {
-                        token:          TokenUId {
+                        token:              TokenUId {
                             id:       fp_1_token_1,
                             contract: fp_1_pre_sale.0,
                         },
-                        rate:           Rate::new(1, 1).unwrap(),
-                        security_token: TokenUId {
+                        rate:               Rate::new(1, 1).unwrap(),
+                        security_token:     TokenUId {
                             contract: fp_1_contract.0,
                             id:       fp_1_token_1,
                         },
+                        currency_token:     None,
+                        soft_cap:           0.into(),
+                        hard_cap:           1_000_000.into(),
+                        deadline:           chain
+                            .chain
+                            .block_time()
+                            .checked_add(concordium_smart_contract_testing::Duration::from_days(
+                                365,
+                            ))
+                            .unwrap(),
+                        funds_receiver:     admin.address().into(),
+                        cooling_off_period: concordium_smart_contract_testing::Duration::from_days(
+                            14,
+                        ),
+                        min_investment:     None,
+                        max_investment:     None,
+                        investors:          FundInvestors::Any,
+                        vesting:            None,
                     }),
                 )
             })
@@ -538,6 +559,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 100.into(),
                 invested_value:                 100.into(),
                 current_portfolio_value:        Decimal::ZERO,
+                locked_portfolio_value:         Decimal::ZERO,
                 carbon_tons_offset:             Decimal::ZERO,
                 return_on_investment:           Decimal::from(-100),
                 yearly_return:                  Decimal::from(0),
@@ -552,6 +574,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 200.into(),
                 invested_value:                 200.into(),
                 current_portfolio_value:        Decimal::ZERO,
+                locked_portfolio_value:         Decimal::ZERO,
                 carbon_tons_offset:             Decimal::ZERO,
                 return_on_investment:           Decimal::from(-100),
                 yearly_return:                  Decimal::from(0),
@@ -630,6 +653,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 0.into(),
                 invested_value:                 100.into(),
                 current_portfolio_value:        200.into(), // 100 shares at 2 price
+                locked_portfolio_value:         Decimal::ZERO,
                 yearly_return:                  200.into(),
                 return_on_investment:           100.into(),
                 carbon_tons_offset:             Decimal::ZERO,
@@ -644,6 +668,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 0.into(),
                 invested_value:                 200.into(),
                 current_portfolio_value:        400.into(),
+                locked_portfolio_value:         Decimal::ZERO,
                 yearly_return:                  400.into(),
                 return_on_investment:           100.into(),
                 carbon_tons_offset:             Decimal::ZERO,
@@ -689,6 +714,7 @@ pub async fn test_forest_projects() {
             locked_mint_fund_euro_e_amount: 0.into(),
             invested_value:                 100.into(),
             current_portfolio_value:        200.into(), // 100 shares at 2 price
+            locked_portfolio_value:         Decimal::ZERO,
             yearly_return:                  200.into(),
             return_on_investment:           100.into(),
             carbon_tons_offset:             Decimal::ZERO,
@@ -704,6 +730,7 @@ pub async fn test_forest_projects() {
             invested_value:                 100.into(),
             // (initial price of 2 + 12 months increase of 1 each month) * 100 shares
             current_portfolio_value:        1400.into(),
+            locked_portfolio_value:         Decimal::ZERO,
             yearly_return:                  1200.into(),
             return_on_investment:           1300.into(),
             carbon_tons_offset:             Decimal::ZERO,
@@ -939,6 +966,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 0.into(),
                 invested_value:                 100.into(),
                 current_portfolio_value:        1400.into(),
+                locked_portfolio_value:         Decimal::ZERO,
                 yearly_return:                  1200.into(),
                 return_on_investment:           1300.into(),
                 carbon_tons_offset:             1000.into(),
@@ -977,6 +1005,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 0.into(),
                 invested_value:                 100.into(),
                 current_portfolio_value:        700.into(),
+                locked_portfolio_value:         Decimal::ZERO,
                 yearly_return:                  500.into(),
                 return_on_investment:           600.into(),
                 carbon_tons_offset:             1000.into(),
@@ -1014,6 +1043,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 0.into(),
                 invested_value:                 100.into(),
                 current_portfolio_value:        700.into(),
+                locked_portfolio_value:         Decimal::ZERO,
                 yearly_return:                  500.into(),
                 return_on_investment:           600.into(),
                 carbon_tons_offset:             1000.into(),
@@ -1035,6 +1065,9 @@ pub async fn test_forest_projects() {
                                 sell_rate:           Rate::new(1, 2).unwrap(),
                                 max_token_amount:    100.into(),
                                 max_currency_amount: 100.into(),
+                                fee:                 None,
+                                currency_token:      None,
+                                settlement:          TransferSettlement::Operator,
                             }),
                         }),
                     )
@@ -1078,6 +1111,7 @@ pub async fn test_forest_projects() {
                 locked_mint_fund_euro_e_amount: 0.into(),
                 invested_value:                 75.into(), // 100 initially invested - 25 taken out
                 current_portfolio_value:        0.into(),
+                locked_portfolio_value:         Decimal::ZERO,
                 yearly_return:                  (-200).into(),
                 return_on_investment:           (-100).into(),
                 carbon_tons_offset:             1000.into(),
@@ -1157,6 +1191,7 @@ pub async fn test_forest_projects() {
             locked_mint_fund_euro_e_amount: 0.into(),
             invested_value:                 100.into(),
             current_portfolio_value:        700.into(), // 50 shares at 14 price
+            locked_portfolio_value:         Decimal::ZERO,
             yearly_return:                  500.into(),
             return_on_investment:           600.into(),
             carbon_tons_offset:             1000.into(),
@@ -1170,52 +1205,56 @@ pub async fn test_forest_projects() {
             .await;
         assert_eq!(user_1_txns.data, vec![
             UserTransaction {
-                cognito_user_id:                 user_1.id.clone(),
+                cognito_user_id: user_1.id.clone(),
                 transaction_hash:
                     "1fc244af2b96d0169a177e2559af29e0484744e4b8501d1044d76c9f7b3cf307".to_string(),
-                transaction_type:                "buy".to_string(),
-                forest_project_id:               fp_1.id,
-                currency_amount:                 Decimal::from(25),
-                account_address:                 user_1.account_address.clone(),
+                transaction_type: "buy".to_string(),
+                forest_project_id: fp_1.id,
+                currency_amount: Decimal::from(25),
+                account_address: user_1.account_address.clone(),
                 currency_token_contract_address: euroe.0.to_decimal(),
-                currency_token_id:               0.into(),
-                block_height:                    Decimal::from(17),
+                currency_token_id: 0.into(),
+                block_height: Decimal::from(17),
+                fee_amount: Decimal::ZERO,
             },
             UserTransaction {
-                cognito_user_id:                 user_1.id.clone(),
+                cognito_user_id: user_1.id.clone(),
                 transaction_hash:
                     "daceee899cdb68711b2b16b1708d684b193062163a2ef8ca80e5a3f002212822".to_string(),
-                transaction_type:                "sell".to_string(),
-                forest_project_id:               fp_1.id,
-                currency_amount:                 Decimal::from(25),
-                account_address:                 user_1.account_address.clone(),
+                transaction_type: "sell".to_string(),
+                forest_project_id: fp_1.id,
+                currency_amount: Decimal::from(25),
+                account_address: user_1.account_address.clone(),
                 currency_token_contract_address: euroe.0.to_decimal(),
-                currency_token_id:               0.into(),
-                block_height:                    Decimal::from(15),
+                currency_token_id: 0.into(),
+                block_height: Decimal::from(15),
+                fee_amount: Decimal::ZERO,
             },
             UserTransaction {
-                cognito_user_id:                 user_1.id.clone(),
+                cognito_user_id: user_1.id.clone(),
                 transaction_hash:
                     "51be0458d20b1ce2ff5316e7e473109c26f5d8b457037edf8c4529f6ff82bddb".to_string(),
-                forest_project_id:               fp_1.id,
-                transaction_type:                "claimed".to_string(),
-                currency_amount:                 Decimal::from(100),
-                account_address:                 user_1.account_address.clone(),
+                forest_project_id: fp_1.id,
+                transaction_type: "claimed".to_string(),
+                currency_amount: Decimal::from(100),
+                account_address: user_1.account_address.clone(),
                 currency_token_contract_address: euroe.0.to_decimal(),
-                currency_token_id:               0.into(),
-                block_height:                    Decimal::from(9),
+                currency_token_id: 0.into(),
+                block_height: Decimal::from(9),
+                fee_amount: Decimal::ZERO,
             },
             UserTransaction {
-                cognito_user_id:                 user_1.id.clone(),
+                cognito_user_id: user_1.id.clone(),
                 transaction_hash:
                     "83440636eff7b2ec0f78ef7b8e480a033e8aeb67e6ecb657ce9bcdfdb21aa744".to_string(),
-                transaction_type:                "invested".to_string(),
-                forest_project_id:               fp_1.id,
-                currency_amount:                 Decimal::from(100),
-                account_address:                 user_1.account_address.clone(),
+                transaction_type: "invested".to_string(),
+                forest_project_id: fp_1.id,
+                currency_amount: Decimal::from(100),
+                account_address: user_1.account_address.clone(),
                 currency_token_contract_address: euroe.0.to_decimal(),
-                currency_token_id:               0.into(),
-                block_height:                    Decimal::from(6),
+                currency_token_id: 0.into(),
+                block_height: Decimal::from(6),
+                fee_amount: Decimal::ZERO,
             },
         ]);
     }
@@ -1380,15 +1419,15 @@ pub fn deploy_upwood_contracts(
 ) {
     let currency_token = TokenUId {
         contract: euro_e_contract.0,
-        id:       TokenIdUnit(),
+        id:       TokenIdVec(vec![]),
     };
     let mint_fund_contract = admin
         .transact(|account| {
             chain.init(
                 account,
                 MintFundTestClient::init_payload(&security_mint_fund::types::InitParam {
-                    agents: vec![],
-                    currency_token,
+                    agents:         vec![],
+                    currency_token: currency_token.clone(),
                 }),
             )
         })
@@ -1815,6 +1854,7 @@ pub async fn create_user(
                         }],
                         credentials: vec![],
                     },
+                    validity: None,
                 }),
             )
         })
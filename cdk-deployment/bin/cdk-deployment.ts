@@ -214,6 +214,13 @@ const apiStack = new BackendApiStack(app, "BackendApiStack", {
 		TREE_NFT_AGENT_WALLET_JSON_STR: process.env.TREE_NFT_AGENT_WALLET_JSON_STR!,
 		OFFCHAIN_REWARDS_AGENT_WALLET_JSON_STR:
 			process.env.OFFCHAIN_REWARDS_AGENT_WALLET_JSON_STR!,
+		// optional, identities can not be registered through the api without it
+		...(process.env.IDENTITY_REGISTRY_AGENT_WALLET_JSON_STR
+			? {
+					IDENTITY_REGISTRY_AGENT_WALLET_JSON_STR:
+						process.env.IDENTITY_REGISTRY_AGENT_WALLET_JSON_STR,
+				}
+			: {}),
 		FILEBASE_ACCESS_KEY_ID: process.env.FILEBASE_ACCESS_KEY_ID!,
 		FILEBASE_SECRET_ACCESS_KEY: process.env.FILEBASE_SECRET_ACCESS_KEY!,
 		//other
@@ -244,6 +251,8 @@ const apiStack = new BackendApiStack(app, "BackendApiStack", {
 		OFFCHAIN_REWARDS_CONTRACT_INDEX:
 			process.env.OFFCHAIN_REWARDS_CONTRACT_INDEX!,
 		AFFILIATE_COMMISSION: process.env.AFFILIATE_COMMISSION!,
+		IDENTITY_REGISTRATION_BATCH_SIZE:
+			process.env.IDENTITY_REGISTRATION_BATCH_SIZE!,
 		FILES_BUCKET_NAME: filesStack.filesBucket.bucketName,
 		FILES_PRESIGNED_URL_EXPIRY_SECS:
 			process.env.FILES_PRESIGNED_URL_EXPIRY_SECS!,
@@ -0,0 +1,18 @@
+use concordium_protocols::concordium_cis2_ext::cis2_client::Cis2ClientError;
+use concordium_std::*;
+
+#[derive(Serial, Reject, SchemaType)]
+pub enum Error {
+    ParseError,
+    Unauthorized,
+    InvalidTradingWindow,
+    CallContractError,
+}
+
+impl From<ParseError> for Error {
+    fn from(_: ParseError) -> Self { Error::ParseError }
+}
+
+impl From<Cis2ClientError> for Error {
+    fn from(_: Cis2ClientError) -> Self { Error::CallContractError }
+}
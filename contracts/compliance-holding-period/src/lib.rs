@@ -0,0 +1,217 @@
+use concordium_cis2::BalanceOfQuery;
+use concordium_protocols::concordium_cis2_ext::cis2_client::Cis2Client;
+use concordium_protocols::concordium_cis2_security::{
+    BurnedParam, CanTransferParam, MintedParam, TokenUId, TransferredParam,
+};
+use concordium_std::*;
+use error::Error;
+use state::*;
+use types::*;
+
+pub mod error;
+mod state;
+pub mod types;
+
+/// Returns the balance of the holder in the token contract.
+fn balance_of(
+    host: &Host<State>,
+    token_id: &TokenUId<TokenId>,
+    holder: Address,
+) -> ContractResult<TokenAmount> {
+    let balance = host.invoke_balance_of_single(&token_id.contract, BalanceOfQuery {
+        token_id: token_id.id.clone(),
+        address:  holder,
+    })?;
+    Ok(balance)
+}
+
+/// Initializes the module with the compliance contract calling its hooks, the holding period of acquired tokens and
+/// the trading windows.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTradingWindow` if a trading window does not end after it starts.
+#[init(
+    contract = "rwa_compliance_module_holding_period",
+    error = "Error",
+    parameter = "InitParams"
+)]
+pub fn init(ctx: &InitContext, state_builder: &mut StateBuilder) -> ContractResult<State> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+    ensure!(
+        params.trading_windows.iter().all(TradingWindow::is_valid),
+        Error::InvalidTradingWindow
+    );
+
+    Ok(State::new(
+        params.compliance,
+        params.holding_period,
+        params.trading_windows,
+        state_builder,
+    ))
+}
+
+/// Replaces the trading windows. An empty list allows transfers at any time.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender does not match the owner.
+/// Returns `Error::InvalidTradingWindow` if a trading window does not end after it starts.
+#[receive(
+    contract = "rwa_compliance_module_holding_period",
+    name = "setTradingWindows",
+    parameter = "Vec<TradingWindow>",
+    error = "Error",
+    mutable
+)]
+fn set_trading_windows(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    ensure!(
+        ctx.sender().matches_account(&ctx.owner()),
+        Error::Unauthorized
+    );
+    let trading_windows: Vec<TradingWindow> = ctx.parameter_cursor().get()?;
+    ensure!(
+        trading_windows.iter().all(TradingWindow::is_valid),
+        Error::InvalidTradingWindow
+    );
+    host.state_mut().set_trading_windows(trading_windows);
+
+    Ok(())
+}
+
+/// Returns the balance of the holder and the part of it which can be transferred. The balance which is not tracked
+/// by the module, such as the balance held before the module was added or received by a recovery, is unlocked.
+///
+/// # Errors
+///
+/// Returns `Error::CallContractError` if the balance could not be queried from the token contract.
+#[receive(
+    contract = "rwa_compliance_module_holding_period",
+    name = "holdingOf",
+    parameter = "HoldingOfParams",
+    return_value = "Holding",
+    error = "Error"
+)]
+fn holding_of(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<Holding> {
+    let params: HoldingOfParams = ctx.parameter_cursor().get()?;
+    let balance = balance_of(host, &params.token_id, params.holder)?;
+    Ok(host.state().holding(
+        &params.token_id,
+        params.holder,
+        balance,
+        ctx.metadata().slot_time(),
+    ))
+}
+
+/// Returns false if the transfer happens outside of the trading windows or if the sender has not held the
+/// transferred amount for the holding period. Minting is not limited.
+///
+/// # Errors
+///
+/// Returns `Error::CallContractError` if the balance of the sender could not be queried from the token contract.
+#[receive(
+    contract = "rwa_compliance_module_holding_period",
+    name = "canTransfer",
+    parameter = "CanTransferParam<TokenId, TokenAmount>",
+    return_value = "bool",
+    error = "Error"
+)]
+fn can_transfer(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
+    let params: CanTransferParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    let can_transfer = match params.from {
+        Some(from) => host.state().can_transfer(
+            &params.token_id,
+            from,
+            balance_of(host, &params.token_id, from)?,
+            params.amount,
+            ctx.metadata().slot_time(),
+        ),
+        None => true,
+    };
+
+    Ok(can_transfer)
+}
+
+/// Records the acquisition time of the minted tokens.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_holding_period",
+    name = "minted",
+    parameter = "MintedParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn minted(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: MintedParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    host.state_mut().add(
+        &params.token_id,
+        params.owner,
+        params.amount,
+        ctx.metadata().slot_time(),
+    );
+
+    Ok(())
+}
+
+/// Removes the oldest lots of the sender exceeding its remaining balance and records the acquisition time of the
+/// transferred tokens by the receiver.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+/// Returns `Error::CallContractError` if the balance of the sender could not be queried from the token contract.
+#[receive(
+    contract = "rwa_compliance_module_holding_period",
+    name = "transferred",
+    parameter = "TransferredParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn transferred(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: TransferredParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    let balance = balance_of(host, &params.token_id, params.from)?;
+    let state = host.state_mut();
+    state.trim(&params.token_id, params.from, balance);
+    state.add(
+        &params.token_id,
+        params.to,
+        params.amount,
+        ctx.metadata().slot_time(),
+    );
+
+    Ok(())
+}
+
+/// Removes the oldest lots of the owner exceeding its remaining balance.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+/// Returns `Error::CallContractError` if the balance of the owner could not be queried from the token contract.
+#[receive(
+    contract = "rwa_compliance_module_holding_period",
+    name = "burned",
+    parameter = "BurnedParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn burned(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: BurnedParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    let balance = balance_of(host, &params.token_id, params.owner)?;
+    host.state_mut()
+        .trim(&params.token_id, params.owner, balance);
+
+    Ok(())
+}
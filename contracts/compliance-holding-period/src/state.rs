@@ -0,0 +1,156 @@
+use concordium_protocols::concordium_cis2_security::TokenUId;
+use concordium_std::{
+    Address, ContractAddress, DeserialWithState, Duration, Serial, Serialize, StateApi,
+    StateBuilder, StateMap, Timestamp,
+};
+
+use super::types::{Holding, TokenAmount, TokenId, TradingWindow};
+
+/// Tokens acquired by a holder at the same time.
+#[derive(Serialize, Clone, Copy)]
+pub struct Lot {
+    pub acquired: Timestamp,
+    pub amount:   TokenAmount,
+}
+
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct State<S=StateApi> {
+    compliance:      ContractAddress,
+    holding_period:  Duration,
+    trading_windows: Vec<TradingWindow>,
+    /// Lots of each holder & token ordered by the time of acquisition.
+    lots:            StateMap<(TokenUId<TokenId>, Address), Vec<Lot>, S>,
+}
+
+impl State<StateApi> {
+    pub fn new(
+        compliance: ContractAddress,
+        holding_period: Duration,
+        trading_windows: Vec<TradingWindow>,
+        state_builder: &mut StateBuilder,
+    ) -> Self {
+        Self {
+            compliance,
+            holding_period,
+            trading_windows,
+            lots: state_builder.new_map(),
+        }
+    }
+
+    pub fn compliance(&self) -> ContractAddress { self.compliance }
+
+    pub fn set_trading_windows(&mut self, trading_windows: Vec<TradingWindow>) {
+        self.trading_windows = trading_windows;
+    }
+
+    pub fn is_trading_open(&self, now: Timestamp) -> bool {
+        self.trading_windows.is_empty()
+            || self
+                .trading_windows
+                .iter()
+                .any(|window| window.contains(now))
+    }
+
+    /// Returns the balance of the holder along with the part of it which can be transferred.
+    /// The balance not covered by the lots of the holder is unlocked, this includes the balance held before the
+    /// module was added and the balance moved to the holder by a recovery.
+    pub fn holding(
+        &self,
+        token_id: &TokenUId<TokenId>,
+        holder: Address,
+        balance: TokenAmount,
+        now: Timestamp,
+    ) -> Holding {
+        let locked = self
+            .lots
+            .get(&(token_id.clone(), holder))
+            .map(|lots| {
+                lots.iter()
+                    .filter(|lot| !self.is_unlocked(lot, now))
+                    .fold(0u64, |locked, lot| locked.saturating_add(lot.amount.0))
+            })
+            .unwrap_or(0);
+
+        Holding {
+            balance,
+            unlocked: TokenAmount::from(balance.0.saturating_sub(locked)),
+        }
+    }
+
+    /// Returns true if trading is open and at least `amount` of the balance of the sender is unlocked.
+    pub fn can_transfer(
+        &self,
+        token_id: &TokenUId<TokenId>,
+        from: Address,
+        balance: TokenAmount,
+        amount: TokenAmount,
+        now: Timestamp,
+    ) -> bool {
+        self.is_trading_open(now)
+            && self
+                .holding(token_id, from, balance, now)
+                .unlocked
+                .ge(&amount)
+    }
+
+    /// Records `amount` of the token acquired by the holder at `now`.
+    pub fn add(
+        &mut self,
+        token_id: &TokenUId<TokenId>,
+        holder: Address,
+        amount: TokenAmount,
+        now: Timestamp,
+    ) {
+        if amount.0 == 0 {
+            return;
+        }
+
+        let mut lots = self.lots.entry((token_id.clone(), holder)).or_default();
+        match lots.last_mut() {
+            Some(last) if last.acquired == now => {
+                last.amount.0 = last.amount.0.saturating_add(amount.0)
+            }
+            _ => lots.push(Lot {
+                acquired: now,
+                amount,
+            }),
+        }
+    }
+
+    /// Removes the oldest lots of the holder until the lots do not exceed the balance.
+    /// The untracked balance is spent before any of the lots.
+    pub fn trim(&mut self, token_id: &TokenUId<TokenId>, holder: Address, balance: TokenAmount) {
+        let key = (token_id.clone(), holder);
+        let mut lots = match self.lots.get(&key) {
+            Some(lots) => lots.clone(),
+            None => return,
+        };
+
+        let tracked = lots
+            .iter()
+            .fold(0u64, |tracked, lot| tracked.saturating_add(lot.amount.0));
+        let mut excess = tracked.saturating_sub(balance.0);
+        while excess > 0 && !lots.is_empty() {
+            if lots[0].amount.0 > excess {
+                lots[0].amount.0 -= excess;
+                excess = 0;
+            } else {
+                excess -= lots[0].amount.0;
+                lots.remove(0);
+            }
+        }
+
+        if lots.is_empty() {
+            self.lots.remove(&key);
+        } else {
+            let _ = self.lots.insert(key, lots);
+        }
+    }
+
+    fn is_unlocked(&self, lot: &Lot, now: Timestamp) -> bool {
+        lot.acquired
+            .checked_add(self.holding_period)
+            .is_some_and(|unlock_time| unlock_time <= now)
+    }
+}
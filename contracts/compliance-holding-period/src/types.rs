@@ -0,0 +1,46 @@
+use concordium_cis2::{TokenAmountU64, TokenIdVec};
+use concordium_protocols::concordium_cis2_security::TokenUId;
+use concordium_std::{Address, ContractAddress, Duration, SchemaType, Serialize, Timestamp};
+
+use crate::error::Error;
+
+pub type ContractResult<T> = Result<T, Error>;
+pub type TokenAmount = TokenAmountU64;
+pub type TokenId = TokenIdVec;
+
+/// A period of time in which transfers are allowed. The start is inclusive & the end is exclusive.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradingWindow {
+    pub start: Timestamp,
+    pub end:   Timestamp,
+}
+
+impl TradingWindow {
+    pub fn is_valid(&self) -> bool { self.start < self.end }
+
+    pub fn contains(&self, now: Timestamp) -> bool { self.start <= now && now < self.end }
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct InitParams {
+    /// The compliance contract which calls the `minted`, `transferred` & `burned` hooks of the module.
+    pub compliance:      ContractAddress,
+    /// Time the tokens have to be held after being acquired (minted or received) before they can be transferred.
+    pub holding_period:  Duration,
+    /// When not empty transfers are only allowed within one of the windows.
+    pub trading_windows: Vec<TradingWindow>,
+}
+
+/// Parameters for the `holdingOf` function.
+#[derive(Serialize, SchemaType)]
+pub struct HoldingOfParams {
+    pub token_id: TokenUId<TokenId>,
+    pub holder:   Address,
+}
+
+/// Balance of a holder and the part of it which can be transferred.
+#[derive(Serialize, SchemaType, Debug, PartialEq, Eq)]
+pub struct Holding {
+    pub balance:  TokenAmount,
+    pub unlocked: TokenAmount,
+}
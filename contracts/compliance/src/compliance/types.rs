@@ -7,7 +7,7 @@ pub type ContractResult<T> = Result<T, Error>;
 pub type TokenAmount = TokenAmountU64;
 pub type TokenId = TokenIdVec;
 pub type Module = ContractAddress;
-#[derive(Serialize, SchemaType)]
+#[derive(Serialize, SchemaType, Debug)]
 pub struct AgentUpdatedEvent {
     pub agent: Address,
 }
@@ -0,0 +1,218 @@
+use concordium_protocols::concordium_cis2_security::identity_registry_client::IdentityRegistryClient;
+use concordium_protocols::concordium_cis2_security::CanTransferParam;
+use concordium_std::*;
+use state::*;
+use types::*;
+
+mod state;
+pub mod types;
+
+/// Initializes the module with the identity registry and the agents allowed to update the lists. The lists are empty
+/// and are updated using `updateList`.
+#[init(
+    contract = "rwa_compliance_module_jurisdictions",
+    event = "Event",
+    error = "Error",
+    parameter = "InitParams",
+    enable_logger
+)]
+pub fn init(
+    ctx: &InitContext,
+    state_builder: &mut StateBuilder,
+    logger: &mut Logger,
+) -> ContractResult<State> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+    let mut agents = params.agents;
+    agents.push(Address::Account(ctx.init_origin()));
+    for agent in agents.iter() {
+        logger.log(&Event::AgentAdded(AgentUpdatedEvent { agent: *agent }))?;
+    }
+
+    Ok(State::new(params.identity_registry, agents, state_builder))
+}
+
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "agents",
+    return_value = "Vec<Address>",
+    error = "Error"
+)]
+pub fn agents(_: &ReceiveContext, host: &Host<State>) -> ContractResult<Vec<Address>> {
+    Ok(host.state().agents.iter().map(|a| *a).collect())
+}
+
+/// Adds the given address as an agent.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender does not match the owner.
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "addAgent",
+    mutable,
+    enable_logger,
+    parameter = "Address",
+    error = "Error"
+)]
+pub fn add_agent(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        ctx.sender().matches_account(&ctx.owner()),
+        Error::Unauthorized
+    );
+    let agent: Address = ctx.parameter_cursor().get()?;
+    ensure!(
+        host.state_mut().agents.insert(agent),
+        Error::AgentAlreadyExists
+    );
+    logger.log(&Event::AgentAdded(AgentUpdatedEvent { agent }))?;
+
+    Ok(())
+}
+
+/// Removes the given address as an agent.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender does not match the owner.
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "removeAgent",
+    mutable,
+    enable_logger,
+    parameter = "Address",
+    error = "Error"
+)]
+pub fn remove_agent(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        ctx.sender().matches_account(&ctx.owner()),
+        Error::Unauthorized
+    );
+    let agent: Address = ctx.parameter_cursor().get()?;
+    ensure!(host.state_mut().agents.remove(&agent), Error::AgentNotFound);
+    logger.log(&Event::AgentRemoved(AgentUpdatedEvent { agent }))?;
+
+    Ok(())
+}
+
+/// Adds countries to & removes countries from an allow or a block list.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "updateList",
+    mutable,
+    enable_logger,
+    parameter = "UpdateListParams",
+    error = "Error"
+)]
+pub fn update_list(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        host.state().agents.contains(&ctx.sender()),
+        Error::Unauthorized
+    );
+    let params: UpdateListParams = ctx.parameter_cursor().get()?;
+    let list = host.state_mut().list_mut(params.jurisdiction, params.list);
+    for country in params.add.iter() {
+        list.insert(country.clone());
+    }
+    for country in params.remove.iter() {
+        list.remove(country);
+    }
+    logger.log(&Event::ListUpdated(params))?;
+
+    Ok(())
+}
+
+/// Returns the countries in an allow or a block list.
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "list",
+    parameter = "ListParams",
+    return_value = "Vec<types::AttributeValue>",
+    error = "Error"
+)]
+pub fn list(
+    ctx: &ReceiveContext,
+    host: &Host<State>,
+) -> ContractResult<Vec<types::AttributeValue>> {
+    let params: ListParams = ctx.parameter_cursor().get()?;
+    Ok(host
+        .state()
+        .list(params.jurisdiction, params.list)
+        .iter()
+        .map(|country| country.clone())
+        .collect())
+}
+
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "burned",
+    error = "Error"
+)]
+fn burned(_: &ReceiveContext, _: &Host<State>) -> ContractResult<()> { Ok(()) }
+
+/// Returns false if the nationality or the country of residence of the sender or the receiver is not allowed.
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "canTransfer",
+    parameter = "CanTransferParam<TokenId, TokenAmount>",
+    return_value = "bool",
+    error = "Error"
+)]
+fn can_transfer(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
+    let params: CanTransferParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    if let Some(from) = params.from {
+        if !is_allowed(host, from)? {
+            return Ok(false);
+        }
+    }
+
+    is_allowed(host, params.to)
+}
+
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "minted",
+    error = "Error"
+)]
+fn minted(_: &ReceiveContext, _: &Host<State>) -> ContractResult<()> { Ok(()) }
+
+#[receive(
+    contract = "rwa_compliance_module_jurisdictions",
+    name = "transferred",
+    error = "Error"
+)]
+fn transferred(_: &ReceiveContext, _: &Host<State>) -> ContractResult<()> { Ok(()) }
+
+/// Returns true if the nationality & the country of residence of the identity of the address are allowed.
+fn is_allowed(host: &Host<State>, address: Address) -> ContractResult<bool> {
+    let state = host.state();
+    let identity =
+        host.invoke_identity_registry_get_identity(&state.identity_registry(), address)?;
+    for jurisdiction in Jurisdiction::ALL {
+        let country = identity
+            .attributes
+            .iter()
+            .find(|attribute| attribute.tag.eq(&jurisdiction.attribute_tag()))
+            .map(|attribute| &attribute.value);
+        if !state.is_allowed(jurisdiction, country) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,82 @@
+use concordium_std::{
+    Address, ContractAddress, DeserialWithState, Serial, StateApi, StateBuilder, StateSet,
+};
+
+use super::types::{AttributeValue, Jurisdiction, ListType};
+
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct State<S=StateApi> {
+    identity_registry:     ContractAddress,
+    pub agents:            StateSet<Address, S>,
+    allowed_nationalities: StateSet<AttributeValue, S>,
+    blocked_nationalities: StateSet<AttributeValue, S>,
+    allowed_residences:    StateSet<AttributeValue, S>,
+    blocked_residences:    StateSet<AttributeValue, S>,
+}
+
+impl State<StateApi> {
+    pub fn new(
+        identity_registry: ContractAddress,
+        agents: Vec<Address>,
+        state_builder: &mut StateBuilder,
+    ) -> Self {
+        let mut state = Self {
+            identity_registry,
+            agents: state_builder.new_set(),
+            allowed_nationalities: state_builder.new_set(),
+            blocked_nationalities: state_builder.new_set(),
+            allowed_residences: state_builder.new_set(),
+            blocked_residences: state_builder.new_set(),
+        };
+
+        for agent in agents {
+            state.agents.insert(agent);
+        }
+
+        state
+    }
+
+    pub fn identity_registry(&self) -> ContractAddress { self.identity_registry }
+
+    pub fn list(
+        &self,
+        jurisdiction: Jurisdiction,
+        list: ListType,
+    ) -> &StateSet<AttributeValue, StateApi> {
+        match (jurisdiction, list) {
+            (Jurisdiction::Nationality, ListType::Allow) => &self.allowed_nationalities,
+            (Jurisdiction::Nationality, ListType::Block) => &self.blocked_nationalities,
+            (Jurisdiction::CountryOfResidence, ListType::Allow) => &self.allowed_residences,
+            (Jurisdiction::CountryOfResidence, ListType::Block) => &self.blocked_residences,
+        }
+    }
+
+    pub fn list_mut(
+        &mut self,
+        jurisdiction: Jurisdiction,
+        list: ListType,
+    ) -> &mut StateSet<AttributeValue, StateApi> {
+        match (jurisdiction, list) {
+            (Jurisdiction::Nationality, ListType::Allow) => &mut self.allowed_nationalities,
+            (Jurisdiction::Nationality, ListType::Block) => &mut self.blocked_nationalities,
+            (Jurisdiction::CountryOfResidence, ListType::Allow) => &mut self.allowed_residences,
+            (Jurisdiction::CountryOfResidence, ListType::Block) => &mut self.blocked_residences,
+        }
+    }
+
+    /// Returns true if the country is not blocked and is in the allow list, in case the allow list is not empty.
+    /// A missing country is only allowed if the allow list is empty.
+    pub fn is_allowed(&self, jurisdiction: Jurisdiction, country: Option<&AttributeValue>) -> bool {
+        let allowed = self.list(jurisdiction, ListType::Allow);
+        // `StateSet::is_empty` only looks up the prefix entry which does not exist for a non empty set
+        let allow_all = allowed.iter().next().is_none();
+        match country {
+            Some(country) => {
+                !self.list(jurisdiction, ListType::Block).contains(country)
+                    && (allow_all || allowed.contains(country))
+            }
+            None => allow_all,
+        }
+    }
+}
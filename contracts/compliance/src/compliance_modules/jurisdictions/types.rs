@@ -0,0 +1,72 @@
+use concordium_cis2::{TokenAmountU64, TokenIdVec};
+use concordium_protocols::concordium_cis2_security;
+use concordium_std::attributes::{COUNTRY_OF_RESIDENCE, NATIONALITY};
+use concordium_std::{Address, ContractAddress, SchemaType, Serialize};
+
+use crate::compliance;
+pub use crate::compliance::types::AgentUpdatedEvent;
+
+pub type AttributeTag = concordium_cis2_security::AttributeTag;
+pub type AttributeValue = concordium_cis2_security::AttributeValue;
+pub type ContractResult<T> = Result<T, Error>;
+pub type TokenAmount = TokenAmountU64;
+pub type TokenId = TokenIdVec;
+pub type Error = compliance::error::Error;
+
+/// The identity attribute a list of countries applies to.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jurisdiction {
+    Nationality,
+    CountryOfResidence,
+}
+
+impl Jurisdiction {
+    pub const ALL: [Jurisdiction; 2] =
+        [Jurisdiction::Nationality, Jurisdiction::CountryOfResidence];
+
+    pub fn attribute_tag(&self) -> AttributeTag {
+        match self {
+            Jurisdiction::Nationality => NATIONALITY.0,
+            Jurisdiction::CountryOfResidence => COUNTRY_OF_RESIDENCE.0,
+        }
+    }
+}
+
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListType {
+    /// When not empty only the countries in the list are allowed.
+    Allow,
+    /// The countries in the list are not allowed.
+    Block,
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct InitParams {
+    pub identity_registry: ContractAddress,
+    /// Agents allowed to update the lists in addition to the contract owner.
+    pub agents:            Vec<Address>,
+}
+
+/// Parameters for the `updateList` function.
+#[derive(Serialize, SchemaType, Clone, Debug)]
+pub struct UpdateListParams {
+    pub jurisdiction: Jurisdiction,
+    pub list:         ListType,
+    pub add:          Vec<AttributeValue>,
+    pub remove:       Vec<AttributeValue>,
+}
+
+/// Parameters for the `list` function.
+#[derive(Serialize, SchemaType)]
+pub struct ListParams {
+    pub jurisdiction: Jurisdiction,
+    pub list:         ListType,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub enum Event {
+    AgentAdded(AgentUpdatedEvent),
+    AgentRemoved(AgentUpdatedEvent),
+    /// Emitted when countries are added to or removed from a list.
+    ListUpdated(UpdateListParams),
+}
@@ -0,0 +1,113 @@
+use concordium_protocols::concordium_cis2_security::{
+    BurnedParam, CanTransferParam, MintedParam, TransferredParam,
+};
+use concordium_std::*;
+use state::*;
+use types::*;
+
+mod state;
+pub mod types;
+
+/// Initializes the module with the compliance contract calling its hooks and the maximum balance of a single
+/// holder.
+#[init(
+    contract = "rwa_compliance_module_max_balance",
+    error = "Error",
+    parameter = "InitParams"
+)]
+pub fn init(ctx: &InitContext, state_builder: &mut StateBuilder) -> ContractResult<State> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+
+    Ok(State::new(
+        params.compliance,
+        params.max_balance,
+        state_builder,
+    ))
+}
+
+/// Returns false if the balance of the receiver would exceed the maximum balance after the transfer.
+#[receive(
+    contract = "rwa_compliance_module_max_balance",
+    name = "canTransfer",
+    parameter = "CanTransferParam<TokenId, TokenAmount>",
+    return_value = "bool",
+    error = "Error"
+)]
+fn can_transfer(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
+    let params: CanTransferParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    Ok(host
+        .state()
+        .can_transfer(&params.token_id, params.from, params.to, params.amount))
+}
+
+/// Tracks the balance of the owner of the minted tokens.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_max_balance",
+    name = "minted",
+    parameter = "MintedParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn minted(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: MintedParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    host.state_mut()
+        .balances_mut()
+        .add(&params.token_id, params.owner, params.amount);
+
+    Ok(())
+}
+
+/// Tracks the balances of the sender & the receiver of the transferred tokens.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_max_balance",
+    name = "transferred",
+    parameter = "TransferredParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn transferred(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: TransferredParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    let balances = host.state_mut().balances_mut();
+    balances.sub(&params.token_id, params.from, params.amount);
+    balances.add(&params.token_id, params.to, params.amount);
+
+    Ok(())
+}
+
+/// Tracks the balance of the owner of the burned tokens.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_max_balance",
+    name = "burned",
+    parameter = "BurnedParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn burned(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: BurnedParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    host.state_mut()
+        .balances_mut()
+        .sub(&params.token_id, params.owner, params.amount);
+
+    Ok(())
+}
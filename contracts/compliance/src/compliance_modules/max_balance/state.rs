@@ -0,0 +1,50 @@
+use concordium_protocols::concordium_cis2_security::TokenUId;
+use concordium_std::{Address, ContractAddress, DeserialWithState, Serial, StateApi, StateBuilder};
+
+use super::types::{TokenAmount, TokenId};
+use crate::compliance_modules::token_balances::TokenBalances;
+
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct State<S=StateApi> {
+    compliance:  ContractAddress,
+    max_balance: TokenAmount,
+    balances:    TokenBalances<S>,
+}
+
+impl State<StateApi> {
+    pub fn new(
+        compliance: ContractAddress,
+        max_balance: TokenAmount,
+        state_builder: &mut StateBuilder,
+    ) -> Self {
+        Self {
+            compliance,
+            max_balance,
+            balances: TokenBalances::new(state_builder),
+        }
+    }
+
+    pub fn compliance(&self) -> ContractAddress { self.compliance }
+
+    /// Returns true if the balance of the receiver stays within the maximum balance after receiving `amount`.
+    pub fn can_transfer(
+        &self,
+        token_id: &TokenUId<TokenId>,
+        from: Option<Address>,
+        to: Address,
+        amount: TokenAmount,
+    ) -> bool {
+        if from.is_some_and(|from| from.eq(&to)) {
+            return true;
+        }
+
+        self.balances
+            .balance_of(token_id, to)
+            .0
+            .checked_add(amount.0)
+            .is_some_and(|balance| balance <= self.max_balance.0)
+    }
+
+    pub fn balances_mut(&mut self) -> &mut TokenBalances { &mut self.balances }
+}
@@ -0,0 +1,125 @@
+use concordium_protocols::concordium_cis2_security::{
+    BurnedParam, CanTransferParam, MintedParam, TokenUId, TransferredParam,
+};
+use concordium_std::*;
+use state::*;
+use types::*;
+
+mod state;
+pub mod types;
+
+/// Initializes the module with the compliance contract calling its hooks and the maximum number of holders of
+/// each token.
+#[init(
+    contract = "rwa_compliance_module_max_holders",
+    error = "Error",
+    parameter = "InitParams"
+)]
+pub fn init(ctx: &InitContext, state_builder: &mut StateBuilder) -> ContractResult<State> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+
+    Ok(State::new(
+        params.compliance,
+        params.max_holders,
+        state_builder,
+    ))
+}
+
+/// Returns the number of distinct holders of the token.
+#[receive(
+    contract = "rwa_compliance_module_max_holders",
+    name = "holderCount",
+    parameter = "TokenUId<TokenId>",
+    return_value = "u64",
+    error = "Error"
+)]
+fn holder_count(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<u64> {
+    let token_id: TokenUId<TokenId> = ctx.parameter_cursor().get()?;
+    Ok(host.state().holder_count(&token_id))
+}
+
+/// Returns false if the transfer would make the receiver a new holder of the token while the token already has the
+/// maximum number of holders.
+#[receive(
+    contract = "rwa_compliance_module_max_holders",
+    name = "canTransfer",
+    parameter = "CanTransferParam<TokenId, TokenAmount>",
+    return_value = "bool",
+    error = "Error"
+)]
+fn can_transfer(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
+    let params: CanTransferParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    Ok(host
+        .state()
+        .can_transfer(&params.token_id, params.from, params.to, params.amount))
+}
+
+/// Tracks the balance of the owner of the minted tokens.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_max_holders",
+    name = "minted",
+    parameter = "MintedParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn minted(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: MintedParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    host.state_mut()
+        .add(&params.token_id, params.owner, params.amount);
+
+    Ok(())
+}
+
+/// Tracks the balances of the sender & the receiver of the transferred tokens.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_max_holders",
+    name = "transferred",
+    parameter = "TransferredParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn transferred(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: TransferredParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    let state = host.state_mut();
+    state.sub(&params.token_id, params.from, params.amount);
+    state.add(&params.token_id, params.to, params.amount);
+
+    Ok(())
+}
+
+/// Tracks the balance of the owner of the burned tokens.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_max_holders",
+    name = "burned",
+    parameter = "BurnedParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn burned(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: BurnedParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    host.state_mut()
+        .sub(&params.token_id, params.owner, params.amount);
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use concordium_protocols::concordium_cis2_security::TokenUId;
+use concordium_std::{
+    Address, ContractAddress, DeserialWithState, Serial, StateApi, StateBuilder, StateMap,
+};
+
+use super::types::{TokenAmount, TokenId};
+use crate::compliance_modules::token_balances::TokenBalances;
+
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct State<S=StateApi> {
+    compliance:    ContractAddress,
+    max_holders:   u64,
+    holder_counts: StateMap<TokenUId<TokenId>, u64, S>,
+    balances:      TokenBalances<S>,
+}
+
+impl State<StateApi> {
+    pub fn new(
+        compliance: ContractAddress,
+        max_holders: u64,
+        state_builder: &mut StateBuilder,
+    ) -> Self {
+        Self {
+            compliance,
+            max_holders,
+            holder_counts: state_builder.new_map(),
+            balances: TokenBalances::new(state_builder),
+        }
+    }
+
+    pub fn compliance(&self) -> ContractAddress { self.compliance }
+
+    pub fn holder_count(&self, token_id: &TokenUId<TokenId>) -> u64 {
+        self.holder_counts
+            .get(token_id)
+            .map(|count| *count)
+            .unwrap_or(0)
+    }
+
+    /// Returns true if transferring `amount` of the token from `from` keeps the number of holders within the limit.
+    pub fn can_transfer(
+        &self,
+        token_id: &TokenUId<TokenId>,
+        from: Option<Address>,
+        to: Address,
+        amount: TokenAmount,
+    ) -> bool {
+        if self.balances.balance_of(token_id, to).0 > 0 {
+            return true;
+        }
+
+        let mut holder_count = self.holder_count(token_id);
+        // The sender stops being a holder if the whole balance is transferred
+        if let Some(from) = from {
+            if from.ne(&to) && self.balances.balance_of(token_id, from).eq(&amount) {
+                holder_count = holder_count.saturating_sub(1);
+            }
+        }
+
+        holder_count < self.max_holders
+    }
+
+    pub fn add(&mut self, token_id: &TokenUId<TokenId>, holder: Address, amount: TokenAmount) {
+        if self.balances.add(token_id, holder, amount) {
+            *self.holder_counts.entry(token_id.clone()).or_insert(0) += 1;
+        }
+    }
+
+    pub fn sub(&mut self, token_id: &TokenUId<TokenId>, holder: Address, amount: TokenAmount) {
+        if self.balances.sub(token_id, holder, amount) {
+            let holder_count = self.holder_count(token_id).saturating_sub(1);
+            let _ = self.holder_counts.insert(token_id.clone(), holder_count);
+        }
+    }
+}
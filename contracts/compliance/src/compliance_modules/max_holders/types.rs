@@ -0,0 +1,16 @@
+use concordium_cis2::{TokenAmountU64, TokenIdVec};
+use concordium_std::{ContractAddress, SchemaType, Serialize};
+
+use crate::compliance;
+
+pub type ContractResult<T> = Result<T, Error>;
+pub type TokenAmount = TokenAmountU64;
+pub type TokenId = TokenIdVec;
+pub type Error = compliance::error::Error;
+#[derive(Serialize, SchemaType)]
+pub struct InitParams {
+    /// The compliance contract which calls the `minted`, `transferred` & `burned` hooks of the module.
+    pub compliance:  ContractAddress,
+    /// Maximum number of distinct holders of each token.
+    pub max_holders: u64,
+}
@@ -1 +1,6 @@
 pub mod allowed_nationalities;
+pub mod jurisdictions;
+pub mod max_balance;
+pub mod max_holders;
+mod token_balances;
+pub mod transfer_volume;
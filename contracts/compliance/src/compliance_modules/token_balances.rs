@@ -0,0 +1,67 @@
+use concordium_protocols::concordium_cis2_security::TokenUId;
+use concordium_std::{Address, DeserialWithState, Serial, StateApi, StateBuilder, StateMap};
+
+use crate::compliance::types::{TokenAmount, TokenId};
+
+/// Balances of the holders of the tokens checked by a compliance module.
+/// Kept up to date using the `minted`, `transferred` & `burned` hooks of the module.
+/// The module should be added to the compliance contract before any tokens are minted.
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct TokenBalances<S=StateApi> {
+    balances: StateMap<(TokenUId<TokenId>, Address), TokenAmount, S>,
+}
+
+impl TokenBalances<StateApi> {
+    pub fn new(state_builder: &mut StateBuilder) -> Self {
+        Self {
+            balances: state_builder.new_map(),
+        }
+    }
+
+    pub fn balance_of(&self, token_id: &TokenUId<TokenId>, holder: Address) -> TokenAmount {
+        self.balances
+            .get(&(token_id.clone(), holder))
+            .map(|balance| *balance)
+            .unwrap_or(TokenAmount::from(0))
+    }
+
+    /// Adds `amount` to the balance of the holder.
+    /// Returns true if the holder did not hold the token before.
+    pub fn add(
+        &mut self,
+        token_id: &TokenUId<TokenId>,
+        holder: Address,
+        amount: TokenAmount,
+    ) -> bool {
+        let mut balance = self
+            .balances
+            .entry((token_id.clone(), holder))
+            .or_insert(TokenAmount::from(0));
+        let is_new_holder = balance.0 == 0;
+        balance.0 = balance.0.saturating_add(amount.0);
+        is_new_holder && amount.0 > 0
+    }
+
+    /// Subtracts `amount` from the balance of the holder.
+    /// Returns true if the holder does not hold the token anymore.
+    pub fn sub(
+        &mut self,
+        token_id: &TokenUId<TokenId>,
+        holder: Address,
+        amount: TokenAmount,
+    ) -> bool {
+        let key = (token_id.clone(), holder);
+        let remaining = match self.balances.get(&key) {
+            Some(balance) => balance.0.saturating_sub(amount.0),
+            None => return false,
+        };
+        if remaining == 0 {
+            self.balances.remove(&key);
+            true
+        } else {
+            let _ = self.balances.insert(key, TokenAmount::from(remaining));
+            false
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use concordium_protocols::concordium_cis2_security::{CanTransferParam, TransferredParam};
+use concordium_std::*;
+use state::*;
+use types::*;
+
+mod state;
+pub mod types;
+
+/// Initializes the module with the compliance contract calling its hooks and the daily & monthly transfer limits of
+/// a single investor.
+#[init(
+    contract = "rwa_compliance_module_transfer_volume",
+    error = "Error",
+    parameter = "InitParams"
+)]
+pub fn init(ctx: &InitContext, state_builder: &mut StateBuilder) -> ContractResult<State> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+
+    Ok(State::new(
+        params.compliance,
+        params.daily_limit,
+        params.monthly_limit,
+        state_builder,
+    ))
+}
+
+#[receive(
+    contract = "rwa_compliance_module_transfer_volume",
+    name = "burned",
+    error = "Error"
+)]
+fn burned(_: &ReceiveContext, _: &Host<State>) -> ContractResult<()> { Ok(()) }
+
+#[receive(
+    contract = "rwa_compliance_module_transfer_volume",
+    name = "minted",
+    error = "Error"
+)]
+fn minted(_: &ReceiveContext, _: &Host<State>) -> ContractResult<()> { Ok(()) }
+
+/// Returns false if the sender would exceed the daily or the monthly transfer limit with the transfer.
+/// Minting is not limited.
+#[receive(
+    contract = "rwa_compliance_module_transfer_volume",
+    name = "canTransfer",
+    parameter = "CanTransferParam<TokenId, TokenAmount>",
+    return_value = "bool",
+    error = "Error"
+)]
+fn can_transfer(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
+    let params: CanTransferParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    let can_transfer = match params.from {
+        Some(from) => host.state().can_transfer(
+            &params.token_id,
+            from,
+            params.amount,
+            ctx.metadata().slot_time(),
+        ),
+        None => true,
+    };
+
+    Ok(can_transfer)
+}
+
+/// Adds the transferred amount to the transfer volume of the sender.
+///
+/// # Errors
+/// Returns `Error::Unauthorized` if the sender is not the compliance contract.
+#[receive(
+    contract = "rwa_compliance_module_transfer_volume",
+    name = "transferred",
+    parameter = "TransferredParam<TokenId, TokenAmount>",
+    error = "Error",
+    mutable
+)]
+fn transferred(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
+    let params: TransferredParam<TokenId, TokenAmount> = ctx.parameter_cursor().get()?;
+    ensure!(
+        ctx.sender().matches_contract(&host.state().compliance()),
+        Error::Unauthorized
+    );
+    host.state_mut().add(
+        &params.token_id,
+        params.from,
+        params.amount,
+        ctx.metadata().slot_time(),
+    );
+
+    Ok(())
+}
+
+/// Returns the amounts of the token transferred by the investor in the current day & month.
+#[receive(
+    contract = "rwa_compliance_module_transfer_volume",
+    name = "volumeOf",
+    parameter = "VolumeOfParams",
+    return_value = "(TokenAmount, TokenAmount)",
+    error = "Error"
+)]
+fn volume_of(
+    ctx: &ReceiveContext,
+    host: &Host<State>,
+) -> ContractResult<(TokenAmount, TokenAmount)> {
+    let params: VolumeOfParams = ctx.parameter_cursor().get()?;
+    let volume = host.state().volume(
+        &params.token_id,
+        params.investor,
+        ctx.metadata().slot_time(),
+    );
+
+    Ok((volume.daily_volume, volume.monthly_volume))
+}
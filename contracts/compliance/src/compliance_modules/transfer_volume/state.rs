@@ -0,0 +1,117 @@
+use concordium_protocols::concordium_cis2_security::TokenUId;
+use concordium_std::{
+    Address, ContractAddress, DeserialWithState, Serial, Serialize, StateApi, StateBuilder,
+    StateMap, Timestamp,
+};
+
+use super::types::{TokenAmount, TokenId};
+
+const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+const MONTH_MILLIS: u64 = 30 * DAY_MILLIS;
+
+/// Amount of a token transferred by an investor in the current day & month.
+/// Days & months are fixed periods counted from the unix epoch.
+#[derive(Serialize, Clone, Copy, Default)]
+pub struct TransferVolume {
+    pub day:            u64,
+    pub daily_volume:   TokenAmount,
+    pub month:          u64,
+    pub monthly_volume: TokenAmount,
+}
+
+impl TransferVolume {
+    /// Returns the volume in the day & month of `now`.
+    pub fn at(self, now: Timestamp) -> Self {
+        let day = now.timestamp_millis() / DAY_MILLIS;
+        let month = now.timestamp_millis() / MONTH_MILLIS;
+        Self {
+            day,
+            daily_volume: if self.day == day {
+                self.daily_volume
+            } else {
+                TokenAmount::from(0)
+            },
+            month,
+            monthly_volume: if self.month == month {
+                self.monthly_volume
+            } else {
+                TokenAmount::from(0)
+            },
+        }
+    }
+
+    pub fn add(self, amount: TokenAmount) -> Self {
+        Self {
+            daily_volume: TokenAmount::from(self.daily_volume.0.saturating_add(amount.0)),
+            monthly_volume: TokenAmount::from(self.monthly_volume.0.saturating_add(amount.0)),
+            ..self
+        }
+    }
+}
+
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct State<S=StateApi> {
+    compliance:    ContractAddress,
+    daily_limit:   Option<TokenAmount>,
+    monthly_limit: Option<TokenAmount>,
+    volumes:       StateMap<(TokenUId<TokenId>, Address), TransferVolume, S>,
+}
+
+impl State<StateApi> {
+    pub fn new(
+        compliance: ContractAddress,
+        daily_limit: Option<TokenAmount>,
+        monthly_limit: Option<TokenAmount>,
+        state_builder: &mut StateBuilder,
+    ) -> Self {
+        Self {
+            compliance,
+            daily_limit,
+            monthly_limit,
+            volumes: state_builder.new_map(),
+        }
+    }
+
+    pub fn compliance(&self) -> ContractAddress { self.compliance }
+
+    pub fn volume(
+        &self,
+        token_id: &TokenUId<TokenId>,
+        investor: Address,
+        now: Timestamp,
+    ) -> TransferVolume {
+        self.volumes
+            .get(&(token_id.clone(), investor))
+            .map(|volume| *volume)
+            .unwrap_or_default()
+            .at(now)
+    }
+
+    /// Returns true if the transfer volume of the investor stays within the limits after transferring `amount`.
+    pub fn can_transfer(
+        &self,
+        token_id: &TokenUId<TokenId>,
+        investor: Address,
+        amount: TokenAmount,
+        now: Timestamp,
+    ) -> bool {
+        let volume = self.volume(token_id, investor, now).add(amount);
+        self.daily_limit
+            .is_none_or(|limit| volume.daily_volume.le(&limit))
+            && self
+                .monthly_limit
+                .is_none_or(|limit| volume.monthly_volume.le(&limit))
+    }
+
+    pub fn add(
+        &mut self,
+        token_id: &TokenUId<TokenId>,
+        investor: Address,
+        amount: TokenAmount,
+        now: Timestamp,
+    ) {
+        let volume = self.volume(token_id, investor, now).add(amount);
+        let _ = self.volumes.insert((token_id.clone(), investor), volume);
+    }
+}
@@ -0,0 +1,27 @@
+use concordium_cis2::{TokenAmountU64, TokenIdVec};
+use concordium_protocols::concordium_cis2_security::TokenUId;
+use concordium_std::{Address, ContractAddress, SchemaType, Serialize};
+
+use crate::compliance;
+
+pub type ContractResult<T> = Result<T, Error>;
+pub type TokenAmount = TokenAmountU64;
+pub type TokenId = TokenIdVec;
+pub type Error = compliance::error::Error;
+#[derive(Serialize, SchemaType)]
+pub struct InitParams {
+    /// The compliance contract which calls the `transferred` hook of the module.
+    pub compliance:    ContractAddress,
+    /// Maximum amount of each token a single investor can transfer in a day. `None` for no daily limit.
+    pub daily_limit:   Option<TokenAmount>,
+    /// Maximum amount of each token a single investor can transfer in a month (30 days). `None` for no monthly
+    /// limit.
+    pub monthly_limit: Option<TokenAmount>,
+}
+
+/// Parameters for the `volumeOf` function.
+#[derive(Serialize, SchemaType)]
+pub struct VolumeOfParams {
+    pub token_id: TokenUId<TokenId>,
+    pub investor: Address,
+}
@@ -1,13 +1,14 @@
-use concordium_cis2::IsTokenId;
+use concordium_cis2::{BalanceOfQuery, BalanceOfQueryParams, BalanceOfQueryResponse, IsTokenId};
 use concordium_std::*;
 
 use super::{
-    AddTokenParams, AgentWithRoles, Burn, BurnParams, FreezeParam, FreezeParams, IsPausedResponse,
-    MintParam, MintParams, PauseParam, PauseParams, RecoverParam,
+    AddTokenParams, AgentWithRoles, BalanceOfAtQueryParams, Burn, BurnParams, FreezeParam,
+    FreezeParams, IsPausedResponse, MintParam, MintParams, PauseParam, PauseParams, RecoverParam,
+    SupplyOfQueryParams,
 };
 use crate::concordium_cis2_ext::cis2_client::{Cis2Client, Cis2ClientError};
 use crate::concordium_cis2_ext::{ContractMetadataUrl, IsTokenAmount};
-use crate::contract_client::{invoke_contract, invoke_contract_read_only};
+use crate::contract_client::{invoke_contract, invoke_contract_read_only, ContractClientError};
 
 pub type Cis2SecurityClientError = Cis2ClientError;
 
@@ -150,6 +151,32 @@ pub trait Cis2SecurityClient: Cis2Client {
         contract: &ContractAddress,
         address: &Address,
     ) -> Result<Option<Address>, Cis2ClientError>;
+
+    fn invoke_balance_of_un_frozen<T: IsTokenId, A: IsTokenAmount>(
+        &self,
+        contract: &ContractAddress,
+        params: &BalanceOfQueryParams<T>,
+    ) -> Result<BalanceOfQueryResponse<A>, Cis2ClientError>;
+
+    fn invoke_balance_of_un_frozen_single<T: IsTokenId, A: IsTokenAmount+Copy>(
+        &self,
+        contract: &ContractAddress,
+        params: BalanceOfQuery<T>,
+    ) -> Result<A, Cis2ClientError>;
+
+    fn invoke_snapshot(&mut self, contract: &ContractAddress) -> Result<u64, Cis2ClientError>;
+
+    fn invoke_balance_of_at<T: IsTokenId, A: IsTokenAmount>(
+        &self,
+        contract: &ContractAddress,
+        params: &BalanceOfAtQueryParams<T>,
+    ) -> Result<BalanceOfQueryResponse<A>, Cis2ClientError>;
+
+    fn invoke_supply_of<T: IsTokenId, A: IsTokenAmount>(
+        &self,
+        contract: &ContractAddress,
+        params: &SupplyOfQueryParams<T>,
+    ) -> Result<Vec<A>, Cis2ClientError>;
 }
 
 impl<S> Cis2SecurityClient for Host<S>
@@ -460,4 +487,70 @@ where S: Serial+DeserialWithState<ExternStateApi>
             address,
         )
     }
+
+    #[inline]
+    fn invoke_balance_of_un_frozen<T: IsTokenId, A: IsTokenAmount>(
+        &self,
+        contract: &ContractAddress,
+        params: &BalanceOfQueryParams<T>,
+    ) -> Result<BalanceOfQueryResponse<A>, Cis2ClientError> {
+        invoke_contract_read_only(
+            self,
+            contract,
+            EntrypointName::new_unchecked("balanceOfUnFrozen"),
+            params,
+        )
+    }
+
+    #[inline]
+    fn invoke_balance_of_un_frozen_single<T: IsTokenId, A: IsTokenAmount+Copy>(
+        &self,
+        contract: &ContractAddress,
+        params: BalanceOfQuery<T>,
+    ) -> Result<A, Cis2ClientError> {
+        let res: BalanceOfQueryResponse<A> =
+            self.invoke_balance_of_un_frozen(contract, &BalanceOfQueryParams {
+                queries: vec![params],
+            })?;
+        let res = res.0.first().ok_or(ContractClientError::InvalidResponse)?;
+        Ok(*res)
+    }
+
+    #[inline]
+    fn invoke_snapshot(&mut self, contract: &ContractAddress) -> Result<u64, Cis2ClientError> {
+        invoke_contract(
+            self,
+            contract,
+            EntrypointName::new_unchecked("snapshot"),
+            &(),
+        )
+    }
+
+    #[inline]
+    fn invoke_balance_of_at<T: IsTokenId, A: IsTokenAmount>(
+        &self,
+        contract: &ContractAddress,
+        params: &BalanceOfAtQueryParams<T>,
+    ) -> Result<BalanceOfQueryResponse<A>, Cis2ClientError> {
+        invoke_contract_read_only(
+            self,
+            contract,
+            EntrypointName::new_unchecked("balanceOfAt"),
+            params,
+        )
+    }
+
+    #[inline]
+    fn invoke_supply_of<T: IsTokenId, A: IsTokenAmount>(
+        &self,
+        contract: &ContractAddress,
+        params: &SupplyOfQueryParams<T>,
+    ) -> Result<Vec<A>, Cis2ClientError> {
+        invoke_contract_read_only(
+            self,
+            contract,
+            EntrypointName::new_unchecked("supplyOf"),
+            params,
+        )
+    }
 }
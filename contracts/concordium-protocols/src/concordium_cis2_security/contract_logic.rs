@@ -1,6 +1,6 @@
 use concordium_std::{
-    ensure, Address, ContractAddress, DeserialWithState, ExternStateApi, HasHost, Host,
-    MetadataUrl, Serial, Serialize, StateBuilder,
+    ensure, Address, ContractAddress, DeserialWithState, ExternStateApi, HasHost, HasStateApi,
+    Host, MetadataUrl, Serial, Serialize, StateApi, StateBuilder, StateMap,
 };
 
 use super::{
@@ -58,6 +58,7 @@ pub fn authorize_and_transfer<
             ensure!(
                 host.invoke_compiliance_can_transfer(&security.compliance, &CanTransferParam {
                     token_id: compliance_token,
+                    from: Some(from),
                     to: to_address,
                     amount,
                 })
@@ -192,6 +193,7 @@ pub fn authorize_and_mint<
                 host.invoke_compiliance_can_transfer(&security.compliance, &CanTransferParam {
                     token_id: compliance_token,
                     amount:   amount.total(),
+                    from:     None,
                     to:       owner,
                 })
                 .map_err(|_| Error::ContractClientError)?,
@@ -273,3 +275,75 @@ impl<A: IsTokenAmount> SecurityTokenState<A> {
 
     pub fn metadata_url(&self) -> &MetadataUrl { &self.metadata_url }
 }
+
+/// The balances of the holders at the snapshots, keyed by the holder (and token). The balance is
+/// recorded lazily, before it is first updated after a snapshot is taken. Each checkpoint is a
+/// separate state entry so that recording a balance does not get more expensive as the number
+/// of snapshots grows.
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct BalanceCheckpoints<K, A, S=StateApi> {
+    /// The number of checkpoints recorded for each key.
+    counts:      StateMap<K, u64, S>,
+    /// The snapshot id & the balance at that snapshot by the key & the index of the checkpoint.
+    /// The snapshot ids of a key are ascending by index.
+    checkpoints: StateMap<(K, u64), (u64, A), S>,
+}
+
+impl<K, A, S> BalanceCheckpoints<K, A, S>
+where
+    K: Serialize+Clone,
+    A: IsTokenAmount+Serialize,
+    S: HasStateApi,
+{
+    pub fn new(state_builder: &mut StateBuilder<S>) -> Self {
+        Self {
+            counts:      state_builder.new_map(),
+            checkpoints: state_builder.new_map(),
+        }
+    }
+
+    /// Records the `balance` of `key` at `snapshot_id` unless a balance has already been
+    /// recorded for it. The snapshot id `0` means that no snapshot has been taken.
+    pub fn record(&mut self, key: K, snapshot_id: u64, balance: A) {
+        if snapshot_id == 0 {
+            return;
+        }
+        let count = self.counts.get(&key).map_or(0, |count| *count);
+        let recorded = count > 0
+            && self
+                .checkpoints
+                .get(&(key.clone(), count - 1))
+                .is_some_and(|checkpoint| checkpoint.0 >= snapshot_id);
+        if recorded {
+            return;
+        }
+        let _ = self
+            .checkpoints
+            .insert((key.clone(), count), (snapshot_id, balance));
+        let _ = self.counts.insert(key, count + 1);
+    }
+
+    /// Returns the balance of `key` at `snapshot_id` or `None` if the balance has not
+    /// been updated since the snapshot was taken.
+    pub fn balance_at(&self, key: &K, snapshot_id: u64) -> Option<A> {
+        let count = self.counts.get(key).map_or(0, |count| *count);
+        // Binary search for the first checkpoint at or after the snapshot
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let id = self.checkpoints.get(&(key.clone(), mid))?.0;
+            if id < snapshot_id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == count {
+            return None;
+        }
+        self.checkpoints
+            .get(&(key.clone(), low))
+            .map(|checkpoint| checkpoint.1)
+    }
+}
@@ -1,8 +1,12 @@
 use concordium_cis2::{Cis2Event, IsTokenAmount, IsTokenId, TokenAmountU64, TokenIdVec};
 use concordium_std::schema::SchemaType;
-use concordium_std::{AccountAddress, Address, ContractAddress, Cursor, SchemaType, Serialize};
+use concordium_std::{
+    AccountAddress, Address, ContractAddress, Cursor, Duration, OwnedEntrypointName, SchemaType,
+    Serialize, Timestamp,
+};
 
-use super::TokenUId;
+use super::{Document, Governance, TokenUId};
+use crate::concordium_cis3::NonceEvent;
 
 /// Represents an event that is triggered when an agent is updated (Added /
 /// Removed).
@@ -45,6 +49,58 @@ pub struct RecoverEvent {
     pub new_account:  Address,
 }
 
+/// Represents the event when the recovery of a lost account is initiated.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct RecoveryInitiatedEvent {
+    pub lost_account:  Address,
+    pub new_account:   Address,
+    /// The time from which the recovery can be executed.
+    pub execute_after: Timestamp,
+}
+
+/// Represents the event when the recovery delay is updated.
+#[derive(Serialize, SchemaType, Debug)]
+#[concordium(transparent)]
+pub struct RecoveryDelayUpdatedEvent(pub Duration);
+
+/// Represents the event when a document of a token is set or removed.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct DocumentUpdatedEvent<T: IsTokenId+SchemaType> {
+    pub token_id: T,
+    pub name:     String,
+    /// The document, `None` if it has been removed.
+    pub document: Option<Document>,
+}
+
+/// Represents the event when a snapshot of the balances is taken.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct SnapshotEvent {
+    pub snapshot_id: u64,
+}
+
+/// Represents the event when the governance of the contract is set or removed.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct GovernanceUpdatedEvent {
+    pub governance: Option<Governance>,
+}
+
+/// Represents the event when a proposal is created.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct ProposalCreatedEvent {
+    pub proposal_id:   u64,
+    pub proposer:      Address,
+    pub entrypoint:    OwnedEntrypointName,
+    pub execute_after: Timestamp,
+}
+
+/// Represents the event when a proposal is approved, executed or cancelled by
+/// an approver.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct ProposalEvent {
+    pub proposal_id: u64,
+    pub approver:    Address,
+}
+
 #[derive(Serialize, SchemaType, Debug)]
 pub struct TokenDeposited {
     pub token_id: TokenUId<TokenIdVec>,
@@ -62,7 +118,7 @@ where
     #[concordium(tag = 241)]
     TokenRemoved(T),
 
-    /// Event triggered when an account is recovered.
+    /// Event triggered when the recovery of an account is executed.
     #[concordium(tag = 242)]
     Recovered(RecoverEvent),
 
@@ -98,6 +154,51 @@ where
     #[concordium(tag = 250)]
     AgentAdded(AgentUpdatedEvent<R>),
 
+    /// Event triggered when the nonce of an account is used by a CIS-3 permit.
+    /// The CIS-3 tag 250 is already used by `AgentAdded`.
+    #[concordium(tag = 240)]
+    Nonce(NonceEvent),
+
+    /// Event triggered when a snapshot of the balances is taken.
+    #[concordium(tag = 239)]
+    Snapshot(SnapshotEvent),
+
+    /// Event triggered when the governance is set or removed.
+    #[concordium(tag = 238)]
+    GovernanceUpdated(GovernanceUpdatedEvent),
+
+    /// Event triggered when a proposal is created.
+    #[concordium(tag = 237)]
+    ProposalCreated(ProposalCreatedEvent),
+
+    /// Event triggered when a proposal is approved.
+    #[concordium(tag = 236)]
+    ProposalApproved(ProposalEvent),
+
+    /// Event triggered when a proposal is executed.
+    #[concordium(tag = 235)]
+    ProposalExecuted(ProposalEvent),
+
+    /// Event triggered when a proposal is cancelled.
+    #[concordium(tag = 234)]
+    ProposalCancelled(ProposalEvent),
+
+    /// Event triggered when the recovery of a lost account is initiated.
+    #[concordium(tag = 233)]
+    RecoveryInitiated(RecoveryInitiatedEvent),
+
+    /// Event triggered when a pending recovery is cancelled.
+    #[concordium(tag = 232)]
+    RecoveryCancelled(RecoverEvent),
+
+    /// Event triggered when a document of a token is set or removed.
+    #[concordium(tag = 231)]
+    DocumentUpdated(DocumentUpdatedEvent<T>),
+
+    /// Event triggered when the recovery delay is updated.
+    #[concordium(tag = 230)]
+    RecoveryDelayUpdated(RecoveryDelayUpdatedEvent),
+
     /// Event forwarded from the CIS2 contract.
     #[concordium(forward = cis2_events)]
     Cis2(Cis2Event<T, A>),
@@ -2,7 +2,7 @@ use concordium_std::{
     Address, ContractAddress, DeserialWithState, EntrypointName, ExternStateApi, Host, Serial,
 };
 
-use super::{Identity, IDENTITY_REGISTRY_STANDARD_IDENTIFIER};
+use super::{Identity, VerifyAttributesParams, IDENTITY_REGISTRY_STANDARD_IDENTIFIER};
 use crate::contract_client::{invoke_contract_read_only, supports, ContractClientError};
 
 pub type IdentityRegistryClientError = ContractClientError<()>;
@@ -20,6 +20,12 @@ pub trait IdentityRegistryClient {
         address: &Address,
     ) -> Result<bool, IdentityRegistryClientError>;
 
+    fn invoke_identity_registry_verify_attributes(
+        &self,
+        contract: &ContractAddress,
+        params: &VerifyAttributesParams,
+    ) -> Result<bool, IdentityRegistryClientError>;
+
     fn invoke_supports_rwa_identity_registry_standard(
         &self,
         contract: &ContractAddress,
@@ -57,6 +63,20 @@ where S: Serial+DeserialWithState<ExternStateApi>
         )
     }
 
+    #[inline]
+    fn invoke_identity_registry_verify_attributes(
+        &self,
+        contract: &ContractAddress,
+        params: &VerifyAttributesParams,
+    ) -> Result<bool, IdentityRegistryClientError> {
+        invoke_contract_read_only(
+            self,
+            contract,
+            EntrypointName::new_unchecked("verifyAttributes"),
+            params,
+        )
+    }
+
     #[inline]
     fn invoke_supports_rwa_identity_registry_standard(
         &self,
@@ -4,12 +4,16 @@ pub mod contract_logic;
 mod event;
 pub mod identity_registry_client;
 mod params_compliance;
+mod params_document;
+mod params_governance;
 mod params_identity_registry;
 mod params_token;
 
 use concordium_cis2::StandardIdentifier;
 pub use event::*;
 pub use params_compliance::*;
+pub use params_document::*;
+pub use params_governance::*;
 pub use params_identity_registry::*;
 pub use params_token::*;
 pub const COMPLIANCE_STANDARD_IDENTIFIER: StandardIdentifier =
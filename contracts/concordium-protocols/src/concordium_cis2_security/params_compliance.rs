@@ -8,6 +8,8 @@ use super::TokenUId;
 pub struct CanTransferParam<T: IsTokenId, A: IsTokenAmount> {
     /// The ID of the token to transfer.
     pub token_id: TokenUId<T>,
+    /// The address to transfer from. `None` if the tokens are being minted.
+    pub from:     Option<Address>,
     /// The address to transfer to.
     pub to:       Address,
     /// The amount of tokens to transfer.
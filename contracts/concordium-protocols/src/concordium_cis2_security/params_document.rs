@@ -0,0 +1,38 @@
+use concordium_cis2::IsTokenId;
+use concordium_std::{SchemaType, Serialize, Timestamp};
+
+/// A document of a token, like an offering document or a legal contract
+/// (ERC-1643).
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub struct Document {
+    pub uri:          String,
+    /// The SHA-256 hash of the content of the document.
+    pub content_hash: [u8; 32],
+    /// The time the document was last set.
+    pub timestamp:    Timestamp,
+}
+
+#[derive(Serialize, SchemaType, Clone)]
+pub struct SetDocumentParams<T: IsTokenId> {
+    pub token_id:     T,
+    /// The name of the document, unique for the token.
+    pub name:         String,
+    pub uri:          String,
+    /// The SHA-256 hash of the content of the document.
+    pub content_hash: [u8; 32],
+}
+
+#[derive(Serialize, SchemaType, Clone)]
+pub struct DocumentQuery<T: IsTokenId> {
+    pub token_id: T,
+    pub name:     String,
+}
+
+/// An update of a document of a token.
+#[derive(Serialize, SchemaType, Clone)]
+pub enum DocumentUpdate<T: IsTokenId> {
+    /// Sets the document, replacing the document with the same name.
+    Set(SetDocumentParams<T>),
+    /// Removes the document.
+    Remove(DocumentQuery<T>),
+}
@@ -0,0 +1,63 @@
+use concordium_std::{Address, Duration, OwnedEntrypointName, SchemaType, Serialize, Timestamp};
+
+/// The approvers which govern a security token contract. Once set, agents can
+/// only be added or removed, and the governance can only be updated, by
+/// executing a proposal. Privileged roles granted to the contract itself can
+/// likewise only be exercised by executing a proposal.
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub struct Governance {
+    /// The addresses which can propose, approve, execute & cancel proposals.
+    pub approvers: Vec<Address>,
+    /// The number of approvals (M of the N approvers) required to execute a
+    /// proposal.
+    pub threshold: u32,
+    /// The time after a proposal is created before it can be executed.
+    pub delay:     Duration,
+}
+
+impl Governance {
+    /// Returns true if the threshold can be met by the approvers & there are no
+    /// duplicate approvers.
+    pub fn is_valid(&self) -> bool {
+        self.threshold > 0
+            && self.threshold as usize <= self.approvers.len()
+            && self
+                .approvers
+                .iter()
+                .enumerate()
+                .all(|(i, approver)| !self.approvers[..i].contains(approver))
+    }
+
+    pub fn is_approver(&self, address: &Address) -> bool { self.approvers.contains(address) }
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct ProposeParams {
+    /// The entrypoint of the contract which is invoked when the proposal is
+    /// executed.
+    pub entrypoint: OwnedEntrypointName,
+    /// The serialized parameter of the entrypoint.
+    pub parameter:  Vec<u8>,
+}
+
+/// An update of a proposal by an approver.
+#[derive(Serialize, SchemaType, Debug)]
+pub enum ProposalUpdate {
+    /// Approves the proposal with the id.
+    Approve(u64),
+    /// Executes the proposal with the id.
+    Execute(u64),
+    /// Cancels the proposal with the id.
+    Cancel(u64),
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct Proposal {
+    pub proposer:      Address,
+    pub entrypoint:    OwnedEntrypointName,
+    pub parameter:     Vec<u8>,
+    /// The approvers which have approved the proposal.
+    pub approvals:     Vec<Address>,
+    /// The time from which the proposal can be executed.
+    pub execute_after: Timestamp,
+}
@@ -21,3 +21,127 @@ pub struct Identity {
     pub attributes:  Vec<IdentityAttribute>,
     pub credentials: Vec<IdentityCredential>,
 }
+
+/// Condition on the value of an identity attribute.
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub enum Predicate {
+    /// The value is equal to the given value.
+    Equals(AttributeValue),
+    /// The value is one of the given values.
+    In(Vec<AttributeValue>),
+    /// The value is none of the given values.
+    NotIn(Vec<AttributeValue>),
+    /// The value is greater than or equal to the given value. Values are compared as numbers when both are numeric
+    /// and lexicographically otherwise.
+    GreaterOrEqual(AttributeValue),
+    /// The value is less than or equal to the given value. Values are compared as numbers when both are numeric and
+    /// lexicographically otherwise.
+    LessOrEqual(AttributeValue),
+    /// The value is a date of birth formatted as `YYYYMMDD` of a person which is at least the given number of years
+    /// old.
+    MinimumAge(u8),
+}
+
+/// A predicate on the attribute with the given tag.
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub struct AttributePredicate {
+    pub tag:       AttributeTag,
+    pub predicate: Predicate,
+}
+
+/// Parameters for the `verifyAttributes` function of the identity registry.
+#[derive(Serialize, SchemaType)]
+pub struct VerifyAttributesParams {
+    pub address:    Address,
+    pub predicates: Vec<AttributePredicate>,
+}
+
+impl Predicate {
+    /// Returns true if the attribute value satisfies the predicate at time `now`.
+    pub fn is_satisfied(&self, value: &str, now: Timestamp) -> bool {
+        match self {
+            Predicate::Equals(expected) => value.eq(expected),
+            Predicate::In(values) => values.iter().any(|v| v.eq(value)),
+            Predicate::NotIn(values) => !values.iter().any(|v| v.eq(value)),
+            Predicate::GreaterOrEqual(bound) => compare_values(value, bound).is_ge(),
+            Predicate::LessOrEqual(bound) => compare_values(value, bound).is_le(),
+            Predicate::MinimumAge(years) => match value.parse::<u32>() {
+                Ok(date_of_birth) if value.len() == 8 => {
+                    let (year, month, day) = civil_date(now);
+                    match year.checked_sub(*years as u32) {
+                        Some(year) => date_of_birth <= year * 10_000 + month * 100 + day,
+                        None => false,
+                    }
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+fn compare_values(a: &str, b: &str) -> core::cmp::Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Returns the UTC (year, month, day) of the timestamp.
+fn civil_date(timestamp: Timestamp) -> (u32, u32, u32) {
+    // Converts days since the unix epoch to a proleptic gregorian date in eras of 400 years.
+    let days = timestamp.timestamp_millis() / 86_400_000 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year as u32, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use concordium_std::Timestamp;
+
+    use super::{civil_date, Predicate};
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(
+            civil_date(Timestamp::from_timestamp_millis(0)),
+            (1970, 1, 1)
+        );
+        // 2024-02-29T12:00:00Z
+        assert_eq!(
+            civil_date(Timestamp::from_timestamp_millis(1_709_208_000_000)),
+            (2024, 2, 29)
+        );
+        // 2025-12-31T23:59:59Z
+        assert_eq!(
+            civil_date(Timestamp::from_timestamp_millis(1_767_225_599_000)),
+            (2025, 12, 31)
+        );
+    }
+
+    #[test]
+    fn predicates() {
+        // 2024-02-29T12:00:00Z
+        let now = Timestamp::from_timestamp_millis(1_709_208_000_000);
+        assert!(Predicate::MinimumAge(18).is_satisfied("20060229", now));
+        assert!(!Predicate::MinimumAge(18).is_satisfied("20060301", now));
+        assert!(!Predicate::MinimumAge(18).is_satisfied("2006", now));
+        assert!(Predicate::In(vec!["DE".into(), "US".into()]).is_satisfied("US", now));
+        assert!(!Predicate::NotIn(vec!["DE".into(), "US".into()]).is_satisfied("US", now));
+        assert!(Predicate::GreaterOrEqual("9".into()).is_satisfied("10", now));
+        assert!(!Predicate::LessOrEqual("9".into()).is_satisfied("10", now));
+        assert!(Predicate::Equals("US".into()).is_satisfied("US", now));
+    }
+}
@@ -1,5 +1,7 @@
-use concordium_cis2::{IsTokenId, Receiver};
-use concordium_std::{ensure, Address, ContractAddress, MetadataUrl, SchemaType, Serialize};
+use concordium_cis2::{BalanceOfQuery, IsTokenId, Receiver};
+use concordium_std::{
+    ensure, Address, ContractAddress, Duration, MetadataUrl, SchemaType, Serialize, Timestamp,
+};
 
 use crate::concordium_cis2_ext::IsTokenAmount;
 
@@ -158,12 +160,54 @@ pub struct FreezeParams<T: IsTokenId, A: IsTokenAmount> {
     pub tokens: Vec<FreezeParam<T, A>>,
 }
 
+#[derive(Serialize, SchemaType)]
+pub struct BalanceOfAtQueryParams<T: IsTokenId> {
+    /// The snapshot at which the balances are queried.
+    pub snapshot_id: u64,
+    pub queries:     Vec<BalanceOfQuery<T>>,
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct SupplyOfQueryParams<T: IsTokenId> {
+    pub tokens: Vec<T>,
+}
+
 #[derive(Serialize, SchemaType)]
 pub struct RecoverParam {
     pub lost_account: Address,
     pub new_account:  Address,
 }
 
+/// The challenge window of a recovery when the contract is initialized.
+pub const DEFAULT_RECOVERY_DELAY: Duration = Duration::from_days(7);
+
+/// A recovery of a lost account which has been initiated but not yet executed.
+/// The holder of the lost account can cancel it until it is executed.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingRecovery {
+    pub new_account:   Address,
+    /// The time from which the recovery can be executed.
+    pub execute_after: Timestamp,
+}
+
+/// An update of the recovery of lost accounts, other than initiating a recovery.
+#[derive(Serialize, SchemaType, Debug)]
+pub enum RecoveryUpdate {
+    /// Executes the pending recovery of the lost account once the recovery delay has passed.
+    Execute(Address),
+    /// Cancels the pending recovery of the lost account.
+    Cancel(Address),
+    /// Sets the recovery delay. Applies to recoveries initiated afterwards.
+    SetDelay(Duration),
+}
+
+/// The recovery delay and the pending recovery of an account.
+#[derive(Serialize, SchemaType, Debug, PartialEq, Eq)]
+pub struct Recovery {
+    pub delay:   Duration,
+    pub pending: Option<PendingRecovery>,
+}
+
 #[derive(Serialize, SchemaType)]
 pub struct Agent {
     pub address: Address,
@@ -24,6 +24,16 @@ impl PermitMessage {
         Self::serial(self, &mut out).map_err(|_| ParseError::default())?;
         Ok(bytes)
     }
+
+    /// The hash of the message signed by the `signer` wallet.
+    /// Wallets sign the message prepended with the signer's address and 8 zero bytes.
+    pub fn hash<T>(&self, signer: AccountAddress, hasher: T) -> Result<[u8; 32], ParseError>
+    where T: FnOnce(Vec<u8>) -> [u8; 32] {
+        let mut bytes: Vec<u8> = signer.0.to_vec();
+        bytes.extend_from_slice(&[0u8; 8]);
+        bytes.extend(self.bytes()?);
+        Ok(hasher(bytes))
+    }
 }
 
 #[derive(SchemaType, Serialize, Debug)]
@@ -33,6 +43,30 @@ pub struct PermitParam {
     pub message: PermitMessage,
 }
 
+/// Event logged when the nonce of an account is used by a permit.
+#[derive(SchemaType, Serialize, PartialEq, Eq, Debug)]
+pub struct NonceEvent {
+    pub account: AccountAddress,
+    pub nonce: u64,
+}
+
+#[derive(SchemaType, Serialize, Debug)]
+pub struct NonceOfQueryParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<AccountAddress>,
+}
+
+/// The next nonce of each of the queried accounts.
+#[derive(SchemaType, Serialize, Debug, PartialEq, Eq)]
+#[concordium(transparent)]
+pub struct NonceOfQueryResponse(#[concordium(size_length = 2)] pub Vec<u64>);
+
+#[derive(SchemaType, Serialize, Debug)]
+pub struct SupportsPermitQueryParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<OwnedEntrypointName>,
+}
+
 #[cfg(test)]
 mod tests {
     use concordium_std::{ContractAddress, Cursor, Deserial, OwnedEntrypointName, Timestamp};
@@ -24,6 +24,10 @@ pub enum Error {
     InvalidIssuer,
     /// Triggered when there is an error calling a contract.
     CallContractError,
+    /// Triggered when the validity window of an identity does not end after it starts.
+    InvalidValidity,
+    AddressAlreadyRegistered,
+    AddressNotLinked,
 }
 
 impl From<ParseError> for Error {
@@ -156,6 +156,12 @@ pub fn remove_agent(
 }
 
 /// Register Identity.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+/// Returns `Error::InvalidValidity` if the validity window does not end after
+/// it starts.
 #[receive(
     contract = "rwa_identity_registry",
     name = "registerIdentity",
@@ -174,14 +180,73 @@ pub fn register_identity(
         host.state().agents.contains(&ctx.sender()),
         Error::Unauthorized
     );
-    let RegisterIdentityParams { identity, address }: RegisterIdentityParams =
-        ctx.parameter_cursor().get()?;
+    let params: RegisterIdentityParams = ctx.parameter_cursor().get()?;
+    register(host, params, logger)
+}
+
+/// Register multiple identities in a single transaction. Either all the
+/// identities are registered or none of them are.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+/// Returns `Error::InvalidValidity` if the validity window of any identity
+/// does not end after it starts.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "registerIdentities",
+    mutable,
+    enable_logger,
+    parameter = "Vec<RegisterIdentityParams>",
+    error = "Error"
+)]
+pub fn register_identities(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        host.state().agents.contains(&ctx.sender()),
+        Error::Unauthorized
+    );
+    let params: Vec<RegisterIdentityParams> = ctx.parameter_cursor().get()?;
+    for params in params {
+        register(host, params, logger)?;
+    }
+
+    Ok(())
+}
+
+fn register(
+    host: &mut Host<State>,
+    RegisterIdentityParams {
+        identity,
+        address,
+        validity,
+    }: RegisterIdentityParams,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        validity.is_none_or(|v| v.valid_from < v.valid_until),
+        Error::InvalidValidity
+    );
     let (state, state_builder) = host.state_and_builder();
+    ensure!(
+        state.links.get(&address).is_none(),
+        Error::AddressAlreadyRegistered
+    );
 
-    // Register the identity and log the event.
-    let _ = state
-        .identities
-        .insert(address, IdentityState::new(identity, state_builder));
+    // Register the identity, keeping the linked addresses of a replaced identity, and log the
+    // event.
+    let mut identity_state = IdentityState::new(identity, validity, state_builder);
+    if let Some(replaced) = state.identities.remove_and_get(&address) {
+        replaced
+            .linked_addresses()
+            .into_iter()
+            .for_each(|linked| identity_state.link(linked));
+        replaced.delete();
+    }
+    let _ = state.identities.insert(address, identity_state);
     logger.log(&Event::IdentityRegistered(IdentityUpdatedEvent { address }))?;
 
     Ok(())
@@ -192,15 +257,14 @@ pub fn register_identity(
 ///
 /// This function is called to check if an address is associated with a verified
 /// identity. It retrieves the identity associated with the address from the
-/// state, and checks the status of all credentials associated with the
+/// state, checks that the current time is within the validity window of the
+/// identity and checks the status of all credentials associated with the
 /// identity. If all credentials are active, the identity is considered
 /// verified.
 ///
 /// # Errors
 ///
-/// Returns `Error::IdentityNotFound` if the identity associated with the
-/// address could not be found. Returns `Error::ParseError` if the parameters
-/// could not be parsed.
+/// Returns `Error::ParseError` if the parameters could not be parsed.
 #[receive(
     contract = "rwa_identity_registry",
     name = "isVerified",
@@ -210,13 +274,24 @@ pub fn register_identity(
 )]
 pub fn is_verified(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
     let address: Address = ctx.parameter_cursor().get()?;
+    match host.state().identity(&address) {
+        Some(identity) => {
+            Ok(verification_failure(host, &identity, ctx.metadata().slot_time())?.is_none())
+        }
+        None => Ok(false),
+    }
+}
 
-    // Check that the identity exists.
-    let identity = host.state().identities.get(&address);
-    let identity = match identity {
-        Some(identity) => identity,
-        None => return Ok(false),
-    };
+/// Returns the reason the identity is not valid at `now`, `None` if the
+/// identity is valid.
+fn verification_failure(
+    host: &Host<State>,
+    identity: &IdentityState<StateApi>,
+    now: Timestamp,
+) -> ContractResult<Option<InvalidReason>> {
+    if let Some(reason) = identity.validity_failure(now) {
+        return Ok(Some(reason));
+    }
 
     let issuers = host.state().issuers.iter().map(|i| *i);
     for issuer in issuers {
@@ -225,19 +300,141 @@ pub fn is_verified(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<b
             Some(credential_holder_id) => {
                 cis4_client::credential_status(host, &issuer, credential_holder_id)?
             }
-            None => return Ok(false),
+            None => return Ok(Some(InvalidReason::CredentialNotActive(issuer))),
         };
 
         // If the credential is not active, the identity is not verified.
         if credential_status.ne(&concordium_cis4::CredentialStatus::Active) {
-            return Ok(false);
+            return Ok(Some(InvalidReason::CredentialNotActive(issuer)));
         }
     }
 
-    Ok(true)
+    Ok(None)
+}
+
+/// Returns true if the identity of the address has all the attributes of the
+/// predicates and their values satisfy the predicates. Only the attributes are
+/// checked, `isVerified` should be used to check the validity of the identity.
+///
+/// # Errors
+///
+/// Returns `Error::ParseError` if the parameters could not be parsed.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "verifyAttributes",
+    parameter = "VerifyAttributesParams",
+    return_value = "bool",
+    error = "Error"
+)]
+pub fn verify_attributes(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
+    let params: VerifyAttributesParams = ctx.parameter_cursor().get()?;
+    let identity = match host.state().identity(&params.address) {
+        Some(identity) => identity,
+        None => return Ok(false),
+    };
+
+    let now = ctx.metadata().slot_time();
+    Ok(params
+        .predicates
+        .iter()
+        .all(|AttributePredicate { tag, predicate }| {
+            identity
+                .attribute(tag)
+                .is_some_and(|value| predicate.is_satisfied(&value, now))
+        }))
 }
 
-/// Delete multiple identities.
+/// Returns the validity window of the identity of the address, `None` if the
+/// identity does not expire.
+///
+/// # Errors
+///
+/// Returns `Error::IdentityNotFound` if the identity associated with the
+/// address could not be found.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "identityValidity",
+    parameter = "Address",
+    return_value = "Option<ValidityWindow>",
+    error = "Error"
+)]
+pub fn identity_validity(
+    ctx: &ReceiveContext,
+    host: &Host<State>,
+) -> ContractResult<Option<ValidityWindow>> {
+    let address: Address = ctx.parameter_cursor().get()?;
+    host.state()
+        .identity(&address)
+        .map(|i| i.validity())
+        .ok_or(Error::IdentityNotFound)
+}
+
+/// Re-verifies the identities of the given addresses. Logs
+/// `IdentityInvalidated` for every identity which is no longer valid since the
+/// last re-verification and `IdentityRevalidated` for every invalidated
+/// identity which is valid again. Addresses without an identity are skipped.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "reverifyIdentities",
+    mutable,
+    enable_logger,
+    parameter = "Vec<Address>",
+    error = "Error"
+)]
+pub fn reverify_identities(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        host.state().agents.contains(&ctx.sender()),
+        Error::Unauthorized
+    );
+    let addresses: Vec<Address> = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+
+    for address in addresses {
+        let (failure, is_invalidated) = match host.state().identities.get(&address) {
+            Some(identity) => (
+                verification_failure(host, &identity, now)?,
+                identity.is_invalidated(),
+            ),
+            None => continue,
+        };
+
+        match failure {
+            Some(reason) if !is_invalidated => {
+                logger.log(&Event::IdentityInvalidated(IdentityInvalidatedEvent {
+                    address,
+                    reason,
+                }))?;
+            }
+            None if is_invalidated => {
+                logger.log(&Event::IdentityRevalidated(IdentityUpdatedEvent {
+                    address,
+                }))?;
+            }
+            _ => continue,
+        }
+
+        if let Some(mut identity) = host.state_mut().identities.get_mut(&address) {
+            identity.set_invalidated(failure.is_some());
+        }
+    }
+
+    Ok(())
+}
+
+/// Delete an identity.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+/// Returns `Error::IdentityNotFound` if the address has no identity.
 #[receive(
     contract = "rwa_identity_registry",
     name = "deleteIdentity",
@@ -258,22 +455,185 @@ pub fn delete_identity(
     );
 
     let address: Address = ctx.parameter_cursor().get()?;
+    delete(host, address, logger)
+}
+
+/// Delete multiple identities in a single transaction. Either all the
+/// identities are deleted or none of them are.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+/// Returns `Error::IdentityNotFound` if any of the addresses has no identity.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "deleteIdentities",
+    mutable,
+    enable_logger,
+    parameter = "Vec<Address>",
+    error = "Error"
+)]
+pub fn delete_identities(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        host.state().agents.contains(&ctx.sender()),
+        Error::Unauthorized
+    );
+    let addresses: Vec<Address> = ctx.parameter_cursor().get()?;
+    for address in addresses {
+        delete(host, address, logger)?;
+    }
+
+    Ok(())
+}
+
+fn delete(host: &mut Host<State>, address: Address, logger: &mut Logger) -> ContractResult<()> {
     let state = host.state_mut();
+    let identity = state
+        .identities
+        .remove_and_get(&address)
+        .ok_or(Error::IdentityNotFound)?;
+    for linked in identity.linked_addresses() {
+        state.links.remove(&linked);
+        logger.log(&Event::AddressUnlinked(AddressLinkedEvent {
+            identity: address,
+            address:  linked,
+        }))?;
+    }
+    identity.delete();
 
+    logger.log(&Event::IdentityRemoved(IdentityUpdatedEvent { address }))?;
+
+    Ok(())
+}
+
+/// Links addresses (accounts or contract wallets) to a registered identity.
+/// The linked addresses resolve to the identity in `isVerified`,
+/// `verifyAttributes`, `identityValidity`, `hasIdentity` & `getIdentity`.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+/// Returns `Error::IdentityNotFound` if no identity is registered for the
+/// identity address.
+/// Returns `Error::AddressAlreadyRegistered` if any of the addresses has an
+/// identity or is already linked.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "linkAddresses",
+    mutable,
+    enable_logger,
+    parameter = "LinkAddressesParams",
+    error = "Error"
+)]
+pub fn link_addresses(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
     ensure!(
-        state
-            .identities
-            .remove_and_get(&address)
-            .map(|i| i.delete())
-            .is_some(),
+        host.state().agents.contains(&ctx.sender()),
+        Error::Unauthorized
+    );
+    let LinkAddressesParams {
+        identity,
+        addresses,
+    }: LinkAddressesParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    ensure!(
+        state.identities.get(&identity).is_some(),
         Error::IdentityNotFound
     );
 
-    logger.log(&Event::IdentityRemoved(IdentityUpdatedEvent { address }))?;
+    for address in addresses.iter() {
+        ensure!(
+            address.ne(&identity)
+                && state.identities.get(address).is_none()
+                && state.links.insert(*address, identity).is_none(),
+            Error::AddressAlreadyRegistered
+        );
+        logger.log(&Event::AddressLinked(AddressLinkedEvent {
+            identity,
+            address: *address,
+        }))?;
+    }
+    if let Some(mut identity_state) = state.identities.get_mut(&identity) {
+        addresses
+            .into_iter()
+            .for_each(|address| identity_state.link(address));
+    }
 
     Ok(())
 }
 
+/// Unlinks addresses from the identities they are linked to.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not an agent.
+/// Returns `Error::AddressNotLinked` if any of the addresses is not linked to
+/// an identity.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "unlinkAddresses",
+    mutable,
+    enable_logger,
+    parameter = "Vec<Address>",
+    error = "Error"
+)]
+pub fn unlink_addresses(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    ensure!(
+        host.state().agents.contains(&ctx.sender()),
+        Error::Unauthorized
+    );
+    let addresses: Vec<Address> = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    for address in addresses {
+        let identity = state
+            .links
+            .remove_and_get(&address)
+            .ok_or(Error::AddressNotLinked)?;
+        if let Some(mut identity_state) = state.identities.get_mut(&identity) {
+            identity_state.unlink(&address);
+        }
+        logger.log(&Event::AddressUnlinked(AddressLinkedEvent {
+            identity,
+            address,
+        }))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the addresses linked to the identity the input address resolves to.
+///
+/// # Errors
+///
+/// Returns `Error::IdentityNotFound` if the address has no identity and is not
+/// linked to one.
+#[receive(
+    contract = "rwa_identity_registry",
+    name = "linkedAddresses",
+    parameter = "Address",
+    return_value = "Vec<Address>",
+    error = "Error"
+)]
+pub fn linked_addresses(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<Vec<Address>> {
+    let address: Address = ctx.parameter_cursor().get()?;
+    host.state()
+        .identity(&address)
+        .map(|i| i.linked_addresses())
+        .ok_or(Error::IdentityNotFound)
+}
+
 /// Return true if the input address has a registered Identity.
 #[receive(
     contract = "rwa_identity_registry",
@@ -285,7 +645,7 @@ pub fn delete_identity(
 pub fn has_identity(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool> {
     let address: Address = ctx.parameter_cursor().get()?;
     let state = host.state();
-    Ok(state.identities.get(&address).is_some())
+    Ok(state.identity(&address).is_some())
 }
 
 /// Return the identity of the input address.
@@ -300,8 +660,7 @@ pub fn get_identity(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<
     let address: Address = ctx.parameter_cursor().get()?;
     let state = host.state();
     state
-        .identities
-        .get(&address)
+        .identity(&address)
         .map(|i| i.to_identity())
         .ok_or(Error::IdentityNotFound)
 }
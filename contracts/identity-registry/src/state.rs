@@ -7,6 +7,11 @@ use super::types::{AttributeTag, AttributeValue, *};
 pub struct IdentityState<S> {
     attributes:  StateMap<AttributeTag, AttributeValue, S>,
     credentials: StateMap<Issuer, CredentialId, S>,
+    validity:    Option<ValidityWindow>,
+    /// True if the last re-verification of the identity found it not valid.
+    invalidated: bool,
+    /// Addresses, other than the address of the identity, which resolve to the identity.
+    linked:      StateSet<Address, S>,
 }
 
 /// Implementation of the `IdentityState` struct.
@@ -61,10 +66,40 @@ impl<S: HasStateApi> IdentityState<S> {
     pub fn credential_id(&self, issuer: &Issuer) -> Option<CredentialId> {
         self.credentials.get(issuer).map(|i| *i)
     }
+
+    /// Retrieves the value of the attribute with the specified tag.
+    pub fn attribute(&self, tag: &AttributeTag) -> Option<AttributeValue> {
+        self.attributes.get(tag).map(|v| v.clone())
+    }
+
+    pub fn validity(&self) -> Option<ValidityWindow> { self.validity }
+
+    /// Returns the reason the identity is not valid at `now` because of its validity window.
+    pub fn validity_failure(&self, now: Timestamp) -> Option<InvalidReason> {
+        match self.validity {
+            Some(validity) if now < validity.valid_from => Some(InvalidReason::NotYetValid),
+            Some(validity) if now >= validity.valid_until => Some(InvalidReason::Expired),
+            _ => None,
+        }
+    }
+
+    pub fn is_invalidated(&self) -> bool { self.invalidated }
+
+    pub fn set_invalidated(&mut self, invalidated: bool) { self.invalidated = invalidated; }
+
+    pub fn linked_addresses(&self) -> Vec<Address> { self.linked.iter().map(|a| *a).collect() }
+
+    pub fn link(&mut self, address: Address) { self.linked.insert(address); }
+
+    pub fn unlink(&mut self, address: &Address) { self.linked.remove(address); }
 }
 
 impl<S: HasStateApi> PartialEq for IdentityState<S> {
     fn eq(&self, other: &Self) -> bool {
+        if self.validity.ne(&other.validity) {
+            return false;
+        }
+
         for (tag, val) in self.attributes.iter() {
             let is_attr_same = other
                 .attributes
@@ -92,10 +127,17 @@ impl<S: HasStateApi> PartialEq for IdentityState<S> {
 }
 
 impl<S: HasStateApi> IdentityState<S> {
-    pub fn new(identity: Identity, state_builder: &mut StateBuilder<S>) -> Self {
+    pub fn new(
+        identity: Identity,
+        validity: Option<ValidityWindow>,
+        state_builder: &mut StateBuilder<S>,
+    ) -> Self {
         let mut ret = Self {
-            attributes:  state_builder.new_map(),
+            attributes: state_builder.new_map(),
             credentials: state_builder.new_map(),
+            validity,
+            invalidated: false,
+            linked: state_builder.new_set(),
         };
 
         for IdentityAttribute { tag, value } in identity.attributes {
@@ -114,6 +156,8 @@ impl<S: HasStateApi> IdentityState<S> {
 #[concordium(state_parameter = "S")]
 pub struct State<S=StateApi> {
     pub identities: StateMap<Address, IdentityState<S>, S>,
+    /// Linked addresses and the address of the identity they resolve to.
+    pub links:      StateMap<Address, Address, S>,
     pub issuers:    StateSet<Issuer, S>,
     pub agents:     StateSet<Address, S>,
 }
@@ -123,6 +167,7 @@ impl<S: HasStateApi> State<S> {
     pub fn new(agents: Vec<Address>, state_builder: &mut StateBuilder<S>) -> Self {
         let mut state = State {
             identities: state_builder.new_map(),
+            links:      state_builder.new_map(),
             issuers:    state_builder.new_set(),
             agents:     state_builder.new_set(),
         };
@@ -133,4 +178,20 @@ impl<S: HasStateApi> State<S> {
 
         state
     }
+
+    /// Returns the address of the identity the address resolves to. This is the address itself
+    /// unless it is linked to an identity.
+    pub fn identity_address(&self, address: &Address) -> Address {
+        self.links.get(address).map(|a| *a).unwrap_or(*address)
+    }
+
+    /// Returns the identity registered for the address or the identity the address is linked to.
+    pub fn identity(&self, address: &Address) -> Option<StateRef<'_, IdentityState<S>>> {
+        self.identities.get(&self.identity_address(address))
+    }
+
+    /// Returns true if the address has a registered identity or is linked to one.
+    pub fn is_registered(&self, address: &Address) -> bool {
+        self.identities.get(address).is_some() || self.links.get(address).is_some()
+    }
 }
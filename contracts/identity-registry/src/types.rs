@@ -5,8 +5,10 @@ pub type AttributeTag = concordium_cis2_security::AttributeTag;
 pub type AttributeValue = concordium_cis2_security::AttributeValue;
 pub type Identity = concordium_cis2_security::Identity;
 pub type Issuer = concordium_cis2_security::Issuer;
-pub use concordium_cis2_security::{IdentityAttribute, IdentityCredential};
-use concordium_std::{Address, SchemaType, Serialize};
+pub use concordium_cis2_security::{
+    AttributePredicate, IdentityAttribute, IdentityCredential, Predicate, VerifyAttributesParams,
+};
+use concordium_std::{Address, SchemaType, Serialize, Timestamp};
 pub type CredentialId = concordium_std::PublicKeyEd25519;
 /// Represents an event that is triggered when an identity is updated.
 #[derive(Serialize, SchemaType, Debug)]
@@ -32,6 +34,36 @@ pub struct AgentUpdatedEvent {
     pub agent: Address,
 }
 
+/// The reason an identity is not valid.
+#[derive(Serialize, SchemaType, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// The validity window of the identity has not started yet.
+    NotYetValid,
+    /// The validity window of the identity has ended.
+    Expired,
+    /// The credential of the identity from the issuer is missing or not active.
+    CredentialNotActive(Issuer),
+}
+
+/// Represents an event that is triggered when an identity is invalidated.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct IdentityInvalidatedEvent {
+    /// The address associated with the identity.
+    pub address: Address,
+    /// The reason the identity is not valid.
+    pub reason:  InvalidReason,
+}
+
+/// Represents an event that is triggered when an address is linked to / unlinked from an
+/// identity.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct AddressLinkedEvent {
+    /// The address with which the identity was registered.
+    pub identity: Address,
+    /// The address linked to / unlinked from the identity.
+    pub address:  Address,
+}
+
 /// Represents the different types of events that can be triggered in the
 /// contract.
 #[derive(Serialize, SchemaType, Debug)]
@@ -49,10 +81,36 @@ pub enum Event {
     AgentAdded(AgentUpdatedEvent),
     /// Triggered when an agent is removed.
     AgentRemoved(AgentUpdatedEvent),
+    /// Triggered when a re-verification finds that a verified identity is no longer valid.
+    IdentityInvalidated(IdentityInvalidatedEvent),
+    /// Triggered when a re-verification finds that an invalidated identity is valid again.
+    IdentityRevalidated(IdentityUpdatedEvent),
+    /// Triggered when an address is linked to an identity.
+    AddressLinked(AddressLinkedEvent),
+    /// Triggered when an address is unlinked from an identity.
+    AddressUnlinked(AddressLinkedEvent),
+}
+/// The period of time in which an identity is valid. The start is inclusive & the end is exclusive.
+#[derive(Serialize, SchemaType, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidityWindow {
+    pub valid_from:  Timestamp,
+    pub valid_until: Timestamp,
 }
+
 /// Parameters for registering an identity.
 #[derive(Serialize, SchemaType)]
 pub struct RegisterIdentityParams {
     pub identity: Identity,
     pub address:  Address,
+    /// The period of time in which the identity is valid. `None` if the identity does not expire.
+    pub validity: Option<ValidityWindow>,
+}
+
+/// Parameters for linking addresses to an identity.
+#[derive(Serialize, SchemaType)]
+pub struct LinkAddressesParams {
+    /// The address with which the identity was registered.
+    pub identity:  Address,
+    /// The addresses (accounts or contract wallets) which resolve to the identity.
+    pub addresses: Vec<Address>,
 }
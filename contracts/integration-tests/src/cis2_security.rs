@@ -6,7 +6,12 @@ use concordium_cis2::{
 };
 use concordium_protocols::concordium_cis2_ext::IsTokenAmount;
 use concordium_protocols::concordium_cis2_security::{
-    AddTokenParams, Agent, AgentWithRoles, BurnParams, FreezeParams, MintParams, PauseParams,
+    AddTokenParams, Agent, AgentWithRoles, BalanceOfAtQueryParams, BurnParams, Document,
+    DocumentQuery, DocumentUpdate, FreezeParams, Governance, MintParams, PauseParams, Proposal,
+    ProposalUpdate, ProposeParams, RecoverParam, Recovery, RecoveryUpdate, SetDocumentParams,
+};
+use concordium_protocols::concordium_cis3::{
+    NonceOfQueryParams, NonceOfQueryResponse, PermitParam,
 };
 use concordium_smart_contract_testing::*;
 use concordium_std::{Deserial, ParseError, Serial};
@@ -355,6 +360,149 @@ pub trait Cis2SecurityPayloads<I: Serial, R: Serial, T: IsTokenId, A: IsTokenAmo
             message:      OwnedParameter::from_serial(payload).unwrap(),
         }
     }
+    fn permit_payload(&self, payload: &PermitParam) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("permit"),
+            ),
+            message:      OwnedParameter::from_serial(payload).unwrap(),
+        }
+    }
+    fn nonce_of_payload(&self, payload: &NonceOfQueryParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("nonceOf"),
+            ),
+            message:      OwnedParameter::from_serial(payload).unwrap(),
+        }
+    }
+    fn snapshot_payload(&self) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("snapshot"),
+            ),
+            message:      OwnedParameter::empty(),
+        }
+    }
+    fn balance_of_at_payload(&self, payload: &BalanceOfAtQueryParams<T>) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("balanceOfAt"),
+            ),
+            message:      OwnedParameter::from_serial(payload).unwrap(),
+        }
+    }
+    fn set_governance_payload(&self, governance: &Option<Governance>) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("setGovernance"),
+            ),
+            message:      OwnedParameter::from_serial(governance).unwrap(),
+        }
+    }
+    fn propose_payload(&self, payload: &ProposeParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("propose"),
+            ),
+            message:      OwnedParameter::from_serial(payload).unwrap(),
+        }
+    }
+    fn update_proposal_payload(&self, update: &ProposalUpdate) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("updateProposal"),
+            ),
+            message:      OwnedParameter::from_serial(update).unwrap(),
+        }
+    }
+    fn proposal_payload(&self, proposal_id: &u64) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("proposal"),
+            ),
+            message:      OwnedParameter::from_serial(proposal_id).unwrap(),
+        }
+    }
+    fn recover_payload(&self, params: &RecoverParam) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("recover"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+    fn update_recovery_payload(&self, update: &RecoveryUpdate) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("updateRecovery"),
+            ),
+            message:      OwnedParameter::from_serial(update).unwrap(),
+        }
+    }
+    fn recovery_payload(&self, lost_account: &Address) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("recovery"),
+            ),
+            message:      OwnedParameter::from_serial(lost_account).unwrap(),
+        }
+    }
+    fn update_document_payload(&self, update: &DocumentUpdate<T>) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("updateDocument"),
+            ),
+            message:      OwnedParameter::from_serial(update).unwrap(),
+        }
+    }
+    fn documents_payload(&self, token_id: &T) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("documents"),
+            ),
+            message:      OwnedParameter::from_serial(token_id).unwrap(),
+        }
+    }
 }
 
 pub trait Cis2SecurityResponses {
@@ -366,6 +514,7 @@ pub trait Cis2SecurityResponses {
     ) -> Result<BalanceOfQueryResponse<A>, ParseError>;
     fn balance_of<A: IsTokenAmount>(&self) -> Result<BalanceOfQueryResponse<A>, ParseError>;
     fn balance_of_single<A: IsTokenAmount>(&self) -> Result<A, ParseError>;
+    fn nonce_of(&self) -> Result<NonceOfQueryResponse, ParseError>;
 }
 impl Cis2SecurityResponses for ContractInvokeSuccess {
     fn agents<R: Deserial>(&self) -> Result<Vec<AgentWithRoles<R>>, ParseError> {
@@ -395,6 +544,8 @@ impl Cis2SecurityResponses for ContractInvokeSuccess {
             .next()
             .ok_or({ ParseError {} })
     }
+
+    fn nonce_of(&self) -> Result<NonceOfQueryResponse, ParseError> { self.parse_return_value() }
 }
 pub trait Cis2SecurityTestClient<I: Serial, R: Serial+Deserial, T: IsTokenId, A: IsTokenAmount>:
     Cis2SecurityPayloads<I, R, T, A> {
@@ -644,4 +795,308 @@ pub trait Cis2SecurityTestClient<I: Serial, R: Serial+Deserial, T: IsTokenId, A:
             .balance_of_un_frozen()
             .map_err(|_| ContractInvokeErrorOrParseError::ParseError)
     }
+
+    fn permit(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        payload: &PermitParam,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.permit_payload(payload),
+        )
+    }
+
+    fn nonce_of(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        payload: &NonceOfQueryParams,
+    ) -> Result<NonceOfQueryResponse, ContractInvokeErrorOrParseError> {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.nonce_of_payload(payload),
+            )
+            .map_err(ContractInvokeErrorOrParseError::ContractInvokeError)?
+            .nonce_of()
+            .map_err(|_| ContractInvokeErrorOrParseError::ParseError)
+    }
+
+    fn snapshot(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.snapshot_payload(),
+        )
+    }
+
+    fn balance_of_at(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        payload: &BalanceOfAtQueryParams<T>,
+    ) -> Result<BalanceOfQueryResponse<A>, ContractInvokeErrorOrParseError> {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.balance_of_at_payload(payload),
+            )
+            .map_err(ContractInvokeErrorOrParseError::ContractInvokeError)?
+            .balance_of()
+            .map_err(|_| ContractInvokeErrorOrParseError::ParseError)
+    }
+
+    fn set_governance(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        governance: &Option<Governance>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.set_governance_payload(governance),
+        )
+    }
+
+    fn propose(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        payload: &ProposeParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.propose_payload(payload),
+        )
+    }
+
+    fn approve_proposal(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        proposal_id: u64,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_proposal_payload(&ProposalUpdate::Approve(proposal_id)),
+        )
+    }
+
+    fn execute_proposal(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        proposal_id: u64,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_proposal_payload(&ProposalUpdate::Execute(proposal_id)),
+        )
+    }
+
+    fn cancel_proposal(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        proposal_id: u64,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_proposal_payload(&ProposalUpdate::Cancel(proposal_id)),
+        )
+    }
+
+    fn proposal(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        proposal_id: u64,
+    ) -> Result<Proposal, ContractInvokeErrorOrParseError> {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.proposal_payload(&proposal_id),
+            )
+            .map_err(ContractInvokeErrorOrParseError::ContractInvokeError)?
+            .parse_return_value()
+            .map_err(|_| ContractInvokeErrorOrParseError::ParseError)
+    }
+
+    fn recover(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &RecoverParam,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.recover_payload(params),
+        )
+    }
+
+    fn execute_recovery(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        lost_account: &Address,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_recovery_payload(&RecoveryUpdate::Execute(*lost_account)),
+        )
+    }
+
+    fn cancel_recovery(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        lost_account: &Address,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_recovery_payload(&RecoveryUpdate::Cancel(*lost_account)),
+        )
+    }
+
+    fn set_recovery_delay(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        recovery_delay: &Duration,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_recovery_payload(&RecoveryUpdate::SetDelay(*recovery_delay)),
+        )
+    }
+
+    fn recovery(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        lost_account: &Address,
+    ) -> Result<Recovery, ContractInvokeErrorOrParseError> {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.recovery_payload(lost_account),
+            )
+            .map_err(ContractInvokeErrorOrParseError::ContractInvokeError)?
+            .parse_return_value()
+            .map_err(|_| ContractInvokeErrorOrParseError::ParseError)
+    }
+
+    fn set_document(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &SetDocumentParams<T>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError>
+    where
+        T: Clone,
+    {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_document_payload(&DocumentUpdate::Set(params.clone())),
+        )
+    }
+
+    fn remove_document(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        query: &DocumentQuery<T>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError>
+    where
+        T: Clone,
+    {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_document_payload(&DocumentUpdate::Remove(query.clone())),
+        )
+    }
+
+    fn document(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        query: &DocumentQuery<T>,
+    ) -> Result<Option<Document>, ContractInvokeErrorOrParseError> {
+        let document = self
+            .documents(chain, sender, &query.token_id)?
+            .into_iter()
+            .find(|(name, _)| name.eq(&query.name))
+            .map(|(_, document)| document);
+        Ok(document)
+    }
+
+    fn documents(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        token_id: &T,
+    ) -> Result<Vec<(String, Document)>, ContractInvokeErrorOrParseError> {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.documents_payload(token_id),
+            )
+            .map_err(ContractInvokeErrorOrParseError::ContractInvokeError)?
+            .parse_return_value()
+            .map_err(|_| ContractInvokeErrorOrParseError::ParseError)
+    }
 }
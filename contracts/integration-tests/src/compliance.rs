@@ -4,8 +4,15 @@ use concordium_smart_contract_testing::*;
 use super::MAX_ENERGY;
 use crate::contract_base::{ContractPayloads, ContractTestClient};
 const MODULE_BYTES: &[u8] = include_bytes!("../../compliance/contract.wasm.v1");
+const HOLDING_PERIOD_MODULE_BYTES: &[u8] =
+    include_bytes!("../../compliance-holding-period/contract.wasm.v1");
 pub const CONTRACT_NAME_COMPLIANCE: &str = "init_rwa_compliance";
 pub const CONTRACT_NAME_NATIONALITIES: &str = "init_rwa_compliance_module_allowed_nationalities";
+pub const CONTRACT_NAME_MAX_HOLDERS: &str = "init_rwa_compliance_module_max_holders";
+pub const CONTRACT_NAME_MAX_BALANCE: &str = "init_rwa_compliance_module_max_balance";
+pub const CONTRACT_NAME_TRANSFER_VOLUME: &str = "init_rwa_compliance_module_transfer_volume";
+pub const CONTRACT_NAME_JURISDICTIONS: &str = "init_rwa_compliance_module_jurisdictions";
+pub const CONTRACT_NAME_HOLDING_PERIOD: &str = "init_rwa_compliance_module_holding_period";
 pub fn deploy_module(chain: &mut Chain, sender: &Account) -> ModuleDeploySuccess {
     let module = WasmModule::from_slice(MODULE_BYTES).unwrap();
     chain
@@ -13,6 +20,13 @@ pub fn deploy_module(chain: &mut Chain, sender: &Account) -> ModuleDeploySuccess
         .expect("deploying module")
 }
 
+pub fn deploy_holding_period_module(chain: &mut Chain, sender: &Account) -> ModuleDeploySuccess {
+    let module = WasmModule::from_slice(HOLDING_PERIOD_MODULE_BYTES).unwrap();
+    chain
+        .module_deploy_v1(Signer::with_one_key(), sender.address, module)
+        .expect("deploying holding period module")
+}
+
 pub struct ComplianceTestClient(pub ContractAddress);
 impl ContractPayloads<concordium_rwa_compliance::compliance::types::InitParams>
     for ComplianceTestClient
@@ -30,6 +44,34 @@ impl ContractTestClient<concordium_rwa_compliance::compliance::types::InitParams
 {
     fn new(contract_address: ContractAddress) -> Self { Self(contract_address) }
 }
+impl ComplianceTestClient {
+    pub fn add_module_payload(&self, module: ContractAddress) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("addModule"),
+            ),
+            message:      OwnedParameter::from_serial(&module).unwrap(),
+        }
+    }
+
+    pub fn add_module(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        module: ContractAddress,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.add_module_payload(module),
+        )
+    }
+}
 
 pub fn init(
     chain: &mut Chain,
@@ -106,3 +148,167 @@ pub fn init_nationalities(
 
     Ok((init, module_ref, contract_name))
 }
+
+pub struct MaxHoldersModuleTestClient(pub ContractAddress);
+impl ContractPayloads<concordium_rwa_compliance::compliance_modules::max_holders::types::InitParams>
+    for MaxHoldersModuleTestClient
+{
+    fn module() -> WasmModule { WasmModule::from_slice(MODULE_BYTES).unwrap() }
+
+    fn contract_name() -> OwnedContractName {
+        OwnedContractName::new_unchecked(CONTRACT_NAME_MAX_HOLDERS.to_string())
+    }
+
+    fn contract_address(&self) -> ContractAddress { self.0 }
+}
+impl
+    ContractTestClient<
+        concordium_rwa_compliance::compliance_modules::max_holders::types::InitParams,
+    > for MaxHoldersModuleTestClient
+{
+    fn new(contract_address: ContractAddress) -> Self { Self(contract_address) }
+}
+
+pub struct MaxBalanceModuleTestClient(pub ContractAddress);
+impl ContractPayloads<concordium_rwa_compliance::compliance_modules::max_balance::types::InitParams>
+    for MaxBalanceModuleTestClient
+{
+    fn module() -> WasmModule { WasmModule::from_slice(MODULE_BYTES).unwrap() }
+
+    fn contract_name() -> OwnedContractName {
+        OwnedContractName::new_unchecked(CONTRACT_NAME_MAX_BALANCE.to_string())
+    }
+
+    fn contract_address(&self) -> ContractAddress { self.0 }
+}
+impl
+    ContractTestClient<
+        concordium_rwa_compliance::compliance_modules::max_balance::types::InitParams,
+    > for MaxBalanceModuleTestClient
+{
+    fn new(contract_address: ContractAddress) -> Self { Self(contract_address) }
+}
+
+pub struct TransferVolumeModuleTestClient(pub ContractAddress);
+impl
+    ContractPayloads<
+        concordium_rwa_compliance::compliance_modules::transfer_volume::types::InitParams,
+    > for TransferVolumeModuleTestClient
+{
+    fn module() -> WasmModule { WasmModule::from_slice(MODULE_BYTES).unwrap() }
+
+    fn contract_name() -> OwnedContractName {
+        OwnedContractName::new_unchecked(CONTRACT_NAME_TRANSFER_VOLUME.to_string())
+    }
+
+    fn contract_address(&self) -> ContractAddress { self.0 }
+}
+impl
+    ContractTestClient<
+        concordium_rwa_compliance::compliance_modules::transfer_volume::types::InitParams,
+    > for TransferVolumeModuleTestClient
+{
+    fn new(contract_address: ContractAddress) -> Self { Self(contract_address) }
+}
+
+pub struct JurisdictionsModuleTestClient(pub ContractAddress);
+impl
+    ContractPayloads<
+        concordium_rwa_compliance::compliance_modules::jurisdictions::types::InitParams,
+    > for JurisdictionsModuleTestClient
+{
+    fn module() -> WasmModule { WasmModule::from_slice(MODULE_BYTES).unwrap() }
+
+    fn contract_name() -> OwnedContractName {
+        OwnedContractName::new_unchecked(CONTRACT_NAME_JURISDICTIONS.to_string())
+    }
+
+    fn contract_address(&self) -> ContractAddress { self.0 }
+}
+impl
+    ContractTestClient<
+        concordium_rwa_compliance::compliance_modules::jurisdictions::types::InitParams,
+    > for JurisdictionsModuleTestClient
+{
+    fn new(contract_address: ContractAddress) -> Self { Self(contract_address) }
+}
+impl JurisdictionsModuleTestClient {
+    pub fn update_list_payload(
+        &self,
+        params: &concordium_rwa_compliance::compliance_modules::jurisdictions::types::UpdateListParams,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("updateList"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn update_list(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &concordium_rwa_compliance::compliance_modules::jurisdictions::types::UpdateListParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_list_payload(params),
+        )
+    }
+}
+
+pub struct HoldingPeriodModuleTestClient(pub ContractAddress);
+impl ContractPayloads<concordium_rwa_compliance_holding_period::types::InitParams>
+    for HoldingPeriodModuleTestClient
+{
+    fn module() -> WasmModule { WasmModule::from_slice(HOLDING_PERIOD_MODULE_BYTES).unwrap() }
+
+    fn contract_name() -> OwnedContractName {
+        OwnedContractName::new_unchecked(CONTRACT_NAME_HOLDING_PERIOD.to_string())
+    }
+
+    fn contract_address(&self) -> ContractAddress { self.0 }
+}
+impl ContractTestClient<concordium_rwa_compliance_holding_period::types::InitParams>
+    for HoldingPeriodModuleTestClient
+{
+    fn new(contract_address: ContractAddress) -> Self { Self(contract_address) }
+}
+impl HoldingPeriodModuleTestClient {
+    pub fn set_trading_windows_payload(
+        &self,
+        params: &Vec<concordium_rwa_compliance_holding_period::types::TradingWindow>,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("setTradingWindows"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn set_trading_windows(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &Vec<concordium_rwa_compliance_holding_period::types::TradingWindow>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.set_trading_windows_payload(params),
+        )
+    }
+}
@@ -2,7 +2,8 @@
 
 use concordium_base::smart_contracts::WasmModule;
 use concordium_rwa_identity_registry::types::{
-    Identity, IdentityAttribute, RegisterIdentityParams,
+    Identity, IdentityAttribute, LinkAddressesParams, RegisterIdentityParams,
+    VerifyAttributesParams,
 };
 use concordium_smart_contract_testing::{
     module_load_v1, Account, Chain, ContractEvent, ContractInitError, ContractInitSuccess,
@@ -46,6 +47,97 @@ pub trait IdentityRegistryPayloads: ContractPayloads<()> {
             message:      OwnedParameter::from_serial(params).unwrap(),
         }
     }
+    fn register_identities_payload(
+        &self,
+        params: &Vec<RegisterIdentityParams>,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("registerIdentities"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+    fn delete_identities_payload(&self, addresses: &Vec<Address>) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("deleteIdentities"),
+            ),
+            message:      OwnedParameter::from_serial(addresses).unwrap(),
+        }
+    }
+    fn link_addresses_payload(&self, params: &LinkAddressesParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("linkAddresses"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+    fn unlink_addresses_payload(&self, addresses: &Vec<Address>) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("unlinkAddresses"),
+            ),
+            message:      OwnedParameter::from_serial(addresses).unwrap(),
+        }
+    }
+    fn linked_addresses_payload(&self, address: &Address) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("linkedAddresses"),
+            ),
+            message:      OwnedParameter::from_serial(address).unwrap(),
+        }
+    }
+    fn is_verified_payload(&self, address: &Address) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("isVerified"),
+            ),
+            message:      OwnedParameter::from_serial(address).unwrap(),
+        }
+    }
+    fn verify_attributes_payload(&self, params: &VerifyAttributesParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("verifyAttributes"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+    fn reverify_identities_payload(&self, addresses: &Vec<Address>) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("reverifyIdentities"),
+            ),
+            message:      OwnedParameter::from_serial(addresses).unwrap(),
+        }
+    }
 }
 #[derive(Clone, Copy)]
 pub struct IdentityRegistryTestClient(pub ContractAddress);
@@ -90,6 +182,128 @@ impl IdentityRegistryTestClient {
             self.register_identity_payload(params),
         )
     }
+
+    pub fn register_identities(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &Vec<RegisterIdentityParams>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            SIGNER,
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.register_identities_payload(params),
+        )
+    }
+
+    pub fn delete_identities(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        addresses: &Vec<Address>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            SIGNER,
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.delete_identities_payload(addresses),
+        )
+    }
+
+    pub fn link_addresses(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &LinkAddressesParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            SIGNER,
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.link_addresses_payload(params),
+        )
+    }
+
+    pub fn unlink_addresses(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        addresses: &Vec<Address>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            SIGNER,
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.unlink_addresses_payload(addresses),
+        )
+    }
+
+    pub fn linked_addresses(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        address: &Address,
+    ) -> Result<Vec<Address>, ContractInvokeError> {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.linked_addresses_payload(address),
+            )
+            .map(|res| res.parse_return_value().expect("parse linked addresses"))
+    }
+
+    pub fn is_verified(&self, chain: &Chain, sender: &Account, address: &Address) -> bool {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.is_verified_payload(address),
+            )
+            .expect("is verified")
+            .parse_return_value()
+            .expect("parse is verified")
+    }
+
+    pub fn verify_attributes(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        params: &VerifyAttributesParams,
+    ) -> bool {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.verify_attributes_payload(params),
+            )
+            .expect("verify attributes")
+            .parse_return_value()
+            .expect("parse verify attributes")
+    }
+
+    pub fn reverify_identities(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        addresses: &Vec<Address>,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            SIGNER,
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.reverify_identities_payload(addresses),
+        )
+    }
 }
 impl IdentityRegistryPayloads for IdentityRegistryTestClient {}
 
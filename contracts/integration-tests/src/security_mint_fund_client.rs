@@ -3,8 +3,9 @@
 use concordium_base::smart_contracts::WasmModule;
 use concordium_smart_contract_testing::*;
 use security_mint_fund::types::{
-    AddFundParams, ClaimInvestmentParams, FundState, InitParam, SecurityTokenUId,
-    TransferInvestParams, UpdateFundStateParams,
+    AddFundParams, ClaimInvestmentParams, FundState, InitParam, ReleaseVestedParams,
+    SecurityTokenUId, TransferInvestParams, UpdateFundAllowlistParams, UpdateFundStateParams,
+    WithdrawInvestmentParams,
 };
 
 use super::MAX_ENERGY;
@@ -40,6 +41,48 @@ impl MintFundTestClient {
         }
     }
 
+    pub fn withdraw_investment_payload(
+        &self,
+        params: &WithdrawInvestmentParams,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("withdrawInvestment"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn release_vested_payload(&self, params: &ReleaseVestedParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("releaseVested"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn update_fund_allowlist_payload(
+        &self,
+        params: &UpdateFundAllowlistParams,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("updateFundAllowlist"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
     pub fn update_fund_state_payload(
         &self,
         params: &UpdateFundStateParams,
@@ -82,6 +125,18 @@ impl MintFundTestClient {
         }
     }
 
+    pub fn finalize_fund_payload(&self, fund_id: SecurityTokenUId) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("finalizeFund"),
+            ),
+            message:      OwnedParameter::from_serial(&fund_id).unwrap(),
+        }
+    }
+
     pub fn remove_fund_payload(&self, fund_id: SecurityTokenUId) -> UpdateContractPayload {
         UpdateContractPayload {
             address:      self.0,
@@ -111,6 +166,51 @@ impl MintFundTestClient {
         )
     }
 
+    pub fn withdraw_investment(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &WithdrawInvestmentParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.withdraw_investment_payload(params),
+        )
+    }
+
+    pub fn release_vested(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &ReleaseVestedParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.release_vested_payload(params),
+        )
+    }
+
+    pub fn update_fund_allowlist(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &UpdateFundAllowlistParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.update_fund_allowlist_payload(params),
+        )
+    }
+
     pub fn update_fund_state(
         &self,
         chain: &mut Chain,
@@ -156,6 +256,21 @@ impl MintFundTestClient {
         )
     }
 
+    pub fn finalize_fund(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        fund_id: SecurityTokenUId,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.finalize_fund_payload(fund_id),
+        )
+    }
+
     pub fn remove_fund(
         &self,
         chain: &mut Chain,
@@ -5,7 +5,8 @@ use concordium_protocols::concordium_cis2_security::AgentWithRoles;
 use concordium_smart_contract_testing::*;
 use concordium_std::{ContractName, ParseError};
 use security_p2p_trading::{
-    AddMarketParams, AgentRole, ExchangeParams, InitParam, Market, MintParams, SecurityTokenAddress,
+    AddMarketParams, AgentRole, ExchangeParams, FillOrderParams, InitParam, Market, MintParams,
+    OrderId, PlaceOrderParams, WithdrawLiquidityParams,
 };
 
 use super::MAX_ENERGY;
@@ -71,7 +72,7 @@ pub trait P2PTradingClientPayloads: ContractPayloads<InitParam> {
         }
     }
 
-    fn remove_market_payload(&self, params: &SecurityTokenAddress) -> UpdateContractPayload {
+    fn remove_market_payload(&self, params: &ContractAddress) -> UpdateContractPayload {
         UpdateContractPayload {
             address:      self.contract_address(),
             amount:       Amount::zero(),
@@ -130,6 +131,81 @@ pub trait P2PTradingClientPayloads: ContractPayloads<InitParam> {
             message:      OwnedParameter::from_serial(params).unwrap(),
         }
     }
+
+    fn place_order_payload(&self, params: &PlaceOrderParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("placeOrder"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    fn cancel_order_payload(&self, params: &OrderId) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("cancelOrder"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    fn fill_order_payload(&self, params: &FillOrderParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("fillOrder"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    fn refund_order_payload(&self, params: &OrderId) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("refundOrder"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    fn withdraw_liquidity_payload(
+        &self,
+        params: &WithdrawLiquidityParams,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("withdrawLiquidity"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    fn get_order_payload(&self, params: &OrderId) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("getOrder"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
 }
 
 impl P2PTradeTestClient {
@@ -182,7 +258,7 @@ impl P2PTradeTestClient {
         &self,
         chain: &mut Chain,
         sender: &Account,
-        params: &SecurityTokenAddress,
+        params: &ContractAddress,
     ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
         chain.contract_update(
             Signer::with_one_key(),
@@ -251,4 +327,93 @@ impl P2PTradeTestClient {
             self.mint_payload(params),
         )
     }
+
+    pub fn place_order(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &PlaceOrderParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.place_order_payload(params),
+        )
+    }
+
+    pub fn cancel_order(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &OrderId,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.cancel_order_payload(params),
+        )
+    }
+
+    pub fn refund_order(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &OrderId,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.refund_order_payload(params),
+        )
+    }
+
+    pub fn fill_order(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &FillOrderParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.fill_order_payload(params),
+        )
+    }
+
+    pub fn withdraw_liquidity(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &WithdrawLiquidityParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.withdraw_liquidity_payload(params),
+        )
+    }
+
+    pub fn get_order(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &OrderId,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_invoke(
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.get_order_payload(params),
+        )
+    }
 }
@@ -31,6 +31,123 @@ impl Cis2TestClient<InitParam, TokenId, TokenAmount> for SftMultiTestClient {}
 impl Cis2SecurityPayloads<InitParam, AgentRole, TokenId, TokenAmount> for SftMultiTestClient {}
 impl Cis2SecurityTestClient<InitParam, AgentRole, TokenId, TokenAmount> for SftMultiTestClient {}
 
+impl SftMultiTestClient {
+    pub fn create_corporate_action_payload(
+        &self,
+        action: &CorporateAction,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("createCorporateAction"),
+            ),
+            message:      OwnedParameter::from_serial(action).unwrap(),
+        }
+    }
+
+    pub fn apply_corporate_action_payload(
+        &self,
+        params: &ApplyCorporateActionParams,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("applyCorporateAction"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn complete_corporate_action_payload(&self, action_id: &u64) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("completeCorporateAction"),
+            ),
+            message:      OwnedParameter::from_serial(action_id).unwrap(),
+        }
+    }
+
+    pub fn corporate_action_payload(&self, action_id: &u64) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.contract_address(),
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                Self::contract_name().as_contract_name(),
+                EntrypointName::new_unchecked("corporateAction"),
+            ),
+            message:      OwnedParameter::from_serial(action_id).unwrap(),
+        }
+    }
+
+    pub fn create_corporate_action(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        action: &CorporateAction,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.create_corporate_action_payload(action),
+        )
+    }
+
+    pub fn apply_corporate_action(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &ApplyCorporateActionParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.apply_corporate_action_payload(params),
+        )
+    }
+
+    pub fn complete_corporate_action(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        action_id: u64,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.complete_corporate_action_payload(&action_id),
+        )
+    }
+
+    pub fn corporate_action(
+        &self,
+        chain: &Chain,
+        sender: &Account,
+        action_id: u64,
+    ) -> Result<CorporateAction, ContractInvokeError> {
+        chain
+            .contract_invoke(
+                sender.address,
+                sender.address.into(),
+                MAX_ENERGY,
+                self.corporate_action_payload(&action_id),
+            )
+            .map(|res| res.parse_return_value().expect("parse corporate action"))
+    }
+}
+
 pub fn deploy_module(chain: &mut Chain, sender: &Account) -> ModuleDeploySuccess {
     let module = WasmModule::from_slice(MODULE_BYTES).unwrap();
     chain
@@ -3,7 +3,8 @@ use concordium_protocols::concordium_cis2_security::AgentWithRoles;
 use concordium_smart_contract_testing::*;
 use concordium_std::ContractName;
 use security_sft_multi_yielder::{
-    AgentRole, InitParam, RemoveYieldParams, UpsertYieldParams, YieldParams,
+    AgentRole, ClaimParams, ClaimYieldsBatchParams, InitParam, RemoveYieldParams, ReturnPoolParams,
+    SetClaimModeParams, SetPoolTokenParams, UpsertYieldParams, YieldForBatchParams, YieldParams,
 };
 
 use crate::contract_base::{ContractPayloads, ContractTestClient};
@@ -64,6 +65,81 @@ impl SftMultiYielderTestClient {
         }
     }
 
+    pub fn return_pool_payload(&self, params: &ReturnPoolParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("returnPool"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn set_claim_mode_payload(&self, params: &SetClaimModeParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("setClaimMode"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn set_pool_token_payload(&self, params: &SetPoolTokenParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("setPoolToken"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn claim_yields_payload(&self, params: &ClaimParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("claimYields"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn yield_for_batch_payload(&self, params: &YieldForBatchParams) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("yieldForBatch"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
+    pub fn claim_yields_batch_payload(
+        &self,
+        params: &ClaimYieldsBatchParams,
+    ) -> UpdateContractPayload {
+        UpdateContractPayload {
+            address:      self.0,
+            amount:       Amount::zero(),
+            receive_name: OwnedReceiveName::construct_unchecked(
+                CONTRACT_NAME,
+                EntrypointName::new_unchecked("claimYieldsBatch"),
+            ),
+            message:      OwnedParameter::from_serial(params).unwrap(),
+        }
+    }
+
     pub fn add_agent_payload(&self, params: &AgentWithRoles<AgentRole>) -> UpdateContractPayload {
         UpdateContractPayload {
             address:      self.0,
@@ -135,6 +211,96 @@ impl SftMultiYielderTestClient {
         )
     }
 
+    pub fn return_pool(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &ReturnPoolParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.return_pool_payload(params),
+        )
+    }
+
+    pub fn set_claim_mode(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &SetClaimModeParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.set_claim_mode_payload(params),
+        )
+    }
+
+    pub fn set_pool_token(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &SetPoolTokenParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.set_pool_token_payload(params),
+        )
+    }
+
+    pub fn claim_yields(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &ClaimParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.claim_yields_payload(params),
+        )
+    }
+
+    pub fn yield_for_batch(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &YieldForBatchParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.yield_for_batch_payload(params),
+        )
+    }
+
+    pub fn claim_yields_batch(
+        &self,
+        chain: &mut Chain,
+        sender: &Account,
+        params: &ClaimYieldsBatchParams,
+    ) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender.address,
+            sender.address.into(),
+            MAX_ENERGY,
+            self.claim_yields_batch_payload(params),
+        )
+    }
+
     pub fn add_agent(
         &self,
         chain: &mut Chain,
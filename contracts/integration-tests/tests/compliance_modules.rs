@@ -0,0 +1,467 @@
+#![cfg(test)]
+
+use cis2_security::{Cis2SecurityTestClient, Cis2TestClient};
+use compliance::{
+    init_nationalities, ComplianceTestClient, HoldingPeriodModuleTestClient,
+    JurisdictionsModuleTestClient, MaxBalanceModuleTestClient, MaxHoldersModuleTestClient,
+    TransferVolumeModuleTestClient,
+};
+use concordium_cis2::{AdditionalData, TokenAmountU64, TokenIdUnit, Transfer};
+use concordium_protocols::concordium_cis2_security::{
+    Identity, SecurityParams, TokenAmountSecurity,
+};
+use concordium_rwa_compliance::compliance_modules::jurisdictions::types::{
+    Jurisdiction, ListType, UpdateListParams,
+};
+use concordium_rwa_compliance::compliance_modules::{
+    jurisdictions, max_balance, max_holders, transfer_volume,
+};
+use concordium_rwa_compliance_holding_period::types::TradingWindow;
+use concordium_rwa_identity_registry::types::{IdentityAttribute, RegisterIdentityParams};
+use concordium_smart_contract_testing::*;
+use concordium_std::attributes::{COUNTRY_OF_RESIDENCE, NATIONALITY};
+use contract_base::{ContractPayloads, ContractTestClient};
+use identity_registry::IdentityRegistryTestClient;
+use integration_tests::*;
+use security_sft_single::types::*;
+use security_sft_single_client::SftSingleTestClient;
+
+const TOKEN_ID: TokenIdUnit = TokenIdUnit();
+const METADATA_URL: &str = "example.com";
+const ADMIN: AccountAddress = AccountAddress([0; 32]);
+const HOLDER: AccountAddress = AccountAddress([2; 32]);
+const HOLDER_2: AccountAddress = AccountAddress([3; 32]);
+const HOLDER_3: AccountAddress = AccountAddress([4; 32]);
+const COMPLIANT_NATIONALITIES: [&str; 2] = ["IN", "US"];
+/// Countries of residence of `HOLDER`, `HOLDER_2` & `HOLDER_3`.
+const RESIDENCES: [&str; 3] = ["US", "DE", "RU"];
+const DEFAULT_ACC_BALANCE: Amount = Amount {
+    micro_ccd: 1_000_000_000_u64,
+};
+
+#[test]
+fn max_holders() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (ir_contract, compliance_contract) = setup_chain(&mut chain, &admin);
+    let module =
+        MaxHoldersModuleTestClient::init(&mut chain, &admin, &max_holders::types::InitParams {
+            compliance:  compliance_contract.contract_address(),
+            max_holders: 2,
+        })
+        .expect("init max holders module");
+    compliance_contract
+        .add_module(&mut chain, &admin, module.contract_address())
+        .expect("add module");
+    let token_contract =
+        create_token_contract(&mut chain, &admin, &ir_contract, &compliance_contract);
+
+    mint(&mut chain, &admin, &token_contract, HOLDER, 10).expect("mint holder");
+    mint(&mut chain, &admin, &token_contract, HOLDER_2, 10).expect("mint holder 2");
+    mint(&mut chain, &admin, &token_contract, HOLDER_3, 10).expect_err("mint third holder");
+    mint(&mut chain, &admin, &token_contract, HOLDER, 10).expect("mint existing holder");
+
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_3, 5)
+        .expect_err("transfer to third holder");
+    // The sender stops being a holder when transferring the whole balance
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_3, 20)
+        .expect("transfer whole balance to third holder");
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER_3, 5)
+        .expect("transfer to existing holder");
+    mint(&mut chain, &admin, &token_contract, HOLDER, 10).expect_err("mint previous holder");
+}
+
+#[test]
+fn max_balance() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (ir_contract, compliance_contract) = setup_chain(&mut chain, &admin);
+    let module =
+        MaxBalanceModuleTestClient::init(&mut chain, &admin, &max_balance::types::InitParams {
+            compliance:  compliance_contract.contract_address(),
+            max_balance: TokenAmountU64(100),
+        })
+        .expect("init max balance module");
+    compliance_contract
+        .add_module(&mut chain, &admin, module.contract_address())
+        .expect("add module");
+    let token_contract =
+        create_token_contract(&mut chain, &admin, &ir_contract, &compliance_contract);
+
+    mint(&mut chain, &admin, &token_contract, HOLDER, 101).expect_err("mint above max balance");
+    mint(&mut chain, &admin, &token_contract, HOLDER, 100).expect("mint max balance");
+    mint(&mut chain, &admin, &token_contract, HOLDER, 1).expect_err("mint above max balance");
+
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 60).expect("transfer");
+    mint(&mut chain, &admin, &token_contract, HOLDER, 60).expect("mint up to max balance");
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 1)
+        .expect_err("transfer above max balance");
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER_3, 60).expect("transfer");
+}
+
+#[test]
+fn transfer_volume() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (ir_contract, compliance_contract) = setup_chain(&mut chain, &admin);
+    let module = TransferVolumeModuleTestClient::init(
+        &mut chain,
+        &admin,
+        &transfer_volume::types::InitParams {
+            compliance:    compliance_contract.contract_address(),
+            daily_limit:   Some(TokenAmountU64(10)),
+            monthly_limit: Some(TokenAmountU64(25)),
+        },
+    )
+    .expect("init transfer volume module");
+    compliance_contract
+        .add_module(&mut chain, &admin, module.contract_address())
+        .expect("add module");
+    let token_contract =
+        create_token_contract(&mut chain, &admin, &ir_contract, &compliance_contract);
+
+    // Minting is not limited
+    mint(&mut chain, &admin, &token_contract, HOLDER, 100).expect("mint");
+
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 10).expect("transfer daily limit");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1)
+        .expect_err("transfer above daily limit");
+    // The limits are per sender
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 10).expect("transfer other sender");
+
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 10).expect("transfer next day");
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 6)
+        .expect_err("transfer above monthly limit");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 5).expect("transfer monthly limit");
+
+    chain
+        .tick_block_time(Duration::from_days(30))
+        .expect("tick block time");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 10).expect("transfer next month");
+}
+
+#[test]
+fn jurisdictions() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (ir_contract, compliance_contract) = setup_chain(&mut chain, &admin);
+    let module = JurisdictionsModuleTestClient::init(
+        &mut chain,
+        &admin,
+        &jurisdictions::types::InitParams {
+            identity_registry: ir_contract.contract_address(),
+            agents:            vec![],
+        },
+    )
+    .expect("init jurisdictions module");
+    compliance_contract
+        .add_module(&mut chain, &admin, module.contract_address())
+        .expect("add module");
+    let token_contract =
+        create_token_contract(&mut chain, &admin, &ir_contract, &compliance_contract);
+
+    // Empty lists allow everyone
+    for holder in [HOLDER, HOLDER_2, HOLDER_3] {
+        mint(&mut chain, &admin, &token_contract, holder, 10).expect("mint");
+    }
+
+    let block_residence = UpdateListParams {
+        jurisdiction: Jurisdiction::CountryOfResidence,
+        list:         ListType::Block,
+        add:          vec![RESIDENCES[2].to_string()],
+        remove:       vec![],
+    };
+    module
+        .update_list(
+            &mut chain,
+            &Account::new(HOLDER, DEFAULT_ACC_BALANCE),
+            &block_residence,
+        )
+        .expect_err("update list by non agent");
+    module
+        .update_list(&mut chain, &admin, &block_residence)
+        .expect("block residence");
+    mint(&mut chain, &admin, &token_contract, HOLDER_3, 10).expect_err("mint to blocked");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_3, 1).expect_err("transfer to blocked");
+    // The sender is checked as well
+    transfer(&mut chain, &token_contract, HOLDER_3, HOLDER, 1).expect_err("transfer from blocked");
+
+    module
+        .update_list(&mut chain, &admin, &UpdateListParams {
+            jurisdiction: Jurisdiction::Nationality,
+            list:         ListType::Allow,
+            add:          vec![COMPLIANT_NATIONALITIES[1].to_string()],
+            remove:       vec![],
+        })
+        .expect("allow nationality");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1).expect("transfer allowed");
+    module
+        .update_list(&mut chain, &admin, &UpdateListParams {
+            jurisdiction: Jurisdiction::CountryOfResidence,
+            list:         ListType::Allow,
+            add:          vec![RESIDENCES[0].to_string()],
+            remove:       vec![],
+        })
+        .expect("allow residence");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1)
+        .expect_err("transfer to not allowed residence");
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 1)
+        .expect_err("transfer from not allowed residence");
+
+    module
+        .update_list(&mut chain, &admin, &UpdateListParams {
+            jurisdiction: Jurisdiction::CountryOfResidence,
+            list:         ListType::Allow,
+            add:          vec![],
+            remove:       vec![RESIDENCES[0].to_string()],
+        })
+        .expect("remove allowed residence");
+    module
+        .update_list(&mut chain, &admin, &UpdateListParams {
+            remove: vec![RESIDENCES[2].to_string()],
+            add: vec![],
+            ..block_residence
+        })
+        .expect("unblock residence");
+    transfer(&mut chain, &token_contract, HOLDER_3, HOLDER_2, 1).expect("transfer unblocked");
+}
+
+#[test]
+fn holding_period() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (ir_contract, compliance_contract) = setup_chain(&mut chain, &admin);
+    compliance::deploy_holding_period_module(&mut chain, &admin);
+    let module = HoldingPeriodModuleTestClient::init(
+        &mut chain,
+        &admin,
+        &concordium_rwa_compliance_holding_period::types::InitParams {
+            compliance:      compliance_contract.contract_address(),
+            holding_period:  Duration::from_days(10),
+            trading_windows: vec![],
+        },
+    )
+    .expect("init holding period module");
+    compliance_contract
+        .add_module(&mut chain, &admin, module.contract_address())
+        .expect("add module");
+    let token_contract =
+        create_token_contract(&mut chain, &admin, &ir_contract, &compliance_contract);
+
+    mint(&mut chain, &admin, &token_contract, HOLDER, 100).expect("mint");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1)
+        .expect_err("transfer within holding period");
+
+    chain
+        .tick_block_time(Duration::from_days(10))
+        .expect("tick block time");
+    mint(&mut chain, &admin, &token_contract, HOLDER, 100).expect("mint");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 50).expect("transfer held tokens");
+    // Only the tokens held for the holding period can be transferred
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 51)
+        .expect_err("transfer more than held tokens");
+    // The holding period starts again for the receiver
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 1)
+        .expect_err("transfer received tokens");
+
+    chain
+        .tick_block_time(Duration::from_days(10))
+        .expect("tick block time");
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 50).expect("transfer received tokens");
+
+    let window_start = chain
+        .block_time()
+        .checked_add(Duration::from_days(1))
+        .unwrap();
+    let trading_windows = vec![TradingWindow {
+        start: window_start,
+        end:   window_start.checked_add(Duration::from_days(1)).unwrap(),
+    }];
+    module
+        .set_trading_windows(
+            &mut chain,
+            &Account::new(HOLDER, DEFAULT_ACC_BALANCE),
+            &trading_windows,
+        )
+        .expect_err("set trading windows by non owner");
+    module
+        .set_trading_windows(&mut chain, &admin, &vec![TradingWindow {
+            start: window_start,
+            end:   window_start,
+        }])
+        .expect_err("set empty trading window");
+    module
+        .set_trading_windows(&mut chain, &admin, &trading_windows)
+        .expect("set trading windows");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1)
+        .expect_err("transfer before trading window");
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1).expect("transfer in trading window");
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1)
+        .expect_err("transfer after trading window");
+}
+
+#[test]
+fn holding_period_untracked_balance() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (ir_contract, compliance_contract) = setup_chain(&mut chain, &admin);
+    let token_contract =
+        create_token_contract(&mut chain, &admin, &ir_contract, &compliance_contract);
+    // Tokens minted before the module is added are not tracked by it
+    mint(&mut chain, &admin, &token_contract, HOLDER, 100).expect("mint");
+
+    compliance::deploy_holding_period_module(&mut chain, &admin);
+    let module = HoldingPeriodModuleTestClient::init(
+        &mut chain,
+        &admin,
+        &concordium_rwa_compliance_holding_period::types::InitParams {
+            compliance:      compliance_contract.contract_address(),
+            holding_period:  Duration::from_days(10),
+            trading_windows: vec![],
+        },
+    )
+    .expect("init holding period module");
+    compliance_contract
+        .add_module(&mut chain, &admin, module.contract_address())
+        .expect("add module");
+
+    mint(&mut chain, &admin, &token_contract, HOLDER, 100).expect("mint");
+    // The untracked balance is unlocked
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 100)
+        .expect("transfer untracked balance");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 1)
+        .expect_err("transfer within holding period");
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 1)
+        .expect_err("transfer received tokens");
+
+    chain
+        .tick_block_time(Duration::from_days(10))
+        .expect("tick block time");
+    transfer(&mut chain, &token_contract, HOLDER, HOLDER_2, 100).expect("transfer held tokens");
+    // The tokens received after the holding period started again are locked
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 101)
+        .expect_err("transfer recently received tokens");
+    transfer(&mut chain, &token_contract, HOLDER_2, HOLDER, 100).expect("transfer received tokens");
+}
+
+fn mint(
+    chain: &mut Chain,
+    admin: &Account,
+    token_contract: &SftSingleTestClient,
+    owner: AccountAddress,
+    amount: u64,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    token_contract.mint(chain, admin, &MintParams {
+        owners:   vec![MintParam {
+            amount:  TokenAmountSecurity::new_un_frozen(TokenAmountU64(amount)),
+            address: owner.into(),
+        }],
+        token_id: TOKEN_ID,
+    })
+}
+
+fn transfer(
+    chain: &mut Chain,
+    token_contract: &SftSingleTestClient,
+    from: AccountAddress,
+    to: AccountAddress,
+    amount: u64,
+) -> Result<ContractInvokeSuccess, ContractInvokeError> {
+    token_contract.transfer_single(chain, &Account::new(from, DEFAULT_ACC_BALANCE), Transfer {
+        token_id: TOKEN_ID,
+        amount:   TokenAmountU64(amount),
+        from:     from.into(),
+        to:       to.into(),
+        data:     AdditionalData::empty(),
+    })
+}
+
+fn create_token_contract(
+    chain: &mut Chain,
+    admin: &Account,
+    ir_contract: &IdentityRegistryTestClient,
+    compliance_contract: &ComplianceTestClient,
+) -> SftSingleTestClient {
+    SftSingleTestClient::init(chain, admin, &InitParam {
+        security:     Some(SecurityParams {
+            compliance:        compliance_contract.contract_address(),
+            identity_registry: ir_contract.contract_address(),
+        }),
+        metadata_url: ContractMetadataUrl {
+            hash: None,
+            url:  METADATA_URL.to_string(),
+        },
+        agents:       vec![],
+    })
+    .expect("init token contract")
+}
+
+/// Sets up the chain with a compliance contract only allowing compliant nationalities and registers `HOLDER`,
+/// `HOLDER_2` & `HOLDER_3` with a compliant nationality and their country of residence.
+fn setup_chain(
+    chain: &mut Chain,
+    admin: &Account,
+) -> (IdentityRegistryTestClient, ComplianceTestClient) {
+    chain.create_account(admin.clone());
+
+    identity_registry::deploy_module(chain, admin);
+    compliance::deploy_module(chain, admin);
+    security_sft_single_client::deploy_module(chain, admin);
+
+    let ir_contract =
+        IdentityRegistryTestClient::init(chain, admin, &()).expect("identity registry init");
+    for (holder, residence) in [HOLDER, HOLDER_2, HOLDER_3].into_iter().zip(RESIDENCES) {
+        chain.create_account(Account::new(holder, DEFAULT_ACC_BALANCE));
+        ir_contract
+            .register_identity(chain, admin, &RegisterIdentityParams {
+                address:  holder.into(),
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![
+                        IdentityAttribute {
+                            tag:   NATIONALITY.0,
+                            value: COMPLIANT_NATIONALITIES[1].to_string(),
+                        },
+                        IdentityAttribute {
+                            tag:   COUNTRY_OF_RESIDENCE.0,
+                            value: residence.to_string(),
+                        },
+                    ],
+                },
+                validity: None,
+            })
+            .expect("register identity");
+    }
+
+    let (compliance_module, ..) = init_nationalities(
+        chain,
+        admin,
+        &concordium_rwa_compliance::compliance_modules::allowed_nationalities::types::InitParams {
+            nationalities:     COMPLIANT_NATIONALITIES
+                .iter()
+                .map(|n| n.to_string())
+                .collect(),
+            identity_registry: ir_contract.contract_address(),
+        },
+    )
+    .expect("init nationalities module");
+    let (compliance, ..) = compliance::init(chain, admin, vec![compliance_module.contract_address])
+        .expect("init compliance module");
+
+    (
+        ir_contract,
+        ComplianceTestClient(compliance.contract_address),
+    )
+}
@@ -0,0 +1,374 @@
+#![cfg(test)]
+
+use concordium_protocols::concordium_cis2_security::Identity;
+use concordium_rwa_identity_registry::types::{
+    AddressLinkedEvent, AttributePredicate, Event, IdentityAttribute, IdentityInvalidatedEvent,
+    IdentityUpdatedEvent, InvalidReason, LinkAddressesParams, Predicate, RegisterIdentityParams,
+    ValidityWindow, VerifyAttributesParams,
+};
+use concordium_smart_contract_testing::*;
+use concordium_std::attributes::{DOB, NATIONALITY};
+use contract_base::ContractTestClient;
+use identity_registry::IdentityRegistryTestClient;
+use integration_tests::*;
+
+const ADMIN: AccountAddress = AccountAddress([0; 32]);
+const HOLDER: AccountAddress = AccountAddress([2; 32]);
+const HOLDER_2: AccountAddress = AccountAddress([3; 32]);
+const DEFAULT_ACC_BALANCE: Amount = Amount {
+    micro_ccd: 1_000_000_000_u64,
+};
+
+#[test]
+fn identity_validity() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    identity_registry::deploy_module(&mut chain, &admin);
+    chain.create_account(holder.clone());
+    let ir_contract =
+        IdentityRegistryTestClient::init(&mut chain, &admin, &()).expect("init identity registry");
+
+    let valid_from = chain
+        .block_time()
+        .checked_add(Duration::from_days(1))
+        .unwrap();
+    let register_params = |validity: ValidityWindow| RegisterIdentityParams {
+        address:  holder.address.into(),
+        identity: Identity {
+            attributes:  vec![],
+            credentials: vec![],
+        },
+        validity: Some(validity),
+    };
+    ir_contract
+        .register_identity(
+            &mut chain,
+            &admin,
+            &register_params(ValidityWindow {
+                valid_from,
+                valid_until: valid_from,
+            }),
+        )
+        .expect_err("register identity with empty validity");
+    ir_contract
+        .register_identity(
+            &mut chain,
+            &admin,
+            &register_params(ValidityWindow {
+                valid_from,
+                valid_until: valid_from.checked_add(Duration::from_days(10)).unwrap(),
+            }),
+        )
+        .expect("register identity");
+    assert!(!ir_contract.is_verified(&chain, &admin, &holder.address.into()));
+
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    assert!(ir_contract.is_verified(&chain, &admin, &holder.address.into()));
+    ir_contract
+        .reverify_identities(&mut chain, &holder, &vec![holder.address.into()])
+        .expect_err("reverify by non agent");
+    let res = ir_contract
+        .reverify_identities(&mut chain, &admin, &vec![holder.address.into()])
+        .expect("reverify valid identity");
+    assert!(parse_events(&res).is_empty());
+
+    chain
+        .tick_block_time(Duration::from_days(10))
+        .expect("tick block time");
+    assert!(!ir_contract.is_verified(&chain, &admin, &holder.address.into()));
+    let res = ir_contract
+        .reverify_identities(&mut chain, &admin, &vec![
+            holder.address.into(),
+            admin.address.into(),
+        ])
+        .expect("reverify expired identity");
+    let holder_address: Address = holder.address.into();
+    assert!(matches!(
+        parse_events(&res).as_slice(),
+        [Event::IdentityInvalidated(IdentityInvalidatedEvent {
+            address,
+            reason: InvalidReason::Expired,
+        })] if *address == holder_address
+    ));
+    // Invalidated identities are only reported once
+    let res = ir_contract
+        .reverify_identities(&mut chain, &admin, &vec![holder.address.into()])
+        .expect("reverify invalidated identity");
+    assert!(parse_events(&res).is_empty());
+
+    ir_contract
+        .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+            validity: None,
+            ..register_params(ValidityWindow {
+                valid_from,
+                valid_until: valid_from,
+            })
+        })
+        .expect("register identity without expiry");
+    let res = ir_contract
+        .reverify_identities(&mut chain, &admin, &vec![holder.address.into()])
+        .expect("reverify registered identity");
+    assert!(parse_events(&res).is_empty());
+    assert!(ir_contract.is_verified(&chain, &admin, &holder.address.into()));
+}
+
+#[test]
+fn batch_register_and_delete() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    identity_registry::deploy_module(&mut chain, &admin);
+    chain.create_account(holder.clone());
+    let ir_contract =
+        IdentityRegistryTestClient::init(&mut chain, &admin, &()).expect("init identity registry");
+
+    let holders: Vec<Address> = vec![holder.address.into(), HOLDER_2.into()];
+    let register_params =
+        |address: Address, validity: Option<ValidityWindow>| RegisterIdentityParams {
+            address,
+            identity: Identity {
+                attributes:  vec![IdentityAttribute {
+                    tag:   NATIONALITY.0,
+                    value: "DE".to_string(),
+                }],
+                credentials: vec![],
+            },
+            validity,
+        };
+
+    // An invalid entry rejects the whole batch
+    let now = chain.block_time();
+    ir_contract
+        .register_identities(&mut chain, &admin, &vec![
+            register_params(holders[0], None),
+            register_params(
+                holders[1],
+                Some(ValidityWindow {
+                    valid_from:  now,
+                    valid_until: now,
+                }),
+            ),
+        ])
+        .expect_err("register identities with invalid validity");
+    assert!(!ir_contract.is_verified(&chain, &admin, &holders[0]));
+
+    let params = holders.iter().map(|h| register_params(*h, None)).collect();
+    ir_contract
+        .register_identities(&mut chain, &holder, &params)
+        .expect_err("register identities by non agent");
+    let res = ir_contract
+        .register_identities(&mut chain, &admin, &params)
+        .expect("register identities");
+    let registered: Vec<Address> = parse_events(&res)
+        .into_iter()
+        .map(|e| match e {
+            Event::IdentityRegistered(IdentityUpdatedEvent { address }) => address,
+            e => panic!("unexpected event {:?}", e),
+        })
+        .collect();
+    assert_eq!(registered, holders);
+    assert!(holders
+        .iter()
+        .all(|h| ir_contract.is_verified(&chain, &admin, h)));
+
+    // Deleting a missing identity rejects the whole batch
+    ir_contract
+        .delete_identities(&mut chain, &admin, &vec![holders[0], ADMIN.into()])
+        .expect_err("delete missing identity");
+    assert!(ir_contract.is_verified(&chain, &admin, &holders[0]));
+    let res = ir_contract
+        .delete_identities(&mut chain, &admin, &holders)
+        .expect("delete identities");
+    let removed: Vec<Address> = parse_events(&res)
+        .into_iter()
+        .map(|e| match e {
+            Event::IdentityRemoved(IdentityUpdatedEvent { address }) => address,
+            e => panic!("unexpected event {:?}", e),
+        })
+        .collect();
+    assert_eq!(removed, holders);
+    assert!(holders
+        .iter()
+        .all(|h| !ir_contract.is_verified(&chain, &admin, h)));
+}
+
+#[test]
+fn linked_addresses() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    identity_registry::deploy_module(&mut chain, &admin);
+    chain.create_account(holder.clone());
+    let ir_contract =
+        IdentityRegistryTestClient::init(&mut chain, &admin, &()).expect("init identity registry");
+
+    let identity: Address = holder.address.into();
+    let linked_account: Address = HOLDER_2.into();
+    let linked_wallet: Address = ContractAddress::new(1000, 0).into();
+    let link_params = LinkAddressesParams {
+        identity,
+        addresses: vec![linked_account, linked_wallet],
+    };
+    ir_contract
+        .link_addresses(&mut chain, &admin, &link_params)
+        .expect_err("link addresses to a missing identity");
+    let register_params = |address: Address| RegisterIdentityParams {
+        address,
+        identity: Identity {
+            attributes:  vec![IdentityAttribute {
+                tag:   NATIONALITY.0,
+                value: "DE".to_string(),
+            }],
+            credentials: vec![],
+        },
+        validity: None,
+    };
+    ir_contract
+        .register_identity(&mut chain, &admin, &register_params(identity))
+        .expect("register identity");
+    ir_contract
+        .link_addresses(&mut chain, &holder, &link_params)
+        .expect_err("link addresses by non agent");
+    let res = ir_contract
+        .link_addresses(&mut chain, &admin, &link_params)
+        .expect("link addresses");
+    let linked: Vec<Address> = parse_events(&res)
+        .into_iter()
+        .map(|e| match e {
+            Event::AddressLinked(AddressLinkedEvent {
+                identity: i,
+                address,
+            }) if i == identity => address,
+            e => panic!("unexpected event {:?}", e),
+        })
+        .collect();
+    assert_eq!(linked, link_params.addresses);
+    assert!(link_params
+        .addresses
+        .iter()
+        .all(|a| ir_contract.is_verified(&chain, &admin, a)));
+    assert_eq!(
+        ir_contract
+            .linked_addresses(&chain, &admin, &linked_wallet)
+            .expect("linked addresses"),
+        link_params.addresses
+    );
+
+    // Linked addresses can neither be linked again nor registered
+    ir_contract
+        .link_addresses(&mut chain, &admin, &LinkAddressesParams {
+            identity,
+            addresses: vec![linked_account],
+        })
+        .expect_err("link an already linked address");
+    ir_contract
+        .register_identity(&mut chain, &admin, &register_params(linked_account))
+        .expect_err("register a linked address");
+
+    // Re-registering the identity keeps its links
+    ir_contract
+        .register_identity(&mut chain, &admin, &register_params(identity))
+        .expect("re-register identity");
+    assert!(ir_contract.is_verified(&chain, &admin, &linked_account));
+
+    ir_contract
+        .unlink_addresses(&mut chain, &admin, &vec![linked_account])
+        .expect("unlink address");
+    assert!(!ir_contract.is_verified(&chain, &admin, &linked_account));
+    ir_contract
+        .unlink_addresses(&mut chain, &admin, &vec![linked_account])
+        .expect_err("unlink an address which is not linked");
+
+    let res = ir_contract
+        .delete_identities(&mut chain, &admin, &vec![identity])
+        .expect("delete identity");
+    assert!(matches!(
+        parse_events(&res).as_slice(),
+        [
+            Event::AddressUnlinked(AddressLinkedEvent { address, .. }),
+            Event::IdentityRemoved(_),
+        ] if *address == linked_wallet
+    ));
+    assert!(!ir_contract.is_verified(&chain, &admin, &linked_wallet));
+    ir_contract
+        .linked_addresses(&chain, &admin, &linked_wallet)
+        .expect_err("linked addresses of a deleted identity");
+}
+
+#[test]
+fn verify_attributes() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    // 2024-02-29T12:00:00Z
+    let mut chain = Chain::builder()
+        .block_time(Timestamp::from_timestamp_millis(1_709_208_000_000))
+        .build()
+        .expect("build chain");
+    chain.create_account(admin.clone());
+    identity_registry::deploy_module(&mut chain, &admin);
+    chain.create_account(holder.clone());
+    let ir_contract =
+        IdentityRegistryTestClient::init(&mut chain, &admin, &()).expect("init identity registry");
+    ir_contract
+        .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+            address:  holder.address.into(),
+            identity: Identity {
+                attributes:  vec![
+                    IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: "US".to_string(),
+                    },
+                    IdentityAttribute {
+                        tag:   DOB.0,
+                        value: "20060301".to_string(),
+                    },
+                ],
+                credentials: vec![],
+            },
+            validity: None,
+        })
+        .expect("register identity");
+
+    let verify = |chain: &Chain, predicates: Vec<AttributePredicate>| {
+        ir_contract.verify_attributes(chain, &admin, &VerifyAttributesParams {
+            address: holder.address.into(),
+            predicates,
+        })
+    };
+    let nationality_in = AttributePredicate {
+        tag:       NATIONALITY.0,
+        predicate: Predicate::In(vec!["DE".to_string(), "US".to_string()]),
+    };
+    let adult = AttributePredicate {
+        tag:       DOB.0,
+        predicate: Predicate::MinimumAge(18),
+    };
+    assert!(verify(&chain, vec![nationality_in.clone()]));
+    assert!(!verify(&chain, vec![nationality_in.clone(), adult.clone()]));
+    assert!(!verify(&chain, vec![AttributePredicate {
+        tag:       NATIONALITY.0,
+        predicate: Predicate::NotIn(vec!["US".to_string()]),
+    }]));
+    // Missing attributes never satisfy a predicate
+    assert!(!verify(&chain, vec![AttributePredicate {
+        tag:       concordium_std::attributes::COUNTRY_OF_RESIDENCE.0,
+        predicate: Predicate::NotIn(vec!["US".to_string()]),
+    }]));
+
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    assert!(verify(&chain, vec![nationality_in, adult]));
+}
+
+fn parse_events(res: &ContractInvokeSuccess) -> Vec<Event> {
+    res.events()
+        .flat_map(|(_, events)| events.iter().map(|e| e.parse().expect("parse event")))
+        .collect()
+}
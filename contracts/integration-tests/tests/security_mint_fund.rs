@@ -18,10 +18,13 @@ use concordium_std::attributes::NATIONALITY;
 use contract_base::{ContractPayloads, ContractTestClient};
 use euroe::EuroETestClient;
 use identity_registry::IdentityRegistryTestClient;
+use integration_tests::cis2_conversions::to_token_id_vec;
 use integration_tests::*;
 use security_mint_fund::types::{
-    AddFundParams, ClaimInvestmentParam, ClaimInvestmentParams, InitParam, TransferInvestParams,
-    UpdateFundState, UpdateFundStateParams,
+    AddFundParams, ClaimInvestmentParam, ClaimInvestmentParams, FundInvestors,
+    IdentityAttributeRequirement, InitParam, ReleaseVestedParams, SecurityTokenUId,
+    TransferInvestParams, UpdateFundAllowlistParams, UpdateFundState, UpdateFundStateParams,
+    VestingSchedule, WithdrawInvestmentParams,
 };
 use security_mint_fund_client::MintFundTestClient;
 use security_sft_multi_client::SftMultiTestClient;
@@ -60,7 +63,7 @@ fn normal_flow() {
 
     let fund_contract = MintFundTestClient::init(&mut chain, &admin, &InitParam {
         currency_token: TokenUId {
-            id:       TokenIdUnit(),
+            id:       to_token_id_vec(TokenIdUnit()),
             contract: euroe_contract.contract_address(),
         },
         agents:         vec![],
@@ -77,6 +80,7 @@ fn normal_flow() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity investor 1");
     ir_contract
@@ -89,6 +93,7 @@ fn normal_flow() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity investor 2");
 
@@ -148,6 +153,10 @@ fn normal_flow() {
         id:       investment_token_id,
     };
     // Adding fund
+    let deadline = chain
+        .block_time()
+        .checked_add(Duration::from_days(30))
+        .expect("deadline");
     fund_contract
         .add_fund(&mut chain, &admin, &AddFundParams {
             token: TokenUId {
@@ -156,6 +165,16 @@ fn normal_flow() {
             },
             rate: Rate::new(1000, 1).unwrap(),
             security_token,
+            currency_token: None,
+            soft_cap: 1000.into(),
+            hard_cap: 5000.into(),
+            deadline,
+            funds_receiver: treasury.address.into(),
+            cooling_off_period: Duration::from_days(14),
+            min_investment: None,
+            max_investment: None,
+            investors: FundInvestors::Any,
+            vesting: None,
         })
         .expect("add fund");
 
@@ -317,6 +336,611 @@ fn normal_flow() {
     );
 }
 
+#[test]
+fn caps_and_deadline() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, fund_contract, token_contract, _) =
+        setup_fund_contract(&mut chain, &admin);
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    let treasury = Account::new(TREASURY, DEFAULT_ACC_BALANCE);
+    euroe_contract
+        .update_operator_single(&mut chain, &investor_1, &UpdateOperator {
+            update:   OperatorUpdate::Add,
+            operator: fund_contract.contract_address().into(),
+        })
+        .expect("update operator investor 1");
+
+    // Fund reaching its hard cap is finalized as `Success`
+    let fund_success = add_fund(
+        &mut chain,
+        &admin,
+        &fund_contract,
+        &token_contract,
+        TokenIdU64(0),
+        Duration::from_days(10),
+        Duration::from_days(1),
+    );
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_success,
+            amount:         1000.into(),
+        })
+        .expect("transfer_invest");
+    fund_contract
+        .finalize_fund(&mut chain, &investor_1, fund_success)
+        .expect_err("finalize_fund before deadline & hard cap");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_success,
+            amount:         2001.into(),
+        })
+        .expect_err("transfer_invest beyond hard cap");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_success,
+            amount:         2000.into(),
+        })
+        .expect("transfer_invest up to hard cap");
+    fund_contract
+        .finalize_fund(&mut chain, &investor_1, fund_success)
+        .expect("finalize_fund hard cap reached");
+    fund_contract
+        .finalize_fund(&mut chain, &investor_1, fund_success)
+        .expect_err("finalize_fund already finalized");
+    fund_contract
+        .claim_investment(&mut chain, &investor_1, &ClaimInvestmentParams {
+            investments: vec![ClaimInvestmentParam {
+                security_token: fund_success,
+                investor:       investor_1.address,
+            }],
+        })
+        .expect("claim_investment success");
+
+    // Fund missing its soft cap by the deadline is finalized as `Fail`
+    let fund_fail = add_fund(
+        &mut chain,
+        &admin,
+        &fund_contract,
+        &token_contract,
+        TokenIdU64(1),
+        Duration::from_days(10),
+        Duration::from_days(1),
+    );
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_fail,
+            amount:         1000.into(),
+        })
+        .expect("transfer_invest");
+    chain
+        .tick_block_time(Duration::from_days(10))
+        .expect("tick block time");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_fail,
+            amount:         1000.into(),
+        })
+        .expect_err("transfer_invest after deadline");
+    fund_contract
+        .finalize_fund(&mut chain, &investor_1, fund_fail)
+        .expect("finalize_fund deadline passed");
+    fund_contract
+        .claim_investment(&mut chain, &investor_1, &ClaimInvestmentParams {
+            investments: vec![ClaimInvestmentParam {
+                security_token: fund_fail,
+                investor:       investor_1.address,
+            }],
+        })
+        .expect("claim_investment fail");
+
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        address:  investor_1.address.into(),
+                        token_id: TokenIdUnit(),
+                    },
+                    BalanceOfQuery {
+                        address:  fund_contract.contract_address().into(),
+                        token_id: TokenIdUnit(),
+                    },
+                    BalanceOfQuery {
+                        address:  treasury.address.into(),
+                        token_id: TokenIdUnit(),
+                    }
+                ],
+            })
+            .expect("balance of euroe"),
+        BalanceOfQueryResponse(vec![2000.into(), 0.into(), 3000.into()])
+    );
+}
+
+#[test]
+fn withdraw_investment() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, fund_contract, token_contract, _) =
+        setup_fund_contract(&mut chain, &admin);
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    euroe_contract
+        .update_operator_single(&mut chain, &investor_1, &UpdateOperator {
+            update:   OperatorUpdate::Add,
+            operator: fund_contract.contract_address().into(),
+        })
+        .expect("update operator investor 1");
+    let security_token = add_fund(
+        &mut chain,
+        &admin,
+        &fund_contract,
+        &token_contract,
+        TokenIdU64(0),
+        Duration::from_days(30),
+        Duration::from_days(1),
+    );
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token,
+            amount: 3000.into(),
+        })
+        .expect("transfer_invest");
+
+    // Partial withdrawal within the cooling-off period
+    fund_contract
+        .withdraw_investment(&mut chain, &investor_1, &WithdrawInvestmentParams {
+            security_token,
+            amount: 3001.into(),
+        })
+        .expect_err("withdraw_investment more than invested");
+    fund_contract
+        .withdraw_investment(&mut chain, &investor_1, &WithdrawInvestmentParams {
+            security_token,
+            amount: 1000.into(),
+        })
+        .expect("withdraw_investment partial");
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        address:  investor_1.address.into(),
+                        token_id: TokenIdUnit(),
+                    },
+                    BalanceOfQuery {
+                        address:  fund_contract.contract_address().into(),
+                        token_id: TokenIdUnit(),
+                    },
+                ],
+            })
+            .expect("balance of euroe"),
+        BalanceOfQueryResponse(vec![3000.into(), 2000.into()])
+    );
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![BalanceOfQuery {
+                    address:  investor_1.address.into(),
+                    token_id: security_token.id,
+                }],
+            })
+            .expect("balance of token"),
+        BalanceOfQueryResponse(vec![2.into()])
+    );
+
+    // Withdrawal after the cooling-off period
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    fund_contract
+        .withdraw_investment(&mut chain, &investor_1, &WithdrawInvestmentParams {
+            security_token,
+            amount: 1000.into(),
+        })
+        .expect_err("withdraw_investment after cooling-off period");
+
+    // Only the amount of a new investment can be withdrawn within its cooling-off period
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token,
+            amount: 0.into(),
+        })
+        .expect_err("transfer_invest zero amount");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token,
+            amount: 1000.into(),
+        })
+        .expect("transfer_invest");
+    fund_contract
+        .withdraw_investment(&mut chain, &investor_1, &WithdrawInvestmentParams {
+            security_token,
+            amount: 1001.into(),
+        })
+        .expect_err("withdraw_investment more than invested within cooling-off period");
+    fund_contract
+        .withdraw_investment(&mut chain, &investor_1, &WithdrawInvestmentParams {
+            security_token,
+            amount: 1000.into(),
+        })
+        .expect("withdraw_investment within cooling-off period");
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        address:  investor_1.address.into(),
+                        token_id: TokenIdUnit(),
+                    },
+                    BalanceOfQuery {
+                        address:  fund_contract.contract_address().into(),
+                        token_id: TokenIdUnit(),
+                    },
+                ],
+            })
+            .expect("balance of euroe"),
+        BalanceOfQueryResponse(vec![3000.into(), 2000.into()])
+    );
+}
+
+#[test]
+fn investment_limits_and_investors() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, fund_contract, token_contract, ir_contract) =
+        setup_fund_contract(&mut chain, &admin);
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    euroe_contract
+        .update_operator_single(&mut chain, &investor_1, &UpdateOperator {
+            update:   OperatorUpdate::Add,
+            operator: fund_contract.contract_address().into(),
+        })
+        .expect("update operator investor 1");
+
+    // Investment limits
+    let mut params = AddFundParams {
+        min_investment: Some(3000.into()),
+        max_investment: Some(2000.into()),
+        ..fund_params(&mut chain, &admin, &token_contract, TokenIdU64(0))
+    };
+    fund_contract
+        .add_fund(&mut chain, &admin, &params)
+        .expect_err("add fund with minimum above maximum");
+    params.min_investment = Some(1000.into());
+    fund_contract
+        .add_fund(&mut chain, &admin, &params)
+        .expect("add fund with limits");
+    let fund_limits = params.security_token;
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_limits,
+            amount:         999.into(),
+        })
+        .expect_err("transfer_invest below minimum");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_limits,
+            amount:         1000.into(),
+        })
+        .expect("transfer_invest minimum");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_limits,
+            amount:         1001.into(),
+        })
+        .expect_err("transfer_invest above maximum");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_limits,
+            amount:         1000.into(),
+        })
+        .expect("transfer_invest up to maximum");
+    fund_contract
+        .withdraw_investment(&mut chain, &investor_1, &WithdrawInvestmentParams {
+            security_token: fund_limits,
+            amount:         1001.into(),
+        })
+        .expect_err("withdraw_investment leaving less than minimum");
+    fund_contract
+        .withdraw_investment(&mut chain, &investor_1, &WithdrawInvestmentParams {
+            security_token: fund_limits,
+            amount:         1000.into(),
+        })
+        .expect("withdraw_investment leaving minimum");
+
+    // Allowlist
+    let fund_allowlist = fund_params(&mut chain, &admin, &token_contract, TokenIdU64(1));
+    let fund_allowlist = AddFundParams {
+        investors: FundInvestors::Allowlist,
+        ..fund_allowlist
+    };
+    fund_contract
+        .add_fund(&mut chain, &admin, &fund_allowlist)
+        .expect("add fund with allowlist");
+    let fund_allowlist = fund_allowlist.security_token;
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_allowlist,
+            amount:         1000.into(),
+        })
+        .expect_err("transfer_invest not in allowlist");
+    fund_contract
+        .update_fund_allowlist(&mut chain, &investor_1, &UpdateFundAllowlistParams {
+            security_token: fund_allowlist,
+            add:            vec![investor_1.address],
+            remove:         vec![],
+        })
+        .expect_err("update_fund_allowlist by non agent");
+    fund_contract
+        .update_fund_allowlist(&mut chain, &admin, &UpdateFundAllowlistParams {
+            security_token: fund_allowlist,
+            add:            vec![investor_1.address],
+            remove:         vec![],
+        })
+        .expect("update_fund_allowlist");
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_allowlist,
+            amount:         1000.into(),
+        })
+        .expect("transfer_invest in allowlist");
+
+    // Required identity attribute
+    let fund_attribute = |chain: &mut Chain, token_id: TokenIdU64, value: &str| {
+        let params = AddFundParams {
+            investors: FundInvestors::IdentityAttribute(IdentityAttributeRequirement {
+                identity_registry: ir_contract.contract_address(),
+                tag:               NATIONALITY.0,
+                value:             value.to_string(),
+            }),
+            ..fund_params(chain, &admin, &token_contract, token_id)
+        };
+        fund_contract
+            .add_fund(chain, &admin, &params)
+            .expect("add fund with identity attribute");
+        params.security_token
+    };
+    let fund_attribute_other =
+        fund_attribute(&mut chain, TokenIdU64(2), COMPLIANT_NATIONALITIES[0]);
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_attribute_other,
+            amount:         1000.into(),
+        })
+        .expect_err("transfer_invest without identity attribute");
+    let fund_attribute = fund_attribute(&mut chain, TokenIdU64(3), COMPLIANT_NATIONALITIES[1]);
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token: fund_attribute,
+            amount:         1000.into(),
+        })
+        .expect("transfer_invest with identity attribute");
+}
+
+#[test]
+fn vesting() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, fund_contract, token_contract, _) =
+        setup_fund_contract(&mut chain, &admin);
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    euroe_contract
+        .update_operator_single(&mut chain, &investor_1, &UpdateOperator {
+            update:   OperatorUpdate::Add,
+            operator: fund_contract.contract_address().into(),
+        })
+        .expect("update operator investor 1");
+    let params = AddFundParams {
+        vesting: Some(VestingSchedule {
+            lock_up:        Duration::from_days(10),
+            vesting_period: Duration::from_days(100),
+        }),
+        ..fund_params(&mut chain, &admin, &token_contract, TokenIdU64(0))
+    };
+    fund_contract
+        .add_fund(&mut chain, &admin, &params)
+        .expect("add fund");
+    let security_token = params.security_token;
+    fund_contract
+        .transfer_invest(&mut chain, &investor_1, &TransferInvestParams {
+            security_token,
+            amount: 3000.into(),
+        })
+        .expect("transfer_invest");
+    fund_contract
+        .finalize_fund(&mut chain, &investor_1, security_token)
+        .expect("finalize_fund");
+    let release_params = ReleaseVestedParams {
+        security_token,
+        investors: vec![INVESTOR_1],
+    };
+    fund_contract
+        .release_vested(&mut chain, &investor_1, &release_params)
+        .expect_err("release_vested before claim");
+
+    // Claimed tokens stay frozen during the lock-up
+    fund_contract
+        .claim_investment(&mut chain, &investor_1, &ClaimInvestmentParams {
+            investments: vec![ClaimInvestmentParam {
+                security_token,
+                investor: INVESTOR_1,
+            }],
+        })
+        .expect("claim_investment");
+    let frozen_balance = |chain: &mut Chain| {
+        token_contract
+            .balance_of_frozen(chain, &admin, &BalanceOfQueryParams {
+                queries: vec![BalanceOfQuery {
+                    address:  INVESTOR_1.into(),
+                    token_id: security_token.id,
+                }],
+            })
+            .expect("balance of frozen")
+    };
+    assert_eq!(
+        frozen_balance(&mut chain),
+        BalanceOfQueryResponse(vec![3.into()])
+    );
+    fund_contract
+        .remove_fund(&mut chain, &admin, security_token)
+        .expect_err("remove_fund with vesting tokens");
+
+    // A third of the tokens vest a third of the way through the vesting period
+    chain
+        .tick_block_time(Duration::from_days(10 + 34))
+        .expect("tick block time");
+    fund_contract
+        .release_vested(&mut chain, &investor_1, &release_params)
+        .expect("release_vested partial");
+    assert_eq!(
+        frozen_balance(&mut chain),
+        BalanceOfQueryResponse(vec![2.into()])
+    );
+
+    // All the tokens are released at the end of the vesting period
+    chain
+        .tick_block_time(Duration::from_days(66))
+        .expect("tick block time");
+    fund_contract
+        .release_vested(&mut chain, &investor_1, &release_params)
+        .expect("release_vested full");
+    assert_eq!(
+        frozen_balance(&mut chain),
+        BalanceOfQueryResponse(vec![0.into()])
+    );
+    fund_contract
+        .release_vested(&mut chain, &investor_1, &release_params)
+        .expect_err("release_vested after full release");
+    fund_contract
+        .remove_fund(&mut chain, &admin, security_token)
+        .expect("remove_fund after vesting");
+}
+
+/// Sets up the chain with a fund contract and a token contract which the fund contract can mint, burn & un freeze.
+/// `INVESTOR_1` is registered as a compliant identity and holds 5000 EuroE.
+fn setup_fund_contract(
+    chain: &mut Chain,
+    admin: &Account,
+) -> (
+    EuroETestClient,
+    MintFundTestClient,
+    SftMultiTestClient,
+    IdentityRegistryTestClient,
+) {
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(chain, admin, &COMPLIANT_NATIONALITIES);
+    chain.create_account(Account::new(TREASURY, DEFAULT_ACC_BALANCE));
+    chain.create_account(Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE));
+    euroe_contract
+        .mint(chain, admin, &euroe::MintParams {
+            owner:  INVESTOR_1.into(),
+            amount: 5000.into(),
+        })
+        .expect("euroe mint investor 1");
+    ir_contract
+        .register_identity(chain, admin, &RegisterIdentityParams {
+            address:  INVESTOR_1.into(),
+            identity: Identity {
+                credentials: vec![],
+                attributes:  vec![IdentityAttribute {
+                    tag:   NATIONALITY.0,
+                    value: COMPLIANT_NATIONALITIES[1].to_string(),
+                }],
+            },
+            validity: None,
+        })
+        .expect("register identity investor 1");
+
+    let fund_contract = MintFundTestClient::init(chain, admin, &InitParam {
+        currency_token: TokenUId {
+            id:       to_token_id_vec(TokenIdUnit()),
+            contract: euroe_contract.contract_address(),
+        },
+        agents:         vec![],
+    })
+    .expect("init fund contract");
+    let token_contract = create_token_contract_multi(
+        chain,
+        admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+    );
+    token_contract
+        .add_agent(chain, admin, &AgentWithRoles {
+            address: fund_contract.contract_address().into(),
+            roles:   vec![
+                security_sft_multi::types::AgentRole::Mint,
+                security_sft_multi::types::AgentRole::ForcedBurn,
+                security_sft_multi::types::AgentRole::UnFreeze,
+            ],
+        })
+        .expect("add agent token");
+    (euroe_contract, fund_contract, token_contract, ir_contract)
+}
+
+/// Adds a fund with a soft cap of 2000 and a hard cap of 3000 which mints `token_id` of `token_contract` as both the
+/// initial & the security token.
+fn add_fund(
+    chain: &mut Chain,
+    admin: &Account,
+    fund_contract: &MintFundTestClient,
+    token_contract: &SftMultiTestClient,
+    token_id: TokenIdU64,
+    deadline: Duration,
+    cooling_off_period: Duration,
+) -> SecurityTokenUId {
+    let params = AddFundParams {
+        deadline: chain.block_time().checked_add(deadline).expect("deadline"),
+        cooling_off_period,
+        ..fund_params(chain, admin, token_contract, token_id)
+    };
+    fund_contract
+        .add_fund(chain, admin, &params)
+        .expect("add fund");
+    params.security_token
+}
+
+/// Adds `token_id` to `token_contract` and returns the parameters of a fund which mints it as both the initial & the
+/// security token. The fund has a soft cap of 2000, a hard cap of 3000 and no investment limits.
+fn fund_params(
+    chain: &mut Chain,
+    admin: &Account,
+    token_contract: &SftMultiTestClient,
+    token_id: TokenIdU64,
+) -> AddFundParams {
+    token_contract
+        .add_token(chain, admin, &AddTokenParams {
+            token_id,
+            token_metadata: concordium_protocols::concordium_cis2_ext::ContractMetadataUrl {
+                url:  "example.com".to_string(),
+                hash: None,
+            },
+        })
+        .expect("add token");
+    let security_token = TokenUId {
+        contract: token_contract.contract_address(),
+        id:       token_id,
+    };
+    AddFundParams {
+        token: security_token,
+        rate: Rate::new(1000, 1).unwrap(),
+        security_token,
+        currency_token: None,
+        soft_cap: 2000.into(),
+        hard_cap: 3000.into(),
+        deadline: chain
+            .block_time()
+            .checked_add(Duration::from_days(30))
+            .expect("deadline"),
+        funds_receiver: TREASURY.into(),
+        cooling_off_period: Duration::from_days(1),
+        min_investment: None,
+        max_investment: None,
+        investors: FundInvestors::Any,
+        vesting: None,
+    }
+}
+
 fn create_token_contract_multi(
     chain: &mut Chain,
     admin: &Account,
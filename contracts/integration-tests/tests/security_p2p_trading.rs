@@ -3,8 +3,8 @@
 use cis2_security::{Cis2SecurityTestClient, Cis2TestClient};
 use compliance::init_nationalities;
 use concordium_cis2::{
-    BalanceOfQuery, BalanceOfQueryParams, BalanceOfQueryResponse, OperatorUpdate, TokenAmountU64,
-    TokenIdU64, TokenIdUnit, UpdateOperator,
+    AdditionalData, BalanceOfQuery, BalanceOfQueryParams, BalanceOfQueryResponse, OperatorUpdate,
+    Receiver, TokenAmountU64, TokenIdU64, TokenIdUnit, Transfer, UpdateOperator,
 };
 use concordium_protocols::concordium_cis2_security::{
     AddTokenParams, AgentWithRoles, Identity, SecurityParams, TokenAmountSecurity, TokenUId,
@@ -17,10 +17,13 @@ use concordium_std::{AccountAddress, Amount};
 use contract_base::{ContractPayloads, ContractTestClient};
 use euroe::EuroETestClient;
 use identity_registry::IdentityRegistryTestClient;
+use integration_tests::cis2_conversions::to_token_id_vec;
 use integration_tests::*;
 use security_p2p_trading::{
-    AddMarketParams, ExchangeParams, Market, MintMarket, MintParams, TokenIdCalculation,
-    TransferMarket,
+    AddMarketParams, EscrowAction, EscrowExchangeParams, EscrowSettlement, ExchangeParams,
+    FillOrderParams, Market, MarketFee, MintMarket, MintParams, MintPriceTier, Order,
+    OrderBookMarket, OrderSettlement, OrderSide, PlaceEscrowOrderParams, PlaceOrderParams,
+    TokenIdCalculation, TransferMarket, TransferSettlement, WithdrawLiquidityParams,
 };
 use security_p2p_trading_client::P2PTradeTestClient;
 use security_sft_multi_client::SftMultiTestClient;
@@ -29,6 +32,7 @@ const METADATA_URL_SFT_REWARDS: &str = "example2.com";
 const ADMIN: AccountAddress = AccountAddress([0; 32]);
 const HOLDER: AccountAddress = AccountAddress([2; 32]);
 const HOLDER_2: AccountAddress = AccountAddress([3; 32]);
+const FEE_RECEIVER: AccountAddress = AccountAddress([4; 32]);
 const COMPLIANT_NATIONALITIES: [&str; 2] = ["IN", "US"];
 const DEFAULT_ACC_BALANCE: Amount = Amount {
     micro_ccd: 1_000_000_000_u64,
@@ -50,7 +54,7 @@ pub fn normal_flow_sft_multi() {
     let trading_contract =
         P2PTradeTestClient::init(&mut chain, &admin, &security_p2p_trading::InitParam {
             currency: TokenUId {
-                id:       euroe_token_id,
+                id:       to_token_id_vec(euroe_token_id),
                 contract: euroe_contract.contract_address(),
             },
             agents:   vec![],
@@ -79,6 +83,7 @@ pub fn normal_flow_sft_multi() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     ir_contract
@@ -91,6 +96,7 @@ pub fn normal_flow_sft_multi() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
 
@@ -135,6 +141,9 @@ pub fn normal_flow_sft_multi() {
                 sell_rate:           rate,
                 max_currency_amount: TokenAmountU64(10_000),
                 max_token_amount:    TokenAmountU64(10),
+                fee:                 None,
+                currency_token:      None,
+                settlement:          TransferSettlement::Operator,
             }),
         })
         .expect("add market");
@@ -202,6 +211,9 @@ pub fn normal_flow_sft_multi() {
             max_token_amount:    TokenAmountU64(20),
             // 10_000 Initial + 10_000 Sold
             max_currency_amount: TokenAmountU64(0),
+            fee:                 None,
+            currency_token:      None,
+            settlement:          TransferSettlement::Operator,
         })
     );
 
@@ -275,6 +287,9 @@ pub fn normal_flow_sft_multi() {
             max_token_amount:    TokenAmountU64(10),
             // 0 Initial + 10_000 Sold
             max_currency_amount: TokenAmountU64(10_000),
+            fee:                 None,
+            currency_token:      None,
+            settlement:          TransferSettlement::Operator,
         })
     );
 }
@@ -297,7 +312,7 @@ pub fn test_flow_mint_sft_multi() {
     let trading_contract =
         P2PTradeTestClient::init(&mut chain, &admin, &security_p2p_trading::InitParam {
             currency: TokenUId {
-                id:       euroe_token_id,
+                id:       to_token_id_vec(euroe_token_id),
                 contract: euroe_contract.contract_address(),
             },
             agents:   vec![],
@@ -325,6 +340,7 @@ pub fn test_flow_mint_sft_multi() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     let token_contract = create_token_contract_multi(
@@ -349,7 +365,7 @@ pub fn test_flow_mint_sft_multi() {
                 liquidity_provider: liquidity_provider.address,
                 rate,
                 token_id: TokenIdCalculation {
-                    diff:   Duration::from_days(1), // 1 day
+                    diff:          Duration::from_days(1), // 1 day
                     start:         now,
                     base_token_id: TokenIdU64(0),
                 },
@@ -358,6 +374,12 @@ pub fn test_flow_mint_sft_multi() {
                     url:  METADATA_URL_SFT_REWARDS.to_string(),
                 },
                 max_token_amount: TokenAmountU64(20),
+                open_time: now,
+                close_time: now.checked_add(Duration::from_days(365)).unwrap(),
+                price_tiers: vec![],
+                max_investor_token_amount: None,
+                fee: None,
+                currency_token: None,
             }),
         })
         .expect("add mint market");
@@ -455,6 +477,1085 @@ pub fn test_flow_mint_sft_multi() {
         .expect_err("mint should fail");
 }
 
+#[test]
+pub fn test_mint_tiers_and_limits_sft_multi() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain
+        .tick_block_time(Duration::from_millis(1))
+        .expect("tick block time");
+    let buyer = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    chain.create_account(buyer.clone());
+    let liquidity_provider = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(liquidity_provider.clone());
+    let fee_receiver = Account::new(FEE_RECEIVER, DEFAULT_ACC_BALANCE);
+    chain.create_account(fee_receiver.clone());
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+
+    let euroe_token_id = TokenIdUnit();
+    let trading_contract =
+        P2PTradeTestClient::init(&mut chain, &admin, &security_p2p_trading::InitParam {
+            currency: TokenUId {
+                id:       to_token_id_vec(euroe_token_id),
+                contract: euroe_contract.contract_address(),
+            },
+            agents:   vec![],
+        })
+        .expect("init trading contract");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  buyer.address.into(),
+            amount: TokenAmountU64(20_000),
+        })
+        .expect("euroe mint");
+    euroe_contract
+        .update_operator_single(&mut chain, &buyer, &UpdateOperator {
+            update:   OperatorUpdate::Add,
+            operator: trading_contract.contract_address().into(),
+        })
+        .expect("update operator");
+    ir_contract
+        .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+            address:  buyer.address.into(),
+            identity: Identity {
+                credentials: vec![],
+                attributes:  vec![IdentityAttribute {
+                    tag:   NATIONALITY.0,
+                    value: COMPLIANT_NATIONALITIES[1].to_string(),
+                }],
+            },
+            validity: None,
+        })
+        .expect("register identity");
+    let token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![AgentWithRoles {
+            address: trading_contract.contract_address().into(),
+            roles:   vec![
+                security_sft_multi::types::AgentRole::AddToken,
+                security_sft_multi::types::AgentRole::Mint,
+            ],
+        }],
+    );
+    let early_bird_rate = Rate::new(500, 1).unwrap();
+    let rate = Rate::new(1000, 1).unwrap();
+    let now = chain.block_time();
+    let mint_market = MintMarket {
+        liquidity_provider: liquidity_provider.address,
+        rate,
+        token_id: TokenIdCalculation {
+            diff:          Duration::from_days(1),
+            start:         now,
+            base_token_id: TokenIdU64(0),
+        },
+        token_metadata_url: security_sft_multi::types::ContractMetadataUrl {
+            hash: None,
+            url:  METADATA_URL_SFT_REWARDS.to_string(),
+        },
+        max_token_amount: TokenAmountU64(100),
+        open_time: now.checked_add(Duration::from_days(1)).unwrap(),
+        close_time: now.checked_add(Duration::from_days(2)).unwrap(),
+        price_tiers: vec![MintPriceTier {
+            rate:         early_bird_rate,
+            token_amount: TokenAmountU64(10),
+        }],
+        max_investor_token_amount: Some(TokenAmountU64(20)),
+        fee: Some(MarketFee {
+            rate:     Rate::new(1, 100).unwrap(),
+            receiver: fee_receiver.address,
+        }),
+        currency_token: None,
+    };
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::Mint(MintMarket {
+                close_time: mint_market.open_time,
+                ..mint_market.clone()
+            }),
+        })
+        .expect_err("close time should be after open time");
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::Mint(mint_market.clone()),
+        })
+        .expect("add mint market");
+    let mint_market_2 = mint_market;
+    let mint = |chain: &mut Chain, amount: u64, rate: Rate| {
+        trading_contract.mint(chain, &buyer, &MintParams {
+            amount: TokenAmountU64(amount),
+            rate,
+            token_contract: token_contract.contract_address(),
+        })
+    };
+
+    mint(&mut chain, 5, early_bird_rate).expect_err("market not open");
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    mint(&mut chain, 5, rate).expect_err("early bird tier is not sold out");
+    mint(&mut chain, 25, early_bird_rate).expect_err("investor limit exceeded");
+    mint(&mut chain, 5, early_bird_rate).expect("mint early bird tier");
+    // The rest of the early bird tier is minted at its rate and the remaining amount at the market rate
+    let res = mint(&mut chain, 10, early_bird_rate).expect("mint spanning tiers");
+    let exchanged: Vec<_> = res
+        .events()
+        .filter(|(address, _)| address.eq(&trading_contract.contract_address()))
+        .flat_map(|(_, events)| events.iter())
+        .filter_map(|e| match e.parse().expect("parse event") {
+            security_p2p_trading::Event::Exchanged(e) => Some((e.rate, e.token_amount)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(exchanged, vec![
+        (early_bird_rate, TokenAmountU64(5)),
+        (rate, TokenAmountU64(5))
+    ]);
+    mint(&mut chain, 5, early_bird_rate).expect_err("early bird tier sold out");
+    mint(&mut chain, 10, rate).expect_err("investor limit exceeded");
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  buyer.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  liquidity_provider.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  fee_receiver.address.into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        // 1% fee is deducted from the currency received by the liquidity provider
+        BalanceOfQueryResponse(vec![10_000.into(), 9_900.into(), 100.into()])
+    );
+
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    mint(&mut chain, 5, rate).expect_err("market closed");
+
+    // The amounts minted by the investors do not carry over to a new market
+    let now = chain.block_time();
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::Mint(MintMarket {
+                open_time: now,
+                close_time: now.checked_add(Duration::from_days(1)).unwrap(),
+                price_tiers: vec![],
+                ..mint_market_2
+            }),
+        })
+        .expect("add new mint market");
+    mint(&mut chain, 10, rate).expect("mint in new market");
+}
+
+#[test]
+pub fn order_book_flow_sft_multi() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let seller = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    chain.create_account(seller.clone());
+    let buyer = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(buyer.clone());
+
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+
+    let euroe_token_id = TokenIdUnit();
+    let trading_contract =
+        P2PTradeTestClient::init(&mut chain, &admin, &security_p2p_trading::InitParam {
+            currency: TokenUId {
+                id:       to_token_id_vec(euroe_token_id),
+                contract: euroe_contract.contract_address(),
+            },
+            agents:   vec![],
+        })
+        .expect("init trading contract");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  buyer.address.into(),
+            amount: TokenAmountU64(30_000),
+        })
+        .expect("euroe mint");
+    euroe_contract
+        .update_operator_single(&mut chain, &buyer, &UpdateOperator {
+            update:   OperatorUpdate::Add,
+            operator: trading_contract.contract_address().into(),
+        })
+        .expect("update operator");
+    for account in [&seller, &buyer] {
+        ir_contract
+            .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+                address:  account.address.into(),
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: COMPLIANT_NATIONALITIES[1].to_string(),
+                    }],
+                },
+                validity: None,
+            })
+            .expect("register identity");
+    }
+
+    const TOKEN_ID: TokenIdU64 = TokenIdU64(0);
+    let token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![AgentWithRoles {
+            address: trading_contract.contract_address().into(),
+            roles:   vec![security_sft_multi::types::AgentRole::Operator],
+        }],
+    );
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id:       TOKEN_ID,
+            token_metadata: security_sft_multi::types::ContractMetadataUrl {
+                hash: None,
+                url:  METADATA_URL_SFT_REWARDS.to_string(),
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            owners:   vec![security_sft_multi::types::MintParam {
+                amount:  TokenAmountSecurity::new_un_frozen(50.into()),
+                address: seller.address.into(),
+            }],
+            token_id: TOKEN_ID,
+        })
+        .expect("mint");
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::OrderBook(OrderBookMarket {
+                token_id:       TOKEN_ID,
+                fee:            None,
+                currency_token: None,
+            }),
+        })
+        .expect("add market");
+
+    let rate = Rate::new(1000, 1).unwrap();
+    trading_contract
+        .place_order(&mut chain, &seller, &PlaceOrderParams {
+            token_contract: token_contract.contract_address(),
+            side: OrderSide::Sell,
+            amount: TokenAmountU64(20),
+            rate,
+        })
+        .expect("place sell order");
+    assert_eq!(
+        trading_contract
+            .get_order(&mut chain, &admin, &0)
+            .expect("get order")
+            .parse_return_value::<Order>()
+            .expect("parse order"),
+        Order {
+            token_contract: token_contract.contract_address(),
+            token_id: TOKEN_ID,
+            owner: seller.address,
+            side: OrderSide::Sell,
+            rate,
+            amount: TokenAmountU64(20),
+            settlement: OrderSettlement::Operator,
+            // Markets without a currency use the default currency of the contract
+            currency_token: TokenUId {
+                id:       to_token_id_vec(euroe_token_id),
+                contract: euroe_contract.contract_address(),
+            },
+        }
+    );
+
+    trading_contract
+        .fill_order(&mut chain, &buyer, &FillOrderParams {
+            order_id: 0,
+            amount:   TokenAmountU64(5),
+        })
+        .expect("partial fill");
+    trading_contract
+        .fill_order(&mut chain, &buyer, &FillOrderParams {
+            order_id: 0,
+            amount:   TokenAmountU64(20),
+        })
+        .expect_err("fill more than open amount");
+    trading_contract
+        .fill_order(&mut chain, &seller, &FillOrderParams {
+            order_id: 0,
+            amount:   TokenAmountU64(5),
+        })
+        .expect_err("fill own order");
+    trading_contract
+        .fill_order(&mut chain, &buyer, &FillOrderParams {
+            order_id: 0,
+            amount:   TokenAmountU64(15),
+        })
+        .expect("full fill");
+    trading_contract
+        .get_order(&mut chain, &admin, &0)
+        .expect_err("filled order is removed");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  seller.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  buyer.address.into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![30.into(), 20.into()])
+    );
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  seller.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  buyer.address.into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![20_000.into(), 10_000.into()])
+    );
+
+    trading_contract
+        .place_order(&mut chain, &buyer, &PlaceOrderParams {
+            token_contract: token_contract.contract_address(),
+            side: OrderSide::Buy,
+            amount: TokenAmountU64(10),
+            rate,
+        })
+        .expect("place buy order");
+    trading_contract
+        .cancel_order(&mut chain, &seller, &1)
+        .expect_err("only the owner can cancel");
+    trading_contract
+        .cancel_order(&mut chain, &buyer, &1)
+        .expect("cancel order");
+    trading_contract
+        .fill_order(&mut chain, &seller, &FillOrderParams {
+            order_id: 1,
+            amount:   TokenAmountU64(10),
+        })
+        .expect_err("fill cancelled order");
+}
+
+#[test]
+pub fn order_book_escrow_flow_sft_multi() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let seller = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    chain.create_account(seller.clone());
+    let buyer = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(buyer.clone());
+
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+
+    let euroe_token_id = TokenIdUnit();
+    let trading_contract =
+        P2PTradeTestClient::init(&mut chain, &admin, &security_p2p_trading::InitParam {
+            currency: TokenUId {
+                id:       to_token_id_vec(euroe_token_id),
+                contract: euroe_contract.contract_address(),
+            },
+            agents:   vec![],
+        })
+        .expect("init trading contract");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  buyer.address.into(),
+            amount: TokenAmountU64(30_000),
+        })
+        .expect("euroe mint");
+    // The trading contract holds the escrowed security tokens and hence needs an identity
+    for address in [
+        Address::Account(seller.address),
+        Address::Account(buyer.address),
+        Address::Contract(trading_contract.contract_address()),
+    ] {
+        ir_contract
+            .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+                address,
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: COMPLIANT_NATIONALITIES[1].to_string(),
+                    }],
+                },
+                validity: None,
+            })
+            .expect("register identity");
+    }
+
+    const TOKEN_ID: TokenIdU64 = TokenIdU64(0);
+    let token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![],
+    );
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id:       TOKEN_ID,
+            token_metadata: security_sft_multi::types::ContractMetadataUrl {
+                hash: None,
+                url:  METADATA_URL_SFT_REWARDS.to_string(),
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            owners:   vec![security_sft_multi::types::MintParam {
+                amount:  TokenAmountSecurity::new_un_frozen(50.into()),
+                address: seller.address.into(),
+            }],
+            token_id: TOKEN_ID,
+        })
+        .expect("mint");
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::OrderBook(OrderBookMarket {
+                token_id:       TOKEN_ID,
+                fee:            None,
+                currency_token: None,
+            }),
+        })
+        .expect("add market");
+
+    let rate = Rate::new(1000, 1).unwrap();
+    let expiry = chain
+        .block_time()
+        .checked_add(Duration::from_days(1))
+        .unwrap();
+    let escrow_receiver = |entrypoint: &str| {
+        Receiver::Contract(
+            trading_contract.contract_address(),
+            OwnedEntrypointName::new_unchecked(entrypoint.to_string()),
+        )
+    };
+    let escrow_data = |action: &EscrowAction| AdditionalData::from(to_bytes(action));
+
+    let place_sell = EscrowAction::PlaceOrder(PlaceEscrowOrderParams {
+        token_contract: token_contract.contract_address(),
+        amount: TokenAmountU64(20),
+        rate,
+        expiry,
+    });
+    token_contract
+        .transfer_single(&mut chain, &seller, Transfer {
+            token_id: TOKEN_ID,
+            amount:   TokenAmountU64(19),
+            from:     seller.address.into(),
+            to:       escrow_receiver("onReceivingSecurity"),
+            data:     escrow_data(&place_sell),
+        })
+        .expect_err("deposit does not match order amount");
+    token_contract
+        .transfer_single(&mut chain, &seller, Transfer {
+            token_id: TOKEN_ID,
+            amount:   TokenAmountU64(20),
+            from:     seller.address.into(),
+            to:       escrow_receiver("onReceivingSecurity"),
+            data:     escrow_data(&place_sell),
+        })
+        .expect("place escrow sell order");
+    assert_eq!(
+        trading_contract
+            .get_order(&mut chain, &admin, &0)
+            .expect("get order")
+            .parse_return_value::<Order>()
+            .expect("parse order"),
+        Order {
+            token_contract: token_contract.contract_address(),
+            token_id: TOKEN_ID,
+            owner: seller.address,
+            side: OrderSide::Sell,
+            rate,
+            amount: TokenAmountU64(20),
+            settlement: OrderSettlement::Escrow(EscrowSettlement { expiry }),
+            // Markets without a currency use the default currency of the contract
+            currency_token: TokenUId {
+                id:       to_token_id_vec(euroe_token_id),
+                contract: euroe_contract.contract_address(),
+            },
+        }
+    );
+    trading_contract
+        .fill_order(&mut chain, &buyer, &FillOrderParams {
+            order_id: 0,
+            amount:   TokenAmountU64(5),
+        })
+        .expect_err("escrow orders can only be filled by depositing");
+
+    euroe_contract
+        .transfer_single(&mut chain, &buyer, Transfer {
+            token_id: euroe_token_id,
+            amount:   TokenAmountU64(5_000),
+            from:     buyer.address.into(),
+            to:       escrow_receiver("onReceivingCurrency"),
+            data:     escrow_data(&EscrowAction::FillOrder(0)),
+        })
+        .expect("fill escrow order");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  seller.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  buyer.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  trading_contract.contract_address().into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![30.into(), 5.into(), 15.into()])
+    );
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  seller.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  buyer.address.into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![5_000.into(), 25_000.into()])
+    );
+
+    trading_contract
+        .refund_order(&mut chain, &admin, &0)
+        .expect_err("order not expired");
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    euroe_contract
+        .transfer_single(&mut chain, &buyer, Transfer {
+            token_id: euroe_token_id,
+            amount:   TokenAmountU64(5_000),
+            from:     buyer.address.into(),
+            to:       escrow_receiver("onReceivingCurrency"),
+            data:     escrow_data(&EscrowAction::FillOrder(0)),
+        })
+        .expect_err("fill expired order");
+    trading_contract
+        .refund_order(&mut chain, &admin, &0)
+        .expect("refund expired order");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  seller.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  trading_contract.contract_address().into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![45.into(), 0.into()])
+    );
+}
+
+#[test]
+pub fn transfer_escrow_flow_sft_multi() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let seller = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    chain.create_account(seller.clone());
+    let liquidity_provider = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(liquidity_provider.clone());
+    let fee_receiver = Account::new(FEE_RECEIVER, DEFAULT_ACC_BALANCE);
+    chain.create_account(fee_receiver.clone());
+
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+
+    let euroe_token_id = TokenIdUnit();
+    let trading_contract =
+        P2PTradeTestClient::init(&mut chain, &admin, &security_p2p_trading::InitParam {
+            currency: TokenUId {
+                id:       to_token_id_vec(euroe_token_id),
+                contract: euroe_contract.contract_address(),
+            },
+            agents:   vec![],
+        })
+        .expect("init trading contract");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  liquidity_provider.address.into(),
+            amount: TokenAmountU64(30_000),
+        })
+        .expect("euroe mint");
+    // The trading contract holds the escrowed liquidity and hence needs an identity
+    for address in [
+        Address::Account(seller.address),
+        Address::Account(liquidity_provider.address),
+        Address::Contract(trading_contract.contract_address()),
+    ] {
+        ir_contract
+            .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+                address,
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: COMPLIANT_NATIONALITIES[1].to_string(),
+                    }],
+                },
+                validity: None,
+            })
+            .expect("register identity");
+    }
+
+    const TOKEN_ID: TokenIdU64 = TokenIdU64(0);
+    // Neither the trader nor the liquidity provider grant operator rights to the trading contract
+    let token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![],
+    );
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id:       TOKEN_ID,
+            token_metadata: security_sft_multi::types::ContractMetadataUrl {
+                hash: None,
+                url:  METADATA_URL_SFT_REWARDS.to_string(),
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            owners:   vec![
+                security_sft_multi::types::MintParam {
+                    amount:  TokenAmountSecurity::new_un_frozen(50.into()),
+                    address: seller.address.into(),
+                },
+                security_sft_multi::types::MintParam {
+                    amount:  TokenAmountSecurity::new_un_frozen(20.into()),
+                    address: liquidity_provider.address.into(),
+                },
+            ],
+            token_id: TOKEN_ID,
+        })
+        .expect("mint");
+
+    let rate = Rate::new(1000, 1).unwrap();
+    let fee = MarketFee {
+        rate:     Rate::new(1, 10).unwrap(),
+        receiver: fee_receiver.address,
+    };
+    let market = TransferMarket {
+        token_id:            TOKEN_ID,
+        liquidity_provider:  liquidity_provider.address,
+        buy_rate:            rate,
+        sell_rate:           rate,
+        max_currency_amount: TokenAmountU64(10_000),
+        max_token_amount:    TokenAmountU64(10),
+        fee:                 Some(fee),
+        currency_token:      None,
+        settlement:          TransferSettlement::Escrow,
+    };
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::Transfer(market.clone()),
+        })
+        .expect_err("escrowed liquidity can only be deposited");
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::Transfer(TransferMarket {
+                max_currency_amount: TokenAmountU64(0),
+                max_token_amount: TokenAmountU64(0),
+                ..market.clone()
+            }),
+        })
+        .expect("add market");
+
+    let escrow_receiver = |entrypoint: &str| {
+        Receiver::Contract(
+            trading_contract.contract_address(),
+            OwnedEntrypointName::new_unchecked(entrypoint.to_string()),
+        )
+    };
+    let escrow_data = |action: &EscrowAction| AdditionalData::from(to_bytes(action));
+    let deposit = EscrowAction::DepositLiquidity(token_contract.contract_address());
+    token_contract
+        .transfer_single(&mut chain, &seller, Transfer {
+            token_id: TOKEN_ID,
+            amount:   TokenAmountU64(10),
+            from:     seller.address.into(),
+            to:       escrow_receiver("onReceivingSecurity"),
+            data:     escrow_data(&deposit),
+        })
+        .expect_err("only the liquidity provider can deposit liquidity");
+    token_contract
+        .transfer_single(&mut chain, &liquidity_provider, Transfer {
+            token_id: TOKEN_ID,
+            amount:   TokenAmountU64(10),
+            from:     liquidity_provider.address.into(),
+            to:       escrow_receiver("onReceivingSecurity"),
+            data:     escrow_data(&deposit),
+        })
+        .expect("deposit token liquidity");
+    euroe_contract
+        .transfer_single(&mut chain, &liquidity_provider, Transfer {
+            token_id: euroe_token_id,
+            amount:   TokenAmountU64(10_000),
+            from:     liquidity_provider.address.into(),
+            to:       escrow_receiver("onReceivingCurrency"),
+            data:     escrow_data(&deposit),
+        })
+        .expect("deposit currency liquidity");
+
+    trading_contract
+        .sell(&mut chain, &seller, &ExchangeParams {
+            amount: TokenAmountU64(10),
+            rate,
+            contract: token_contract.contract_address(),
+        })
+        .expect_err("escrowed markets are traded by depositing");
+    let exchange = EscrowExchangeParams {
+        token_contract: token_contract.contract_address(),
+        rate,
+    };
+    token_contract
+        .transfer_single(&mut chain, &seller, Transfer {
+            token_id: TOKEN_ID,
+            amount:   TokenAmountU64(10),
+            from:     seller.address.into(),
+            to:       escrow_receiver("onReceivingSecurity"),
+            data:     escrow_data(&EscrowAction::Sell(exchange.clone())),
+        })
+        .expect("sell");
+    token_contract
+        .transfer_single(&mut chain, &seller, Transfer {
+            token_id: TOKEN_ID,
+            amount:   TokenAmountU64(1),
+            from:     seller.address.into(),
+            to:       escrow_receiver("onReceivingSecurity"),
+            data:     escrow_data(&EscrowAction::Sell(exchange.clone())),
+        })
+        .expect_err("escrowed currency liquidity exhausted");
+
+    let buyer = seller;
+    euroe_contract
+        .transfer_single(&mut chain, &buyer, Transfer {
+            token_id: euroe_token_id,
+            amount:   TokenAmountU64(5_000),
+            from:     buyer.address.into(),
+            to:       escrow_receiver("onReceivingCurrency"),
+            data:     escrow_data(&EscrowAction::Buy(exchange)),
+        })
+        .expect("buy");
+    assert_eq!(
+        trading_contract
+            .get_market(&mut chain, &admin, &token_contract.contract_address())
+            .expect("get market")
+            .parse_return_value::<Market>()
+            .expect("parse market"),
+        Market::Transfer(TransferMarket {
+            // 10 Deposited + 10 Sold - 5 Bought
+            max_token_amount: TokenAmountU64(15),
+            // 10_000 Deposited - 10_000 Sold + 5_000 Bought - 500 Fee
+            max_currency_amount: TokenAmountU64(4_500),
+            ..market
+        })
+    );
+
+    trading_contract
+        .withdraw_liquidity(&mut chain, &buyer, &WithdrawLiquidityParams {
+            token_contract:  token_contract.contract_address(),
+            token_amount:    TokenAmountU64(5),
+            currency_amount: TokenAmountU64(1_000),
+        })
+        .expect_err("only the liquidity provider can withdraw liquidity");
+    trading_contract
+        .withdraw_liquidity(&mut chain, &liquidity_provider, &WithdrawLiquidityParams {
+            token_contract:  token_contract.contract_address(),
+            token_amount:    TokenAmountU64(5),
+            currency_amount: TokenAmountU64(1_000),
+        })
+        .expect("withdraw liquidity");
+    // The remaining escrowed liquidity is returned to the liquidity provider
+    trading_contract
+        .remove_market(&mut chain, &admin, &token_contract.contract_address())
+        .expect("remove market");
+
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  buyer.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  liquidity_provider.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  trading_contract.contract_address().into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![45.into(), 25.into(), 0.into()])
+    );
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  buyer.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  liquidity_provider.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  fee_receiver.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  trading_contract.contract_address().into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![4_000.into(), 24_500.into(), 1_500.into(), 0.into()])
+    );
+}
+
+#[test]
+pub fn test_market_currency_sft_multi() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let buyer = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    chain.create_account(buyer.clone());
+    let liquidity_provider = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(liquidity_provider.clone());
+
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+    // A second stable coin in which the market is priced
+    let usd_contract = EuroETestClient::init(&mut chain, &admin, &()).expect("init usd");
+    usd_contract
+        .grant_role(&mut chain, &admin, &euroe::RoleTypes {
+            adminrole: admin.address.into(),
+            blockrole: admin.address.into(),
+            burnrole:  admin.address.into(),
+            mintrole:  admin.address.into(),
+            pauserole: admin.address.into(),
+        })
+        .expect("grant role");
+
+    let euroe_token_id = TokenIdUnit();
+    let trading_contract =
+        P2PTradeTestClient::init(&mut chain, &admin, &security_p2p_trading::InitParam {
+            currency: TokenUId {
+                id:       to_token_id_vec(euroe_token_id),
+                contract: euroe_contract.contract_address(),
+            },
+            agents:   vec![],
+        })
+        .expect("init trading contract");
+    for currency_contract in [euroe_contract, usd_contract] {
+        currency_contract
+            .mint(&mut chain, &admin, &euroe::MintParams {
+                owner:  buyer.address.into(),
+                amount: TokenAmountU64(10_000),
+            })
+            .expect("currency mint");
+        currency_contract
+            .update_operator_single(&mut chain, &buyer, &UpdateOperator {
+                update:   OperatorUpdate::Add,
+                operator: trading_contract.contract_address().into(),
+            })
+            .expect("update operator");
+    }
+
+    for account in [&buyer, &liquidity_provider] {
+        ir_contract
+            .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+                address:  account.address.into(),
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: COMPLIANT_NATIONALITIES[1].to_string(),
+                    }],
+                },
+                validity: None,
+            })
+            .expect("register identity");
+    }
+
+    const TOKEN_ID: TokenIdU64 = TokenIdU64(0);
+    let token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![AgentWithRoles {
+            address: trading_contract.contract_address().into(),
+            roles:   vec![security_sft_multi::types::AgentRole::Operator],
+        }],
+    );
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id:       TOKEN_ID,
+            token_metadata: security_sft_multi::types::ContractMetadataUrl {
+                hash: None,
+                url:  METADATA_URL_SFT_REWARDS.to_string(),
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            owners:   vec![security_sft_multi::types::MintParam {
+                amount:  TokenAmountSecurity::new_un_frozen(50.into()),
+                address: liquidity_provider.address.into(),
+            }],
+            token_id: TOKEN_ID,
+        })
+        .expect("mint");
+
+    let rate = Rate::new(1000, 1).unwrap();
+    trading_contract
+        .add_market(&mut chain, &admin, &AddMarketParams {
+            token_contract: token_contract.contract_address(),
+            market:         Market::Transfer(TransferMarket {
+                token_id:            TOKEN_ID,
+                liquidity_provider:  liquidity_provider.address,
+                buy_rate:            rate,
+                sell_rate:           rate,
+                max_currency_amount: TokenAmountU64(10_000),
+                max_token_amount:    TokenAmountU64(50),
+                fee:                 None,
+                currency_token:      Some(TokenUId {
+                    id:       to_token_id_vec(euroe_token_id),
+                    contract: usd_contract.contract_address(),
+                }),
+                settlement:          TransferSettlement::Operator,
+            }),
+        })
+        .expect("add market");
+
+    trading_contract
+        .buy(&mut chain, &buyer, &ExchangeParams {
+            amount: TokenAmountU64(5),
+            rate,
+            contract: token_contract.contract_address(),
+        })
+        .expect("buy");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  buyer.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: TOKEN_ID,
+                        address:  liquidity_provider.address.into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![5.into(), 45.into()])
+    );
+    // The market is paid in the currency of the market
+    assert_eq!(
+        usd_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  buyer.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  liquidity_provider.address.into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![5_000.into(), 5_000.into()])
+    );
+    // The default currency of the contract is not used
+    assert_eq!(
+        euroe_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  buyer.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id: euroe_token_id,
+                        address:  liquidity_provider.address.into(),
+                    }
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![10_000.into(), 0.into()])
+    );
+}
+
 fn create_token_contract_multi(
     chain: &mut Chain,
     admin: &Account,
@@ -0,0 +1,996 @@
+#![cfg(test)]
+
+use cis2_security::{Cis2SecurityTestClient, Cis2TestClient};
+use concordium_base::id::types::AccountKeys;
+use concordium_cis2::{
+    AdditionalData, BalanceOfQuery, BalanceOfQueryParams, BalanceOfQueryResponse, Cis2Event,
+    Receiver, TokenIdU64, Transfer,
+};
+use concordium_protocols::concordium_cis2_security::{
+    AddTokenParams, AgentWithRoles, BalanceOfAtQueryParams, BurnParams, Cis2SecurityEvent,
+    Document, DocumentQuery, DocumentUpdatedEvent, FreezeParam, FreezeParams, Governance,
+    PendingRecovery, ProposalEvent, ProposeParams, RecoverEvent, RecoverParam, Recovery,
+    RecoveryDelayUpdatedEvent, RecoveryInitiatedEvent, SetDocumentParams, SnapshotEvent,
+    TokenAmountSecurity, DEFAULT_RECOVERY_DELAY,
+};
+use concordium_protocols::concordium_cis3::{
+    NonceOfQueryParams, NonceOfQueryResponse, PermitMessage, PermitParam,
+};
+use concordium_protocols::rate::Rate;
+use concordium_smart_contract_testing::*;
+use concordium_std::{
+    to_bytes, AccountSignatures, CredentialSignatures, OwnedEntrypointName, Signature,
+    SignatureEd25519,
+};
+use contract_base::{ContractPayloads, ContractTestClient};
+use integration_tests::*;
+use security_sft_multi::types::*;
+use security_sft_multi_client::SftMultiTestClient;
+use sha2::{Digest, Sha256};
+
+const ADMIN: AccountAddress = AccountAddress([0; 32]);
+const HOLDER: AccountAddress = AccountAddress([2; 32]);
+const HOLDER_2: AccountAddress = AccountAddress([3; 32]);
+const FORCED_TRANSFER_AGENT: AccountAddress = AccountAddress([4; 32]);
+const SPONSOR: AccountAddress = AccountAddress([7; 32]);
+const APPROVERS: [AccountAddress; 3] = [
+    AccountAddress([10; 32]),
+    AccountAddress([11; 32]),
+    AccountAddress([12; 32]),
+];
+const DEFAULT_ACC_BALANCE: Amount = Amount {
+    micro_ccd: 1_000_000_000_u64,
+};
+
+#[test]
+fn corporate_actions() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let holder_2 = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    chain.create_account(holder.clone());
+    chain.create_account(holder_2.clone());
+
+    security_sft_multi_client::deploy_module(&mut chain, &admin);
+    let token_contract = SftMultiTestClient::init(&mut chain, &admin, &InitParam {
+        security: None,
+        agents:   vec![],
+    })
+    .expect("init token contract");
+
+    let token_0 = TokenIdU64(0);
+    let token_1 = TokenIdU64(1);
+    for token_id in [token_0, token_1] {
+        token_contract
+            .add_token(&mut chain, &admin, &AddTokenParams {
+                token_id,
+                token_metadata: ContractMetadataUrl {
+                    url:  "example.com".to_string(),
+                    hash: None,
+                },
+            })
+            .expect("add token");
+    }
+    token_contract
+        .mint(&mut chain, &admin, &MintParams {
+            token_id: token_0,
+            owners:   vec![
+                MintParam {
+                    address: Receiver::Account(holder.address),
+                    amount:  TokenAmountSecurity::new_un_frozen(12.into()),
+                },
+                MintParam {
+                    address: Receiver::Account(holder_2.address),
+                    amount:  TokenAmountSecurity::new_un_frozen(5.into()),
+                },
+            ],
+        })
+        .expect("mint");
+    token_contract
+        .freeze(&mut chain, &admin, &FreezeParams {
+            owner:  holder.address.into(),
+            tokens: vec![FreezeParam {
+                token_id:     token_0,
+                token_amount: 2.into(),
+            }],
+        })
+        .expect("freeze");
+
+    let balance = |chain: &Chain, token_id: TokenId, account: &Account| {
+        token_contract
+            .balance_of_single(chain, &admin, token_id, account.address.into())
+            .expect("balance of")
+    };
+
+    // Split: every unit of token 0 becomes 10 units of token 0.
+    let split = CorporateAction {
+        from_token_id: token_0,
+        to_token_id:   token_0,
+        rate:          Rate::new(10, 1).unwrap(),
+    };
+    token_contract
+        .create_corporate_action(&mut chain, &holder, &split)
+        .expect_err("non agent creating corporate action");
+    token_contract
+        .create_corporate_action(&mut chain, &admin, &CorporateAction {
+            from_token_id: token_0,
+            to_token_id:   TokenIdU64(2),
+            rate:          Rate::new(1, 1).unwrap(),
+        })
+        .expect_err("corporate action to unknown token");
+    let split_id: u64 = token_contract
+        .create_corporate_action(&mut chain, &admin, &split)
+        .expect("create split")
+        .parse_return_value()
+        .expect("parse corporate action id");
+    assert_eq!(
+        token_contract
+            .corporate_action(&chain, &admin, split_id)
+            .expect("corporate action"),
+        split
+    );
+    token_contract
+        .transfer_single(&mut chain, &holder, Transfer {
+            token_id: token_0,
+            amount:   1.into(),
+            from:     holder.address.into(),
+            to:       Receiver::Account(holder_2.address),
+            data:     AdditionalData::empty(),
+        })
+        .expect_err("transfer while corporate action is in progress");
+
+    let res = token_contract
+        .apply_corporate_action(&mut chain, &admin, &ApplyCorporateActionParams {
+            action_id: split_id,
+            holders:   vec![holder.address.into()],
+        })
+        .expect("apply split holder");
+    token_contract
+        .complete_corporate_action(&mut chain, &admin, split_id)
+        .expect_err("complete split before all holders are converted");
+    let events = parse_events(&res);
+    assert!(events.iter().any(|e| matches!(
+        e,
+        Event::Cis2(Cis2Event::Burn(burn)) if burn.amount == 12.into()
+    )));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        Event::Cis2(Cis2Event::Mint(mint)) if mint.amount == 120.into()
+    )));
+    // Replaying a page which contains already converted holders is a no-op for them.
+    token_contract
+        .apply_corporate_action(&mut chain, &admin, &ApplyCorporateActionParams {
+            action_id: split_id,
+            holders:   vec![holder.address.into(), holder_2.address.into()],
+        })
+        .expect("apply split holders");
+    assert_eq!(balance(&chain, token_0, &holder), 120.into());
+    assert_eq!(balance(&chain, token_0, &holder_2), 50.into());
+    token_contract
+        .complete_corporate_action(&mut chain, &admin, split_id)
+        .expect("complete split");
+    token_contract
+        .corporate_action(&chain, &admin, split_id)
+        .expect_err("completed corporate action");
+
+    // Conversion: every 3 units of token 0 become 1 unit of token 1.
+    let conversion_id: u64 = token_contract
+        .create_corporate_action(&mut chain, &admin, &CorporateAction {
+            from_token_id: token_0,
+            to_token_id:   token_1,
+            rate:          Rate::new(1, 3).unwrap(),
+        })
+        .expect("create conversion")
+        .parse_return_value()
+        .expect("parse corporate action id");
+    token_contract
+        .apply_corporate_action(&mut chain, &admin, &ApplyCorporateActionParams {
+            action_id: conversion_id,
+            holders:   vec![holder_2.address.into()],
+        })
+        .expect("apply conversion holder");
+    token_contract
+        .complete_corporate_action(&mut chain, &admin, conversion_id)
+        .expect_err("complete conversion before all holders are converted");
+    token_contract
+        .apply_corporate_action(&mut chain, &admin, &ApplyCorporateActionParams {
+            action_id: conversion_id,
+            holders:   vec![holder.address.into(), holder_2.address.into()],
+        })
+        .expect("apply conversion");
+    token_contract
+        .complete_corporate_action(&mut chain, &admin, conversion_id)
+        .expect("complete conversion");
+
+    assert_eq!(balance(&chain, token_0, &holder), 0.into());
+    assert_eq!(balance(&chain, token_0, &holder_2), 0.into());
+    // Frozen (20) and un frozen (100) balances are converted separately.
+    assert_eq!(balance(&chain, token_1, &holder), 39.into());
+    assert_eq!(balance(&chain, token_1, &holder_2), 16.into());
+}
+
+#[test]
+fn snapshots() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let holder_2 = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    chain.create_account(holder.clone());
+    chain.create_account(holder_2.clone());
+
+    security_sft_multi_client::deploy_module(&mut chain, &admin);
+    let token_contract = SftMultiTestClient::init(&mut chain, &admin, &InitParam {
+        security: None,
+        agents:   vec![],
+    })
+    .expect("init token contract");
+    let token_id = TokenIdU64(0);
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id,
+            token_metadata: ContractMetadataUrl {
+                url:  "example.com".to_string(),
+                hash: None,
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &MintParams {
+            token_id,
+            owners: vec![MintParam {
+                address: Receiver::Account(holder.address),
+                amount:  TokenAmountSecurity::new_un_frozen(10.into()),
+            }],
+        })
+        .expect("mint");
+
+    let transfer = |chain: &mut Chain, amount: u64| {
+        token_contract
+            .transfer_single(chain, &holder, Transfer {
+                token_id,
+                amount: amount.into(),
+                from: holder.address.into(),
+                to: Receiver::Account(holder_2.address),
+                data: AdditionalData::empty(),
+            })
+            .expect("transfer");
+    };
+    let balances_at = |chain: &Chain, snapshot_id: u64| {
+        token_contract.balance_of_at(chain, &admin, &BalanceOfAtQueryParams {
+            snapshot_id,
+            queries: vec![
+                BalanceOfQuery {
+                    token_id,
+                    address: holder.address.into(),
+                },
+                BalanceOfQuery {
+                    token_id,
+                    address: holder_2.address.into(),
+                },
+            ],
+        })
+    };
+
+    token_contract
+        .snapshot(&mut chain, &holder)
+        .expect_err("non agent taking snapshot");
+    balances_at(&chain, 1).expect_err("snapshot not taken");
+    let res = token_contract
+        .snapshot(&mut chain, &admin)
+        .expect("snapshot 1");
+    assert_eq!(
+        res.parse_return_value::<u64>().expect("parse snapshot id"),
+        1
+    );
+    assert!(matches!(parse_events(&res)[..], [
+        Cis2SecurityEvent::Snapshot(SnapshotEvent { snapshot_id: 1 })
+    ]));
+    // Balances which have not been updated since the snapshot are the current balances.
+    assert_eq!(
+        balances_at(&chain, 1).expect("balances at 1"),
+        BalanceOfQueryResponse(vec![10.into(), 0.into()])
+    );
+
+    transfer(&mut chain, 4);
+    transfer(&mut chain, 1);
+    token_contract
+        .snapshot(&mut chain, &admin)
+        .expect("snapshot 2");
+    transfer(&mut chain, 2);
+
+    assert_eq!(
+        balances_at(&chain, 1).expect("balances at 1"),
+        BalanceOfQueryResponse(vec![10.into(), 0.into()])
+    );
+    assert_eq!(
+        balances_at(&chain, 2).expect("balances at 2"),
+        BalanceOfQueryResponse(vec![5.into(), 5.into()])
+    );
+    assert_eq!(
+        token_contract
+            .balance_of_single(&chain, &admin, token_id, holder.address.into())
+            .expect("balance of"),
+        3.into()
+    );
+    balances_at(&chain, 0).expect_err("invalid snapshot");
+    balances_at(&chain, 3).expect_err("snapshot not taken");
+}
+
+#[test]
+fn governance() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    chain.create_account(holder.clone());
+    let approvers = APPROVERS.map(|address| Account::new(address, DEFAULT_ACC_BALANCE));
+    for approver in approvers.iter() {
+        chain.create_account(approver.clone());
+    }
+
+    security_sft_multi_client::deploy_module(&mut chain, &admin);
+    let token_contract = SftMultiTestClient::init(&mut chain, &admin, &InitParam {
+        security: None,
+        agents:   vec![],
+    })
+    .expect("init token contract");
+    let governance = Governance {
+        approvers: approvers.iter().map(|a| a.address.into()).collect(),
+        threshold: 2,
+        delay:     Duration::from_days(1),
+    };
+    token_contract
+        .set_governance(
+            &mut chain,
+            &admin,
+            &Some(Governance {
+                threshold: 4,
+                ..governance.clone()
+            }),
+        )
+        .expect_err("threshold can not be met");
+    token_contract
+        .set_governance(&mut chain, &holder, &Some(governance.clone()))
+        .expect_err("non owner setting governance");
+    token_contract
+        .set_governance(&mut chain, &admin, &Some(governance.clone()))
+        .expect("set governance");
+
+    // Once governed, agents can only be added by executing a proposal.
+    let mint_agent = AgentWithRoles {
+        address: holder.address.into(),
+        roles:   vec![AgentRole::Mint],
+    };
+    token_contract
+        .add_agent(&mut chain, &admin, &mint_agent)
+        .expect_err("owner adding agent");
+    let add_agent = ProposeParams {
+        entrypoint: OwnedEntrypointName::new_unchecked("addAgent".to_string()),
+        parameter:  to_bytes(&mint_agent),
+    };
+    token_contract
+        .propose(&mut chain, &holder, &add_agent)
+        .expect_err("non approver proposing");
+    let proposal_id: u64 = token_contract
+        .propose(&mut chain, &approvers[0], &add_agent)
+        .expect("propose")
+        .parse_return_value()
+        .expect("parse proposal id");
+    token_contract
+        .execute_proposal(&mut chain, &approvers[0], proposal_id)
+        .expect_err("executing proposal without enough approvals");
+    token_contract
+        .approve_proposal(&mut chain, &approvers[0], proposal_id)
+        .expect_err("approving proposal twice");
+    token_contract
+        .approve_proposal(&mut chain, &approvers[1], proposal_id)
+        .expect("approve proposal");
+    token_contract
+        .execute_proposal(&mut chain, &approvers[2], proposal_id)
+        .expect_err("executing time locked proposal");
+    let proposal = token_contract
+        .proposal(&chain, &admin, proposal_id)
+        .expect("proposal");
+    assert_eq!(proposal.approvals.len(), 2);
+    assert_eq!(proposal.parameter, add_agent.parameter);
+
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    let res = token_contract
+        .execute_proposal(&mut chain, &approvers[2], proposal_id)
+        .expect("execute proposal");
+    assert!(parse_events(&res).iter().any(|e| matches!(
+        e,
+        Cis2SecurityEvent::ProposalExecuted(ProposalEvent { proposal_id: 0, .. })
+    )));
+    assert!(token_contract
+        .is_agent(&mut chain, &admin, &mint_agent)
+        .expect("is agent"));
+    token_contract
+        .proposal(&chain, &admin, proposal_id)
+        .expect_err("executed proposal");
+    token_contract
+        .execute_proposal(&mut chain, &approvers[2], proposal_id)
+        .expect_err("executing proposal twice");
+
+    let proposal_id: u64 = token_contract
+        .propose(&mut chain, &approvers[1], &ProposeParams {
+            entrypoint: OwnedEntrypointName::new_unchecked("removeAgent".to_string()),
+            parameter:  to_bytes(&Address::from(holder.address)),
+        })
+        .expect("propose")
+        .parse_return_value()
+        .expect("parse proposal id");
+    token_contract
+        .cancel_proposal(&mut chain, &holder, proposal_id)
+        .expect_err("non approver cancelling proposal");
+    token_contract
+        .cancel_proposal(&mut chain, &approvers[0], proposal_id)
+        .expect("cancel proposal");
+    token_contract
+        .approve_proposal(&mut chain, &approvers[2], proposal_id)
+        .expect_err("approving cancelled proposal");
+}
+
+#[test]
+fn governed_forced_transfer() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let holder_2 = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    chain.create_account(holder.clone());
+    chain.create_account(holder_2.clone());
+    let agent = Account::new(FORCED_TRANSFER_AGENT, DEFAULT_ACC_BALANCE);
+    chain.create_account(agent.clone());
+    let approvers = APPROVERS.map(|address| Account::new(address, DEFAULT_ACC_BALANCE));
+    for approver in approvers.iter() {
+        chain.create_account(approver.clone());
+    }
+
+    security_sft_multi_client::deploy_module(&mut chain, &admin);
+    let token_contract = SftMultiTestClient::init(&mut chain, &admin, &InitParam {
+        security: None,
+        agents:   vec![],
+    })
+    .expect("init token contract");
+    let token_id = TokenIdU64(0);
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id,
+            token_metadata: ContractMetadataUrl {
+                url:  "example.com".to_string(),
+                hash: None,
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &MintParams {
+            token_id,
+            owners: vec![MintParam {
+                address: Receiver::Account(holder.address),
+                amount:  TokenAmountSecurity::new_un_frozen(10.into()),
+            }],
+        })
+        .expect("mint");
+    token_contract
+        .add_agent(&mut chain, &admin, &AgentWithRoles {
+            address: agent.address.into(),
+            roles:   vec![AgentRole::ForcedTransfer],
+        })
+        .expect("add forced transfer agent");
+    let forced_transfer = |amount: u64| Transfer {
+        token_id,
+        amount: amount.into(),
+        from: holder.address.into(),
+        to: Receiver::Account(holder_2.address),
+        data: AdditionalData::empty(),
+    };
+    token_contract
+        .transfer_single(&mut chain, &agent, forced_transfer(1))
+        .expect("forced transfer by agent");
+
+    token_contract
+        .set_governance(
+            &mut chain,
+            &admin,
+            &Some(Governance {
+                approvers: approvers.iter().map(|a| a.address.into()).collect(),
+                threshold: 2,
+                delay:     Duration::from_days(1),
+            }),
+        )
+        .expect("set governance");
+    // Once governed, a lone forced transfer agent can not transfer the tokens of a holder.
+    token_contract
+        .transfer_single(&mut chain, &agent, forced_transfer(2))
+        .expect_err("forced transfer by agent");
+
+    let proposal_id: u64 = token_contract
+        .propose(&mut chain, &approvers[0], &ProposeParams {
+            entrypoint: OwnedEntrypointName::new_unchecked("transfer".to_string()),
+            parameter:  to_bytes(&concordium_cis2::TransferParams(vec![forced_transfer(2)])),
+        })
+        .expect("propose")
+        .parse_return_value()
+        .expect("parse proposal id");
+    token_contract
+        .approve_proposal(&mut chain, &approvers[1], proposal_id)
+        .expect("approve proposal");
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    token_contract
+        .execute_proposal(&mut chain, &approvers[2], proposal_id)
+        .expect("execute forced transfer proposal");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id,
+                        address: holder.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id,
+                        address: holder_2.address.into(),
+                    },
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![7.into(), 3.into()])
+    );
+}
+
+#[test]
+fn recovery() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let new_account = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    chain.create_account(holder.clone());
+    chain.create_account(new_account.clone());
+
+    security_sft_multi_client::deploy_module(&mut chain, &admin);
+    let token_contract = SftMultiTestClient::init(&mut chain, &admin, &InitParam {
+        security: None,
+        agents:   vec![],
+    })
+    .expect("init token contract");
+    let token_id = TokenIdU64(0);
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id,
+            token_metadata: ContractMetadataUrl {
+                url:  "example.com".to_string(),
+                hash: None,
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &MintParams {
+            token_id,
+            owners: vec![MintParam {
+                address: Receiver::Account(holder.address),
+                amount:  TokenAmountSecurity::new_un_frozen(10.into()),
+            }],
+        })
+        .expect("mint");
+
+    let lost_account: Address = holder.address.into();
+    let recover_param = RecoverParam {
+        lost_account,
+        new_account: new_account.address.into(),
+    };
+    token_contract
+        .recover(&mut chain, &new_account, &recover_param)
+        .expect_err("non agent initiating recovery");
+    let res = token_contract
+        .recover(&mut chain, &admin, &recover_param)
+        .expect("initiate recovery");
+    let execute_after = chain
+        .block_time()
+        .checked_add(DEFAULT_RECOVERY_DELAY)
+        .unwrap();
+    assert!(matches!(parse_events(&res)[..], [
+        Cis2SecurityEvent::RecoveryInitiated(RecoveryInitiatedEvent { execute_after: e, .. })
+    ] if e == execute_after));
+    assert_eq!(
+        token_contract
+            .recovery(&chain, &admin, &lost_account)
+            .expect("recovery"),
+        Recovery {
+            delay:   DEFAULT_RECOVERY_DELAY,
+            pending: Some(PendingRecovery {
+                new_account: new_account.address.into(),
+                execute_after,
+            }),
+        }
+    );
+    token_contract
+        .recover(&mut chain, &admin, &recover_param)
+        .expect_err("recovery already pending");
+    token_contract
+        .execute_recovery(&mut chain, &admin, &lost_account)
+        .expect_err("executing recovery in the challenge window");
+
+    // The holder still controls the account and cancels the recovery.
+    token_contract
+        .cancel_recovery(&mut chain, &new_account, &lost_account)
+        .expect_err("cancelling recovery of another account");
+    let res = token_contract
+        .cancel_recovery(&mut chain, &holder, &lost_account)
+        .expect("cancel recovery");
+    assert!(matches!(parse_events(&res)[..], [
+        Cis2SecurityEvent::RecoveryCancelled(RecoverEvent { .. })
+    ]));
+    assert_eq!(
+        token_contract
+            .recovery(&chain, &admin, &lost_account)
+            .expect("recovery")
+            .pending,
+        None
+    );
+    chain
+        .tick_block_time(DEFAULT_RECOVERY_DELAY)
+        .expect("tick block time");
+    token_contract
+        .execute_recovery(&mut chain, &admin, &lost_account)
+        .expect_err("executing cancelled recovery");
+
+    token_contract
+        .set_recovery_delay(&mut chain, &holder, &Duration::from_days(1))
+        .expect_err("non owner setting recovery delay");
+    let res = token_contract
+        .set_recovery_delay(&mut chain, &admin, &Duration::from_days(1))
+        .expect("set recovery delay");
+    assert!(matches!(parse_events(&res)[..], [
+        Cis2SecurityEvent::RecoveryDelayUpdated(RecoveryDelayUpdatedEvent(delay))
+    ] if delay == Duration::from_days(1)));
+    token_contract
+        .recover(&mut chain, &admin, &recover_param)
+        .expect("initiate recovery");
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    token_contract
+        .execute_recovery(&mut chain, &new_account, &lost_account)
+        .expect_err("non agent executing recovery");
+    let res = token_contract
+        .execute_recovery(&mut chain, &admin, &lost_account)
+        .expect("execute recovery");
+    assert!(matches!(parse_events(&res)[..], [
+        Cis2SecurityEvent::Recovered(RecoverEvent { .. })
+    ]));
+    assert_eq!(
+        token_contract
+            .balance_of_single(&chain, &admin, token_id, new_account.address.into())
+            .expect("balance of"),
+        10.into()
+    );
+    token_contract
+        .cancel_recovery(&mut chain, &holder, &lost_account)
+        .expect_err("cancelling executed recovery");
+    token_contract
+        .recover(&mut chain, &admin, &recover_param)
+        .expect_err("recovering recovered account");
+}
+
+#[test]
+fn documents() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let holder = Account::new(HOLDER, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    chain.create_account(holder.clone());
+
+    security_sft_multi_client::deploy_module(&mut chain, &admin);
+    let token_contract = SftMultiTestClient::init(&mut chain, &admin, &InitParam {
+        security: None,
+        agents:   vec![],
+    })
+    .expect("init token contract");
+    let token_id = TokenIdU64(0);
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id,
+            token_metadata: ContractMetadataUrl {
+                url:  "example.com".to_string(),
+                hash: None,
+            },
+        })
+        .expect("add token");
+
+    let legal_contract = SetDocumentParams {
+        token_id,
+        name: "legal_contract".to_string(),
+        uri: "example.com/legal_contract.pdf".to_string(),
+        content_hash: [1; 32],
+    };
+    let query = DocumentQuery {
+        token_id,
+        name: legal_contract.name.clone(),
+    };
+    token_contract
+        .set_document(&mut chain, &holder, &legal_contract)
+        .expect_err("non agent setting document");
+    token_contract
+        .set_document(&mut chain, &admin, &SetDocumentParams {
+            token_id: TokenIdU64(1),
+            ..legal_contract.clone()
+        })
+        .expect_err("setting document of invalid token");
+    token_contract
+        .set_document(&mut chain, &admin, &SetDocumentParams {
+            name: "".to_string(),
+            ..legal_contract.clone()
+        })
+        .expect_err("setting document without name");
+    let res = token_contract
+        .set_document(&mut chain, &admin, &legal_contract)
+        .expect("set document");
+    let document = Document {
+        uri:          legal_contract.uri.clone(),
+        content_hash: legal_contract.content_hash,
+        timestamp:    chain.block_time(),
+    };
+    assert!(matches!(&parse_events(&res)[..], [
+        Cis2SecurityEvent::DocumentUpdated(DocumentUpdatedEvent { name, document: Some(d), .. })
+    ] if name == "legal_contract" && d == &document));
+    assert_eq!(
+        token_contract
+            .document(&chain, &admin, &query)
+            .expect("document"),
+        Some(document)
+    );
+
+    chain
+        .tick_block_time(Duration::from_days(1))
+        .expect("tick block time");
+    token_contract
+        .set_document(&mut chain, &admin, &SetDocumentParams {
+            content_hash: [2; 32],
+            ..legal_contract.clone()
+        })
+        .expect("update document");
+    token_contract
+        .set_document(&mut chain, &admin, &SetDocumentParams {
+            name: "offering_document".to_string(),
+            ..legal_contract.clone()
+        })
+        .expect("set document");
+    assert_eq!(
+        token_contract
+            .document(&chain, &admin, &query)
+            .expect("document"),
+        Some(Document {
+            uri:          legal_contract.uri.clone(),
+            content_hash: [2; 32],
+            timestamp:    chain.block_time(),
+        })
+    );
+    let mut names: Vec<String> = token_contract
+        .documents(&chain, &admin, &token_id)
+        .expect("documents")
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec![
+        "legal_contract".to_string(),
+        "offering_document".to_string()
+    ]);
+    assert!(token_contract
+        .documents(&chain, &admin, &TokenIdU64(1))
+        .expect("documents")
+        .is_empty());
+
+    token_contract
+        .remove_document(&mut chain, &holder, &query)
+        .expect_err("non agent removing document");
+    let res = token_contract
+        .remove_document(&mut chain, &admin, &query)
+        .expect("remove document");
+    assert!(matches!(parse_events(&res)[..], [
+        Cis2SecurityEvent::DocumentUpdated(DocumentUpdatedEvent { document: None, .. })
+    ]));
+    token_contract
+        .remove_document(&mut chain, &admin, &query)
+        .expect_err("removing removed document");
+    assert_eq!(
+        token_contract
+            .document(&chain, &admin, &query)
+            .expect("document"),
+        None
+    );
+}
+
+#[test]
+fn permit() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    // The holder signs the permits and holds no CCD to pay for transactions.
+    let holder_keys = AccountKeys::singleton(&mut rand::thread_rng());
+    let holder = Account::new_with_keys(
+        HOLDER,
+        AccountBalance::new(Amount::zero(), Amount::zero(), Amount::zero()).unwrap(),
+        (&holder_keys).into(),
+    );
+    let holder_2 = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    let sponsor = Account::new(SPONSOR, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    chain.create_account(admin.clone());
+    chain.create_account(holder.clone());
+    chain.create_account(holder_2.clone());
+    chain.create_account(sponsor.clone());
+
+    security_sft_multi_client::deploy_module(&mut chain, &admin);
+    let token_contract = SftMultiTestClient::init(&mut chain, &admin, &InitParam {
+        security: None,
+        agents:   vec![],
+    })
+    .expect("init token contract");
+    let token_id = TokenIdU64(0);
+    token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id,
+            token_metadata: ContractMetadataUrl {
+                url:  "example.com".to_string(),
+                hash: None,
+            },
+        })
+        .expect("add token");
+    token_contract
+        .mint(&mut chain, &admin, &MintParams {
+            token_id,
+            owners: vec![MintParam {
+                address: Receiver::Account(holder.address),
+                amount:  TokenAmountSecurity::new_un_frozen(50.into()),
+            }],
+        })
+        .expect("mint");
+
+    let transfer: TransferParams = concordium_cis2::TransferParams(vec![Transfer {
+        token_id,
+        amount: 30.into(),
+        from: holder.address.into(),
+        to: Receiver::Account(holder_2.address),
+        data: AdditionalData::empty(),
+    }]);
+    let message = PermitMessage {
+        contract_address: token_contract.contract_address(),
+        nonce:            0,
+        timestamp:        Timestamp::from_timestamp_millis(
+            chain.block_time().timestamp_millis() + 60_000,
+        ),
+        entry_point:      OwnedEntrypointName::new_unchecked("transfer".to_string()),
+        payload:          to_bytes(&transfer),
+    };
+    token_contract
+        .permit(&mut chain, &sponsor, &PermitParam {
+            signature: sign_permit(&holder_keys, holder.address, &message),
+            signer:    holder_2.address,
+            message:   permit_message(&message),
+        })
+        .expect_err("permitted with another signer");
+    let permit = PermitParam {
+        signature: sign_permit(&holder_keys, holder.address, &message),
+        signer: holder.address,
+        message,
+    };
+    token_contract
+        .permit(&mut chain, &sponsor, &permit)
+        .expect("transfer with permit");
+    token_contract
+        .permit(&mut chain, &sponsor, &permit)
+        .expect_err("replayed permit");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &admin, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        token_id,
+                        address: holder.address.into(),
+                    },
+                    BalanceOfQuery {
+                        token_id,
+                        address: holder_2.address.into(),
+                    },
+                ],
+            })
+            .expect("balance of"),
+        BalanceOfQueryResponse(vec![20.into(), 30.into()])
+    );
+    assert_eq!(
+        token_contract
+            .nonce_of(&mut chain, &sponsor, &NonceOfQueryParams {
+                queries: vec![holder.address, holder_2.address],
+            })
+            .expect("nonce of"),
+        NonceOfQueryResponse(vec![1, 0])
+    );
+
+    let burn = BurnParams(vec![Burn {
+        token_id,
+        amount: 10.into(),
+        owner: holder.address.into(),
+    }]);
+    let message = PermitMessage {
+        nonce: 1,
+        entry_point: OwnedEntrypointName::new_unchecked("burn".to_string()),
+        payload: to_bytes(&burn),
+        ..permit_message(&permit.message)
+    };
+    chain
+        .tick_block_time(Duration::from_millis(60_000))
+        .expect("tick block time");
+    token_contract
+        .permit(&mut chain, &sponsor, &PermitParam {
+            signature: sign_permit(&holder_keys, holder.address, &message),
+            signer:    holder.address,
+            message:   permit_message(&message),
+        })
+        .expect_err("permitted expired message");
+    let message = PermitMessage {
+        timestamp: Timestamp::from_timestamp_millis(chain.block_time().timestamp_millis() + 60_000),
+        ..message
+    };
+    token_contract
+        .permit(&mut chain, &sponsor, &PermitParam {
+            signature: sign_permit(&holder_keys, holder.address, &message),
+            signer:    holder.address,
+            message:   permit_message(&message),
+        })
+        .expect("burn with permit");
+    assert_eq!(
+        token_contract
+            .balance_of_single(&chain, &admin, token_id, holder.address.into())
+            .expect("balance of"),
+        10.into()
+    );
+}
+
+fn parse_events(res: &ContractInvokeSuccess) -> Vec<Event> {
+    res.events()
+        .flat_map(|(_, events)| events.iter().map(|e| e.parse().expect("parse event")))
+        .collect()
+}
+
+fn permit_message(message: &PermitMessage) -> PermitMessage {
+    PermitMessage {
+        contract_address: message.contract_address,
+        nonce:            message.nonce,
+        timestamp:        message.timestamp,
+        entry_point:      message.entry_point.clone(),
+        payload:          message.payload.clone(),
+    }
+}
+
+fn sign_permit(
+    keys: &AccountKeys,
+    signer: AccountAddress,
+    message: &PermitMessage,
+) -> AccountSignatures {
+    let hash = message
+        .hash(signer, |data| Sha256::digest(data).into())
+        .expect("hash permit message");
+    let sigs = keys
+        .sign_data(&hash)
+        .into_iter()
+        .map(|(credential_index, signatures)| {
+            (credential_index.index, CredentialSignatures {
+                sigs: signatures
+                    .into_iter()
+                    .map(|(key_index, signature)| {
+                        let signature = signature.sig.try_into().expect("ed25519 signature");
+                        (key_index.0, Signature::Ed25519(SignatureEd25519(signature)))
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+    AccountSignatures { sigs }
+}
@@ -1,4 +1,6 @@
-use concordium_cis2::{TokenIdU64, TokenIdUnit, UpdateOperator};
+use concordium_cis2::{
+    AdditionalData, Receiver, TokenAmountU64, TokenIdU64, TokenIdUnit, Transfer, UpdateOperator,
+};
 use concordium_protocols::concordium_cis2_ext::ContractMetadataUrl;
 use concordium_protocols::concordium_cis2_security::{
     AddTokenParams, AgentWithRoles, MintParam, SecurityParams, TokenAmountSecurity,
@@ -9,7 +11,9 @@ use concordium_rwa_identity_registry::types::{
 };
 use concordium_smart_contract_testing::{Account, Chain};
 use concordium_std::attributes::NATIONALITY;
-use concordium_std::{AccountAddress, Amount, ContractAddress};
+use concordium_std::{
+    from_bytes, to_bytes, AccountAddress, Amount, ContractAddress, Duration, OwnedEntrypointName,
+};
 use integration_tests::cis2_conversions::to_token_id_vec;
 use integration_tests::cis2_security::{Cis2SecurityTestClient, Cis2TestClient};
 use integration_tests::compliance::init_nationalities;
@@ -23,7 +27,11 @@ use integration_tests::{
     compliance, security_mint_fund_client, security_p2p_trading_client, security_sft_multi_client,
     security_sft_single_client,
 };
-use security_sft_multi_yielder::{YieldCalculation, YieldParam, YieldState};
+use security_sft_multi_yielder::{
+    AccrualPeriod, BatchYieldResult, ClaimMode, ClaimParam, ClaimParams, DepositPoolParams,
+    ReturnPoolParams, SetClaimModeParams, SetPoolTokenParams, YieldCalculation,
+    YieldForBatchParams, YieldParam, YieldState,
+};
 
 const ADMIN: AccountAddress = AccountAddress([0; 32]);
 const INVESTOR_1: AccountAddress = AccountAddress([2; 32]);
@@ -101,6 +109,7 @@ fn normal_flow() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity investor 1");
     ir_contract
@@ -113,6 +122,7 @@ fn normal_flow() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity investor 2");
 
@@ -287,6 +297,801 @@ fn normal_flow() {
     );
 }
 
+#[test]
+fn time_based_yields() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+    let treasury = Account::new(TREASURY, DEFAULT_ACC_BALANCE);
+    chain.create_account(treasury.clone());
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    chain.create_account(investor_1.clone());
+
+    let yielder_contract = SftMultiYielderTestClient::init(
+        &mut chain,
+        &admin,
+        &security_sft_multi_yielder::InitParam {
+            agents:   vec![],
+            treasury: treasury.address.into(),
+        },
+    )
+    .expect("init yielder contract");
+    euroe_contract
+        .update_operator_single(&mut chain, &treasury, &UpdateOperator {
+            operator: yielder_contract.contract_address().into(),
+            update:   concordium_cis2::OperatorUpdate::Add,
+        })
+        .expect("add yielder as operator");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  treasury.address.into(),
+            amount: 10_000_000_000.into(),
+        })
+        .expect("euroe mint to treasury");
+    ir_contract
+        .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+            address:  investor_1.address.into(),
+            identity: Identity {
+                credentials: vec![],
+                attributes:  vec![IdentityAttribute {
+                    tag:   NATIONALITY.0,
+                    value: COMPLIANT_NATIONALITIES[1].to_string(),
+                }],
+            },
+            validity: None,
+        })
+        .expect("register identity investor 1");
+
+    let security_token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![AgentWithRoles {
+            address: yielder_contract.contract_address().into(),
+            roles:   vec![
+                security_sft_multi::types::AgentRole::Operator,
+                security_sft_multi::types::AgentRole::Mint,
+            ],
+        }],
+    );
+    let token_ver_0 = TokenIdU64(0);
+    let token_ver_1 = TokenIdU64(1);
+    for token_id in [token_ver_0, token_ver_1] {
+        security_token_contract
+            .add_token(&mut chain, &admin, &AddTokenParams {
+                token_id,
+                token_metadata: ContractMetadataUrl {
+                    url:  "example.com".to_string(),
+                    hash: None,
+                },
+            })
+            .expect("add token wrapped");
+    }
+    security_token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            token_id: token_ver_0,
+            owners:   vec![MintParam {
+                address: investor_1.address.into(),
+                amount:  TokenAmountSecurity::new_un_frozen(1000.into()),
+            }],
+        })
+        .expect("mint wrapped token investor 1");
+
+    // Token version 0 is added without yields so that time based yields accrue from its creation.
+    yielder_contract
+        .upsert_yield(
+            &mut chain,
+            &admin,
+            &security_sft_multi_yielder::UpsertYieldParams {
+                token_contract: security_token_contract.contract_address(),
+                token_id:       token_ver_0,
+                yields:         vec![],
+            },
+        )
+        .expect("upsert token version 0");
+    chain
+        .tick_block_time(Duration::from_days(10))
+        .expect("tick block time");
+    yielder_contract
+        .upsert_yield(
+            &mut chain,
+            &admin,
+            &security_sft_multi_yielder::UpsertYieldParams {
+                token_contract: security_token_contract.contract_address(),
+                token_id:       token_ver_1,
+                yields:         vec![
+                    YieldState {
+                        contract:    euroe_contract.contract_address(),
+                        token_id:    to_token_id_vec(TokenIdUnit()),
+                        calculation: YieldCalculation::TimeSimpleInterest(
+                            // 1 EUR per 100 wrapped tokens per day
+                            Rate::new(1, 100).expect("valid rate"),
+                            AccrualPeriod::Day,
+                        ),
+                    },
+                    YieldState {
+                        contract:    euroe_contract.contract_address(),
+                        token_id:    to_token_id_vec(TokenIdUnit()),
+                        calculation: YieldCalculation::TimeCompoundInterest(
+                            // 1% per day compounded daily
+                            Rate::new(1, 100).expect("valid rate"),
+                            AccrualPeriod::Day,
+                        ),
+                    },
+                ],
+            },
+        )
+        .expect("upsert yield");
+
+    yielder_contract
+        .yield_for(
+            &mut chain,
+            &investor_1,
+            &security_sft_multi_yielder::YieldParams {
+                owner:  investor_1.address,
+                yields: vec![YieldParam {
+                    amount:         1000.into(),
+                    token_contract: security_token_contract.contract_address(),
+                    token_ver_from: token_ver_0,
+                    token_ver_to:   token_ver_1,
+                }],
+            },
+        )
+        .expect("yield for investor 1");
+    assert_eq!(
+        security_token_contract
+            .balance_of_single(&chain, &investor_1, token_ver_1, investor_1.address.into())
+            .expect("balance of investor 1"),
+        1000.into()
+    );
+    // Simple: 1000 * 10 / 100 = 100, Compound: 1000 * (1.01 ^ 10 - 1) = 104.62
+    assert_eq!(
+        euroe_contract
+            .balance_of_single(
+                &chain,
+                &investor_1,
+                TokenIdUnit(),
+                investor_1.address.into(),
+            )
+            .expect("euroe balance of investor 1"),
+        (100 + 104).into()
+    );
+}
+
+#[test]
+fn pool_distribution() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+    let treasury = Account::new(TREASURY, DEFAULT_ACC_BALANCE);
+    chain.create_account(treasury.clone());
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    chain.create_account(investor_1.clone());
+    let investor_2 = Account::new(INVESTOR_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(investor_2.clone());
+
+    let yielder_contract = SftMultiYielderTestClient::init(
+        &mut chain,
+        &admin,
+        &security_sft_multi_yielder::InitParam {
+            agents:   vec![],
+            treasury: treasury.address.into(),
+        },
+    )
+    .expect("init yielder contract");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  admin.address.into(),
+            amount: 1_000.into(),
+        })
+        .expect("euroe mint to admin");
+    for investor in [&investor_1, &investor_2] {
+        ir_contract
+            .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+                address:  investor.address.into(),
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: COMPLIANT_NATIONALITIES[1].to_string(),
+                    }],
+                },
+                validity: None,
+            })
+            .expect("register identity investor");
+    }
+
+    let security_token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![AgentWithRoles {
+            address: yielder_contract.contract_address().into(),
+            roles:   vec![
+                security_sft_multi::types::AgentRole::Operator,
+                security_sft_multi::types::AgentRole::Mint,
+                security_sft_multi::types::AgentRole::Snapshot,
+            ],
+        }],
+    );
+    let token_ver_0 = TokenIdU64(0);
+    let token_ver_1 = TokenIdU64(1);
+    for token_id in [token_ver_0, token_ver_1] {
+        security_token_contract
+            .add_token(&mut chain, &admin, &AddTokenParams {
+                token_id,
+                token_metadata: ContractMetadataUrl {
+                    url:  "example.com".to_string(),
+                    hash: None,
+                },
+            })
+            .expect("add token wrapped");
+    }
+    security_token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            token_id: token_ver_0,
+            owners:   vec![
+                MintParam {
+                    address: investor_1.address.into(),
+                    amount:  TokenAmountSecurity::new_un_frozen(1000.into()),
+                },
+                MintParam {
+                    address: investor_2.address.into(),
+                    amount:  TokenAmountSecurity::new_un_frozen(2000.into()),
+                },
+            ],
+        })
+        .expect("mint wrapped token investors");
+
+    // Harvest revenue of 1000 EUR deposited for token version 1
+    let pool_receiver = Receiver::Contract(
+        yielder_contract.contract_address(),
+        OwnedEntrypointName::new_unchecked("depositPool".to_string()),
+    );
+    let pool_params = DepositPoolParams {
+        token_contract: security_token_contract.contract_address(),
+        token_id:       token_ver_1,
+        token_ids:      vec![token_ver_0],
+        expiry:         chain
+            .block_time()
+            .checked_add(Duration::from_days(10))
+            .expect("expiry"),
+    };
+    euroe_contract
+        .transfer_single(&mut chain, &investor_1, Transfer {
+            token_id: TokenIdUnit(),
+            amount:   TokenAmountU64(0),
+            from:     investor_1.address.into(),
+            to:       pool_receiver.clone(),
+            data:     AdditionalData::from(to_bytes(&pool_params)),
+        })
+        .expect_err("pool deposited by non agent");
+    euroe_contract
+        .transfer_single(&mut chain, &admin, Transfer {
+            token_id: TokenIdUnit(),
+            amount:   TokenAmountU64(1_000),
+            from:     admin.address.into(),
+            to:       pool_receiver.clone(),
+            data:     AdditionalData::from(to_bytes(&pool_params)),
+        })
+        .expect_err("pool deposited from a yield token contract which is not allowed");
+    yielder_contract
+        .set_pool_token(&mut chain, &investor_1, &SetPoolTokenParams {
+            contract: euroe_contract.contract_address(),
+            allowed:  true,
+        })
+        .expect_err("pool token set by non agent");
+    yielder_contract
+        .set_pool_token(&mut chain, &admin, &SetPoolTokenParams {
+            contract: euroe_contract.contract_address(),
+            allowed:  true,
+        })
+        .expect("set pool token");
+    euroe_contract
+        .transfer_single(&mut chain, &admin, Transfer {
+            token_id: TokenIdUnit(),
+            amount:   TokenAmountU64(1_000),
+            from:     admin.address.into(),
+            to:       pool_receiver.clone(),
+            data:     AdditionalData::from(to_bytes(&DepositPoolParams {
+                token_contract: security_token_contract.contract_address(),
+                token_id:       TokenIdU64(2),
+                token_ids:      vec![token_ver_0],
+                expiry:         pool_params.expiry,
+            })),
+        })
+        .expect_err("pool deposited for a token version which does not exist");
+    euroe_contract
+        .transfer_single(&mut chain, &admin, Transfer {
+            token_id: TokenIdUnit(),
+            amount:   TokenAmountU64(1_000),
+            from:     admin.address.into(),
+            to:       pool_receiver,
+            data:     AdditionalData::from(to_bytes(&pool_params)),
+        })
+        .expect("deposit pool");
+    assert_eq!(
+        euroe_contract
+            .balance_of_single(
+                &chain,
+                &admin,
+                TokenIdUnit(),
+                yielder_contract.contract_address().into(),
+            )
+            .expect("euroe balance of yielder"),
+        1_000.into()
+    );
+
+    // Tokens received after the deposit do not earn a share of the pool
+    security_token_contract
+        .transfer_single(&mut chain, &investor_2, Transfer {
+            token_id: token_ver_0,
+            amount:   TokenAmountU64(1000),
+            from:     investor_2.address.into(),
+            to:       Receiver::Account(investor_1.address),
+            data:     AdditionalData::empty(),
+        })
+        .expect("transfer to investor 1");
+    yielder_contract
+        .yield_for(
+            &mut chain,
+            &investor_1,
+            &security_sft_multi_yielder::YieldParams {
+                owner:  investor_1.address,
+                yields: vec![YieldParam {
+                    amount:         2000.into(),
+                    token_contract: security_token_contract.contract_address(),
+                    token_ver_from: token_ver_0,
+                    token_ver_to:   token_ver_1,
+                }],
+            },
+        )
+        .expect("yield for investor 1");
+    assert_eq!(
+        euroe_contract
+            .balance_of_single(
+                &chain,
+                &investor_1,
+                TokenIdUnit(),
+                investor_1.address.into(),
+            )
+            .expect("euroe balance of investor 1"),
+        333.into()
+    );
+    assert_eq!(
+        security_token_contract
+            .balance_of_single(&chain, &investor_1, token_ver_1, investor_1.address.into())
+            .expect("balance of investor 1"),
+        2000.into()
+    );
+
+    let return_params = ReturnPoolParams {
+        token_contract: security_token_contract.contract_address(),
+        token_id:       token_ver_1,
+        yield_contract: euroe_contract.contract_address(),
+        yield_token_id: to_token_id_vec(TokenIdUnit()),
+    };
+    yielder_contract
+        .return_pool(&mut chain, &admin, &return_params)
+        .expect_err("pool not expired");
+    chain
+        .tick_block_time(Duration::from_days(10))
+        .expect("tick block time");
+    yielder_contract
+        .return_pool(&mut chain, &admin, &return_params)
+        .expect("return expired pool");
+    assert_eq!(
+        euroe_contract
+            .balance_of_single(&chain, &admin, TokenIdUnit(), treasury.address.into())
+            .expect("euroe balance of treasury"),
+        667.into()
+    );
+
+    // Investor 2 claims after expiry and does not receive a share of the pool
+    yielder_contract
+        .yield_for(
+            &mut chain,
+            &investor_2,
+            &security_sft_multi_yielder::YieldParams {
+                owner:  investor_2.address,
+                yields: vec![YieldParam {
+                    amount:         1000.into(),
+                    token_contract: security_token_contract.contract_address(),
+                    token_ver_from: token_ver_0,
+                    token_ver_to:   token_ver_1,
+                }],
+            },
+        )
+        .expect_err("no yields after pool expiry");
+}
+
+#[test]
+fn checkpoint_claims() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+    let treasury = Account::new(TREASURY, DEFAULT_ACC_BALANCE);
+    chain.create_account(treasury.clone());
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    chain.create_account(investor_1.clone());
+
+    let yielder_contract = SftMultiYielderTestClient::init(
+        &mut chain,
+        &admin,
+        &security_sft_multi_yielder::InitParam {
+            agents:   vec![],
+            treasury: treasury.address.into(),
+        },
+    )
+    .expect("init yielder contract");
+    euroe_contract
+        .update_operator_single(&mut chain, &treasury, &UpdateOperator {
+            operator: yielder_contract.contract_address().into(),
+            update:   concordium_cis2::OperatorUpdate::Add,
+        })
+        .expect("add yielder as operator");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  treasury.address.into(),
+            amount: 10_000_000_000.into(),
+        })
+        .expect("euroe mint to treasury");
+    ir_contract
+        .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+            address:  investor_1.address.into(),
+            identity: Identity {
+                credentials: vec![],
+                attributes:  vec![IdentityAttribute {
+                    tag:   NATIONALITY.0,
+                    value: COMPLIANT_NATIONALITIES[1].to_string(),
+                }],
+            },
+            validity: None,
+        })
+        .expect("register identity investor 1");
+
+    let investor_2 = Account::new(INVESTOR_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(investor_2.clone());
+    ir_contract
+        .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+            address:  investor_2.address.into(),
+            identity: Identity {
+                credentials: vec![],
+                attributes:  vec![IdentityAttribute {
+                    tag:   NATIONALITY.0,
+                    value: COMPLIANT_NATIONALITIES[1].to_string(),
+                }],
+            },
+            validity: None,
+        })
+        .expect("register identity investor 2");
+
+    // The yielder only takes snapshots of the security token to claim against checkpoints.
+    let security_token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![AgentWithRoles {
+            address: yielder_contract.contract_address().into(),
+            roles:   vec![security_sft_multi::types::AgentRole::Snapshot],
+        }],
+    );
+    let token_ver_0 = TokenIdU64(0);
+    let token_ver_1 = TokenIdU64(1);
+    let token_ver_2 = TokenIdU64(2);
+    let token_ver_3 = TokenIdU64(3);
+    security_token_contract
+        .add_token(&mut chain, &admin, &AddTokenParams {
+            token_id:       token_ver_0,
+            token_metadata: ContractMetadataUrl {
+                url:  "example.com".to_string(),
+                hash: None,
+            },
+        })
+        .expect("add token wrapped");
+    security_token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            token_id: token_ver_0,
+            owners:   vec![MintParam {
+                address: investor_1.address.into(),
+                amount:  TokenAmountSecurity::new_un_frozen(1000.into()),
+            }],
+        })
+        .expect("mint wrapped token investor 1");
+
+    let claim = |owner: &Account, token_ver_to| ClaimParams {
+        owner:  owner.address,
+        claims: vec![ClaimParam {
+            token_contract: security_token_contract.contract_address(),
+            token_id: token_ver_0,
+            token_ver_to,
+        }],
+    };
+    yielder_contract
+        .claim_yields(&mut chain, &investor_1, &claim(&investor_1, token_ver_1))
+        .expect_err("claim yields in versioned mode");
+    yielder_contract
+        .set_claim_mode(&mut chain, &investor_1, &SetClaimModeParams {
+            token_contract: security_token_contract.contract_address(),
+            mode:           ClaimMode::Checkpoint,
+        })
+        .expect_err("set claim mode by non agent");
+    yielder_contract
+        .set_claim_mode(&mut chain, &admin, &SetClaimModeParams {
+            token_contract: security_token_contract.contract_address(),
+            mode:           ClaimMode::Checkpoint,
+        })
+        .expect("set claim mode");
+    let upsert_yield = |chain: &mut Chain, token_id| {
+        yielder_contract
+            .upsert_yield(
+                chain,
+                &admin,
+                &security_sft_multi_yielder::UpsertYieldParams {
+                    token_contract: security_token_contract.contract_address(),
+                    token_id,
+                    yields: vec![YieldState {
+                        contract:    euroe_contract.contract_address(),
+                        token_id:    to_token_id_vec(TokenIdUnit()),
+                        calculation: YieldCalculation::Quantity(
+                            Rate::new(1, 10).expect("valid rate"),
+                        ),
+                    }],
+                },
+            )
+            .expect("upsert yield");
+    };
+    upsert_yield(&mut chain, token_ver_1);
+    upsert_yield(&mut chain, token_ver_2);
+
+    yielder_contract
+        .claim_yields(&mut chain, &investor_1, &claim(&investor_1, token_ver_1))
+        .expect("claim yields up to version 1");
+    yielder_contract
+        .claim_yields(&mut chain, &investor_1, &claim(&investor_1, token_ver_1))
+        .expect_err("claim yields up to version 1 again");
+    let yield_for_params = security_sft_multi_yielder::YieldParams {
+        owner:  investor_1.address,
+        yields: vec![YieldParam {
+            amount:         1000.into(),
+            token_contract: security_token_contract.contract_address(),
+            token_ver_from: token_ver_0,
+            token_ver_to:   token_ver_2,
+        }],
+    };
+    yielder_contract
+        .yield_for(&mut chain, &investor_1, &yield_for_params)
+        .expect_err("yield for in checkpoint mode");
+
+    // Tokens transferred after a version was added do not earn the yields of the version again.
+    security_token_contract
+        .transfer_single(&mut chain, &investor_1, Transfer {
+            token_id: token_ver_0,
+            amount:   TokenAmountU64(1000),
+            from:     investor_1.address.into(),
+            to:       Receiver::Account(investor_2.address),
+            data:     AdditionalData::empty(),
+        })
+        .expect("transfer to investor 2");
+    yielder_contract
+        .claim_yields(&mut chain, &investor_2, &claim(&investor_2, token_ver_2))
+        .expect_err("claim yields of versions added before the transfer");
+    yielder_contract
+        .claim_yields(&mut chain, &investor_1, &claim(&investor_1, token_ver_2))
+        .expect("claim yields up to version 2");
+    upsert_yield(&mut chain, token_ver_3);
+    yielder_contract
+        .claim_yields(&mut chain, &investor_1, &claim(&investor_1, token_ver_3))
+        .expect_err("claim yields of versions added after the transfer");
+    yielder_contract
+        .claim_yields(&mut chain, &investor_2, &claim(&investor_2, token_ver_3))
+        .expect("claim yields up to version 3");
+
+    // The security tokens are left untouched.
+    assert_eq!(
+        security_token_contract
+            .balance_of_single(&chain, &investor_2, token_ver_0, investor_2.address.into())
+            .expect("balance of investor 2"),
+        1000.into()
+    );
+    assert_eq!(
+        euroe_contract
+            .balance_of_single(
+                &chain,
+                &investor_1,
+                TokenIdUnit(),
+                investor_1.address.into(),
+            )
+            .expect("euroe balance of investor 1"),
+        (100 + 100).into()
+    );
+    assert_eq!(
+        euroe_contract
+            .balance_of_single(
+                &chain,
+                &investor_2,
+                TokenIdUnit(),
+                investor_2.address.into(),
+            )
+            .expect("euroe balance of investor 2"),
+        100.into()
+    );
+
+    // Switching back to the versioned mode does not pay the claimed yields again.
+    yielder_contract
+        .set_claim_mode(&mut chain, &admin, &SetClaimModeParams {
+            token_contract: security_token_contract.contract_address(),
+            mode:           ClaimMode::Versioned,
+        })
+        .expect("set claim mode");
+    yielder_contract
+        .yield_for(&mut chain, &investor_1, &yield_for_params)
+        .expect_err("yield for claimed versions");
+    yielder_contract
+        .set_claim_mode(&mut chain, &admin, &SetClaimModeParams {
+            token_contract: security_token_contract.contract_address(),
+            mode:           ClaimMode::Checkpoint,
+        })
+        .expect_err("set claim mode with existing token versions");
+}
+
+#[test]
+fn batch_yields() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (euroe_contract, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+    let treasury = Account::new(TREASURY, DEFAULT_ACC_BALANCE);
+    chain.create_account(treasury.clone());
+    let investor_1 = Account::new(INVESTOR_1, DEFAULT_ACC_BALANCE);
+    chain.create_account(investor_1.clone());
+    let investor_2 = Account::new(INVESTOR_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(investor_2.clone());
+
+    let yielder_contract = SftMultiYielderTestClient::init(
+        &mut chain,
+        &admin,
+        &security_sft_multi_yielder::InitParam {
+            agents:   vec![],
+            treasury: treasury.address.into(),
+        },
+    )
+    .expect("init yielder contract");
+    euroe_contract
+        .update_operator_single(&mut chain, &treasury, &UpdateOperator {
+            operator: yielder_contract.contract_address().into(),
+            update:   concordium_cis2::OperatorUpdate::Add,
+        })
+        .expect("add yielder as operator");
+    euroe_contract
+        .mint(&mut chain, &admin, &euroe::MintParams {
+            owner:  treasury.address.into(),
+            amount: 10_000_000_000.into(),
+        })
+        .expect("euroe mint to treasury");
+    for investor in [&investor_1, &investor_2] {
+        ir_contract
+            .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+                address:  investor.address.into(),
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: COMPLIANT_NATIONALITIES[1].to_string(),
+                    }],
+                },
+                validity: None,
+            })
+            .expect("register identity investor");
+    }
+
+    let security_token_contract = create_token_contract_multi(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+        vec![AgentWithRoles {
+            address: yielder_contract.contract_address().into(),
+            roles:   vec![
+                security_sft_multi::types::AgentRole::Operator,
+                security_sft_multi::types::AgentRole::Mint,
+            ],
+        }],
+    );
+    let token_ver_0 = TokenIdU64(0);
+    let token_ver_1 = TokenIdU64(1);
+    for token_id in [token_ver_0, token_ver_1] {
+        security_token_contract
+            .add_token(&mut chain, &admin, &AddTokenParams {
+                token_id,
+                token_metadata: ContractMetadataUrl {
+                    url:  "example.com".to_string(),
+                    hash: None,
+                },
+            })
+            .expect("add token wrapped");
+    }
+    security_token_contract
+        .mint(&mut chain, &admin, &security_sft_multi::types::MintParams {
+            token_id: token_ver_0,
+            owners:   vec![MintParam {
+                address: investor_1.address.into(),
+                amount:  TokenAmountSecurity::new_un_frozen(1000.into()),
+            }],
+        })
+        .expect("mint wrapped token investor 1");
+    yielder_contract
+        .upsert_yield(
+            &mut chain,
+            &admin,
+            &security_sft_multi_yielder::UpsertYieldParams {
+                token_contract: security_token_contract.contract_address(),
+                token_id:       token_ver_1,
+                yields:         vec![YieldState {
+                    contract:    euroe_contract.contract_address(),
+                    token_id:    to_token_id_vec(TokenIdUnit()),
+                    calculation: YieldCalculation::Quantity(Rate::new(1, 10).expect("valid rate")),
+                }],
+            },
+        )
+        .expect("upsert yield");
+
+    // Investor 2 holds no security tokens and has no yields.
+    let batch = YieldForBatchParams {
+        owners: [(&investor_1, 1000), (&investor_2, 0)]
+            .into_iter()
+            .map(
+                |(investor, amount)| security_sft_multi_yielder::YieldParams {
+                    owner:  investor.address,
+                    yields: vec![YieldParam {
+                        amount:         amount.into(),
+                        token_contract: security_token_contract.contract_address(),
+                        token_ver_from: token_ver_0,
+                        token_ver_to:   token_ver_1,
+                    }],
+                },
+            )
+            .collect(),
+    };
+    yielder_contract
+        .yield_for_batch(&mut chain, &investor_1, &batch)
+        .expect_err("yield for batch by non operator");
+    let results: Vec<BatchYieldResult> = from_bytes(
+        &yielder_contract
+            .yield_for_batch(&mut chain, &admin, &batch)
+            .expect("yield for batch")
+            .return_value,
+    )
+    .expect("parse batch results");
+    assert_eq!(results, vec![
+        BatchYieldResult::Yielded,
+        BatchYieldResult::NoYield
+    ]);
+    assert_eq!(
+        security_token_contract
+            .balance_of_single(&chain, &investor_1, token_ver_1, investor_1.address.into())
+            .expect("balance of investor 1"),
+        1000.into()
+    );
+    assert_eq!(
+        euroe_contract
+            .balance_of_single(
+                &chain,
+                &investor_1,
+                TokenIdUnit(),
+                investor_1.address.into(),
+            )
+            .expect("euroe balance of investor 1"),
+        100.into()
+    );
+}
+
 fn create_carbon_credits_contract(chain: &mut Chain, admin: &Account) -> SftSingleTestClient {
     SftSingleTestClient::init(chain, admin, &security_sft_single::types::InitParam {
         security:     None,
@@ -2,6 +2,7 @@
 
 use cis2_security::{Cis2SecurityTestClient, Cis2TestClient};
 use compliance::init_nationalities;
+use concordium_base::id::types::AccountKeys;
 use concordium_cis2::{
     AdditionalData, BalanceOfQuery, BalanceOfQueryParams, BalanceOfQueryResponse, TokenAmountU64,
     TokenIdUnit, Transfer,
@@ -10,14 +11,19 @@ use concordium_protocols::concordium_cis2_security::{
     AgentWithRoles, BurnParams, FreezeParam, FreezeParams, Identity, PauseParams, SecurityParams,
     TokenAmountSecurity,
 };
+use concordium_protocols::concordium_cis3::{
+    NonceOfQueryParams, NonceOfQueryResponse, PermitMessage, PermitParam,
+};
 use concordium_rwa_identity_registry::types::{IdentityAttribute, RegisterIdentityParams};
 use concordium_smart_contract_testing::*;
 use concordium_std::attributes::NATIONALITY;
+use concordium_std::{AccountSignatures, CredentialSignatures, Signature, SignatureEd25519};
 use contract_base::{ContractPayloads, ContractTestClient};
 use identity_registry::IdentityRegistryTestClient;
 use integration_tests::*;
 use security_sft_single::types::*;
 use security_sft_single_client::SftSingleTestClient;
+use sha2::{Digest, Sha256};
 
 const TOKEN_ID: TokenIdUnit = TokenIdUnit();
 const METADATA_URL: &str = "example.com";
@@ -28,6 +34,7 @@ const HOLDER_2: AccountAddress = AccountAddress([3; 32]);
 const HOLDER_3: AccountAddress = AccountAddress([4; 32]);
 const AGENT_FORCED_TRANSFER: AccountAddress = AccountAddress([5; 32]);
 const AGENT_FORCED_BURN: AccountAddress = AccountAddress([6; 32]);
+const SPONSOR: AccountAddress = AccountAddress([7; 32]);
 const COMPLIANT_NATIONALITIES: [&str; 2] = ["IN", "US"];
 const DEFAULT_ACC_BALANCE: Amount = Amount {
     micro_ccd: 1_000_000_000_u64,
@@ -78,6 +85,7 @@ fn mint() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     let holder_2 = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
@@ -160,6 +168,7 @@ fn burn() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     ir_contract
@@ -172,6 +181,7 @@ fn burn() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
 
@@ -347,6 +357,7 @@ fn forced_burn() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     ir_contract
@@ -359,6 +370,7 @@ fn forced_burn() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
 
@@ -559,6 +571,7 @@ fn transfer() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     ir_contract
@@ -571,6 +584,7 @@ fn transfer() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     // holder 3 is not registered with the Identity Registry
@@ -711,6 +725,7 @@ fn forced_transfer() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     ir_contract
@@ -723,6 +738,7 @@ fn forced_transfer() {
                     value: COMPLIANT_NATIONALITIES[1].to_string(),
                 }],
             },
+            validity: None,
         })
         .expect("register identity");
     // holder 3 is not registered with the Identity Registry
@@ -900,6 +916,197 @@ fn forced_transfer() {
         .expect_err("transferred paused token");
 }
 
+#[test]
+fn permit() {
+    let admin = Account::new(ADMIN, DEFAULT_ACC_BALANCE);
+    let mut chain = Chain::new();
+    let (_, ir_contract, compliance_contract) =
+        setup_chain(&mut chain, &admin, &COMPLIANT_NATIONALITIES);
+    let token_contract = create_token_contract(
+        &mut chain,
+        &admin,
+        compliance_contract,
+        ir_contract.contract_address(),
+    );
+    // The holder signs the permits and holds no CCD to pay for transactions.
+    let holder_keys = AccountKeys::singleton(&mut rand::thread_rng());
+    let holder = Account::new_with_keys(
+        HOLDER,
+        AccountBalance::new(Amount::zero(), Amount::zero(), Amount::zero()).unwrap(),
+        (&holder_keys).into(),
+    );
+    chain.create_account(holder.clone());
+    let holder_2 = Account::new(HOLDER_2, DEFAULT_ACC_BALANCE);
+    chain.create_account(holder_2.clone());
+    let sponsor = Account::new(SPONSOR, DEFAULT_ACC_BALANCE);
+    chain.create_account(sponsor.clone());
+
+    for address in [holder.address, holder_2.address] {
+        ir_contract
+            .register_identity(&mut chain, &admin, &RegisterIdentityParams {
+                address:  Address::Account(address),
+                identity: Identity {
+                    credentials: vec![],
+                    attributes:  vec![IdentityAttribute {
+                        tag:   NATIONALITY.0,
+                        value: COMPLIANT_NATIONALITIES[1].to_string(),
+                    }],
+                },
+                validity: None,
+            })
+            .expect("register identity");
+    }
+    token_contract
+        .mint(&mut chain, &admin, &MintParams {
+            owners:   vec![MintParam {
+                amount:  TokenAmountSecurity::new_un_frozen(TokenAmountU64(50)),
+                address: holder.address.into(),
+            }],
+            token_id: TOKEN_ID,
+        })
+        .expect("should mint");
+
+    let transfer: TransferParams = concordium_cis2::TransferParams(vec![Transfer {
+        token_id: TOKEN_ID,
+        amount:   30.into(),
+        from:     Address::Account(holder.address),
+        to:       holder_2.address.into(),
+        data:     AdditionalData::empty(),
+    }]);
+    let message = PermitMessage {
+        contract_address: token_contract.contract_address(),
+        nonce:            0,
+        timestamp:        Timestamp::from_timestamp_millis(
+            chain.block_time().timestamp_millis() + 60_000,
+        ),
+        entry_point:      OwnedEntrypointName::new_unchecked("transfer".to_string()),
+        payload:          to_bytes(&transfer),
+    };
+    token_contract
+        .permit(&mut chain, &sponsor, &PermitParam {
+            signature: sign_permit(&holder_keys, holder.address, &message),
+            signer:    holder_2.address,
+            message:   permit_message(&message),
+        })
+        .expect_err("permitted with another signer");
+    let permit = PermitParam {
+        signature: sign_permit(&holder_keys, holder.address, &message),
+        signer: holder.address,
+        message,
+    };
+    token_contract
+        .permit(&mut chain, &sponsor, &permit)
+        .expect("should transfer with permit");
+    token_contract
+        .permit(&mut chain, &sponsor, &permit)
+        .expect_err("replayed permit");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &holder, &BalanceOfQueryParams {
+                queries: vec![
+                    BalanceOfQuery {
+                        address:  holder.address.into(),
+                        token_id: TOKEN_ID,
+                    },
+                    BalanceOfQuery {
+                        address:  holder_2.address.into(),
+                        token_id: TOKEN_ID,
+                    }
+                ],
+            })
+            .unwrap(),
+        BalanceOfQueryResponse(vec![20.into(), 30.into()])
+    );
+    assert_eq!(
+        token_contract
+            .nonce_of(&mut chain, &sponsor, &NonceOfQueryParams {
+                queries: vec![holder.address, holder_2.address],
+            })
+            .unwrap(),
+        NonceOfQueryResponse(vec![1, 0])
+    );
+
+    let burn = BurnParams(vec![Burn {
+        token_id: TOKEN_ID,
+        amount:   10.into(),
+        owner:    holder.address.into(),
+    }]);
+    let message = PermitMessage {
+        nonce: 1,
+        entry_point: OwnedEntrypointName::new_unchecked("burn".to_string()),
+        payload: to_bytes(&burn),
+        ..permit_message(&permit.message)
+    };
+    chain
+        .tick_block_time(Duration::from_millis(60_000))
+        .expect("tick block time");
+    token_contract
+        .permit(&mut chain, &sponsor, &PermitParam {
+            signature: sign_permit(&holder_keys, holder.address, &message),
+            signer:    holder.address,
+            message:   permit_message(&message),
+        })
+        .expect_err("permitted expired message");
+    let message = PermitMessage {
+        timestamp: Timestamp::from_timestamp_millis(chain.block_time().timestamp_millis() + 60_000),
+        ..message
+    };
+    token_contract
+        .permit(&mut chain, &sponsor, &PermitParam {
+            signature: sign_permit(&holder_keys, holder.address, &message),
+            signer:    holder.address,
+            message:   permit_message(&message),
+        })
+        .expect("should burn with permit");
+    assert_eq!(
+        token_contract
+            .balance_of(&chain, &holder, &BalanceOfQueryParams {
+                queries: vec![BalanceOfQuery {
+                    address:  holder.address.into(),
+                    token_id: TOKEN_ID,
+                }],
+            })
+            .unwrap(),
+        BalanceOfQueryResponse(vec![10.into()])
+    );
+}
+
+fn permit_message(message: &PermitMessage) -> PermitMessage {
+    PermitMessage {
+        contract_address: message.contract_address,
+        nonce:            message.nonce,
+        timestamp:        message.timestamp,
+        entry_point:      message.entry_point.clone(),
+        payload:          message.payload.clone(),
+    }
+}
+
+fn sign_permit(
+    keys: &AccountKeys,
+    signer: AccountAddress,
+    message: &PermitMessage,
+) -> AccountSignatures {
+    let hash = message
+        .hash(signer, |data| Sha256::digest(data).into())
+        .expect("hash permit message");
+    let sigs = keys
+        .sign_data(&hash)
+        .into_iter()
+        .map(|(credential_index, signatures)| {
+            (credential_index.index, CredentialSignatures {
+                sigs: signatures
+                    .into_iter()
+                    .map(|(key_index, signature)| {
+                        let signature = signature.sig.try_into().expect("ed25519 signature");
+                        (key_index.0, Signature::Ed25519(SignatureEd25519(signature)))
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+    AccountSignatures { sigs }
+}
+
 fn create_token_contract(
     chain: &mut Chain,
     admin: &Account,
@@ -15,18 +15,30 @@
 //!
 //! At Some point in future time the owner of the Contract can set the state of the this contract to `Success` Or `Fail`
 //!
+//! Every fund has a soft cap, a hard cap and a deadline. Investments beyond the hard cap or after the deadline are rejected.
+//! Once the deadline has passed or the hard cap has been reached anyone can finalize the fund,
+//! which sets the state to `Success` if the soft cap has been met and `Fail` otherwise.
+//!
 //! At any point in the future the investor can request to
 //! * Cancel their Investment, Only if the state of the contract is `Open` or `Fail` this request will
 //!     * return their invested `Currency` token
 //!     * burn their `Tokens`
+//! * Withdraw (part of) their Investment, Only if the state of the contract is `Open` and up to the amount invested within the cooling-off period this request will
+//!     * return the withdrawn `Currency` token
+//!     * burn the corresponding `Tokens`
 //! * Claim their Investment, Only if the state of the contract is `Success` this request will
 //!     * mint their `Investment` token
 //!     * burn their `Token`
+//!
+//! If the fund has a vesting schedule the claimed `Investment` tokens are kept frozen
+//! and un frozen over time according to the schedule using `releaseVested`.
+//! Vested tokens stay frozen until `releaseVested` is executed in a separate transaction.
 pub mod types;
 use concordium_cis2::{AdditionalData, Receiver, Transfer};
 use concordium_protocols::concordium_cis2_ext::cis2_client::Cis2Client;
 use concordium_protocols::concordium_cis2_ext::{IsTokenAmount, ToAdditionalData};
 use concordium_protocols::concordium_cis2_security::cis2_security_client::Cis2SecurityClient;
+use concordium_protocols::concordium_cis2_security::identity_registry_client::IdentityRegistryClient;
 use concordium_protocols::concordium_cis2_security::{
     AgentWithRoles, Burn, FreezeParam, MintParam, TokenAmountSecurity, TokenUId,
 };
@@ -38,12 +50,51 @@ use types::*;
 #[derive(Serial, DeserialWithState, Debug)]
 #[concordium(state_parameter = "S")]
 pub struct Fund<S> {
-    pub state:       FundState,
-    pub investments: StateMap<AccountAddress, CurrencyTokenAmount, S>,
+    pub state:              FundState,
+    pub investments:        StateMap<AccountAddress, Investment, S>,
     /// This is the token which will be minted in locked state after investment. This is the initial token minted by the contract upon investment.
-    pub token:       SecurityTokenUId,
+    pub token:              SecurityTokenUId,
     /// This is the rate  which will be used to convert from `currency_token` token to `security_token` Token.
-    pub rate:        Rate,
+    pub rate:               Rate,
+    /// This is the token which is used to invest in the fund.
+    pub currency_token:     CurrencyTokenUId,
+    /// Minimum total investment required for the fund to succeed.
+    pub soft_cap:           CurrencyTokenAmount,
+    /// Maximum total investment accepted by the fund.
+    pub hard_cap:           CurrencyTokenAmount,
+    /// Time after which no more investments are accepted.
+    pub deadline:           Timestamp,
+    /// Receiver of the invested currency when the fund is finalized as `Success`.
+    pub funds_receiver:     Receiver,
+    /// Total currency amount invested in the fund.
+    pub total_investment:   CurrencyTokenAmount,
+    /// Period after an investment during which the investor can withdraw it.
+    pub cooling_off_period: Duration,
+    /// Minimum total investment of a single investor.
+    pub min_investment:     Option<CurrencyTokenAmount>,
+    /// Maximum total investment of a single investor.
+    pub max_investment:     Option<CurrencyTokenAmount>,
+    /// Investors who are allowed to invest in the fund.
+    pub investors:          FundInvestors,
+    /// Investors allowed to invest when `investors` is `FundInvestors::Allowlist`.
+    pub allowlist:          StateSet<AccountAddress, S>,
+    /// Lock-up & vesting of the claimed security tokens.
+    pub vesting:            Option<VestingSchedule>,
+    /// Claimed security tokens of each investor which are not yet fully released.
+    pub vestings:           StateMap<AccountAddress, Vesting, S>,
+}
+
+impl<S: HasStateApi> Fund<S> {
+    /// Ensures that the total investment of a single investor is within the investment limits of the fund.
+    pub fn ensure_investment_limits(&self, amount: CurrencyTokenAmount) -> ContractResult<()> {
+        if let Some(min_investment) = self.min_investment {
+            ensure!(amount.ge(&min_investment), Error::InvestmentBelowMinimum);
+        }
+        if let Some(max_investment) = self.max_investment {
+            ensure!(amount.le(&max_investment), Error::InvestmentAboveMaximum);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serial, DeserialWithState)]
@@ -52,8 +103,8 @@ pub struct State<S=StateApi> {
     /// Agents who can interact with this contract.
     /// Agents are allowed to Start a new fund, Update the fund & force the claim for a user Or force the return of amount to a user.
     pub agents:         StateMap<Address, StateSet<AgentRole, S>, S>,
-    /// This is the token which is used to fund this contract.
-    /// This will usualle be EuroE Token
+    /// This is the default token which is used to invest in the funds of this contract.
+    /// Used by the funds which do not specify their own currency. This will usually be EuroE Token
     pub currency_token: CurrencyTokenUId,
     /// Map of all the funds which are currently in this contract.
     /// Key is the token which will be minted in locked state after investment. This is the initial token minted by the contract upon investment.
@@ -81,7 +132,7 @@ fn init(
     logger: &mut Logger,
 ) -> InitResult<State> {
     let params: InitParam = ctx.parameter_cursor().get()?;
-    logger.log(&Event::Initialized(params.currency_token))?;
+    logger.log(&Event::Initialized(params.currency_token.clone()))?;
     let agents = {
         let mut agents = state_builder.new_map();
         let roles = {
@@ -194,12 +245,41 @@ fn add_fund(
         state.has_agent(ctx.sender(), AgentRole::AddFund),
         Error::UnAuthorized
     );
+    ensure!(params.soft_cap.le(&params.hard_cap), Error::InvalidFundCaps);
+    if let (Some(min_investment), Some(max_investment)) =
+        (params.min_investment, params.max_investment)
+    {
+        ensure!(
+            min_investment.le(&max_investment),
+            Error::InvalidInvestmentLimits
+        );
+    }
+    ensure!(
+        params.deadline.gt(&ctx.metadata().slot_time()),
+        Error::FundDeadlinePassed
+    );
 
+    let currency_token = params
+        .currency_token
+        .unwrap_or_else(|| state.currency_token.clone());
     let existing = state.funds.insert(params.security_token, Fund {
-        state:       FundState::Open,
-        investments: state_builder.new_map(),
-        token:       params.token,
-        rate:        params.rate,
+        state:              FundState::Open,
+        investments:        state_builder.new_map(),
+        token:              params.token,
+        rate:               params.rate,
+        currency_token:     currency_token.clone(),
+        soft_cap:           params.soft_cap,
+        hard_cap:           params.hard_cap,
+        deadline:           params.deadline,
+        funds_receiver:     params.funds_receiver.clone(),
+        total_investment:   CurrencyTokenAmount::zero(),
+        cooling_off_period: params.cooling_off_period,
+        min_investment:     params.min_investment,
+        max_investment:     params.max_investment,
+        investors:          params.investors.clone(),
+        allowlist:          state_builder.new_set(),
+        vesting:            params.vesting,
+        vestings:           state_builder.new_map(),
     });
     ensure!(existing.is_none(), Error::FundExists);
 
@@ -210,11 +290,24 @@ fn add_fund(
     let _ = host
         .invoke_token_metadata_single(&params.security_token.contract, params.security_token.id)
         .map_err(|_| Error::NonExistentToken)?;
+    let _ = host
+        .invoke_token_metadata_single(&currency_token.contract, currency_token.id.clone())
+        .map_err(|_| Error::NonExistentToken)?;
 
     logger.log(&Event::FundAdded(FundAddedEvent {
-        token:          params.token,
-        rate:           params.rate,
+        token: params.token,
+        rate: params.rate,
         security_token: params.security_token,
+        currency_token,
+        soft_cap: params.soft_cap,
+        hard_cap: params.hard_cap,
+        deadline: params.deadline,
+        funds_receiver: params.funds_receiver,
+        cooling_off_period: params.cooling_off_period,
+        min_investment: params.min_investment,
+        max_investment: params.max_investment,
+        investors: params.investors,
+        vesting: params.vesting,
     }))?;
     Ok(())
 }
@@ -237,11 +330,13 @@ fn remove_fund(
         state.has_agent(ctx.sender(), AgentRole::RemoveFund),
         Error::UnAuthorized
     );
-    let fund = state
+    let mut fund = state
         .funds
         .remove_and_get(&fund_id)
         .ok_or(Error::InvalidFundId)?;
     ensure!(fund.investments.is_empty(), Error::InvalidFundState);
+    ensure!(fund.vestings.is_empty(), Error::InvalidFundState);
+    fund.allowlist.clear();
     logger.log(&Event::FundRemoved(fund_id))?;
     Ok(())
 }
@@ -273,6 +368,7 @@ fn update_fund_state(
             (FundState::Open, UpdateFundState::Success(params)) => {
                 FundState::Success(FundSuccessState {
                     funds_receiver: params.clone(),
+                    success_time:   ctx.metadata().slot_time(),
                 })
             }
             (FundState::Open, UpdateFundState::Fail) => FundState::Fail,
@@ -291,6 +387,92 @@ fn update_fund_state(
     Ok(())
 }
 
+#[receive(
+    contract = "security_mint_fund",
+    name = "updateFundAllowlist",
+    mutable,
+    parameter = "UpdateFundAllowlistParams",
+    enable_logger
+)]
+fn update_fund_allowlist(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: UpdateFundAllowlistParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    ensure!(
+        state.has_agent(ctx.sender(), AgentRole::UpdateFundAllowlist),
+        Error::UnAuthorized
+    );
+    {
+        let mut fund = state
+            .funds
+            .get_mut(&params.security_token)
+            .ok_or(Error::InvalidFundId)?;
+        for investor in params.add.iter() {
+            fund.allowlist.insert(*investor);
+        }
+        for investor in params.remove.iter() {
+            fund.allowlist.remove(investor);
+        }
+    }
+
+    logger.log(&Event::FundAllowlistUpdated(params))?;
+    Ok(())
+}
+
+/// Finalizes an `Open` fund once its deadline has passed or its hard cap has been reached.
+/// The fund is set to `Success` if the soft cap has been met and to `Fail` otherwise.
+/// Can be called by anyone.
+#[receive(
+    contract = "security_mint_fund",
+    name = "finalizeFund",
+    mutable,
+    parameter = "SecurityTokenUId",
+    enable_logger
+)]
+fn finalize_fund(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let security_token: SecurityTokenUId = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let state = {
+        let mut fund = host
+            .state_mut()
+            .funds
+            .get_mut(&security_token)
+            .ok_or(Error::InvalidFundId)?;
+        ensure!(
+            matches!(fund.state, FundState::Open),
+            Error::InvalidFundState
+        );
+        ensure!(
+            now.ge(&fund.deadline) || fund.total_investment.ge(&fund.hard_cap),
+            Error::FundNotFinalizable
+        );
+        if fund.total_investment.ge(&fund.soft_cap) {
+            let funds_receiver = fund.funds_receiver.clone();
+            fund.state = FundState::Success(FundSuccessState {
+                funds_receiver: funds_receiver.clone(),
+                success_time:   now,
+            });
+            UpdateFundState::Success(funds_receiver)
+        } else {
+            fund.state = FundState::Fail;
+            UpdateFundState::Fail
+        }
+    };
+
+    logger.log(&Event::FundStateUpdated(UpdateFundStateParams {
+        security_token,
+        state,
+    }))?;
+    Ok(())
+}
+
 #[receive(
     contract = "security_mint_fund",
     name = "transferInvest",
@@ -298,8 +480,14 @@ fn update_fund_state(
     parameter = "TransferInvestParams"
 )]
 fn transfer_invest(ctx: &ReceiveContext, host: &mut Host<State>) -> ContractResult<()> {
-    let currency_token = host.state().currency_token;
     let params: TransferInvestParams = ctx.parameter_cursor().get()?;
+    let currency_token = host
+        .state()
+        .funds
+        .get(&params.security_token)
+        .ok_or(Error::InvalidFundId)?
+        .currency_token
+        .clone();
     host.invoke_transfer_single(&currency_token.contract, Transfer {
         token_id: currency_token.id,
         amount:   params.amount,
@@ -344,6 +532,7 @@ fn invest(ctx: &ReceiveContext, host: &mut Host<State>, logger: &mut Logger) ->
         },
     };
 
+    ensure_investor_allowed(host, &security_token, from)?;
     let (wrapped_amount, wrapped_token) = {
         let state = host.state_mut();
         let mut fund = state
@@ -351,16 +540,47 @@ fn invest(ctx: &ReceiveContext, host: &mut Host<State>, logger: &mut Logger) ->
             .get_mut(&security_token)
             .ok_or(Error::InvalidFundId)?;
         ensure!(
-            state.currency_token.eq(&currency_token),
-            Error::UnAuthorized
+            fund.currency_token.eq(&currency_token),
+            Error::InvalidCurrency
         );
         match fund.state {
             FundState::Open => {
+                let now = ctx.metadata().slot_time();
+                ensure!(now.lt(&fund.deadline), Error::FundDeadlinePassed);
+                ensure!(
+                    curr_amount.gt(&CurrencyTokenAmount::zero()),
+                    Error::ZeroInvestment
+                );
+                let total_investment = fund
+                    .total_investment
+                    .0
+                    .checked_add(curr_amount.0)
+                    .map(CurrencyTokenAmount::from)
+                    .ok_or(Error::InvestmentOverflow)?;
+                ensure!(total_investment.le(&fund.hard_cap), Error::HardCapExceeded);
+                fund.total_investment = total_investment;
+                let investor_investment = fund
+                    .investments
+                    .get(&from)
+                    .map(|investment| investment.amount)
+                    .unwrap_or_else(CurrencyTokenAmount::zero);
+                fund.ensure_investment_limits(investor_investment + curr_amount)?;
                 // Add the investment to the fund
+                let cooling_off_period = fund.cooling_off_period;
                 fund.investments
                     .entry(from)
-                    .or_insert_with(CurrencyTokenAmount::zero)
-                    .modify(|amount| amount.add_assign(curr_amount));
+                    .or_insert_with(|| Investment {
+                        amount:   CurrencyTokenAmount::zero(),
+                        tranches: Vec::new(),
+                    })
+                    .modify(|investment| {
+                        investment.prune_tranches(now, cooling_off_period);
+                        investment.amount.add_assign(curr_amount);
+                        investment.tranches.push(InvestmentTranche {
+                            amount: curr_amount,
+                            time:   now,
+                        });
+                    });
                 // Convert the currency amount to wrapped amount
                 let security_amount = fund.rate.convert_currency_amount(&curr_amount)?;
                 (security_amount, fund.token)
@@ -384,6 +604,127 @@ fn invest(ctx: &ReceiveContext, host: &mut Host<State>, logger: &mut Logger) ->
     Ok(())
 }
 
+/// Withdraws (part of) the sender's investment from an `Open` fund.
+/// Only the amount invested within the cooling-off period can be withdrawn, each investment has its own cooling-off period.
+/// The withdrawn currency is returned to the investor and the corresponding frozen tokens are burned.
+#[receive(
+    contract = "security_mint_fund",
+    name = "withdrawInvestment",
+    mutable,
+    parameter = "WithdrawInvestmentParams",
+    enable_logger
+)]
+fn withdraw_investment(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let WithdrawInvestmentParams {
+        security_token,
+        amount: currency_amount,
+    } = ctx.parameter_cursor().get()?;
+    let investor = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(Error::UnAuthorized),
+    };
+    let now = ctx.metadata().slot_time();
+
+    let (token, currency_token, security_amount) = {
+        let mut fund = host
+            .state_mut()
+            .funds
+            .get_mut(&security_token)
+            .ok_or(Error::InvalidFundId)?;
+        ensure!(
+            matches!(fund.state, FundState::Open),
+            Error::InvalidFundState
+        );
+        let mut investment = fund
+            .investments
+            .get(&investor)
+            .map(|investment| investment.clone())
+            .ok_or(Error::InvalidInvestor)?;
+        investment.prune_tranches(now, fund.cooling_off_period);
+        investment.withdraw(currency_amount)?;
+        if investment.amount.eq(&CurrencyTokenAmount::zero()) {
+            fund.investments.remove(&investor);
+        } else {
+            fund.ensure_investment_limits(investment.amount)?;
+            fund.investments
+                .entry(investor)
+                .and_modify(|entry| *entry = investment);
+        }
+        fund.total_investment -= currency_amount;
+        let security_amount = fund.rate.convert_currency_amount(&currency_amount)?;
+        (fund.token, fund.currency_token.clone(), security_amount)
+    };
+
+    // Return the withdrawn currency amount
+    host.invoke_transfer_single(&currency_token.contract, Transfer {
+        amount:   currency_amount,
+        token_id: currency_token.id,
+        from:     ctx.self_address().into(),
+        to:       investor.into(),
+        data:     AdditionalData::empty(),
+    })
+    .map_err(|_| Error::CurrencyTokenTransfer)?;
+    // Burn the tokens minted for the withdrawn amount
+    host.invoke_burn_single(&token.contract, Burn {
+        token_id: token.id,
+        amount:   security_amount,
+        owner:    investor.into(),
+    })
+    .map_err(|_| Error::TokenBurn)?;
+
+    logger.log(&Event::InvestmentWithdrawn(InvestedEvent {
+        security_token,
+        investor,
+        security_amount,
+        currency_amount,
+    }))?;
+    Ok(())
+}
+
+/// Ensures that the investor is allowed to invest in the fund
+/// by being in the allowlist of the fund or having the required identity attribute.
+fn ensure_investor_allowed(
+    host: &Host<State>,
+    security_token: &SecurityTokenUId,
+    investor: AccountAddress,
+) -> ContractResult<()> {
+    let requirement = {
+        let fund = host
+            .state()
+            .funds
+            .get(security_token)
+            .ok_or(Error::InvalidFundId)?;
+        match &fund.investors {
+            FundInvestors::Any => return Ok(()),
+            FundInvestors::Allowlist => {
+                ensure!(
+                    fund.allowlist.contains(&investor),
+                    Error::InvestorNotAllowed
+                );
+                return Ok(());
+            }
+            FundInvestors::IdentityAttribute(requirement) => requirement.clone(),
+        }
+    };
+
+    let identity = host
+        .invoke_identity_registry_get_identity(&requirement.identity_registry, investor.into())
+        .map_err(|_| Error::InvestorNotAllowed)?;
+    ensure!(
+        identity
+            .attributes
+            .iter()
+            .any(|attribute| attribute.tag.eq(&requirement.tag)
+                && attribute.value.eq(&requirement.value)),
+        Error::InvestorNotAllowed
+    );
+    Ok(())
+}
+
 #[receive(
     contract = "security_mint_fund",
     name = "claimInvestment",
@@ -401,7 +742,7 @@ fn claim_investment(
     for investment in params.investments {
         let security_token = investment.security_token;
 
-        let (token, currency_token, currency_amount, security_amount, fund_state) = {
+        let (token, currency_token, currency_amount, security_amount, fund_state, vesting) = {
             let state = host.state_mut();
             if !sender.matches_account(&investment.investor) {
                 ensure!(
@@ -409,7 +750,6 @@ fn claim_investment(
                     Error::UnAuthorized
                 );
             }
-            let currency_token = state.currency_token;
             let mut fund = state
                 .funds
                 .get_mut(&investment.security_token)
@@ -418,32 +758,65 @@ fn claim_investment(
             let currency_amount = fund
                 .investments
                 .remove_and_get(&investment.investor)
-                .ok_or(Error::InvalidInvestor)?;
+                .ok_or(Error::InvalidInvestor)?
+                .amount;
             // Promised security amount
             let security_amount = fund.rate.convert_currency_amount(&currency_amount)?;
             (
                 fund.token,
-                currency_token,
+                fund.currency_token.clone(),
                 currency_amount,
                 security_amount,
                 fund.state.clone(),
+                fund.vesting,
             )
         };
 
         match fund_state {
             FundState::Open => bail!(Error::InvalidFundState),
-            FundState::Success(FundSuccessState { funds_receiver }) => {
+            FundState::Success(FundSuccessState { funds_receiver, .. }) => {
                 // Transfer the currency amount to the receiver
                 host.invoke_transfer_single(&currency_token.contract, Transfer {
                     amount:   currency_amount,
-                    token_id: currency_token.id,
+                    token_id: currency_token.id.clone(),
                     from:     ctx.self_address().into(),
                     to:       funds_receiver.clone(),
                     data:     AdditionalData::empty(),
                 })
                 .map_err(|_| Error::CurrencyTokenTransfer)?;
 
-                if security_token.eq(&token) {
+                if vesting.is_some() {
+                    if security_token.ne(&token) {
+                        // Burn the initially minted tokens
+                        host.invoke_burn_single(&token.contract, Burn {
+                            token_id: token.id,
+                            amount:   security_amount,
+                            owner:    investment.investor.into(),
+                        })
+                        .map_err(|_| Error::TokenBurn)?;
+                        // Mint the security tokens frozen till they are vested
+                        host.invoke_mint_single(
+                            &security_token.contract,
+                            security_token.id,
+                            MintParam {
+                                address: investment.investor.into(),
+                                amount:  TokenAmountSecurity::new_frozen(security_amount),
+                            },
+                        )
+                        .map_err(|_| Error::TokenMint)?;
+                    }
+                    host.state_mut()
+                        .funds
+                        .get_mut(&security_token)
+                        .ok_or(Error::InvalidFundId)?
+                        .vestings
+                        .entry(investment.investor)
+                        .or_insert_with(|| Vesting {
+                            amount:   TokenAmount::zero(),
+                            released: TokenAmount::zero(),
+                        })
+                        .modify(|vesting| vesting.amount.add_assign(security_amount));
+                } else if security_token.eq(&token) {
                     // Only Un Freeze Tokens
                     host.invoke_un_freeze_single(
                         &security_token.contract,
@@ -480,12 +853,22 @@ fn claim_investment(
                     investor: investment.investor,
                     currency_amount,
                 }))?;
+                if vesting.is_some() {
+                    // Release the part which has already vested
+                    release_vested_tokens(
+                        host,
+                        logger,
+                        &security_token,
+                        investment.investor,
+                        ctx.metadata().slot_time(),
+                    )?;
+                }
             }
             FundState::Fail => {
                 // Return the Invested currency amount
                 host.invoke_transfer_single(&currency_token.contract, Transfer {
                     amount:   currency_amount,
-                    token_id: currency_token.id,
+                    token_id: currency_token.id.clone(),
                     from:     ctx.self_address().into(),
                     to:       investment.investor.into(),
                     data:     AdditionalData::empty(),
@@ -510,3 +893,76 @@ fn claim_investment(
 
     Ok(())
 }
+
+/// Un freezes the vested security tokens of the investors which have not been released yet.
+/// Can be called by anyone.
+#[receive(
+    contract = "security_mint_fund",
+    name = "releaseVested",
+    mutable,
+    parameter = "ReleaseVestedParams",
+    enable_logger
+)]
+fn release_vested(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: ReleaseVestedParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    for investor in params.investors {
+        release_vested_tokens(host, logger, &params.security_token, investor, now)?;
+    }
+
+    Ok(())
+}
+
+/// Un freezes the part of the security tokens of the investor which has vested at `now` and has not been released yet.
+/// The vesting of the investor is removed once all the tokens have been released.
+fn release_vested_tokens(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    security_token: &SecurityTokenUId,
+    investor: AccountAddress,
+    now: Timestamp,
+) -> ContractResult<()> {
+    let to_release = {
+        let mut fund = host
+            .state_mut()
+            .funds
+            .get_mut(security_token)
+            .ok_or(Error::InvalidFundId)?;
+        let (schedule, success_time) = match (&fund.state, fund.vesting) {
+            (FundState::Success(FundSuccessState { success_time, .. }), Some(schedule)) => {
+                (schedule, *success_time)
+            }
+            _ => bail!(Error::InvalidFundState),
+        };
+        let vesting = *fund.vestings.get(&investor).ok_or(Error::InvalidVesting)?;
+        let vested = schedule.vested_amount(vesting.amount, success_time, now);
+        let to_release = vested - vesting.released;
+        if vested.eq(&vesting.amount) {
+            fund.vestings.remove(&investor);
+        } else {
+            fund.vestings
+                .entry(investor)
+                .and_modify(|vesting| vesting.released = vested);
+        }
+        to_release
+    };
+    if to_release.eq(&TokenAmount::zero()) {
+        return Ok(());
+    }
+
+    host.invoke_un_freeze_single(&security_token.contract, investor.into(), FreezeParam {
+        token_amount: to_release,
+        token_id:     security_token.id,
+    })
+    .map_err(|_| Error::TokenUnFreeze)?;
+    logger.log(&Event::VestingReleased(VestingReleasedEvent {
+        security_token: *security_token,
+        investor,
+        security_amount: to_release,
+    }))?;
+    Ok(())
+}
@@ -1,15 +1,19 @@
 use concordium_cis2::{
-    OnReceivingCis2DataParams, Receiver, TokenAmountU64, TokenIdU64, TokenIdUnit,
+    OnReceivingCis2DataParams, Receiver, TokenAmountU64, TokenIdU64, TokenIdVec,
+};
+use concordium_protocols::concordium_cis2_ext::IsTokenAmount;
+use concordium_protocols::concordium_cis2_security::{
+    AgentWithRoles, AttributeTag, AttributeValue, TokenUId,
 };
-use concordium_protocols::concordium_cis2_security::{AgentWithRoles, TokenUId};
 use concordium_protocols::rate::{ExchangeError, Rate};
 use concordium_std::*;
 
 pub type ContractResult<T> = Result<T, Error>;
 pub type TokenAmount = TokenAmountU64;
 pub type CurrencyTokenAmount = TokenAmountU64;
-pub type CurrencyTokenId = TokenIdUnit;
-/// TokenUId for `Currency` token. This token type should match EuroE token type.
+/// Any fungible CIS-2 token id can be represented, `TokenIdUnit` (EuroE) is serialized as an empty id.
+pub type CurrencyTokenId = TokenIdVec;
+/// TokenUId for `Currency` token.
 pub type CurrencyTokenUId = TokenUId<CurrencyTokenId>;
 /// TokenUId for `Security` token. This token type should match Security Sft Multi token type.
 pub type SecurityTokenUId = TokenUId<TokenIdU64>;
@@ -20,6 +24,7 @@ pub enum AgentRole {
     RemoveFund,
     UpdateFundState,
     Operator,
+    UpdateFundAllowlist,
 }
 
 impl AgentRole {
@@ -29,10 +34,82 @@ impl AgentRole {
             Self::RemoveFund,
             Self::UpdateFundState,
             Self::Operator,
+            Self::UpdateFundAllowlist,
         ]
     }
 }
 
+/// Investors who are allowed to invest in a fund, in addition to the compliance of the security token.
+#[derive(Serialize, SchemaType, Debug, Clone)]
+pub enum FundInvestors {
+    /// Any investor can invest.
+    Any,
+    /// Only investors in the allowlist of the fund can invest. The allowlist is managed with `updateFundAllowlist`.
+    Allowlist,
+    /// Only investors whose identity has the attribute can invest. For example accredited investor status.
+    IdentityAttribute(IdentityAttributeRequirement),
+}
+
+/// Lock-up & linear vesting of the security tokens claimed from a successful fund.
+/// Both periods start when the fund is set to `Success`.
+#[derive(Serialize, SchemaType, Debug, Clone, Copy)]
+pub struct VestingSchedule {
+    /// Period during which none of the claimed tokens are transferable.
+    pub lock_up:        Duration,
+    /// Period after the lock-up over which the claimed tokens become transferable linearly.
+    /// Zero to make all the tokens transferable at the end of the lock-up.
+    pub vesting_period: Duration,
+}
+
+impl VestingSchedule {
+    /// Returns the part of `amount` which has vested at `now` for a fund which succeeded at `start`.
+    pub fn vested_amount(
+        &self,
+        amount: TokenAmount,
+        start: Timestamp,
+        now: Timestamp,
+    ) -> TokenAmount {
+        let lock_up_end = match start.checked_add(self.lock_up) {
+            Some(lock_up_end) => lock_up_end,
+            None => return TokenAmount::from(0),
+        };
+        if now.lt(&lock_up_end) {
+            return TokenAmount::from(0);
+        }
+        let vesting_period = self.vesting_period.millis();
+        let elapsed = now.duration_since(lock_up_end).map_or(0, |d| d.millis());
+        if elapsed >= vesting_period {
+            return amount;
+        }
+        TokenAmount::from((amount.0 as u128 * elapsed as u128 / vesting_period as u128) as u64)
+    }
+}
+
+/// Security tokens of an investor which are released according to the vesting schedule of the fund.
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct Vesting {
+    /// Total claimed security amount under the vesting schedule.
+    pub amount:   TokenAmount,
+    /// Security amount which has already been un frozen.
+    pub released: TokenAmount,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct VestingReleasedEvent {
+    pub security_token:  SecurityTokenUId,
+    pub investor:        AccountAddress,
+    /// Security amount un frozen by this release.
+    pub security_amount: TokenAmount,
+}
+
+/// Identity attribute an investor is required to have in the identity registry.
+#[derive(Serialize, SchemaType, Debug, Clone)]
+pub struct IdentityAttributeRequirement {
+    pub identity_registry: ContractAddress,
+    pub tag:               AttributeTag,
+    pub value:             AttributeValue,
+}
+
 #[derive(Serialize, SchemaType, Debug)]
 pub struct InvestedEvent {
     pub security_token:  SecurityTokenUId,
@@ -43,12 +120,33 @@ pub struct InvestedEvent {
 
 #[derive(Serialize, SchemaType, Debug)]
 pub struct FundAddedEvent {
-    pub token:          SecurityTokenUId,
-    pub rate:           Rate,
-    pub security_token: SecurityTokenUId,
+    pub token:              SecurityTokenUId,
+    pub rate:               Rate,
+    pub security_token:     SecurityTokenUId,
+    /// The currency accepted by the fund.
+    pub currency_token:     CurrencyTokenUId,
+    /// Minimum total investment required for the fund to succeed.
+    pub soft_cap:           CurrencyTokenAmount,
+    /// Maximum total investment accepted by the fund.
+    pub hard_cap:           CurrencyTokenAmount,
+    /// Time after which no more investments are accepted.
+    pub deadline:           Timestamp,
+    /// Receiver of the invested currency when the fund succeeds.
+    pub funds_receiver:     Receiver,
+    /// Period after an investment during which the investor can withdraw it.
+    pub cooling_off_period: Duration,
+    /// Minimum total investment of a single investor.
+    pub min_investment:     Option<CurrencyTokenAmount>,
+    /// Maximum total investment of a single investor.
+    pub max_investment:     Option<CurrencyTokenAmount>,
+    /// Investors who are allowed to invest in the fund.
+    pub investors:          FundInvestors,
+    /// Lock-up & vesting of the claimed security tokens. `None` if they are transferable when claimed.
+    pub vesting:            Option<VestingSchedule>,
 }
 
 #[derive(Serialize, SchemaType, Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Event {
     /// Emitted when the contract is initialized with the currency token
     /// Triggered in the init function
@@ -71,7 +169,7 @@ pub enum Event {
     FundRemoved(SecurityTokenUId),
 
     /// Emitted when a fund's state is updated to either Success or Fail
-    /// Triggered in the update_fund_state function
+    /// Triggered in the update_fund_state & finalize_fund functions
     FundStateUpdated(UpdateFundStateParams),
 
     /// Emitted when an investor makes an investment in an Open fund
@@ -85,6 +183,18 @@ pub enum Event {
     /// Emitted when an investment is cancelled and funds returned to investor
     /// Triggered in the claim_investment function when fund is in Fail state
     InvestmentCancelled(InvestedEvent),
+
+    /// Emitted when an investor withdraws (part of) their investment from an Open fund
+    /// Triggered in the withdraw_investment function
+    InvestmentWithdrawn(InvestedEvent),
+
+    /// Emitted when investors are added to or removed from the allowlist of a fund
+    /// Triggered in the update_fund_allowlist function
+    FundAllowlistUpdated(UpdateFundAllowlistParams),
+
+    /// Emitted when vested security tokens of an investor are un frozen
+    /// Triggered in the claim_investment & release_vested functions
+    VestingReleased(VestingReleasedEvent),
 }
 
 #[derive(Serialize, SchemaType, Debug, Clone)]
@@ -126,6 +236,34 @@ pub enum Error {
     AgentExists,
     /// Thrown when attempting to add a fund with a token ID that's already registered
     FundExists,
+    /// Thrown when investing with a currency token which is not accepted by the fund
+    InvalidCurrency,
+    /// Thrown when adding a fund whose soft cap is greater than its hard cap
+    InvalidFundCaps,
+    /// Thrown when investing in a fund after its deadline
+    FundDeadlinePassed,
+    /// Thrown when an investment would take the fund beyond its hard cap
+    HardCapExceeded,
+    /// Thrown when finalizing a fund before its deadline and before the hard cap is reached
+    FundNotFinalizable,
+    /// Thrown when withdrawing an investment after the cooling-off period of the fund
+    CoolingOffPeriodPassed,
+    /// Thrown when withdrawing more than the invested amount
+    InsufficientInvestment,
+    /// Thrown when adding a fund whose minimum investment is greater than its maximum investment
+    InvalidInvestmentLimits,
+    /// Thrown when the total investment of an investor would be less than the minimum investment of the fund
+    InvestmentBelowMinimum,
+    /// Thrown when the total investment of an investor would be more than the maximum investment of the fund
+    InvestmentAboveMaximum,
+    /// Thrown when the investor is not in the allowlist or does not have the required identity attribute
+    InvestorNotAllowed,
+    /// Thrown when releasing vested tokens of an investor who has no tokens under vesting in the fund
+    InvalidVesting,
+    /// Thrown when an investment overflows the total investment of the fund
+    InvestmentOverflow,
+    /// Thrown when investing a zero amount
+    ZeroInvestment,
 }
 
 impl From<ParseError> for Error {
@@ -145,9 +283,72 @@ pub enum FundState {
     Fail,
 }
 
+/// Investment of a single investor in a fund.
+#[derive(Serialize, Clone, Debug)]
+pub struct Investment {
+    /// Invested currency amount.
+    pub amount:   CurrencyTokenAmount,
+    /// Investments which may still be within their cooling-off period, oldest first.
+    pub tranches: Vec<InvestmentTranche>,
+}
+
+/// A single investment, its cooling-off period starts at the time it was made.
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct InvestmentTranche {
+    pub amount: CurrencyTokenAmount,
+    pub time:   Timestamp,
+}
+
+impl Investment {
+    /// Removes the tranches whose cooling-off period has passed at `now`.
+    pub fn prune_tranches(&mut self, now: Timestamp, cooling_off_period: Duration) {
+        self.tranches.retain(|tranche| {
+            tranche
+                .time
+                .checked_add(cooling_off_period)
+                .is_some_and(|cooling_off_end| now.lt(&cooling_off_end))
+        });
+    }
+
+    /// Currency amount invested within the cooling-off period, assuming the tranches are pruned.
+    pub fn withdrawable(&self) -> CurrencyTokenAmount {
+        self.tranches
+            .iter()
+            .fold(CurrencyTokenAmount::zero(), |sum, tranche| {
+                sum + tranche.amount
+            })
+    }
+
+    /// Withdraws `amount` from the latest tranches.
+    pub fn withdraw(&mut self, amount: CurrencyTokenAmount) -> ContractResult<()> {
+        ensure!(amount.le(&self.amount), Error::InsufficientInvestment);
+        ensure!(
+            amount.le(&self.withdrawable()),
+            Error::CoolingOffPeriodPassed
+        );
+        let mut remaining = amount;
+        while remaining.gt(&CurrencyTokenAmount::zero()) {
+            let tranche = self
+                .tranches
+                .last_mut()
+                .ok_or(Error::CoolingOffPeriodPassed)?;
+            if tranche.amount.gt(&remaining) {
+                tranche.amount -= remaining;
+                break;
+            }
+            remaining -= tranche.amount;
+            self.tranches.pop();
+        }
+        self.amount -= amount;
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Clone, Debug)]
 pub struct FundSuccessState {
     pub funds_receiver: Receiver,
+    /// Time at which the fund was set to `Success`. The vesting schedule of the fund starts at this time.
+    pub success_time:   Timestamp,
 }
 
 pub type InvestReceiveParams =
@@ -167,9 +368,46 @@ pub enum UpdateFundState {
 
 #[derive(Serialize, SchemaType)]
 pub struct AddFundParams {
-    pub token:          SecurityTokenUId,
-    pub rate:           Rate,
+    pub token:              SecurityTokenUId,
+    pub rate:               Rate,
+    pub security_token:     SecurityTokenUId,
+    /// The currency accepted by the fund. `None` for the default currency of the contract.
+    pub currency_token:     Option<CurrencyTokenUId>,
+    /// Minimum total investment required for the fund to succeed.
+    pub soft_cap:           CurrencyTokenAmount,
+    /// Maximum total investment accepted by the fund.
+    pub hard_cap:           CurrencyTokenAmount,
+    /// Time after which no more investments are accepted.
+    pub deadline:           Timestamp,
+    /// Receiver of the invested currency when the fund is finalized as `Success`.
+    pub funds_receiver:     Receiver,
+    /// Period after an investment during which the investor can withdraw it while the fund is `Open`.
+    pub cooling_off_period: Duration,
+    /// Minimum total investment of a single investor. `None` for no minimum.
+    pub min_investment:     Option<CurrencyTokenAmount>,
+    /// Maximum total investment of a single investor. `None` for no maximum.
+    pub max_investment:     Option<CurrencyTokenAmount>,
+    /// Investors who are allowed to invest in the fund.
+    pub investors:          FundInvestors,
+    /// Lock-up & vesting of the claimed security tokens. `None` if they are transferable when claimed.
+    pub vesting:            Option<VestingSchedule>,
+}
+
+/// Parameters for `releaseVested` function.
+#[derive(Serialize, SchemaType)]
+pub struct ReleaseVestedParams {
+    pub security_token: SecurityTokenUId,
+    pub investors:      Vec<AccountAddress>,
+}
+
+/// Parameters for `updateFundAllowlist` function.
+#[derive(Serialize, SchemaType, Debug)]
+pub struct UpdateFundAllowlistParams {
     pub security_token: SecurityTokenUId,
+    /// Investors to add to the allowlist.
+    pub add:            Vec<AccountAddress>,
+    /// Investors to remove from the allowlist.
+    pub remove:         Vec<AccountAddress>,
 }
 
 #[derive(Serialize, SchemaType)]
@@ -183,6 +421,14 @@ pub struct ClaimInvestmentParams {
     pub investments: Vec<ClaimInvestmentParam>,
 }
 
+/// Parameters for `withdrawInvestment` function.
+#[derive(Serialize, SchemaType)]
+pub struct WithdrawInvestmentParams {
+    pub security_token: SecurityTokenUId,
+    /// Currency amount to withdraw, at most the invested amount.
+    pub amount:         CurrencyTokenAmount,
+}
+
 /// Parameters for `transferInvest` function.
 /// This is the parameters which will be used to transfer `Currency` token to this contract.
 #[derive(Serialize, SchemaType)]
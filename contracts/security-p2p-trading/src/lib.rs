@@ -1,4 +1,6 @@
-use concordium_cis2::{AdditionalData, TokenAmountU64, TokenIdU64, TokenIdUnit, Transfer};
+use concordium_cis2::{
+    AdditionalData, OnReceivingCis2DataParams, TokenAmountU64, TokenIdU64, TokenIdVec, Transfer,
+};
 use concordium_protocols::concordium_cis2_ext::cis2_client::Cis2Client;
 use concordium_protocols::concordium_cis2_ext::ContractMetadataUrl;
 use concordium_protocols::concordium_cis2_security::cis2_security_client::Cis2SecurityClient;
@@ -6,13 +8,14 @@ use concordium_protocols::concordium_cis2_security::{
     AddTokenParams, AgentWithRoles, MintParam, TokenAmountSecurity, TokenUId,
 };
 use concordium_protocols::rate::Rate;
-use concordium_std::ops::DerefMut;
+use concordium_std::ops::{Deref, DerefMut};
 use concordium_std::*;
 
-/// The type of the Token Amount used in the EuroE. This should match the type used in the `euroe` contract.
+/// The type of the Token Amount used by the currency tokens. This should match the type used in the `euroe` contract.
 pub type CurrencyTokenAmount = TokenAmountU64;
-/// The type of the token id used in the EuroE contract. This should match the type used in the `euroe` contract.
-pub type CurrencyTokenId = TokenIdUnit;
+/// The type of the token id used by the currency tokens.
+/// Any fungible CIS-2 token id can be represented, `TokenIdUnit` (EuroE) is serialized as an empty id.
+pub type CurrencyTokenId = TokenIdVec;
 pub type CurrencyTokenAddress = TokenUId<CurrencyTokenId>;
 /// The type of the Token Amount used in the contract. This should match the type used in the `security-sft-multi` contract.
 pub type TokenAmount = TokenAmountU64;
@@ -20,6 +23,8 @@ pub type TokenAmount = TokenAmountU64;
 pub type SecurityTokenId = TokenIdU64;
 pub type SecurityTokenAddress = TokenUId<SecurityTokenId>;
 pub type SecurityTokenAmount = TokenAmountU64;
+/// The unique identifier of an order placed in an order book market.
+pub type OrderId = u64;
 pub type ContractResult<T> = Result<T, Error>;
 
 #[derive(Serialize, SchemaType, Debug)]
@@ -32,6 +37,11 @@ pub struct ExchangeEvent {
     pub rate:            Rate,
     pub currency_amount: CurrencyTokenAmount,
     pub exchange_type:   ExchangeType,
+    /// The part of the `currency_amount` which was transferred to the `fee_receiver` instead of the seller.
+    pub fee_amount:      CurrencyTokenAmount,
+    pub fee_receiver:    Option<AccountAddress>,
+    /// The currency in which the `currency_amount` was paid.
+    pub currency_token:  CurrencyTokenAddress,
 }
 
 #[derive(Serialize, SchemaType, Debug)]
@@ -48,6 +58,9 @@ pub enum ExchangeType {
     /// This is the case when the liquidity provider mints tokens for the buyer.
     /// The buyer is the one who buys the tokens from the liquidity provider.
     Mint,
+    /// Traded against an open order of an order book market.
+    /// The seller and the buyer are the order owner and the taker, depending on the side of the order.
+    Order,
 }
 
 #[derive(Serialize, SchemaType, Debug)]
@@ -58,6 +71,51 @@ pub enum Event {
     MarketAdded(AddMarketParams),
     Exchanged(ExchangeEvent),
     MarketRemoved(ContractAddress),
+    OrderPlaced(OrderPlacedEvent),
+    OrderCancelled(OrderCancelledEvent),
+    OrderFilled(OrderFilledEvent),
+    /// The escrowed order was not filled before its expiry and the escrow was returned to the owner.
+    OrderExpired(OrderCancelledEvent),
+    /// The liquidity provider deposited liquidity into an escrowed transfer market.
+    LiquidityDeposited(LiquidityEvent),
+    /// Escrowed liquidity was returned to the liquidity provider of a transfer market.
+    LiquidityWithdrawn(LiquidityEvent),
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct LiquidityEvent {
+    pub token_contract:     ContractAddress,
+    pub liquidity_provider: AccountAddress,
+    pub token_amount:       SecurityTokenAmount,
+    pub currency_amount:    CurrencyTokenAmount,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct OrderPlacedEvent {
+    pub order_id: OrderId,
+    pub order:    Order,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct OrderCancelledEvent {
+    pub order_id:       OrderId,
+    pub token_contract: ContractAddress,
+    pub owner:          AccountAddress,
+    /// The amount of the order which was not filled at the time of cancellation.
+    pub amount:         SecurityTokenAmount,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct OrderFilledEvent {
+    pub order_id:         OrderId,
+    pub token_contract:   ContractAddress,
+    pub token_id:         SecurityTokenId,
+    /// The account which filled the order.
+    pub taker:            AccountAddress,
+    pub token_amount:     SecurityTokenAmount,
+    pub currency_amount:  CurrencyTokenAmount,
+    /// The amount of the order which is still open after this fill.
+    pub remaining_amount: SecurityTokenAmount,
 }
 
 #[derive(Serial, Reject, SchemaType)]
@@ -76,6 +134,21 @@ pub enum Error {
     TokenMint,
     InvalidMarketType,
     MarketTokenLimitExceeded,
+    InvalidOrder,
+    InvalidAmount,
+    OrderAmountExceeded,
+    InvalidSettlement,
+    OrderExpired,
+    OrderNotExpired,
+    MintMarketClosed,
+    /// No longer returned since mints are split across the price tiers. Kept to preserve the
+    /// error codes.
+    MintPriceTierExceeded,
+    InvestorTokenLimitExceeded,
+    InvalidMintMarket,
+    InvalidFee,
+    InvalidCurrency,
+    InvalidEscrowAction,
 }
 impl From<ParseError> for Error {
     fn from(_: ParseError) -> Self { Error::ParseError }
@@ -94,9 +167,15 @@ pub enum AgentRole {
 #[derive(Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 pub struct State<S=StateApi> {
+    /// The currency of the markets which do not specify their own currency.
     pub currency_token: CurrencyTokenAddress,
     pub agents:         StateMap<Address, StateSet<AgentRole, S>, S>,
     pub markets:        StateMap<ContractAddress, Market, S>,
+    pub orders:         StateMap<OrderId, Order, S>,
+    pub next_order_id:  OrderId,
+    /// Amount of tokens minted by each investor in the current mint market of a token contract.
+    pub investor_mints:
+        StateMap<ContractAddress, StateMap<AccountAddress, SecurityTokenAmount, S>, S>,
 }
 
 impl State {
@@ -105,21 +184,97 @@ impl State {
             .get(agent)
             .map_or(false, |roles| roles.contains(&role))
     }
+
+    /// Returns the currency of a market. Markets without a currency are priced in the default currency of the contract.
+    pub fn market_currency(
+        &self,
+        currency_token: Option<CurrencyTokenAddress>,
+    ) -> CurrencyTokenAddress {
+        currency_token.unwrap_or_else(|| self.currency_token.clone())
+    }
+
+    /// Removes the amounts minted by the investors of the token contract, so that the investor
+    /// limits of a market added later on start from zero.
+    pub fn clear_investor_mints(&mut self, token_contract: &ContractAddress) {
+        if let Some(mints) = self.investor_mints.remove_and_get(token_contract) {
+            mints.delete();
+        }
+    }
 }
 
 #[derive(Serialize, Clone, SchemaType, Debug, PartialEq, Eq)]
 pub enum Market {
     Mint(MintMarket),
     Transfer(TransferMarket),
+    OrderBook(OrderBookMarket),
+}
+
+impl Market {
+    pub fn fee(&self) -> Option<MarketFee> {
+        match self {
+            Market::Mint(m) => m.fee,
+            Market::Transfer(m) => m.fee,
+            Market::OrderBook(m) => m.fee,
+        }
+    }
 }
 
 #[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
 pub struct MintMarket {
-    pub token_id:           TokenIdCalculation,
-    pub rate:               Rate,
+    pub token_id: TokenIdCalculation,
+    /// The rate at which tokens are minted once all the price tiers are sold out.
+    pub rate: Rate,
     pub token_metadata_url: ContractMetadataUrl,
     pub liquidity_provider: AccountAddress,
-    pub max_token_amount:   SecurityTokenAmount,
+    pub max_token_amount: SecurityTokenAmount,
+    /// The time from which tokens can be minted.
+    pub open_time: Timestamp,
+    /// The time after which tokens can not be minted anymore.
+    pub close_time: Timestamp,
+    /// Price tiers which are sold in order before the market `rate` applies.
+    /// The amount of a tier decreases as tokens are minted at its rate.
+    pub price_tiers: Vec<MintPriceTier>,
+    /// The maximum amount of tokens a single investor can mint in this market.
+    pub max_investor_token_amount: Option<SecurityTokenAmount>,
+    pub fee: Option<MarketFee>,
+    /// The currency in which the market is priced. `None` for the default currency of the contract.
+    pub currency_token: Option<CurrencyTokenAddress>,
+}
+
+impl MintMarket {
+    /// Takes the amount from the price tiers in order. The part of the amount exceeding all the
+    /// price tiers is priced at the market `rate`.
+    /// Returns the rate and the amount of every part of the amount, in order.
+    pub fn take_price_tiers(
+        &mut self,
+        amount: SecurityTokenAmount,
+    ) -> Vec<(Rate, SecurityTokenAmount)> {
+        let mut parts = Vec::new();
+        let mut remaining = amount.0;
+        for tier in self.price_tiers.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            let taken = tier.token_amount.0.min(remaining);
+            if taken == 0 {
+                continue;
+            }
+            tier.token_amount = SecurityTokenAmount::from(tier.token_amount.0 - taken);
+            remaining -= taken;
+            parts.push((tier.rate, SecurityTokenAmount::from(taken)));
+        }
+        if remaining > 0 || parts.is_empty() {
+            parts.push((self.rate, SecurityTokenAmount::from(remaining)));
+        }
+        parts
+    }
+}
+
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub struct MintPriceTier {
+    pub rate:         Rate,
+    /// The amount of tokens which are still available at the rate of this tier.
+    pub token_amount: SecurityTokenAmount,
 }
 
 #[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
@@ -134,12 +289,103 @@ pub struct TransferMarket {
     pub sell_rate:           Rate,
     pub max_token_amount:    SecurityTokenAmount,
     pub max_currency_amount: CurrencyTokenAmount,
+    pub fee:                 Option<MarketFee>,
+    /// The currency in which the market is priced. `None` for the default currency of the contract.
+    pub currency_token:      Option<CurrencyTokenAddress>,
+    pub settlement:          TransferSettlement,
+}
+
+/// How the trades of a transfer market are settled.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferSettlement {
+    /// Both legs are transferred directly between the trader and the liquidity provider
+    /// using the operator rights of this contract over the tokens of the liquidity provider.
+    Operator,
+    /// The liquidity provider deposits the liquidity of the market into this contract.
+    /// `max_token_amount` and `max_currency_amount` of the market are the escrowed liquidity.
+    /// Traders deposit their leg and the other leg is released from the escrowed liquidity in the same transaction.
+    Escrow,
+}
+
+/// A market in which holders place limit orders which are filled by other investors.
+/// There is no liquidity provider, every trade is between the order owner and the taker.
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub struct OrderBookMarket {
+    pub token_id:       SecurityTokenId,
+    pub fee:            Option<MarketFee>,
+    /// The currency in which the market is priced. `None` for the default currency of the contract.
+    pub currency_token: Option<CurrencyTokenAddress>,
+}
+
+/// The platform fee taken on every exchange in a market.
+/// The fee is deducted from the currency amount received by the seller.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MarketFee {
+    /// The fraction of the currency amount of the exchange which is taken as fee. Should be less than or equal to `1`.
+    pub rate:     Rate,
+    pub receiver: AccountAddress,
+}
+
+impl MarketFee {
+    pub fn is_valid(&self) -> bool { self.rate.is_valid() && self.rate.le_1() }
+
+    /// Calculates the fee for the currency amount of an exchange. The fee is rounded down.
+    pub fn calculate(
+        &self,
+        currency_amount: &CurrencyTokenAmount,
+    ) -> ContractResult<CurrencyTokenAmount> {
+        let (fee_amount, _) = self
+            .rate
+            .convert_token_amount_with_rem(currency_amount)
+            .map_err(|_| Error::InvalidConversion)?;
+        Ok(fee_amount)
+    }
+}
+
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderSide {
+    /// The owner of the order sells security tokens for currency.
+    Sell,
+    /// The owner of the order buys security tokens with currency.
+    Buy,
+}
+
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub struct Order {
+    pub token_contract: ContractAddress,
+    pub token_id:       SecurityTokenId,
+    pub owner:          AccountAddress,
+    pub side:           OrderSide,
+    /// The rate at which the order is traded. Currency amount = token amount * rate.
+    pub rate:           Rate,
+    /// The amount of security tokens which are still open to be filled.
+    pub amount:         SecurityTokenAmount,
+    pub settlement:     OrderSettlement,
+    /// The currency of the market at the time the order was placed.
+    pub currency_token: CurrencyTokenAddress,
+}
+
+/// How the two legs of a trade against an order are settled.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderSettlement {
+    /// Both legs are transferred directly between the accounts using the operator rights of this contract.
+    Operator,
+    /// The owner of the order has deposited their leg into this contract.
+    /// The taker deposits the other leg and both are released in the same transaction.
+    Escrow(EscrowSettlement),
+}
+
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowSettlement {
+    /// After this time the order can not be filled anymore and the escrow can be refunded to the owner.
+    pub expiry: Timestamp,
 }
 
 /// Initialization parameters for the contract.
 #[derive(Serialize, SchemaType, Clone, Debug)]
 pub struct InitParam {
-    /// The token that is being used to pay for the tokens being sold.
+    /// The default currency used to pay for the tokens being sold.
+    /// Used by the markets which do not specify their own currency.
     pub currency: CurrencyTokenAddress,
     pub agents:   Vec<AgentWithRoles<AgentRole>>,
 }
@@ -181,9 +427,12 @@ pub fn init(
         agents
     };
     let state = State {
-        currency_token: params.currency,
+        currency_token: params.currency.clone(),
         agents,
         markets: state_builder.new_map(),
+        orders: state_builder.new_map(),
+        next_order_id: 0,
+        investor_mints: state_builder.new_map(),
     };
 
     logger.log(&Event::Initialized(params.currency))?;
@@ -278,11 +527,41 @@ fn add_market(
         state.has_agent(&ctx.sender(), AgentRole::AddMarket),
         Error::Unauthorized
     );
+    if let Some(fee) = params.market.fee() {
+        ensure!(fee.is_valid(), Error::InvalidFee);
+    }
+    if let Market::Mint(market) = &params.market {
+        ensure!(
+            market.open_time.lt(&market.close_time),
+            Error::InvalidMintMarket
+        );
+        ensure!(
+            market.price_tiers.iter().all(|tier| tier.rate.is_valid()),
+            Error::InvalidRate
+        );
+    }
+    if let Market::Transfer(market) = &params.market {
+        // The liquidity of escrowed markets is deposited by the liquidity provider
+        ensure!(
+            market.settlement.eq(&TransferSettlement::Operator)
+                || (market.max_token_amount.eq(&0.into())
+                    && market.max_currency_amount.eq(&0.into())),
+            Error::InvalidAmount
+        );
+    }
 
+    state.clear_investor_mints(&params.token_contract);
     let existing = state
         .markets
         .insert(params.token_contract, params.market.clone());
-    if existing.is_some() {
+    if let Some(existing) = existing {
+        refund_liquidity(
+            host,
+            logger,
+            ctx.self_address(),
+            params.token_contract,
+            &existing,
+        )?;
         logger.log(&Event::MarketRemoved(params.token_contract))?;
     }
     logger.log(&Event::MarketAdded(params))?;
@@ -301,17 +580,163 @@ fn remove_market(
     host: &mut Host<State>,
     logger: &mut Logger,
 ) -> ContractResult<()> {
-    let market: ContractAddress = ctx.parameter_cursor().get()?;
+    let token_contract: ContractAddress = ctx.parameter_cursor().get()?;
     let (state, _) = host.state_and_builder();
     ensure!(
         state.has_agent(&ctx.sender(), AgentRole::RemoveMarket),
         Error::Unauthorized
     );
-    state
+    let market = state
         .markets
-        .remove_and_get(&market)
+        .remove_and_get(&token_contract)
         .ok_or(Error::InvalidMarket)?;
-    logger.log(&Event::MarketRemoved(market))?;
+    state.clear_investor_mints(&token_contract);
+    refund_liquidity(host, logger, ctx.self_address(), token_contract, &market)?;
+    logger.log(&Event::MarketRemoved(token_contract))?;
+    Ok(())
+}
+
+/// Returns all the escrowed liquidity of a market to its liquidity provider.
+/// Does nothing for markets which are not escrowed transfer markets.
+fn refund_liquidity(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    self_address: ContractAddress,
+    token_contract: ContractAddress,
+    market: &Market,
+) -> ContractResult<()> {
+    let market = match market {
+        Market::Transfer(market) if market.settlement.eq(&TransferSettlement::Escrow) => market,
+        _ => return Ok(()),
+    };
+    if market.max_token_amount.eq(&0.into()) && market.max_currency_amount.eq(&0.into()) {
+        return Ok(());
+    }
+
+    let currency_token = host.state().market_currency(market.currency_token.clone());
+    let liquidity = LiquidityEvent {
+        token_contract,
+        liquidity_provider: market.liquidity_provider,
+        token_amount: market.max_token_amount,
+        currency_amount: market.max_currency_amount,
+    };
+    release_liquidity(
+        host,
+        self_address,
+        market.token_id,
+        &currency_token,
+        &liquidity,
+    )?;
+    logger.log(&Event::LiquidityWithdrawn(liquidity))?;
+    Ok(())
+}
+
+/// Transfers escrowed liquidity of a transfer market from this contract to the liquidity provider.
+fn release_liquidity(
+    host: &mut Host<State>,
+    self_address: ContractAddress,
+    token_id: SecurityTokenId,
+    currency_token: &CurrencyTokenAddress,
+    liquidity: &LiquidityEvent,
+) -> ContractResult<()> {
+    if liquidity.token_amount.gt(&0.into()) {
+        host.invoke_transfer_single(&liquidity.token_contract, Transfer {
+            amount: liquidity.token_amount,
+            token_id,
+            from: self_address.into(),
+            to: liquidity.liquidity_provider.into(),
+            data: AdditionalData::empty(),
+        })
+        .map_err(|_| Error::TokenTransfer)?;
+    }
+    if liquidity.currency_amount.gt(&0.into()) {
+        host.invoke_transfer_single(&currency_token.contract, Transfer {
+            amount:   liquidity.currency_amount,
+            token_id: currency_token.id.clone(),
+            from:     self_address.into(),
+            to:       liquidity.liquidity_provider.into(),
+            data:     AdditionalData::empty(),
+        })
+        .map_err(|_| Error::CurrencyTransfer)?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct WithdrawLiquidityParams {
+    pub token_contract:  ContractAddress,
+    pub token_amount:    SecurityTokenAmount,
+    pub currency_amount: CurrencyTokenAmount,
+}
+
+/// Returns a part of the escrowed liquidity of a transfer market to the liquidity provider.
+/// Can only be called by the liquidity provider of the market.
+#[receive(
+    contract = "security_p2p_trading",
+    name = "withdrawLiquidity",
+    mutable,
+    parameter = "WithdrawLiquidityParams",
+    error = "Error",
+    enable_logger
+)]
+pub fn withdraw_liquidity(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: WithdrawLiquidityParams = ctx.parameter_cursor().get()?;
+    let liquidity_provider = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(Error::Unauthorized),
+    };
+    let (token_id, currency_token) = {
+        let state = host.state_mut();
+        let mut market = state
+            .markets
+            .get_mut(&params.token_contract)
+            .ok_or(Error::InvalidMarket)?;
+        let market = match market.deref_mut() {
+            Market::Mint(_) | Market::OrderBook(_) => bail!(Error::InvalidMarketType),
+            Market::Transfer(m) => m,
+        };
+        ensure!(
+            market.settlement.eq(&TransferSettlement::Escrow),
+            Error::InvalidSettlement
+        );
+        ensure!(
+            market.liquidity_provider.eq(&liquidity_provider),
+            Error::Unauthorized
+        );
+        market.max_token_amount = market
+            .max_token_amount
+            .0
+            .checked_sub(params.token_amount.0)
+            .map(SecurityTokenAmount::from)
+            .ok_or(Error::MarketTokenLimitExceeded)?;
+        market.max_currency_amount = market
+            .max_currency_amount
+            .0
+            .checked_sub(params.currency_amount.0)
+            .map(CurrencyTokenAmount::from)
+            .ok_or(Error::MarketTokenLimitExceeded)?;
+        (market.token_id, market.currency_token.clone())
+    };
+    let currency_token = host.state().market_currency(currency_token);
+
+    let liquidity = LiquidityEvent {
+        token_contract: params.token_contract,
+        liquidity_provider,
+        token_amount: params.token_amount,
+        currency_amount: params.currency_amount,
+    };
+    release_liquidity(
+        host,
+        ctx.self_address(),
+        token_id,
+        &currency_token,
+        &liquidity,
+    )?;
+    logger.log(&Event::LiquidityWithdrawn(liquidity))?;
     Ok(())
 }
 
@@ -352,45 +777,33 @@ pub fn sell(
         Address::Account(a) => a,
         Address::Contract(_) => bail!(Error::Unauthorized),
     };
-    let (currency_token, currency_amount, liquidity_provider, token_id) = {
-        let state = host.state_mut();
-        let mut market = state
-            .markets
-            .get_mut(&params.contract)
-            .ok_or(Error::InvalidMarket)?;
-        let market = match market.deref_mut() {
-            Market::Mint(_) => bail!(Error::InvalidMarketType),
-            Market::Transfer(m) => m,
-        };
-        ensure!(market.buy_rate.eq(&params.rate), Error::InvalidRate);
-        let (currency_amount, _) = market
-            .buy_rate
-            .convert_token_amount_with_rem(&params.amount)
-            .map_err(|_| Error::InvalidConversion)?;
-        market.max_token_amount += params.amount;
-        market.max_currency_amount = market
-            .max_currency_amount
-            .0
-            .checked_sub(currency_amount.0)
-            .map(CurrencyTokenAmount::from)
-            .ok_or(Error::MarketTokenLimitExceeded)?;
-        (
-            state.currency_token,
-            currency_amount,
-            market.liquidity_provider,
-            market.token_id,
-        )
-    };
+    let (
+        TransferMarket {
+            currency_token,
+            liquidity_provider,
+            token_id,
+            fee,
+            ..
+        },
+        currency_amount,
+    ) = transfer_market_sell(
+        host.state_mut(),
+        &params.contract,
+        params.amount,
+        params.rate,
+        TransferSettlement::Operator,
+    )?;
+    let currency_token = host.state().market_currency(currency_token);
 
     // Transfer currency from liquidity provider to seller
-    host.invoke_transfer_single(&currency_token.contract, Transfer {
-        amount:   currency_amount,
-        token_id: currency_token.id,
-        from:     liquidity_provider.into(),
-        to:       seller.into(),
-        data:     AdditionalData::empty(),
-    })
-    .map_err(|_| Error::CurrencyTransfer)?;
+    let fee_amount = transfer_currency(
+        host,
+        &currency_token,
+        liquidity_provider.into(),
+        seller,
+        currency_amount,
+        fee,
+    )?;
     // Transfer tokens from seller to liquidity provider
     host.invoke_transfer_single(&params.contract, Transfer {
         amount: params.amount,
@@ -411,6 +824,9 @@ pub fn sell(
         rate: params.rate,
         currency_amount,
         exchange_type: ExchangeType::Buy,
+        fee_amount,
+        fee_receiver: fee.map(|fee| fee.receiver),
+        currency_token,
     }))?;
     Ok(())
 }
@@ -433,47 +849,33 @@ pub fn buy(
         Address::Account(a) => a,
         Address::Contract(_) => bail!(Error::Unauthorized),
     };
-    let (currency_token, currency_amount, liquidity_provider, token_id) = {
-        let state = host.state_mut();
-        let mut market = state
-            .markets
-            .get_mut(&params.contract)
-            .ok_or(Error::InvalidMarket)?;
-        let market = match market.deref_mut() {
-            Market::Mint(_) => bail!(Error::InvalidMarketType),
-            Market::Transfer(m) => m,
-        };
-
-        ensure!(market.sell_rate.eq(&params.rate), Error::InvalidRate);
-        let (currency_amount, _) = market
-            .sell_rate
-            .convert_token_amount_with_rem(&params.amount)
-            .map_err(|_| Error::InvalidConversion)?;
-        market.max_token_amount = market
-            .max_token_amount
-            .0
-            .checked_sub(params.amount.0)
-            .map(SecurityTokenAmount::from)
-            .ok_or(Error::MarketTokenLimitExceeded)?;
-        market.max_currency_amount += currency_amount;
-
-        (
-            state.currency_token,
-            currency_amount,
-            market.liquidity_provider,
-            market.token_id,
-        )
-    };
+    let (
+        TransferMarket {
+            currency_token,
+            liquidity_provider,
+            token_id,
+            fee,
+            ..
+        },
+        currency_amount,
+    ) = transfer_market_buy(
+        host.state_mut(),
+        &params.contract,
+        params.amount,
+        params.rate,
+        TransferSettlement::Operator,
+    )?;
+    let currency_token = host.state().market_currency(currency_token);
 
     // Transfer tokens from currency from buyer to liquidity provider
-    host.invoke_transfer_single(&currency_token.contract, Transfer {
-        amount:   currency_amount,
-        token_id: currency_token.id,
-        from:     buyer.into(),
-        to:       liquidity_provider.into(),
-        data:     AdditionalData::empty(),
-    })
-    .map_err(|_| Error::CurrencyTransfer)?;
+    let fee_amount = transfer_currency(
+        host,
+        &currency_token,
+        buyer.into(),
+        liquidity_provider,
+        currency_amount,
+        fee,
+    )?;
     // Transfer Tokens from liquidity provider to buyer
     host.invoke_transfer_single(&params.contract, Transfer {
         amount: params.amount,
@@ -494,10 +896,89 @@ pub fn buy(
         rate: params.rate,
         currency_amount,
         exchange_type: ExchangeType::Sell,
+        fee_amount,
+        fee_receiver: fee.map(|fee| fee.receiver),
+        currency_token,
     }))?;
     Ok(())
 }
 
+/// Updates the liquidity of a transfer market for the liquidity provider buying `amount` security tokens at `rate`.
+/// Returns the updated market and the currency amount paid by the liquidity provider.
+fn transfer_market_sell(
+    state: &mut State,
+    token_contract: &ContractAddress,
+    amount: SecurityTokenAmount,
+    rate: Rate,
+    settlement: TransferSettlement,
+) -> ContractResult<(TransferMarket, CurrencyTokenAmount)> {
+    let mut market = state
+        .markets
+        .get_mut(token_contract)
+        .ok_or(Error::InvalidMarket)?;
+    let market = match market.deref_mut() {
+        Market::Mint(_) | Market::OrderBook(_) => bail!(Error::InvalidMarketType),
+        Market::Transfer(m) => m,
+    };
+    ensure!(market.settlement.eq(&settlement), Error::InvalidSettlement);
+    ensure!(market.buy_rate.eq(&rate), Error::InvalidRate);
+    let (currency_amount, _) = market
+        .buy_rate
+        .convert_token_amount_with_rem(&amount)
+        .map_err(|_| Error::InvalidConversion)?;
+    market.max_token_amount += amount;
+    market.max_currency_amount = market
+        .max_currency_amount
+        .0
+        .checked_sub(currency_amount.0)
+        .map(CurrencyTokenAmount::from)
+        .ok_or(Error::MarketTokenLimitExceeded)?;
+    Ok((market.clone(), currency_amount))
+}
+
+/// Updates the liquidity of a transfer market for the liquidity provider selling `amount` security tokens at `rate`.
+/// Returns the updated market and the currency amount paid by the buyer.
+fn transfer_market_buy(
+    state: &mut State,
+    token_contract: &ContractAddress,
+    amount: SecurityTokenAmount,
+    rate: Rate,
+    settlement: TransferSettlement,
+) -> ContractResult<(TransferMarket, CurrencyTokenAmount)> {
+    let mut market = state
+        .markets
+        .get_mut(token_contract)
+        .ok_or(Error::InvalidMarket)?;
+    let market = match market.deref_mut() {
+        Market::Mint(_) | Market::OrderBook(_) => bail!(Error::InvalidMarketType),
+        Market::Transfer(m) => m,
+    };
+    ensure!(market.settlement.eq(&settlement), Error::InvalidSettlement);
+    ensure!(market.sell_rate.eq(&rate), Error::InvalidRate);
+    let (currency_amount, _) = market
+        .sell_rate
+        .convert_token_amount_with_rem(&amount)
+        .map_err(|_| Error::InvalidConversion)?;
+    market.max_token_amount = market
+        .max_token_amount
+        .0
+        .checked_sub(amount.0)
+        .map(SecurityTokenAmount::from)
+        .ok_or(Error::MarketTokenLimitExceeded)?;
+    market.max_currency_amount += match settlement {
+        TransferSettlement::Operator => currency_amount,
+        // Only the part of the payment which is not taken as fee is added to the escrowed liquidity
+        TransferSettlement::Escrow => {
+            let fee_amount = match market.fee {
+                Some(fee) => fee.calculate(&currency_amount)?,
+                None => 0.into(),
+            };
+            CurrencyTokenAmount::from(currency_amount.0 - fee_amount.0)
+        }
+    };
+    Ok((market.clone(), currency_amount))
+}
+
 #[derive(Serialize, SchemaType, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TokenIdCalculation {
     /// The start time of the market. This is the time when the market starts.
@@ -526,6 +1007,8 @@ impl TokenIdCalculation {
 pub struct MintParams {
     pub token_contract: ContractAddress,
     pub amount:         SecurityTokenAmount,
+    /// The rate of the current price tier, or the market rate if all the price tiers are sold
+    /// out.
     pub rate:           Rate,
 }
 
@@ -549,7 +1032,15 @@ pub fn mint(
     };
     let now = ctx.metadata().block_time();
 
-    let (currency_token, currency_amount, token_id, liquidity_provider, token_metadata_url) = {
+    let (
+        currency_token,
+        parts,
+        token_id,
+        liquidity_provider,
+        token_metadata_url,
+        max_investor_token_amount,
+        fee,
+    ) = {
         let state = host.state_mut();
         let mut market = state
             .markets
@@ -557,13 +1048,17 @@ pub fn mint(
             .ok_or(Error::InvalidMarket)?;
         let market = match market.deref_mut() {
             Market::Mint(m) => m,
-            Market::Transfer(_) => bail!(Error::InvalidMarketType),
+            Market::Transfer(_) | Market::OrderBook(_) => bail!(Error::InvalidMarketType),
         };
-        ensure!(market.rate.eq(&params.rate), Error::InvalidRate);
-        let (currency_amount, _) = market
-            .rate
-            .convert_token_amount_with_rem(&params.amount)
-            .map_err(|_| Error::InvalidConversion)?;
+        ensure!(now.ge(&market.open_time), Error::MintMarketNotStarted);
+        ensure!(now.lt(&market.close_time), Error::MintMarketClosed);
+        // A mint which exceeds the current price tier is split across the following tiers and
+        // priced at the rate of each of them
+        let parts = market.take_price_tiers(params.amount);
+        ensure!(
+            parts.first().is_some_and(|(rate, _)| rate.eq(&params.rate)),
+            Error::InvalidRate
+        );
         market.max_token_amount = market
             .max_token_amount
             .0
@@ -572,16 +1067,35 @@ pub fn mint(
             .ok_or(Error::MarketTokenLimitExceeded)?;
 
         (
-            state.currency_token,
-            currency_amount,
+            market.currency_token.clone(),
+            parts,
             market
                 .token_id
                 .calculate_token_id(now)
                 .ok_or(Error::MintMarketNotStarted)?,
             market.liquidity_provider,
             market.token_metadata_url.clone(),
+            market.max_investor_token_amount,
+            market.fee,
         )
     };
+    let currency_token = host.state().market_currency(currency_token);
+
+    {
+        let (state, state_builder) = host.state_and_builder();
+        let mut market_mints = state
+            .investor_mints
+            .entry(params.token_contract)
+            .or_insert_with(|| state_builder.new_map());
+        let mut investor_minted = market_mints.entry(buyer).or_insert(0.into());
+        *investor_minted += params.amount;
+        if let Some(max_investor_token_amount) = max_investor_token_amount {
+            ensure!(
+                investor_minted.le(&max_investor_token_amount),
+                Error::InvestorTokenLimitExceeded
+            );
+        }
+    }
 
     if host
         .invoke_token_metadata_single(&params.token_contract, token_id)
@@ -594,31 +1108,949 @@ pub fn mint(
         .map_err(|_| Error::AddToken)?;
     }
 
-    host.invoke_transfer_single(&currency_token.contract, Transfer {
-        amount:   currency_amount,
-        token_id: currency_token.id,
-        from:     buyer.into(),
-        to:       liquidity_provider.into(),
-        data:     AdditionalData::empty(),
-    })
-    .map_err(|_| Error::CurrencyTransfer)?;
-    host.invoke_mint_single(&params.token_contract, token_id, MintParam {
-        address: buyer.into(),
-        amount:  TokenAmountSecurity {
-            un_frozen: params.amount,
-            ..Default::default()
-        },
-    })
-    .map_err(|_| Error::TokenMint)?;
-    logger.log(&Event::Exchanged(ExchangeEvent {
+    for (rate, token_amount) in parts {
+        let (currency_amount, _) = rate
+            .convert_token_amount_with_rem(&token_amount)
+            .map_err(|_| Error::InvalidConversion)?;
+        let fee_amount = transfer_currency(
+            host,
+            &currency_token,
+            buyer.into(),
+            liquidity_provider,
+            currency_amount,
+            fee,
+        )?;
+        host.invoke_mint_single(&params.token_contract, token_id, MintParam {
+            address: buyer.into(),
+            amount:  TokenAmountSecurity {
+                un_frozen: token_amount,
+                ..Default::default()
+            },
+        })
+        .map_err(|_| Error::TokenMint)?;
+        logger.log(&Event::Exchanged(ExchangeEvent {
+            token_contract: params.token_contract,
+            token_id,
+            seller: liquidity_provider,
+            buyer,
+            token_amount,
+            rate,
+            currency_amount,
+            exchange_type: ExchangeType::Mint,
+            fee_amount,
+            fee_receiver: fee.map(|fee| fee.receiver),
+            currency_token: currency_token.clone(),
+        }))?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct PlaceOrderParams {
+    pub token_contract: ContractAddress,
+    pub side:           OrderSide,
+    pub amount:         SecurityTokenAmount,
+    pub rate:           Rate,
+}
+
+/// Places a limit order in the order book market of the token contract.
+/// The sender of a `Sell` order should have added this contract as an operator of the security token
+/// and the sender of a `Buy` order should have added this contract as an operator of the currency token.
+/// Otherwise the order can not be filled.
+#[receive(
+    contract = "security_p2p_trading",
+    name = "placeOrder",
+    mutable,
+    parameter = "PlaceOrderParams",
+    error = "Error",
+    enable_logger
+)]
+pub fn place_order(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: PlaceOrderParams = ctx.parameter_cursor().get()?;
+    let owner = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(Error::Unauthorized),
+    };
+    ensure!(params.amount.gt(&0.into()), Error::InvalidAmount);
+    ensure!(params.rate.is_valid(), Error::InvalidRate);
+    params
+        .rate
+        .convert_token_amount_with_rem(&params.amount)
+        .map_err(|_| Error::InvalidConversion)?;
+
+    let state = host.state_mut();
+    let market = order_book_market(state, &params.token_contract)?;
+    let currency_token = state.market_currency(market.currency_token);
+    let order_id = state.next_order_id;
+    state.next_order_id += 1;
+    let order = Order {
         token_contract: params.token_contract,
-        token_id,
-        seller: liquidity_provider,
-        buyer,
-        token_amount: params.amount,
+        token_id: market.token_id,
+        owner,
+        side: params.side,
         rate: params.rate,
-        currency_amount,
-        exchange_type: ExchangeType::Mint,
-    }))?;
+        amount: params.amount,
+        settlement: OrderSettlement::Operator,
+        currency_token,
+    };
+    let _ = state.orders.insert(order_id, order.clone());
+    logger.log(&Event::OrderPlaced(OrderPlacedEvent { order_id, order }))?;
+    Ok(())
+}
+
+/// Cancels an open order. Only the owner of the order can cancel it.
+#[receive(
+    contract = "security_p2p_trading",
+    name = "cancelOrder",
+    mutable,
+    parameter = "OrderId",
+    error = "Error",
+    enable_logger
+)]
+pub fn cancel_order(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let order_id: OrderId = ctx.parameter_cursor().get()?;
+    let order = host
+        .state_mut()
+        .orders
+        .remove_and_get(&order_id)
+        .ok_or(Error::InvalidOrder)?;
+    ensure!(
+        ctx.sender().matches_account(&order.owner),
+        Error::Unauthorized
+    );
+    refund_escrow(host, ctx.self_address(), &order)?;
+    logger.log(&Event::OrderCancelled(OrderCancelledEvent {
+        order_id,
+        token_contract: order.token_contract,
+        owner: order.owner,
+        amount: order.amount,
+    }))?;
+    Ok(())
+}
+
+#[receive(
+    contract = "security_p2p_trading",
+    name = "getOrder",
+    parameter = "OrderId",
+    error = "Error"
+)]
+fn get_order(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<Order> {
+    let order_id: OrderId = ctx.parameter_cursor().get()?;
+    let order = host
+        .state()
+        .orders
+        .get(&order_id)
+        .ok_or(Error::InvalidOrder)?;
+    Ok(order.clone())
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct FillOrderParams {
+    pub order_id: OrderId,
+    /// The amount of security tokens to fill. Can be less than the open amount of the order.
+    pub amount:   SecurityTokenAmount,
+}
+
+/// Fills an open order partially or fully.
+/// For a `Sell` order the sender buys the security tokens from the order owner.
+/// For a `Buy` order the sender sells the security tokens to the order owner.
+#[receive(
+    contract = "security_p2p_trading",
+    name = "fillOrder",
+    mutable,
+    parameter = "FillOrderParams",
+    error = "Error",
+    enable_logger
+)]
+pub fn fill_order(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: FillOrderParams = ctx.parameter_cursor().get()?;
+    let taker = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(Error::Unauthorized),
+    };
+    ensure!(params.amount.gt(&0.into()), Error::InvalidAmount);
+
+    let (currency_token, currency_amount, order, fee) = {
+        let state = host.state_mut();
+        let order = {
+            let mut order = state
+                .orders
+                .get_mut(&params.order_id)
+                .ok_or(Error::InvalidOrder)?;
+            ensure!(order.owner.ne(&taker), Error::Unauthorized);
+            ensure!(
+                order.settlement.eq(&OrderSettlement::Operator),
+                Error::InvalidSettlement
+            );
+            order.amount = order
+                .amount
+                .0
+                .checked_sub(params.amount.0)
+                .map(SecurityTokenAmount::from)
+                .ok_or(Error::OrderAmountExceeded)?;
+            order.clone()
+        };
+        let market = order_book_market(state, &order.token_contract)?;
+        if order.amount.eq(&0.into()) {
+            state.orders.remove(&params.order_id);
+        }
+        let (currency_amount, _) = order
+            .rate
+            .convert_token_amount_with_rem(&params.amount)
+            .map_err(|_| Error::InvalidConversion)?;
+        (
+            order.currency_token.clone(),
+            currency_amount,
+            order,
+            market.fee,
+        )
+    };
+
+    let (seller, buyer) = match order.side {
+        OrderSide::Sell => (order.owner, taker),
+        OrderSide::Buy => (taker, order.owner),
+    };
+    // Transfer currency from buyer to seller
+    let fee_amount = transfer_currency(
+        host,
+        &currency_token,
+        buyer.into(),
+        seller,
+        currency_amount,
+        fee,
+    )?;
+    // Transfer tokens from seller to buyer
+    host.invoke_transfer_single(&order.token_contract, Transfer {
+        amount:   params.amount,
+        token_id: order.token_id,
+        from:     seller.into(),
+        to:       buyer.into(),
+        data:     AdditionalData::empty(),
+    })
+    .map_err(|_| Error::TokenTransfer)?;
+
+    logger.log(&Event::OrderFilled(OrderFilledEvent {
+        order_id: params.order_id,
+        token_contract: order.token_contract,
+        token_id: order.token_id,
+        taker,
+        token_amount: params.amount,
+        currency_amount,
+        remaining_amount: order.amount,
+    }))?;
+    logger.log(&Event::Exchanged(ExchangeEvent {
+        token_contract: order.token_contract,
+        token_id: order.token_id,
+        seller,
+        buyer,
+        token_amount: params.amount,
+        rate: order.rate,
+        currency_amount,
+        exchange_type: ExchangeType::Order,
+        fee_amount,
+        fee_receiver: fee.map(|fee| fee.receiver),
+        currency_token,
+    }))?;
     Ok(())
 }
+
+/// Returns the escrowed leg of the remaining amount of the order back to the owner of the order.
+/// Does nothing for orders which are settled using operator rights.
+fn refund_escrow(
+    host: &mut Host<State>,
+    self_address: ContractAddress,
+    order: &Order,
+) -> ContractResult<()> {
+    if order.settlement.eq(&OrderSettlement::Operator) || order.amount.eq(&0.into()) {
+        return Ok(());
+    }
+
+    match order.side {
+        OrderSide::Sell => host
+            .invoke_transfer_single(&order.token_contract, Transfer {
+                amount:   order.amount,
+                token_id: order.token_id,
+                from:     self_address.into(),
+                to:       order.owner.into(),
+                data:     AdditionalData::empty(),
+            })
+            .map_err(|_| Error::TokenTransfer)?,
+        OrderSide::Buy => {
+            let currency_token = &order.currency_token;
+            let currency_amount = order
+                .rate
+                .convert_token_amount(&order.amount)
+                .map_err(|_| Error::InvalidConversion)?;
+            host.invoke_transfer_single(&currency_token.contract, Transfer {
+                amount:   currency_amount,
+                token_id: currency_token.id.clone(),
+                from:     self_address.into(),
+                to:       order.owner.into(),
+                data:     AdditionalData::empty(),
+            })
+            .map_err(|_| Error::CurrencyTransfer)?
+        }
+    };
+    Ok(())
+}
+
+/// Refunds the escrow of an order which has expired without being fully filled.
+/// Can be called by anyone once the expiry of the order has passed.
+#[receive(
+    contract = "security_p2p_trading",
+    name = "refundOrder",
+    mutable,
+    parameter = "OrderId",
+    error = "Error",
+    enable_logger
+)]
+pub fn refund_order(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let order_id: OrderId = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().block_time();
+    let order = host
+        .state_mut()
+        .orders
+        .remove_and_get(&order_id)
+        .ok_or(Error::InvalidOrder)?;
+    match order.settlement {
+        OrderSettlement::Operator => bail!(Error::InvalidSettlement),
+        OrderSettlement::Escrow(EscrowSettlement { expiry }) => {
+            ensure!(now.ge(&expiry), Error::OrderNotExpired)
+        }
+    };
+    refund_escrow(host, ctx.self_address(), &order)?;
+    logger.log(&Event::OrderExpired(OrderCancelledEvent {
+        order_id,
+        token_contract: order.token_contract,
+        owner: order.owner,
+        amount: order.amount,
+    }))?;
+    Ok(())
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct PlaceEscrowOrderParams {
+    pub token_contract: ContractAddress,
+    /// The amount of security tokens of the order.
+    pub amount:         SecurityTokenAmount,
+    pub rate:           Rate,
+    pub expiry:         Timestamp,
+}
+
+/// The action to perform with the tokens deposited into this contract.
+/// This is sent as the `data` of the CIS-2 transfer to this contract.
+#[derive(Serialize, SchemaType, Debug)]
+pub enum EscrowAction {
+    /// Places an escrowed order with the deposited tokens.
+    /// Depositing security tokens places a `Sell` order and depositing currency places a `Buy` order.
+    PlaceOrder(PlaceEscrowOrderParams),
+    /// Fills an escrowed order with the deposited tokens.
+    /// `Sell` orders are filled by depositing currency and `Buy` orders by depositing security tokens.
+    FillOrder(OrderId),
+    /// Adds the deposited tokens to the escrowed liquidity of the transfer market of the token contract.
+    /// Can only be deposited by the liquidity provider of the market.
+    DepositLiquidity(ContractAddress),
+    /// Sells the deposited security tokens to the liquidity provider of an escrowed transfer market.
+    Sell(EscrowExchangeParams),
+    /// Buys security tokens from the liquidity provider of an escrowed transfer market with the deposited currency.
+    Buy(EscrowExchangeParams),
+}
+
+#[derive(Serialize, SchemaType, Debug, Clone)]
+pub struct EscrowExchangeParams {
+    pub token_contract: ContractAddress,
+    pub rate:           Rate,
+}
+
+pub type EscrowCurrencyReceiveParams =
+    OnReceivingCis2DataParams<CurrencyTokenId, CurrencyTokenAmount, EscrowAction>;
+pub type EscrowSecurityReceiveParams =
+    OnReceivingCis2DataParams<SecurityTokenId, SecurityTokenAmount, EscrowAction>;
+
+/// CIS-2 receive hook for the currency token.
+/// The currency is held by this contract until the escrowed trade is settled or refunded.
+#[receive(
+    contract = "security_p2p_trading",
+    name = "onReceivingCurrency",
+    mutable,
+    parameter = "EscrowCurrencyReceiveParams",
+    error = "Error",
+    enable_logger
+)]
+pub fn on_receiving_currency(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let EscrowCurrencyReceiveParams {
+        token_id,
+        amount: currency_amount,
+        from,
+        data: action,
+    } = ctx.parameter_cursor().get()?;
+    let from = match from {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(Error::Unauthorized),
+    };
+    let currency_token = CurrencyTokenAddress {
+        id:       token_id,
+        contract: match ctx.sender() {
+            Address::Account(_) => bail!(Error::Unauthorized),
+            Address::Contract(c) => c,
+        },
+    };
+    let now = ctx.metadata().block_time();
+
+    match action {
+        EscrowAction::PlaceOrder(params) => {
+            {
+                let state = host.state();
+                let market = order_book_market(state, &params.token_contract)?;
+                ensure!(
+                    state
+                        .market_currency(market.currency_token)
+                        .eq(&currency_token),
+                    Error::InvalidCurrency
+                );
+            }
+            let order_amount = params
+                .rate
+                .convert_token_amount(&params.amount)
+                .map_err(|_| Error::InvalidConversion)?;
+            ensure!(order_amount.eq(&currency_amount), Error::InvalidAmount);
+            place_escrow_order(host, logger, now, from, OrderSide::Buy, params)
+        }
+        EscrowAction::FillOrder(order_id) => {
+            let rate = {
+                let state = host.state();
+                let order = state.orders.get(&order_id).ok_or(Error::InvalidOrder)?;
+                ensure!(
+                    order.currency_token.eq(&currency_token),
+                    Error::InvalidCurrency
+                );
+                order.rate
+            };
+            let token_amount = rate
+                .convert_currency_amount(&currency_amount)
+                .map_err(|_| Error::InvalidConversion)?;
+            fill_escrow_order(
+                host,
+                logger,
+                now,
+                ctx.self_address(),
+                from,
+                order_id,
+                OrderSide::Sell,
+                token_amount,
+            )
+        }
+        EscrowAction::DepositLiquidity(token_contract) => {
+            {
+                let state = host.state();
+                let market = escrow_transfer_market(state, &token_contract)?;
+                ensure!(
+                    state
+                        .market_currency(market.currency_token)
+                        .eq(&currency_token),
+                    Error::InvalidCurrency
+                );
+            }
+            deposit_liquidity(
+                host,
+                logger,
+                from,
+                token_contract,
+                0.into(),
+                currency_amount,
+            )
+        }
+        EscrowAction::Buy(params) => buy_escrow(
+            host,
+            logger,
+            ctx.self_address(),
+            from,
+            currency_token,
+            currency_amount,
+            params,
+        ),
+        EscrowAction::Sell(_) => bail!(Error::InvalidEscrowAction),
+    }
+}
+
+/// CIS-2 receive hook for the security tokens traded in order book markets.
+/// The tokens are held by this contract until the escrowed trade is settled or refunded.
+#[receive(
+    contract = "security_p2p_trading",
+    name = "onReceivingSecurity",
+    mutable,
+    parameter = "EscrowSecurityReceiveParams",
+    error = "Error",
+    enable_logger
+)]
+pub fn on_receiving_security(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let EscrowSecurityReceiveParams {
+        token_id,
+        amount: token_amount,
+        from,
+        data: action,
+    } = ctx.parameter_cursor().get()?;
+    let from = match from {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(Error::Unauthorized),
+    };
+    let token_contract = match ctx.sender() {
+        Address::Account(_) => bail!(Error::Unauthorized),
+        Address::Contract(c) => c,
+    };
+    let now = ctx.metadata().block_time();
+
+    match action {
+        EscrowAction::PlaceOrder(params) => {
+            ensure!(
+                params.token_contract.eq(&token_contract),
+                Error::InvalidMarket
+            );
+            ensure!(params.amount.eq(&token_amount), Error::InvalidAmount);
+            ensure!(
+                order_book_market(host.state(), &token_contract)?
+                    .token_id
+                    .eq(&token_id),
+                Error::InvalidMarket
+            );
+            place_escrow_order(host, logger, now, from, OrderSide::Sell, params)
+        }
+        EscrowAction::FillOrder(order_id) => {
+            {
+                let state = host.state();
+                let order = state.orders.get(&order_id).ok_or(Error::InvalidOrder)?;
+                ensure!(
+                    order.token_contract.eq(&token_contract) && order.token_id.eq(&token_id),
+                    Error::InvalidOrder
+                );
+            }
+            fill_escrow_order(
+                host,
+                logger,
+                now,
+                ctx.self_address(),
+                from,
+                order_id,
+                OrderSide::Buy,
+                token_amount,
+            )
+        }
+        EscrowAction::DepositLiquidity(market_contract) => {
+            ensure!(market_contract.eq(&token_contract), Error::InvalidMarket);
+            ensure!(
+                escrow_transfer_market(host.state(), &token_contract)?
+                    .token_id
+                    .eq(&token_id),
+                Error::InvalidMarket
+            );
+            deposit_liquidity(host, logger, from, token_contract, token_amount, 0.into())
+        }
+        EscrowAction::Sell(params) => {
+            ensure!(
+                params.token_contract.eq(&token_contract),
+                Error::InvalidMarket
+            );
+            sell_escrow(
+                host,
+                logger,
+                ctx.self_address(),
+                from,
+                token_id,
+                token_amount,
+                params,
+            )
+        }
+        EscrowAction::Buy(_) => bail!(Error::InvalidEscrowAction),
+    }
+}
+
+fn order_book_market(
+    state: &State,
+    token_contract: &ContractAddress,
+) -> ContractResult<OrderBookMarket> {
+    match state
+        .markets
+        .get(token_contract)
+        .ok_or(Error::InvalidMarket)?
+        .deref()
+    {
+        Market::OrderBook(m) => Ok(m.clone()),
+        Market::Mint(_) | Market::Transfer(_) => bail!(Error::InvalidMarketType),
+    }
+}
+
+fn escrow_transfer_market(
+    state: &State,
+    token_contract: &ContractAddress,
+) -> ContractResult<TransferMarket> {
+    match state
+        .markets
+        .get(token_contract)
+        .ok_or(Error::InvalidMarket)?
+        .deref()
+    {
+        Market::Transfer(m) => {
+            ensure!(
+                m.settlement.eq(&TransferSettlement::Escrow),
+                Error::InvalidSettlement
+            );
+            Ok(m.clone())
+        }
+        Market::Mint(_) | Market::OrderBook(_) => bail!(Error::InvalidMarketType),
+    }
+}
+
+/// Adds the deposit of the liquidity provider to the escrowed liquidity of a transfer market.
+/// The deposited token or currency is expected to be validated against the market by the caller.
+fn deposit_liquidity(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    liquidity_provider: AccountAddress,
+    token_contract: ContractAddress,
+    token_amount: SecurityTokenAmount,
+    currency_amount: CurrencyTokenAmount,
+) -> ContractResult<()> {
+    {
+        let mut market = host
+            .state_mut()
+            .markets
+            .get_mut(&token_contract)
+            .ok_or(Error::InvalidMarket)?;
+        let market = match market.deref_mut() {
+            Market::Mint(_) | Market::OrderBook(_) => bail!(Error::InvalidMarketType),
+            Market::Transfer(m) => m,
+        };
+        ensure!(
+            market.liquidity_provider.eq(&liquidity_provider),
+            Error::Unauthorized
+        );
+        market.max_token_amount += token_amount;
+        market.max_currency_amount += currency_amount;
+    }
+    logger.log(&Event::LiquidityDeposited(LiquidityEvent {
+        token_contract,
+        liquidity_provider,
+        token_amount,
+        currency_amount,
+    }))?;
+    Ok(())
+}
+
+/// Settles the sale of the deposited security tokens to the liquidity provider of an escrowed transfer market.
+/// The currency is paid from the escrowed liquidity and the tokens are added to it.
+fn sell_escrow(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    self_address: ContractAddress,
+    seller: AccountAddress,
+    token_id: SecurityTokenId,
+    token_amount: SecurityTokenAmount,
+    params: EscrowExchangeParams,
+) -> ContractResult<()> {
+    let (market, currency_amount) = transfer_market_sell(
+        host.state_mut(),
+        &params.token_contract,
+        token_amount,
+        params.rate,
+        TransferSettlement::Escrow,
+    )?;
+    ensure!(market.token_id.eq(&token_id), Error::InvalidMarket);
+    let currency_token = host.state().market_currency(market.currency_token);
+
+    // Release the escrowed currency to the seller
+    let fee_amount = transfer_currency(
+        host,
+        &currency_token,
+        self_address.into(),
+        seller,
+        currency_amount,
+        market.fee,
+    )?;
+
+    logger.log(&Event::Exchanged(ExchangeEvent {
+        token_contract: params.token_contract,
+        token_id,
+        seller,
+        buyer: market.liquidity_provider,
+        token_amount,
+        rate: params.rate,
+        currency_amount,
+        exchange_type: ExchangeType::Buy,
+        fee_amount,
+        fee_receiver: market.fee.map(|fee| fee.receiver),
+        currency_token,
+    }))?;
+    Ok(())
+}
+
+/// Settles the purchase of security tokens from the liquidity provider of an escrowed transfer market with the deposited currency.
+/// The tokens are released from the escrowed liquidity and the currency, less the fee, is added to it.
+fn buy_escrow(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    self_address: ContractAddress,
+    buyer: AccountAddress,
+    currency_token: CurrencyTokenAddress,
+    currency_amount: CurrencyTokenAmount,
+    params: EscrowExchangeParams,
+) -> ContractResult<()> {
+    let token_amount = params
+        .rate
+        .convert_currency_amount(&currency_amount)
+        .map_err(|_| Error::InvalidConversion)?;
+    ensure!(token_amount.gt(&0.into()), Error::InvalidAmount);
+    let (market, market_currency_amount) = transfer_market_buy(
+        host.state_mut(),
+        &params.token_contract,
+        token_amount,
+        params.rate,
+        TransferSettlement::Escrow,
+    )?;
+    ensure!(
+        market_currency_amount.eq(&currency_amount),
+        Error::InvalidAmount
+    );
+    ensure!(
+        host.state()
+            .market_currency(market.currency_token)
+            .eq(&currency_token),
+        Error::InvalidCurrency
+    );
+
+    let fee_amount = transfer_fee(
+        host,
+        &currency_token,
+        self_address.into(),
+        currency_amount,
+        market.fee,
+    )?;
+    // Release the escrowed security tokens to the buyer
+    host.invoke_transfer_single(&params.token_contract, Transfer {
+        amount:   token_amount,
+        token_id: market.token_id,
+        from:     self_address.into(),
+        to:       buyer.into(),
+        data:     AdditionalData::empty(),
+    })
+    .map_err(|_| Error::TokenTransfer)?;
+
+    logger.log(&Event::Exchanged(ExchangeEvent {
+        token_contract: params.token_contract,
+        token_id: market.token_id,
+        seller: market.liquidity_provider,
+        buyer,
+        token_amount,
+        rate: params.rate,
+        currency_amount,
+        exchange_type: ExchangeType::Sell,
+        fee_amount,
+        fee_receiver: market.fee.map(|fee| fee.receiver),
+        currency_token,
+    }))?;
+    Ok(())
+}
+
+fn place_escrow_order(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    now: Timestamp,
+    owner: AccountAddress,
+    side: OrderSide,
+    params: PlaceEscrowOrderParams,
+) -> ContractResult<()> {
+    ensure!(params.amount.gt(&0.into()), Error::InvalidAmount);
+    ensure!(params.rate.is_valid(), Error::InvalidRate);
+    ensure!(params.expiry.gt(&now), Error::OrderExpired);
+
+    let state = host.state_mut();
+    let market = order_book_market(state, &params.token_contract)?;
+    let currency_token = state.market_currency(market.currency_token);
+    let order_id = state.next_order_id;
+    state.next_order_id += 1;
+    let order = Order {
+        token_contract: params.token_contract,
+        token_id: market.token_id,
+        owner,
+        side,
+        rate: params.rate,
+        amount: params.amount,
+        settlement: OrderSettlement::Escrow(EscrowSettlement {
+            expiry: params.expiry,
+        }),
+        currency_token,
+    };
+    let _ = state.orders.insert(order_id, order.clone());
+    logger.log(&Event::OrderPlaced(OrderPlacedEvent { order_id, order }))?;
+    Ok(())
+}
+
+/// Settles a fill of an escrowed order.
+/// The leg deposited by the taker and the matching part of the escrowed leg of the owner are released together.
+#[allow(clippy::too_many_arguments)]
+fn fill_escrow_order(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    now: Timestamp,
+    self_address: ContractAddress,
+    taker: AccountAddress,
+    order_id: OrderId,
+    order_side: OrderSide,
+    token_amount: SecurityTokenAmount,
+) -> ContractResult<()> {
+    ensure!(token_amount.gt(&0.into()), Error::InvalidAmount);
+    let (currency_token, currency_amount, order, fee) = {
+        let state = host.state_mut();
+        let order = {
+            let mut order = state.orders.get_mut(&order_id).ok_or(Error::InvalidOrder)?;
+            ensure!(order.owner.ne(&taker), Error::Unauthorized);
+            ensure!(order.side.eq(&order_side), Error::InvalidOrder);
+            match order.settlement {
+                OrderSettlement::Operator => bail!(Error::InvalidSettlement),
+                OrderSettlement::Escrow(EscrowSettlement { expiry }) => {
+                    ensure!(now.lt(&expiry), Error::OrderExpired)
+                }
+            };
+            order.amount = order
+                .amount
+                .0
+                .checked_sub(token_amount.0)
+                .map(SecurityTokenAmount::from)
+                .ok_or(Error::OrderAmountExceeded)?;
+            order.clone()
+        };
+        let market = order_book_market(state, &order.token_contract)?;
+        if order.amount.eq(&0.into()) {
+            state.orders.remove(&order_id);
+        }
+        let currency_amount = order
+            .rate
+            .convert_token_amount(&token_amount)
+            .map_err(|_| Error::InvalidConversion)?;
+        (
+            order.currency_token.clone(),
+            currency_amount,
+            order,
+            market.fee,
+        )
+    };
+
+    let (seller, buyer) = match order.side {
+        OrderSide::Sell => (order.owner, taker),
+        OrderSide::Buy => (taker, order.owner),
+    };
+    // Release the escrowed and the deposited currency to the seller
+    let fee_amount = transfer_currency(
+        host,
+        &currency_token,
+        self_address.into(),
+        seller,
+        currency_amount,
+        fee,
+    )?;
+    // Release the escrowed and the deposited security tokens to the buyer
+    host.invoke_transfer_single(&order.token_contract, Transfer {
+        amount:   token_amount,
+        token_id: order.token_id,
+        from:     self_address.into(),
+        to:       buyer.into(),
+        data:     AdditionalData::empty(),
+    })
+    .map_err(|_| Error::TokenTransfer)?;
+
+    logger.log(&Event::OrderFilled(OrderFilledEvent {
+        order_id,
+        token_contract: order.token_contract,
+        token_id: order.token_id,
+        taker,
+        token_amount,
+        currency_amount,
+        remaining_amount: order.amount,
+    }))?;
+    logger.log(&Event::Exchanged(ExchangeEvent {
+        token_contract: order.token_contract,
+        token_id: order.token_id,
+        seller,
+        buyer,
+        token_amount,
+        rate: order.rate,
+        currency_amount,
+        exchange_type: ExchangeType::Order,
+        fee_amount,
+        fee_receiver: fee.map(|fee| fee.receiver),
+        currency_token,
+    }))?;
+    Ok(())
+}
+
+/// Transfers the currency amount of an exchange to the seller.
+/// The fee of the market is deducted from the amount received by the seller and transferred to the fee receiver.
+/// Returns the fee amount.
+fn transfer_currency(
+    host: &mut Host<State>,
+    currency_token: &CurrencyTokenAddress,
+    from: Address,
+    seller: AccountAddress,
+    currency_amount: CurrencyTokenAmount,
+    fee: Option<MarketFee>,
+) -> ContractResult<CurrencyTokenAmount> {
+    let fee_amount = transfer_fee(host, currency_token, from, currency_amount, fee)?;
+    host.invoke_transfer_single(&currency_token.contract, Transfer {
+        amount: CurrencyTokenAmount::from(currency_amount.0 - fee_amount.0),
+        token_id: currency_token.id.clone(),
+        from,
+        to: seller.into(),
+        data: AdditionalData::empty(),
+    })
+    .map_err(|_| Error::CurrencyTransfer)?;
+    Ok(fee_amount)
+}
+
+/// Transfers the fee of the market for the currency amount of an exchange to the fee receiver.
+/// Returns the fee amount.
+fn transfer_fee(
+    host: &mut Host<State>,
+    currency_token: &CurrencyTokenAddress,
+    from: Address,
+    currency_amount: CurrencyTokenAmount,
+    fee: Option<MarketFee>,
+) -> ContractResult<CurrencyTokenAmount> {
+    let fee_amount = match fee {
+        Some(fee) => fee.calculate(&currency_amount)?,
+        None => 0.into(),
+    };
+    if let Some(fee) = fee.filter(|_| fee_amount.gt(&0.into())) {
+        host.invoke_transfer_single(&currency_token.contract, Transfer {
+            amount: fee_amount,
+            token_id: currency_token.id.clone(),
+            from,
+            to: fee.receiver.into(),
+            data: AdditionalData::empty(),
+        })
+        .map_err(|_| Error::CurrencyTransfer)?;
+    }
+    Ok(fee_amount)
+}
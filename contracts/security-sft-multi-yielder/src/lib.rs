@@ -1,12 +1,16 @@
-use concordium_cis2::{AdditionalData, TokenAmountU64, TokenIdU64, TokenIdVec, Transfer};
+use concordium_cis2::{
+    AdditionalData, BalanceOfQuery, BalanceOfQueryResponse, OnReceivingCis2DataParams, Receiver,
+    TokenAmountU64, TokenIdU64, TokenIdVec, Transfer,
+};
 use concordium_protocols::concordium_cis2_ext::cis2_client::Cis2Client;
 use concordium_protocols::concordium_cis2_ext::IsTokenAmount;
 use concordium_protocols::concordium_cis2_security::cis2_security_client::Cis2SecurityClient;
 use concordium_protocols::concordium_cis2_security::{
-    AgentWithRoles, Burn, MintParam, TokenAmountSecurity,
+    AgentWithRoles, BalanceOfAtQueryParams, Burn, MintParam, SupplyOfQueryParams,
+    TokenAmountSecurity,
 };
 use concordium_protocols::rate::{ExchangeError, Rate};
-use concordium_std::collections::BTreeMap;
+use concordium_std::collections::{BTreeMap, BTreeSet};
 use concordium_std::ops::AddAssign;
 use concordium_std::*;
 
@@ -22,8 +26,8 @@ pub type SecurityTokenAmount = TokenAmountU64;
 pub struct State<S=StateApi> {
     /// The treasury account that holds yield tokens.
     /// This is the account from which the yield tokens are transferred to the user.
-    pub treasury: Address,
-    pub agents:   StateMap<Address, StateSet<AgentRole, S>, S>,
+    pub treasury:    Address,
+    pub agents:      StateMap<Address, StateSet<AgentRole, S>, S>,
     pub yields: StateMap<
         // Security token contract address
         ContractAddress,
@@ -31,10 +35,19 @@ pub struct State<S=StateApi> {
         StateBTreeMap<
             // When the reward is to be given
             SecurityTokenId,
-            Vec<YieldState>,
+            TokenVersionYields,
         >,
         S,
     >,
+    /// Security token contracts whose yields are claimed against checkpoints.
+    /// Contracts which are not present are yielded by burning and minting token versions.
+    pub claim_modes: StateMap<ContractAddress, ClaimMode, S>,
+    /// The last token version up to which a holder has claimed the yields of a held token version.
+    pub checkpoints: StateMap<CheckpointKey, SecurityTokenId, S>,
+    /// Security token amount of a held token version for which a holder has claimed a share of a pool.
+    pub pool_claims: StateMap<PoolClaimKey, SecurityTokenAmount, S>,
+    /// Yield token contracts from which pools can be deposited.
+    pub pool_tokens: StateSet<ContractAddress, S>,
 }
 
 impl State {
@@ -43,6 +56,103 @@ impl State {
             .get(address)
             .is_some_and(|roles_state| roles_state.contains(&role))
     }
+
+    pub fn claim_mode(&self, token_contract: &ContractAddress) -> ClaimMode {
+        self.claim_modes
+            .get(token_contract)
+            .map(|mode| *mode)
+            .unwrap_or(ClaimMode::Versioned)
+    }
+
+    /// Returns true if the token version preceding `token_id` has been added to the yielder.
+    pub fn has_previous_version(
+        &self,
+        token_contract: &ContractAddress,
+        token_id: SecurityTokenId,
+    ) -> bool {
+        token_id.0.checked_sub(1).is_some_and(|previous| {
+            self.yields
+                .get(token_contract)
+                .is_some_and(|contract_yield| contract_yield.get(&TokenIdU64(previous)).is_some())
+        })
+    }
+
+    /// The token version from which the yields of a held token version accrue.
+    pub fn checkpoint(&self, key: &CheckpointKey) -> SecurityTokenId {
+        self.checkpoints
+            .get(key)
+            .map(|token_ver| *token_ver)
+            .unwrap_or(key.token_id)
+            .max(key.token_id)
+    }
+
+    /// Snapshots of the security token balances against which the yields of a held token version
+    /// are calculated from `token_ver_from` up to `token_ver_to`.
+    /// The snapshots of the token versions are only included when the yields are claimed against checkpoints.
+    pub fn snapshot_ids(
+        &self,
+        token_contract: &ContractAddress,
+        token_id: &SecurityTokenId,
+        token_ver_from: SecurityTokenId,
+        token_ver_to: SecurityTokenId,
+        now: Timestamp,
+    ) -> BTreeSet<u64> {
+        let mut snapshot_ids = BTreeSet::new();
+        let contract_yields = match self.yields.get(token_contract) {
+            Some(contract_yields) => contract_yields,
+            None => return snapshot_ids,
+        };
+        let with_versions = self.claim_mode(token_contract).eq(&ClaimMode::Checkpoint);
+        let mut token_ver = token_ver_from;
+        while let Some(next_token_ver) = contract_yields
+            .higher(&token_ver)
+            .map(|v| *v)
+            .filter(|v| v.le(&token_ver_to))
+        {
+            let version = contract_yields.get(&next_token_ver).unwrap();
+            if with_versions {
+                snapshot_ids.extend(version.snapshot_id);
+            }
+            snapshot_ids.extend(
+                version
+                    .pools
+                    .iter()
+                    .filter(|p| now.lt(&p.expiry) && p.token_ids.contains(token_id))
+                    .map(|p| p.snapshot_id),
+            );
+            token_ver = next_token_ver;
+        }
+        snapshot_ids
+    }
+}
+
+/// How the holders of a security token contract claim their yields.
+#[derive(Serialize, SchemaType, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimMode {
+    /// `yieldFor` burns the held token version and mints the version yielded to.
+    Versioned,
+    /// `claimYields` pays the yields and moves the holder's checkpoint without touching the security token.
+    Checkpoint,
+}
+
+#[derive(Serialize, SchemaType, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointKey {
+    pub token_contract: ContractAddress,
+    /// Token version held by the holder.
+    pub token_id:       SecurityTokenId,
+    pub holder:         AccountAddress,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PoolClaimKey {
+    pub token_contract: ContractAddress,
+    /// Token version of the pool.
+    pub token_id:       SecurityTokenId,
+    pub yield_contract: ContractAddress,
+    pub yield_token_id: TokenIdVec,
+    /// Token version held by the holder.
+    pub from_token:     SecurityTokenId,
+    pub holder:         AccountAddress,
 }
 
 #[derive(Serialize, SchemaType, Debug, Clone, Copy)]
@@ -64,6 +174,56 @@ impl AgentRole {
     }
 }
 
+/// Yields of a security token version along with the time at which the version was added.
+#[derive(Serialize, Debug, Clone)]
+pub struct TokenVersionYields {
+    /// Block time at which the token version was first added to the yielder.
+    /// Time based yields accrue between the times of consecutive token versions.
+    pub created_at:  Timestamp,
+    /// Snapshot of the security token balances taken when the version was added.
+    /// Yields claimed against checkpoints are paid on the balances at this snapshot.
+    pub snapshot_id: Option<u64>,
+    pub yields:      Vec<YieldState>,
+    /// Pools of yield tokens distributed pro rata to the holders of the previous token versions.
+    pub pools:       Vec<YieldPool>,
+}
+
+/// A fixed pool of yield tokens held by the yielder.
+/// Holders of the entitled token versions claim a share of the pool
+/// proportional to their balance at the snapshot taken when the pool was deposited.
+#[derive(Serialize, SchemaType, Debug, Clone)]
+pub struct YieldPool {
+    pub contract:    ContractAddress,
+    pub token_id:    TokenIdVec,
+    /// Amount of yield tokens deposited in the pool.
+    pub amount:      YieldTokenAmount,
+    /// Token versions whose holders are entitled to the pool.
+    pub token_ids:   Vec<SecurityTokenId>,
+    /// Snapshot of the security token balances taken at the time of the deposit.
+    pub snapshot_id: u64,
+    /// Supply of the entitled token versions at the time of the deposit.
+    pub supply:      SecurityTokenAmount,
+    /// Amount of yield tokens claimed from or returned by the pool.
+    pub distributed: YieldTokenAmount,
+    /// Time after which the pool can no longer be claimed and the remainder can be returned to the treasury.
+    pub expiry:      Timestamp,
+}
+
+impl YieldPool {
+    pub fn remaining(&self) -> YieldTokenAmount { self.amount - self.distributed }
+
+    pub fn is_yield_token(&self, contract: &ContractAddress, token_id: &TokenIdVec) -> bool {
+        self.contract.eq(contract) && self.token_id.eq(token_id)
+    }
+
+    /// Pro rata share of the pool for the security token amount, capped by the remaining amount of the pool.
+    pub fn share(&self, security_amount: &SecurityTokenAmount) -> YieldTokenAmount {
+        let share = (self.amount.0 as u128 * security_amount.0 as u128) / self.supply.0 as u128;
+        let share = u64::try_from(share).unwrap_or(u64::MAX);
+        TokenAmountU64(share.min(self.remaining().0))
+    }
+}
+
 #[derive(Serialize, SchemaType, Debug, Clone)]
 pub struct YieldState {
     pub contract:    ContractAddress,
@@ -72,13 +232,60 @@ pub struct YieldState {
     pub calculation: YieldCalculation,
 }
 
+/// Fixed point scale used to compound interest rates.
+pub const COMPOUND_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// The unit of time in which time based yields accrue.
+#[derive(Serialize, SchemaType, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccrualPeriod {
+    Second,
+    Day,
+}
+
+impl AccrualPeriod {
+    pub fn millis(&self) -> u64 {
+        match self {
+            AccrualPeriod::Second => 1_000,
+            AccrualPeriod::Day => 86_400_000,
+        }
+    }
+}
+
 #[derive(Serialize, SchemaType, Debug, Clone, Copy)]
 pub enum YieldCalculation {
     Quantity(Rate),
+    /// Interest accrued for every token version (tick).
     SimpleInterest(Rate),
+    /// Interest accrued for every token version (tick), compounded every tick.
+    CompoundInterest(Rate),
+    /// Interest accrued for every complete period elapsed between the creation of the token versions.
+    TimeSimpleInterest(Rate, AccrualPeriod),
+    /// Interest accrued for every complete period elapsed between the creation of the token versions,
+    /// compounded every period.
+    TimeCompoundInterest(Rate, AccrualPeriod),
 }
 
 impl YieldCalculation {
+    /// Time based yields accrue from the time the previous token version was added to the yielder.
+    pub fn is_time_based(&self) -> bool {
+        matches!(
+            self,
+            YieldCalculation::TimeSimpleInterest(..) | YieldCalculation::TimeCompoundInterest(..)
+        )
+    }
+
+    /// Number of ticks for which the yield accrues.
+    /// Version based yields accrue for every token version and time based yields for every complete period elapsed.
+    pub fn ticks(&self, versions: u64, elapsed: Duration) -> u64 {
+        match self {
+            YieldCalculation::TimeSimpleInterest(_, period)
+            | YieldCalculation::TimeCompoundInterest(_, period) => {
+                elapsed.millis() / period.millis()
+            }
+            _ => versions,
+        }
+    }
+
     pub fn calculate_amount(
         &self,
         security_amount: &TokenAmountU64,
@@ -86,12 +293,97 @@ impl YieldCalculation {
     ) -> Result<TokenAmountU64, ExchangeError> {
         let (amount, _) = match self {
             YieldCalculation::Quantity(rate) => rate.convert_token_amount_with_rem(security_amount),
-            YieldCalculation::SimpleInterest(rate) => rate
+            YieldCalculation::SimpleInterest(rate)
+            | YieldCalculation::TimeSimpleInterest(rate, _) => rate
                 .convert_token_amount_with_rem(&TokenAmountU64(security_amount.0 * duration_ticks)),
+            YieldCalculation::CompoundInterest(rate)
+            | YieldCalculation::TimeCompoundInterest(rate, _) => {
+                return compound_interest(rate, security_amount, duration_ticks)
+            }
         }?;
 
         Ok(amount)
     }
+
+    /// Calculates the yield for `duration_ticks` of a token version following token versions
+    /// over which the compound yields have already grown by `factor` (`COMPOUND_SCALE` if none).
+    /// Compound yields accrue on the compound yields of the previous token versions,
+    /// `amount * (factor * (1 + rate) ^ ticks - factor)`, and the grown factor is returned along with the yield.
+    pub fn calculate_accrued_amount(
+        &self,
+        security_amount: &TokenAmountU64,
+        duration_ticks: u64,
+        factor: u128,
+    ) -> Result<(TokenAmountU64, u128), ExchangeError> {
+        match self {
+            YieldCalculation::CompoundInterest(rate)
+            | YieldCalculation::TimeCompoundInterest(rate, _) => {
+                let grown = factor
+                    .checked_mul(compound_factor(rate, duration_ticks)?)
+                    .map(|f| f / COMPOUND_SCALE)
+                    .ok_or(ExchangeError::InvalidRate)?;
+                let amount = (security_amount.0 as u128)
+                    .checked_mul(grown - factor)
+                    .map(|a| a / COMPOUND_SCALE)
+                    .ok_or(ExchangeError::InvalidRate)?;
+                let amount = u64::try_from(amount)
+                    .map(TokenAmountU64)
+                    .map_err(|_| ExchangeError::InvalidRate)?;
+                Ok((amount, grown))
+            }
+            _ => Ok((
+                self.calculate_amount(security_amount, duration_ticks)?,
+                factor,
+            )),
+        }
+    }
+}
+
+/// Calculates `amount * ((1 + rate) ^ ticks - 1)`.
+/// The growth factor is computed in fixed point with `COMPOUND_SCALE` using exponentiation by squaring,
+/// rounding down after every multiplication. The `holder_yields` view in the backend reproduces these steps.
+pub fn compound_interest(
+    rate: &Rate,
+    security_amount: &TokenAmountU64,
+    ticks: u64,
+) -> Result<TokenAmountU64, ExchangeError> {
+    let factor = compound_factor(rate, ticks)?;
+    let amount = (security_amount.0 as u128)
+        .checked_mul(factor - COMPOUND_SCALE)
+        .map(|a| a / COMPOUND_SCALE)
+        .ok_or(ExchangeError::InvalidRate)?;
+    u64::try_from(amount)
+        .map(TokenAmountU64)
+        .map_err(|_| ExchangeError::InvalidRate)
+}
+
+/// Calculates the growth factor `(1 + rate) ^ ticks` in fixed point with `COMPOUND_SCALE`.
+pub fn compound_factor(rate: &Rate, ticks: u64) -> Result<u128, ExchangeError> {
+    if rate.denominator == 0 {
+        return Err(ExchangeError::InvalidRate);
+    }
+    let mul = |a: u128, b: u128| {
+        a.checked_mul(b)
+            .map(|p| p / COMPOUND_SCALE)
+            .ok_or(ExchangeError::InvalidRate)
+    };
+    let mut base = COMPOUND_SCALE
+        .checked_mul(rate.numerator as u128)
+        .map(|r| COMPOUND_SCALE + r / rate.denominator as u128)
+        .ok_or(ExchangeError::InvalidRate)?;
+    let mut factor = COMPOUND_SCALE;
+    let mut ticks = ticks;
+    while ticks > 0 {
+        if ticks & 1 == 1 {
+            factor = mul(factor, base)?;
+        }
+        ticks >>= 1;
+        if ticks > 0 {
+            base = mul(base, base)?;
+        }
+    }
+
+    Ok(factor)
 }
 
 #[derive(Serialize, SchemaType, Debug, Reject)]
@@ -106,6 +398,15 @@ pub enum Error {
     YieldDistribution,
     TokenBurn,
     TokenMint,
+    InvalidPool,
+    PoolExists,
+    PoolNotExpired,
+    PoolNotReturned,
+    InvalidClaimMode,
+    BalanceOf,
+    Snapshot,
+    InvalidPoolToken,
+    MissingPreviousVersion,
 }
 impl From<LogError> for Error {
     fn from(_: LogError) -> Self { Error::LogError }
@@ -122,6 +423,58 @@ pub enum Event {
     YieldRemoved(YieldRemovedEvent),
     YieldDistributed(YieldDistributedEvent),
     TreasuryUpdated(Address),
+    TokenVersionAdded(TokenVersionAddedEvent),
+    TokenVersionRemoved(YieldRemovedEvent),
+    PoolDeposited(PoolDepositedEvent),
+    PoolClaimed(PoolClaimedEvent),
+    PoolReturned(PoolReturnedEvent),
+    ClaimModeUpdated(SetClaimModeParams),
+    YieldClaimed(YieldClaimedEvent),
+    PoolTokenUpdated(SetPoolTokenParams),
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct PoolDepositedEvent {
+    pub token_contract: ContractAddress,
+    pub token_id:       SecurityTokenId,
+    pub yield_contract: ContractAddress,
+    pub yield_token_id: TokenIdVec,
+    pub amount:         YieldTokenAmount,
+    pub supply:         SecurityTokenAmount,
+    pub expiry:         Timestamp,
+    pub token_ids:      Vec<SecurityTokenId>,
+    pub snapshot_id:    u64,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct PoolClaimedEvent {
+    pub token_contract:  ContractAddress,
+    /// Token version of the pool.
+    pub token_id:        SecurityTokenId,
+    pub yield_contract:  ContractAddress,
+    pub yield_token_id:  TokenIdVec,
+    /// Token version held by the claimer.
+    pub from_token:      SecurityTokenId,
+    pub security_amount: SecurityTokenAmount,
+    pub amount:          YieldTokenAmount,
+    pub to:              AccountAddress,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct PoolReturnedEvent {
+    pub token_contract: ContractAddress,
+    pub token_id:       SecurityTokenId,
+    pub yield_contract: ContractAddress,
+    pub yield_token_id: TokenIdVec,
+    pub amount:         YieldTokenAmount,
+    pub to:             Address,
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct TokenVersionAddedEvent {
+    pub token_contract: ContractAddress,
+    pub token_id:       SecurityTokenId,
+    pub created_at:     Timestamp,
 }
 
 #[derive(Serialize, SchemaType, Debug)]
@@ -133,6 +486,20 @@ pub struct YieldDistributedEvent {
     pub to:         AccountAddress,
 }
 
+#[derive(Serialize, SchemaType, Debug)]
+pub struct YieldClaimedEvent {
+    pub token_contract: ContractAddress,
+    /// Token version held by the holder.
+    pub token_id:       SecurityTokenId,
+    /// Checkpoint of the holder before the claim.
+    pub from_token:     SecurityTokenId,
+    /// Checkpoint of the holder after the claim.
+    pub to_token:       SecurityTokenId,
+    /// Balance of the held token version at the snapshots of the claimed token versions.
+    pub amount:         SecurityTokenAmount,
+    pub to:             AccountAddress,
+}
+
 #[derive(Serialize, SchemaType, Debug)]
 pub struct YieldAddedEvent {
     /// The security token contract address.
@@ -201,6 +568,10 @@ fn init(
         treasury: params.treasury,
         agents,
         yields: state_builder.new_map(),
+        claim_modes: state_builder.new_map(),
+        checkpoints: state_builder.new_map(),
+        pool_claims: state_builder.new_map(),
+        pool_tokens: state_builder.new_set(),
     })
 }
 
@@ -318,18 +689,63 @@ fn upsert_yield(
         host.state().is_agent(&ctx.sender(), AgentRole::AddYield),
         Error::UnAuthorized
     );
+    let now = ctx.metadata().slot_time();
+    let is_existing = host
+        .state()
+        .yields
+        .get(&token_contract)
+        .is_some_and(|contract_yield| contract_yield.get(&token_id).is_some());
+    // Time based yields accrue from the time the previous token version was added to the yielder.
+    if yields.iter().any(|y| y.calculation.is_time_based()) {
+        ensure!(
+            host.state().has_previous_version(&token_contract, token_id),
+            Error::MissingPreviousVersion
+        );
+    }
+    // Yields claimed against checkpoints are paid on the balances at the time the version is added.
+    let snapshot_id = match host.state().claim_mode(&token_contract) {
+        ClaimMode::Checkpoint if !is_existing => Some(
+            host.invoke_snapshot(&token_contract)
+                .map_err(|_| Error::Snapshot)?,
+        ),
+        _ => None,
+    };
     let (state, state_builder) = host.state_and_builder();
+    // Replacing the yields of an existing token version keeps the time at which the version was added.
     let existing = state
         .yields
         .entry(token_contract)
         .or_insert_with(|| state_builder.new_btree_map())
-        .modify(|contract_yield| contract_yield.insert(token_id, yields.clone()));
+        .modify(|contract_yield| {
+            let existing = contract_yield.get(&token_id).map(|version| {
+                (
+                    version.created_at,
+                    version.snapshot_id,
+                    version.pools.clone(),
+                )
+            });
+            let is_existing = existing.is_some();
+            let (created_at, snapshot_id, pools) = existing.unwrap_or((now, snapshot_id, vec![]));
+            let _ = contract_yield.insert(token_id, TokenVersionYields {
+                created_at,
+                snapshot_id,
+                yields: yields.clone(),
+                pools,
+            });
+            is_existing
+        });
 
-    if existing.is_some() {
+    if existing {
         logger.log(&Event::YieldRemoved(YieldRemovedEvent {
             token_contract,
             token_id,
         }))?;
+    } else {
+        logger.log(&Event::TokenVersionAdded(TokenVersionAddedEvent {
+            token_contract,
+            token_id,
+            created_at: now,
+        }))?;
     }
 
     logger.log(&Event::YieldAdded(UpsertYieldParams {
@@ -368,6 +784,28 @@ fn remove_yield(
         state.is_agent(&ctx.sender(), AgentRole::RemoveYield),
         Error::UnAuthorized
     );
+    let has_pools = state
+        .yields
+        .get(&token_contract)
+        .and_then(|contract_yield| {
+            contract_yield
+                .get(&token_id)
+                .map(|version| version.pools.iter().any(|p| !p.remaining().is_zero()))
+        })
+        .unwrap_or(false);
+    ensure!(!has_pools, Error::PoolNotReturned);
+    // The time based yields of the next token version accrue from the time this version was added.
+    let next_is_time_based = state
+        .yields
+        .get(&token_contract)
+        .zip(token_id.0.checked_add(1))
+        .and_then(|(contract_yield, next)| {
+            contract_yield
+                .get(&TokenIdU64(next))
+                .map(|version| version.yields.iter().any(|y| y.calculation.is_time_based()))
+        })
+        .unwrap_or(false);
+    ensure!(!next_is_time_based, Error::MissingPreviousVersion);
 
     let existing = state
         .yields
@@ -381,6 +819,10 @@ fn remove_yield(
         token_contract,
         token_id,
     }))?;
+    logger.log(&Event::TokenVersionRemoved(YieldRemovedEvent {
+        token_contract,
+        token_id,
+    }))?;
 
     Ok(())
 }
@@ -410,73 +852,98 @@ fn yield_for(
     ctx: &ReceiveContext,
     host: &mut Host<State>,
     logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: YieldParams = ctx.parameter_cursor().get()?;
+    let sender = ctx.sender();
+    if !sender.matches_account(&params.owner) {
+        let is_agent = host.state().is_agent(&sender, AgentRole::Operator);
+        ensure!(is_agent, Error::UnAuthorized);
+    }
+
+    yield_for_owner(
+        host,
+        logger,
+        params,
+        ctx.metadata().slot_time(),
+        ctx.self_address(),
+    )
+}
+
+/// Distributes the yields of an owner by burning the held token versions and minting the versions yielded to.
+/// Fails with `Error::NoYield` without changing the state if the owner has no yields.
+fn yield_for_owner(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    params: YieldParams,
+    now: Timestamp,
+    self_address: ContractAddress,
 ) -> ContractResult<()> {
     let YieldParams {
         owner,
         yields: mut params,
-    }: YieldParams = ctx.parameter_cursor().get()?;
-
-    let sender = ctx.sender();
-    let state = host.state();
-    let treasury = state.treasury;
-
-    if !sender.matches_account(&owner) {
-        let is_agent = state.is_agent(&sender, AgentRole::Operator);
-        ensure!(is_agent, Error::UnAuthorized);
+    } = params;
+    let mut accruals_from = Vec::with_capacity(params.len());
+    for param in params.iter() {
+        let state = host.state();
+        ensure!(
+            state
+                .claim_mode(&param.token_contract)
+                .eq(&ClaimMode::Versioned),
+            Error::InvalidClaimMode
+        );
+        // Yields already claimed against a checkpoint are not paid again.
+        let accrual_from = state.checkpoint(&CheckpointKey {
+            token_contract: param.token_contract,
+            token_id:       param.token_ver_from,
+            holder:         owner,
+        });
+        let snapshot_ids = state.snapshot_ids(
+            &param.token_contract,
+            &param.token_ver_from,
+            accrual_from,
+            param.token_ver_to,
+            now,
+        );
+        let holding = Holding::query(
+            host,
+            &param.token_contract,
+            owner,
+            param.token_ver_from,
+            Some(param.amount),
+            snapshot_ids,
+        )?;
+        accruals_from.push((accrual_from, holding));
     }
 
-    let mut yields = BTreeMap::new();
+    let state = host.state_mut();
+    let treasury = state.treasury;
+    let mut distribution = Distribution::default();
     // Calculate the yields for the user across contracts.
-    for param in params.iter_mut() {
-        let contract_yields = state
+    for (param, (accrual_from, holding)) in params.iter_mut().zip(accruals_from) {
+        let mut contract_yields_ref = state
             .yields
-            .get(&param.token_contract)
+            .get_mut(&param.token_contract)
             .ok_or(Error::InvalidYield)?;
-
-        let mut token_ver_from = param.token_ver_from;
-        while let Some(next_token_ver) = contract_yields
-            .higher(&token_ver_from)
-            .filter(|v| v.le(&param.token_ver_to))
-        {
-            let next_yields = contract_yields.get(&next_token_ver).unwrap();
-            for next_yield in next_yields.iter() {
-                let amount = next_yield
-                    .calculation
-                    .calculate_amount(
-                        &param.amount,
-                        // Every version is assumed to be a tick.
-                        next_token_ver.0 - token_ver_from.0,
-                    )
-                    .map_err(|_| Error::YieldCalculationError)?;
-
-                if amount.is_zero() {
-                    continue;
-                }
-
-                yields
-                    .entry((next_yield.contract, next_yield.token_id.clone()))
-                    .or_insert(TokenAmountU64::zero())
-                    .add_assign(amount);
-            }
-
-            token_ver_from = *next_token_ver;
-        }
-
-        param.token_ver_to = token_ver_from;
+        let accruals = distribution.add(
+            contract_yields_ref.get_mut(),
+            &mut state.pool_claims,
+            param.token_contract,
+            &holding,
+            accrual_from,
+            param.token_ver_to,
+            owner,
+            now,
+        )?;
+        let token_ver_to = accruals
+            .last()
+            .map_or(accrual_from, |accrual| accrual.to_token);
+        param.token_ver_to = token_ver_to
+            .min(param.token_ver_to)
+            .max(param.token_ver_from);
     }
 
-    ensure!(!yields.is_empty(), Error::NoYield);
-    // Transfer the yields to the user.
-    for ((contract, token_id), amount) in yields.into_iter() {
-        host.invoke_transfer_single(&contract, Transfer {
-            token_id,
-            from: treasury,
-            to: owner.into(),
-            amount,
-            data: AdditionalData::empty(),
-        })
-        .map_err(|_| Error::YieldDistribution)?;
-    }
+    ensure!(!distribution.is_empty(), Error::NoYield);
+    distribution.transfer(host, logger, treasury, self_address, owner)?;
 
     // Updating the security token versions
     for YieldParam {
@@ -513,12 +980,808 @@ fn yield_for(
     Ok(())
 }
 
+#[derive(Serialize, SchemaType, Debug, Clone)]
+pub struct SetClaimModeParams {
+    pub token_contract: ContractAddress,
+    pub mode:           ClaimMode,
+}
+
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "setClaimMode",
+    mutable,
+    parameter = "SetClaimModeParams",
+    enable_logger
+)]
+fn set_claim_mode(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: SetClaimModeParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    ensure!(
+        state.is_agent(&ctx.sender(), AgentRole::AddYield),
+        Error::UnAuthorized
+    );
+    match params.mode {
+        ClaimMode::Versioned => state.claim_modes.remove(&params.token_contract),
+        ClaimMode::Checkpoint => {
+            // The token versions added in the versioned mode have no snapshot to claim against.
+            let has_versions = state
+                .yields
+                .get(&params.token_contract)
+                .is_some_and(|contract_yield| !contract_yield.is_empty());
+            ensure!(!has_versions, Error::InvalidClaimMode);
+            let _ = state.claim_modes.insert(params.token_contract, params.mode);
+        }
+    }
+    logger.log(&Event::ClaimModeUpdated(params))?;
+    Ok(())
+}
+
+#[derive(Serialize, SchemaType, Debug, Clone)]
+pub struct SetPoolTokenParams {
+    /// Yield token contract.
+    pub contract: ContractAddress,
+    /// Whether pools can be deposited from the contract.
+    pub allowed:  bool,
+}
+
+/// Allows or disallows the deposit of pools from a yield token contract.
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "setPoolToken",
+    mutable,
+    parameter = "SetPoolTokenParams",
+    enable_logger
+)]
+fn set_pool_token(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: SetPoolTokenParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    ensure!(
+        state.is_agent(&ctx.sender(), AgentRole::AddYield),
+        Error::UnAuthorized
+    );
+    if params.allowed {
+        state.pool_tokens.insert(params.contract);
+    } else {
+        state.pool_tokens.remove(&params.contract);
+    }
+    logger.log(&Event::PoolTokenUpdated(params))?;
+    Ok(())
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct ClaimParams {
+    pub owner:  AccountAddress,
+    pub claims: Vec<ClaimParam>,
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct ClaimParam {
+    pub token_contract: ContractAddress,
+    /// Token version held by the owner.
+    pub token_id:       SecurityTokenId,
+    /// Token version up to which the yields are claimed.
+    pub token_ver_to:   SecurityTokenId,
+}
+
+/// Claims the yields of the held token versions up to `token_ver_to` for the balances of the owner at the snapshots
+/// taken when the token versions were added. Tokens received after a version was added do not earn its yields.
+/// The security tokens are not burned or minted, instead the checkpoint of the owner is moved to the last version claimed.
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "claimYields",
+    mutable,
+    parameter = "ClaimParams",
+    enable_logger
+)]
+fn claim_yields(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let params: ClaimParams = ctx.parameter_cursor().get()?;
+    let sender = ctx.sender();
+    if !sender.matches_account(&params.owner) {
+        let is_agent = host.state().is_agent(&sender, AgentRole::Operator);
+        ensure!(is_agent, Error::UnAuthorized);
+    }
+
+    claim_for_owner(
+        host,
+        logger,
+        params,
+        ctx.metadata().slot_time(),
+        ctx.self_address(),
+    )
+}
+
+/// Claims the yields of an owner and moves the owner's checkpoints.
+/// Fails with `Error::NoYield` without changing the state if the owner has no yields.
+fn claim_for_owner(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    params: ClaimParams,
+    now: Timestamp,
+    self_address: ContractAddress,
+) -> ContractResult<()> {
+    let ClaimParams { owner, claims } = params;
+    let mut holdings = Vec::with_capacity(claims.len());
+    for claim in claims.iter() {
+        let state = host.state();
+        ensure!(
+            state
+                .claim_mode(&claim.token_contract)
+                .eq(&ClaimMode::Checkpoint),
+            Error::InvalidClaimMode
+        );
+        let from_token = state.checkpoint(&CheckpointKey {
+            token_contract: claim.token_contract,
+            token_id:       claim.token_id,
+            holder:         owner,
+        });
+        let snapshot_ids = state.snapshot_ids(
+            &claim.token_contract,
+            &claim.token_id,
+            from_token,
+            claim.token_ver_to,
+            now,
+        );
+        let holding = Holding::query(
+            host,
+            &claim.token_contract,
+            owner,
+            claim.token_id,
+            None,
+            snapshot_ids,
+        )?;
+        holdings.push((from_token, holding));
+    }
+
+    let state = host.state_mut();
+    let treasury = state.treasury;
+    let mut distribution = Distribution::default();
+    let mut claimed = Vec::with_capacity(claims.len());
+    for (claim, (from_token, holding)) in claims.into_iter().zip(holdings) {
+        let mut contract_yields_ref = state
+            .yields
+            .get_mut(&claim.token_contract)
+            .ok_or(Error::InvalidYield)?;
+        let accruals = distribution.add(
+            contract_yields_ref.get_mut(),
+            &mut state.pool_claims,
+            claim.token_contract,
+            &holding,
+            from_token,
+            claim.token_ver_to,
+            owner,
+            now,
+        )?;
+        // Token versions at which the owner held no tokens do not move the checkpoint.
+        claimed.extend(
+            accruals
+                .into_iter()
+                .filter(|accrual| !accrual.amount.is_zero())
+                .map(|accrual| YieldClaimedEvent {
+                    token_contract: claim.token_contract,
+                    token_id:       claim.token_id,
+                    from_token:     accrual.from_token,
+                    to_token:       accrual.to_token,
+                    amount:         accrual.amount,
+                    to:             owner,
+                }),
+        );
+    }
+
+    ensure!(!distribution.is_empty(), Error::NoYield);
+    for claimed in claimed.iter() {
+        let _ = state.checkpoints.insert(
+            CheckpointKey {
+                token_contract: claimed.token_contract,
+                token_id:       claimed.token_id,
+                holder:         owner,
+            },
+            claimed.to_token,
+        );
+    }
+    distribution.transfer(host, logger, treasury, self_address, owner)?;
+    for claimed in claimed {
+        logger.log(&Event::YieldClaimed(claimed))?;
+    }
+
+    Ok(())
+}
+
+/// Result of yielding for an owner in a batch.
+#[derive(Serialize, SchemaType, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BatchYieldResult {
+    Yielded,
+    /// The owner had no yields and was skipped.
+    NoYield,
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct YieldForBatchParams {
+    pub owners: Vec<YieldParams>,
+}
+
+/// Yields for many owners in a single transaction.
+/// Owners without yields are skipped, any other error aborts the batch.
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "yieldForBatch",
+    mutable,
+    parameter = "YieldForBatchParams",
+    return_value = "Vec<BatchYieldResult>",
+    enable_logger
+)]
+fn yield_for_batch(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<Vec<BatchYieldResult>> {
+    let YieldForBatchParams { owners } = ctx.parameter_cursor().get()?;
+    ensure!(
+        host.state().is_agent(&ctx.sender(), AgentRole::Operator),
+        Error::UnAuthorized
+    );
+
+    let now = ctx.metadata().slot_time();
+    let self_address = ctx.self_address();
+    let mut results = Vec::with_capacity(owners.len());
+    for params in owners {
+        let result = yield_for_owner(host, logger, params, now, self_address);
+        results.push(batch_result(result)?);
+    }
+    Ok(results)
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct ClaimYieldsBatchParams {
+    pub owners: Vec<ClaimParams>,
+}
+
+/// Claims the yields of many owners in a single transaction.
+/// Owners without yields are skipped, any other error aborts the batch.
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "claimYieldsBatch",
+    mutable,
+    parameter = "ClaimYieldsBatchParams",
+    return_value = "Vec<BatchYieldResult>",
+    enable_logger
+)]
+fn claim_yields_batch(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<Vec<BatchYieldResult>> {
+    let ClaimYieldsBatchParams { owners } = ctx.parameter_cursor().get()?;
+    ensure!(
+        host.state().is_agent(&ctx.sender(), AgentRole::Operator),
+        Error::UnAuthorized
+    );
+
+    let now = ctx.metadata().slot_time();
+    let self_address = ctx.self_address();
+    let mut results = Vec::with_capacity(owners.len());
+    for params in owners {
+        let result = claim_for_owner(host, logger, params, now, self_address);
+        results.push(batch_result(result)?);
+    }
+    Ok(results)
+}
+
+fn batch_result(result: ContractResult<()>) -> ContractResult<BatchYieldResult> {
+    match result {
+        Ok(()) => Ok(BatchYieldResult::Yielded),
+        Err(Error::NoYield) => Ok(BatchYieldResult::NoYield),
+        Err(err) => Err(err),
+    }
+}
+
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "checkpoint",
+    parameter = "CheckpointKey",
+    return_value = "SecurityTokenId"
+)]
+fn checkpoint(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<SecurityTokenId> {
+    let key: CheckpointKey = ctx.parameter_cursor().get()?;
+    Ok(host.state().checkpoint(&key))
+}
+
+/// Balances of a held token version against which the yields of a holder are calculated.
+struct Holding {
+    /// Token version held by the holder.
+    token_id: SecurityTokenId,
+    /// Amount of the held token version being yielded.
+    /// `None` if the yields are claimed against the balances at the snapshots of the token versions.
+    amount:   Option<SecurityTokenAmount>,
+    /// Balances of the held token version at the snapshots of the security token contract.
+    balances: BTreeMap<u64, SecurityTokenAmount>,
+}
+
+impl Holding {
+    /// Queries the balances of the held token version at the snapshots from the security token contract.
+    fn query(
+        host: &Host<State>,
+        token_contract: &ContractAddress,
+        holder: AccountAddress,
+        token_id: SecurityTokenId,
+        amount: Option<SecurityTokenAmount>,
+        snapshot_ids: BTreeSet<u64>,
+    ) -> ContractResult<Self> {
+        let mut balances = BTreeMap::new();
+        for snapshot_id in snapshot_ids {
+            let res: BalanceOfQueryResponse<SecurityTokenAmount> = host
+                .invoke_balance_of_at(token_contract, &BalanceOfAtQueryParams {
+                    snapshot_id,
+                    queries: vec![BalanceOfQuery {
+                        token_id,
+                        address: holder.into(),
+                    }],
+                })
+                .map_err(|_| Error::BalanceOf)?;
+            let balance = res.0.first().copied().ok_or(Error::BalanceOf)?;
+            balances.insert(snapshot_id, balance);
+        }
+
+        Ok(Holding {
+            token_id,
+            amount,
+            balances,
+        })
+    }
+
+    fn balance_at(&self, snapshot_id: u64) -> SecurityTokenAmount {
+        self.balances
+            .get(&snapshot_id)
+            .copied()
+            .unwrap_or(TokenAmountU64::zero())
+    }
+}
+
+/// Consecutive token versions yielded for the same security token amount.
+struct Accrual {
+    from_token: SecurityTokenId,
+    to_token:   SecurityTokenId,
+    amount:     SecurityTokenAmount,
+}
+
+/// Yields accumulated for a holder across the token versions of security token contracts.
+#[derive(Default)]
+struct Distribution {
+    yields:      BTreeMap<(ContractAddress, TokenIdVec), YieldTokenAmount>,
+    pool_yields: BTreeMap<(ContractAddress, TokenIdVec), YieldTokenAmount>,
+    pool_claims: Vec<PoolClaimedEvent>,
+}
+
+impl Distribution {
+    fn is_empty(&self) -> bool { self.yields.is_empty() && self.pool_yields.is_empty() }
+
+    /// Adds the yields of the token versions after `token_ver_from` up to `token_ver_to` for the holding.
+    /// Returns the token versions yielded to along with the security token amount they were yielded for.
+    #[allow(clippy::too_many_arguments)]
+    fn add(
+        &mut self,
+        contract_yields: &mut StateBTreeMap<SecurityTokenId, TokenVersionYields>,
+        pool_claims: &mut StateMap<PoolClaimKey, SecurityTokenAmount, StateApi>,
+        token_contract: ContractAddress,
+        holding: &Holding,
+        token_ver_from: SecurityTokenId,
+        token_ver_to: SecurityTokenId,
+        owner: AccountAddress,
+        now: Timestamp,
+    ) -> ContractResult<Vec<Accrual>> {
+        let mut accruals: Vec<Accrual> = vec![];
+        let mut factors = BTreeMap::new();
+        let mut token_ver = token_ver_from;
+        // Time based yields do not accrue from a token version which was never added to the yielder.
+        let mut token_ver_time = contract_yields
+            .get(&token_ver)
+            .map(|version| version.created_at);
+        while let Some(next_token_ver) = contract_yields
+            .higher(&token_ver)
+            .map(|v| *v)
+            .filter(|v| v.le(&token_ver_to))
+        {
+            let mut next_version = contract_yields.get_mut(&next_token_ver).unwrap();
+            let amount = match holding.amount {
+                Some(amount) => amount,
+                None => {
+                    holding.balance_at(next_version.snapshot_id.ok_or(Error::InvalidClaimMode)?)
+                }
+            };
+            let elapsed = token_ver_time
+                .and_then(|from| next_version.created_at.duration_since(from))
+                .unwrap_or(Duration::from_millis(0));
+            self.add_yields(
+                &mut factors,
+                &next_version.yields,
+                &amount,
+                next_token_ver.0 - token_ver.0,
+                elapsed,
+            )?;
+
+            // Expired pools are no longer claimable.
+            for pool in next_version
+                .pools
+                .iter_mut()
+                .filter(|p| now.lt(&p.expiry) && p.token_ids.contains(&holding.token_id))
+            {
+                let key = PoolClaimKey {
+                    token_contract,
+                    token_id: next_token_ver,
+                    yield_contract: pool.contract,
+                    yield_token_id: pool.token_id.clone(),
+                    from_token: holding.token_id,
+                    holder: owner,
+                };
+                // A share is claimed for at most the balance of the holder at the snapshot of the pool.
+                let claimed = pool_claims
+                    .get(&key)
+                    .map(|claimed| *claimed)
+                    .unwrap_or(TokenAmountU64::zero());
+                let available = TokenAmountU64(
+                    holding
+                        .balance_at(pool.snapshot_id)
+                        .0
+                        .saturating_sub(claimed.0),
+                );
+                let security_amount = holding
+                    .amount
+                    .map_or(available, |amount| amount.min(available));
+                let share = pool.share(&security_amount);
+                if share.is_zero() {
+                    continue;
+                }
+
+                let _ = pool_claims.insert(key, claimed + security_amount);
+                pool.distributed.add_assign(share);
+                self.pool_yields
+                    .entry((pool.contract, pool.token_id.clone()))
+                    .or_insert(TokenAmountU64::zero())
+                    .add_assign(share);
+                self.pool_claims.push(PoolClaimedEvent {
+                    token_contract,
+                    token_id: next_token_ver,
+                    yield_contract: pool.contract,
+                    yield_token_id: pool.token_id.clone(),
+                    from_token: holding.token_id,
+                    security_amount,
+                    amount: share,
+                    to: owner,
+                });
+            }
+
+            match accruals.last_mut() {
+                Some(accrual) if accrual.amount.eq(&amount) => accrual.to_token = next_token_ver,
+                _ => accruals.push(Accrual {
+                    from_token: token_ver,
+                    to_token: next_token_ver,
+                    amount,
+                }),
+            }
+            token_ver = next_token_ver;
+            token_ver_time = Some(next_version.created_at);
+        }
+
+        Ok(accruals)
+    }
+
+    /// Adds the yields of a token version accrued for the security token `amount` over `versions` token versions
+    /// and the `elapsed` time since the previous token version.
+    /// `factors` are the growth factors of the compound yields over the previous token versions of the holding.
+    fn add_yields(
+        &mut self,
+        factors: &mut BTreeMap<(ContractAddress, TokenIdVec), u128>,
+        yields: &[YieldState],
+        amount: &SecurityTokenAmount,
+        versions: u64,
+        elapsed: Duration,
+    ) -> ContractResult<()> {
+        for next_yield in yields.iter() {
+            let ticks = next_yield.calculation.ticks(versions, elapsed);
+            let factor = factors
+                .entry((next_yield.contract, next_yield.token_id.clone()))
+                .or_insert(COMPOUND_SCALE);
+            let (amount, grown) = next_yield
+                .calculation
+                .calculate_accrued_amount(amount, ticks, *factor)
+                .map_err(|_| Error::YieldCalculationError)?;
+            *factor = grown;
+
+            if amount.is_zero() {
+                continue;
+            }
+
+            self.yields
+                .entry((next_yield.contract, next_yield.token_id.clone()))
+                .or_insert(TokenAmountU64::zero())
+                .add_assign(amount);
+        }
+
+        Ok(())
+    }
+
+    /// Transfers the yields from the treasury and the pool shares held by the yielder to the owner.
+    fn transfer(
+        self,
+        host: &mut Host<State>,
+        logger: &mut Logger,
+        treasury: Address,
+        self_address: ContractAddress,
+        owner: AccountAddress,
+    ) -> ContractResult<()> {
+        for ((contract, token_id), amount) in self.yields.into_iter() {
+            host.invoke_transfer_single(&contract, Transfer {
+                token_id,
+                from: treasury,
+                to: owner.into(),
+                amount,
+                data: AdditionalData::empty(),
+            })
+            .map_err(|_| Error::YieldDistribution)?;
+        }
+        for ((contract, token_id), amount) in self.pool_yields.into_iter() {
+            host.invoke_transfer_single(&contract, Transfer {
+                token_id,
+                from: self_address.into(),
+                to: owner.into(),
+                amount,
+                data: AdditionalData::empty(),
+            })
+            .map_err(|_| Error::YieldDistribution)?;
+        }
+        for pool_claim in self.pool_claims {
+            logger.log(&Event::PoolClaimed(pool_claim))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct DepositPoolParams {
+    pub token_contract: ContractAddress,
+    /// Token version for which the pool is deposited.
+    /// Holders of the entitled token versions claim the pool when yielding up to this version.
+    pub token_id:       SecurityTokenId,
+    /// Previous token versions whose holders are entitled to the pool.
+    pub token_ids:      Vec<SecurityTokenId>,
+    pub expiry:         Timestamp,
+}
+
+pub type DepositPoolReceiveParams =
+    OnReceivingCis2DataParams<TokenIdVec, YieldTokenAmount, DepositPoolParams>;
+
+/// Receives a pool of yield tokens deposited by an agent with the `AddYield` role.
+/// The yield tokens are transferred to this entrypoint with `DepositPoolParams` as additional data.
+/// Only yield token contracts allowed with `setPoolToken` can deposit pools, as the sender of the transfer is trusted
+/// to have received the yield tokens from the agent.
+/// Only a single pool per yield token can be deposited for a token version of an existing security token.
+/// The supply of the entitled token versions is read from the security token contract and a snapshot
+/// of the balances is taken, against which the holders claim their shares.
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "depositPool",
+    mutable,
+    parameter = "DepositPoolReceiveParams",
+    enable_logger
+)]
+fn deposit_pool(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let DepositPoolReceiveParams {
+        token_id: yield_token_id,
+        amount,
+        from,
+        data: params,
+    } = ctx.parameter_cursor().get()?;
+    let yield_contract = match ctx.sender() {
+        Address::Account(_) => bail!(Error::UnAuthorized),
+        Address::Contract(c) => c,
+    };
+    ensure!(
+        host.state().pool_tokens.contains(&yield_contract),
+        Error::InvalidPoolToken
+    );
+    ensure!(
+        host.state().is_agent(&from, AgentRole::AddYield),
+        Error::UnAuthorized
+    );
+    let now = ctx.metadata().slot_time();
+    ensure!(
+        !amount.is_zero()
+            && params.expiry.gt(&now)
+            && !params.token_ids.is_empty()
+            && params.token_ids.iter().all(|t| t.lt(&params.token_id)),
+        Error::InvalidPool
+    );
+    // The security token contract rejects the query if any of the token versions does not exist.
+    host.invoke_supply_of::<SecurityTokenId, SecurityTokenAmount>(
+        &params.token_contract,
+        &SupplyOfQueryParams {
+            tokens: vec![params.token_id],
+        },
+    )
+    .map_err(|_| Error::InvalidPool)?;
+    let supply: Vec<SecurityTokenAmount> = host
+        .invoke_supply_of(&params.token_contract, &SupplyOfQueryParams {
+            tokens: params.token_ids.clone(),
+        })
+        .map_err(|_| Error::InvalidPool)?;
+    let supply = supply
+        .into_iter()
+        .fold(TokenAmountU64::zero(), |sum, s| sum + s);
+    ensure!(!supply.is_zero(), Error::InvalidPool);
+    let snapshot_id = host
+        .invoke_snapshot(&params.token_contract)
+        .map_err(|_| Error::Snapshot)?;
+
+    let pool = YieldPool {
+        contract: yield_contract,
+        token_id: yield_token_id.clone(),
+        amount,
+        token_ids: params.token_ids.clone(),
+        snapshot_id,
+        supply,
+        distributed: TokenAmountU64::zero(),
+        expiry: params.expiry,
+    };
+    let (state, state_builder) = host.state_and_builder();
+    let version_added = state
+        .yields
+        .entry(params.token_contract)
+        .or_insert_with(|| state_builder.new_btree_map())
+        .modify(|contract_yield| {
+            let existing = contract_yield.get(&params.token_id).map(|version| {
+                (
+                    version.created_at,
+                    version.snapshot_id,
+                    version.yields.clone(),
+                    version.pools.clone(),
+                )
+            });
+            let version_added = existing.is_none();
+            let (created_at, version_snapshot_id, yields, mut pools) =
+                existing.unwrap_or((now, Some(snapshot_id), vec![], vec![]));
+            ensure!(
+                !pools
+                    .iter()
+                    .any(|p| p.is_yield_token(&yield_contract, &yield_token_id)),
+                Error::PoolExists
+            );
+            pools.push(pool);
+            let _ = contract_yield.insert(params.token_id, TokenVersionYields {
+                created_at,
+                snapshot_id: version_snapshot_id,
+                yields,
+                pools,
+            });
+            Ok(version_added)
+        })?;
+
+    if version_added {
+        logger.log(&Event::TokenVersionAdded(TokenVersionAddedEvent {
+            token_contract: params.token_contract,
+            token_id:       params.token_id,
+            created_at:     now,
+        }))?;
+    }
+    logger.log(&Event::PoolDeposited(PoolDepositedEvent {
+        token_contract: params.token_contract,
+        token_id: params.token_id,
+        yield_contract,
+        yield_token_id,
+        amount,
+        supply,
+        expiry: params.expiry,
+        token_ids: params.token_ids,
+        snapshot_id,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serialize, SchemaType, Debug)]
+pub struct ReturnPoolParams {
+    pub token_contract: ContractAddress,
+    pub token_id:       SecurityTokenId,
+    pub yield_contract: ContractAddress,
+    pub yield_token_id: TokenIdVec,
+}
+
+/// Returns the unclaimed remainder of an expired pool to the treasury.
+#[receive(
+    contract = "security_sft_multi_yielder",
+    name = "returnPool",
+    mutable,
+    parameter = "ReturnPoolParams",
+    enable_logger
+)]
+fn return_pool(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let ReturnPoolParams {
+        token_contract,
+        token_id,
+        yield_contract,
+        yield_token_id,
+    }: ReturnPoolParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let (treasury, amount) = {
+        let state = host.state_mut();
+        ensure!(
+            state.is_agent(&ctx.sender(), AgentRole::RemoveYield),
+            Error::UnAuthorized
+        );
+        let treasury = state.treasury;
+        let mut contract_yields = state
+            .yields
+            .get_mut(&token_contract)
+            .ok_or(Error::InvalidPool)?;
+        let mut version = contract_yields
+            .get_mut()
+            .get_mut(&token_id)
+            .ok_or(Error::InvalidPool)?;
+        let pool = version
+            .pools
+            .iter_mut()
+            .find(|p| p.is_yield_token(&yield_contract, &yield_token_id))
+            .ok_or(Error::InvalidPool)?;
+        ensure!(now.ge(&pool.expiry), Error::PoolNotExpired);
+        let amount = pool.remaining();
+        ensure!(!amount.is_zero(), Error::NoYield);
+        pool.distributed = pool.amount;
+        (treasury, amount)
+    };
+
+    let to = match treasury {
+        Address::Account(account) => Receiver::Account(account),
+        Address::Contract(contract) => Receiver::Contract(
+            contract,
+            OwnedEntrypointName::new_unchecked("onReceivingCIS2".to_string()),
+        ),
+    };
+    host.invoke_transfer_single(&yield_contract, Transfer {
+        token_id: yield_token_id.clone(),
+        from: ctx.self_address().into(),
+        to,
+        amount,
+        data: AdditionalData::empty(),
+    })
+    .map_err(|_| Error::YieldDistribution)?;
+    logger.log(&Event::PoolReturned(PoolReturnedEvent {
+        token_contract,
+        token_id,
+        yield_contract,
+        yield_token_id,
+        amount,
+        to: treasury,
+    }))?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use concordium_cis2::TokenAmountU64;
+    use concordium_cis2::{TokenAmountU64, TokenIdU64, TokenIdVec};
     use concordium_protocols::rate::Rate;
+    use concordium_std::collections::BTreeMap;
+    use concordium_std::{ContractAddress, Duration, Timestamp};
 
-    use crate::YieldCalculation;
+    use crate::{AccrualPeriod, Distribution, YieldCalculation, YieldPool, YieldState};
 
     #[test]
     pub fn test_yield_calculations() {
@@ -562,4 +1825,142 @@ mod tests {
         let quantity_amount = quantity.calculate_amount(&1000.into(), 0).unwrap();
         assert_eq!(quantity_amount, TokenAmountU64(5000));
     }
+
+    #[test]
+    pub fn test_compound_interest_calculations() {
+        // 10% per tick
+        let compound_interest = YieldCalculation::CompoundInterest(Rate {
+            numerator:   1,
+            denominator: 10,
+        });
+
+        let amount = TokenAmountU64(1000);
+        assert_eq!(
+            compound_interest.calculate_amount(&amount, 0).unwrap(),
+            TokenAmountU64(0)
+        );
+        assert_eq!(
+            compound_interest.calculate_amount(&amount, 1).unwrap(),
+            TokenAmountU64(100)
+        );
+        // 1000 * (1.1 ^ 2 - 1) = 210
+        assert_eq!(
+            compound_interest.calculate_amount(&amount, 2).unwrap(),
+            TokenAmountU64(210)
+        );
+        // 1000 * (1.1 ^ 10 - 1) = 1593.74...
+        assert_eq!(
+            compound_interest.calculate_amount(&amount, 10).unwrap(),
+            TokenAmountU64(1593)
+        );
+
+        // Growth which does not fit in the fixed point representation
+        let compound_interest = YieldCalculation::CompoundInterest(Rate {
+            numerator:   100,
+            denominator: 1,
+        });
+        assert!(compound_interest.calculate_amount(&amount, 100).is_err());
+    }
+
+    #[test]
+    pub fn test_compound_interest_across_versions() {
+        // 10% per token version
+        let rate = Rate {
+            numerator:   1,
+            denominator: 10,
+        };
+        let contract = ContractAddress::new(1, 0);
+        let simple = TokenIdVec(vec![0]);
+        let compound = TokenIdVec(vec![1]);
+        let yields = vec![
+            YieldState {
+                contract,
+                token_id: simple.clone(),
+                calculation: YieldCalculation::SimpleInterest(rate),
+            },
+            YieldState {
+                contract,
+                token_id: compound.clone(),
+                calculation: YieldCalculation::CompoundInterest(rate),
+            },
+        ];
+
+        // 3 consecutive token versions
+        let mut distribution = Distribution::default();
+        let mut factors = BTreeMap::new();
+        for _ in 0..3 {
+            distribution
+                .add_yields(
+                    &mut factors,
+                    &yields,
+                    &TokenAmountU64(1000),
+                    1,
+                    Duration::from_millis(0),
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            distribution.yields.get(&(contract, simple)),
+            Some(&TokenAmountU64(300))
+        );
+        // 1000 * (1.1 ^ 3 - 1) = 331, the yields of the previous versions compound
+        assert_eq!(
+            distribution.yields.get(&(contract, compound)),
+            Some(&TokenAmountU64(331))
+        );
+        assert_eq!(
+            YieldCalculation::CompoundInterest(rate)
+                .calculate_amount(&TokenAmountU64(1000), 3)
+                .unwrap(),
+            TokenAmountU64(331)
+        );
+    }
+
+    #[test]
+    pub fn test_time_based_ticks() {
+        let rate = Rate {
+            numerator:   1,
+            denominator: 100,
+        };
+        let per_day = YieldCalculation::TimeSimpleInterest(rate, AccrualPeriod::Day);
+        let per_second = YieldCalculation::TimeCompoundInterest(rate, AccrualPeriod::Second);
+        let per_version = YieldCalculation::SimpleInterest(rate);
+
+        // 2 days and 1 second
+        let elapsed = Duration::from_millis(2 * 86_400_000 + 1_500);
+        assert_eq!(per_day.ticks(5, elapsed), 2);
+        assert_eq!(per_second.ticks(5, elapsed), 2 * 86_400 + 1);
+        assert_eq!(per_version.ticks(5, elapsed), 5);
+
+        assert_eq!(
+            per_day
+                .calculate_amount(&TokenAmountU64(1000), per_day.ticks(5, elapsed))
+                .unwrap(),
+            TokenAmountU64(20)
+        );
+    }
+
+    #[test]
+    pub fn test_pool_shares() {
+        let mut pool = YieldPool {
+            contract:    ContractAddress::new(1, 0),
+            token_id:    TokenIdVec(vec![]),
+            amount:      TokenAmountU64(1000),
+            token_ids:   vec![TokenIdU64(0)],
+            snapshot_id: 1,
+            supply:      TokenAmountU64(3000),
+            distributed: TokenAmountU64(0),
+            expiry:      Timestamp::from_timestamp_millis(0),
+        };
+
+        assert_eq!(pool.share(&TokenAmountU64(1000)), TokenAmountU64(333));
+        assert_eq!(pool.share(&TokenAmountU64(2000)), TokenAmountU64(666));
+        assert_eq!(pool.share(&TokenAmountU64(3000)), TokenAmountU64(1000));
+
+        // Shares are capped by the remaining amount of the pool
+        pool.distributed = TokenAmountU64(900);
+        assert_eq!(pool.remaining(), TokenAmountU64(100));
+        assert_eq!(pool.share(&TokenAmountU64(2000)), TokenAmountU64(100));
+    }
 }
@@ -1,22 +1,43 @@
 use concordium_cis2::*;
 use concordium_protocols::concordium_cis2_ext::IsTokenAmount;
+use concordium_protocols::concordium_cis2_security::compliance_client::ComplianceClient;
 use concordium_protocols::concordium_cis2_security::contract_logic::{
-    authorize_and_burn, authorize_and_mint, authorize_and_transfer, Cis2SecurityState,
-    SecurityTokenState,
+    authorize_and_burn, authorize_and_mint, authorize_and_transfer, BalanceCheckpoints,
+    Cis2SecurityState, SecurityTokenState,
 };
 use concordium_protocols::concordium_cis2_security::identity_registry_client::IdentityRegistryClient;
 use concordium_protocols::concordium_cis2_security::{
-    AddTokenParams, AgentUpdatedEvent, ComplianceAdded, FreezeParam, IdentityRegistryAdded, Paused,
-    RecoverEvent, TokenFrozen,
+    AddTokenParams, AgentUpdatedEvent, BurnedParam, ComplianceAdded, DocumentUpdatedEvent,
+    FreezeParam, Governance, GovernanceUpdatedEvent, IdentityRegistryAdded, MintedParam, Paused,
+    Proposal, ProposalCreatedEvent, ProposalEvent, ProposeParams, RecoverEvent,
+    RecoveryDelayUpdatedEvent, RecoveryInitiatedEvent, SnapshotEvent, TokenFrozen, TokenUId,
+    DEFAULT_RECOVERY_DELAY,
+};
+use concordium_protocols::concordium_cis3::{
+    NonceEvent, NonceOfQueryParams, NonceOfQueryResponse, PermitParam, SupportsPermitQueryParams,
+    CIS3_STANDARD_IDENTIFIER,
 };
 use concordium_std::*;
 
 use super::error::Error;
 use super::state::State;
 use super::types::{BalanceOfQueryParams, BalanceOfQueryResponse, *};
-use crate::state::{HolderState, HolderStateActive};
-const SUPPORTS_STANDARDS: [StandardIdentifier<'static>; 2] =
-    [CIS0_STANDARD_IDENTIFIER, CIS2_STANDARD_IDENTIFIER];
+use crate::state::{CorporateActionState, HolderState, HolderStateActive, ProposalState};
+const SUPPORTS_STANDARDS: [StandardIdentifier<'static>; 3] = [
+    CIS0_STANDARD_IDENTIFIER,
+    CIS2_STANDARD_IDENTIFIER,
+    CIS3_STANDARD_IDENTIFIER,
+];
+const TRANSFER_ENTRYPOINT: EntrypointName<'static> = EntrypointName::new_unchecked("transfer");
+const UPDATE_OPERATOR_ENTRYPOINT: EntrypointName<'static> =
+    EntrypointName::new_unchecked("updateOperator");
+const BURN_ENTRYPOINT: EntrypointName<'static> = EntrypointName::new_unchecked("burn");
+/// The entrypoints which can be invoked with a CIS-3 permit.
+const SUPPORTS_PERMIT_ENTRYPOINTS: [EntrypointName<'static>; 3] = [
+    TRANSFER_ENTRYPOINT,
+    UPDATE_OPERATOR_ENTRYPOINT,
+    BURN_ENTRYPOINT,
+];
 
 /// Initializes the contract with the given parameters.
 ///
@@ -73,6 +94,17 @@ pub fn init(
         security: params.security,
         addresses,
         tokens: state_builder.new_map(),
+        nonces: state_builder.new_map(),
+        corporate_actions: state_builder.new_map(),
+        next_corporate_action_id: 0,
+        snapshot_id: 0,
+        balance_checkpoints: BalanceCheckpoints::new(state_builder),
+        governance: None,
+        proposals: state_builder.new_map(),
+        next_proposal_id: 0,
+        recovery_delay: DEFAULT_RECOVERY_DELAY,
+        pending_recoveries: state_builder.new_map(),
+        documents: state_builder.new_map(),
     };
 
     if let Some(security_params) = state.security {
@@ -167,7 +199,8 @@ pub fn compliance(
 ///
 /// # Errors
 ///
-/// Returns an `Error::Unauthorized` error if the caller is not authorized to set the compliance contract.
+/// Returns an `Error::Unauthorized` error if the caller is not authorized to set the compliance contract, or once the
+/// governance is set, is not the contract executing a proposal.
 ///
 /// # Returns
 ///
@@ -186,10 +219,7 @@ pub fn set_compliance(
     logger: &mut Logger,
 ) -> ContractResult<()> {
     let state = host.state_mut();
-    let is_authorized = state
-        .addresses
-        .get(&ctx.sender())
-        .is_some_and(|a| a.is_agent(&[AgentRole::SetCompliance]));
+    let is_authorized = has_governed_role(ctx, state, AgentRole::SetCompliance);
     ensure!(is_authorized, Error::Unauthorized);
 
     let compliance: ContractAddress = ctx.parameter_cursor().get()?;
@@ -237,7 +267,8 @@ pub fn is_agent(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<bool
 ///
 /// # Errors
 ///
-/// Returns `Error::Unauthorized` if the sender does not match the owner.
+/// Returns `Error::Unauthorized` if the sender does not match the owner, or
+/// once the governance is set, is not the contract executing a proposal.
 #[receive(
     contract = "security_sft_multi",
     name = "addAgent",
@@ -252,10 +283,7 @@ pub fn add_agent(
     logger: &mut Logger,
 ) -> ContractResult<()> {
     let params: Agent = ctx.parameter_cursor().get()?;
-    ensure!(
-        ctx.sender().matches_account(&ctx.owner()),
-        Error::Unauthorized
-    );
+    ensure!(is_governor(ctx, host.state()), Error::Unauthorized);
     let (state, state_builder) = host.state_and_builder();
     state
         .addresses
@@ -278,7 +306,8 @@ pub fn add_agent(
 ///
 /// # Errors
 ///
-/// Returns `Error::Unauthorized` if the sender does not match the owner.
+/// Returns `Error::Unauthorized` if the sender does not match the owner, or
+/// once the governance is set, is not the contract executing a proposal.
 #[receive(
     contract = "security_sft_multi",
     name = "removeAgent",
@@ -292,10 +321,7 @@ pub fn remove_agent(
     host: &mut Host<State>,
     logger: &mut Logger,
 ) -> ContractResult<()> {
-    ensure!(
-        ctx.sender().matches_account(&ctx.owner()),
-        Error::Unauthorized
-    );
+    ensure!(is_governor(ctx, host.state()), Error::Unauthorized);
     let address: Address = ctx.parameter_cursor().get()?;
     host.state_mut()
         .addresses
@@ -636,6 +662,20 @@ pub fn is_paused(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<IsP
     Ok(res)
 }
 
+/// Initiates the recovery of a lost account. The recovery can be executed once
+/// the recovery delay has passed, until then the holder of the lost account can
+/// cancel it.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not a `HolderRecovery` agent,
+/// or once the governance is set, is not the contract executing a proposal.
+/// Returns `Error::UnVerifiedIdentity` if the new account is not verified.
+/// Returns `Error::RecoveredAddress` if the lost account has already been
+/// recovered. Returns `Error::InvalidAddress` if the lost account is not a
+/// holder or the new account is already in use.
+/// Returns `Error::InvalidRecovery` if a recovery of the lost account is
+/// already pending.
 #[receive(
     contract = "security_sft_multi",
     name = "recover",
@@ -654,11 +694,17 @@ pub fn recover(
         new_account,
     }: RecoverParam = ctx.parameter_cursor().get()?;
     let state = host.state();
-    let is_authorized = state
-        .addresses
-        .get(&ctx.sender())
-        .is_some_and(|a| a.is_agent(&[AgentRole::HolderRecovery]));
+    let is_authorized = has_governed_role(ctx, state, AgentRole::HolderRecovery);
     ensure!(is_authorized, Error::Unauthorized);
+    match state.addresses.get(&lost_account).as_deref() {
+        Some(HolderState::Active(_)) => {}
+        Some(HolderState::Recovered(_)) => bail!(Error::RecoveredAddress),
+        None => bail!(Error::InvalidAddress),
+    }
+    ensure!(
+        state.addresses.get(&new_account).is_none(),
+        Error::InvalidAddress
+    );
 
     if let Some(security) = state.security {
         ensure!(
@@ -667,15 +713,140 @@ pub fn recover(
         );
     }
 
-    host.state_mut().recover(lost_account, new_account)?;
-    logger.log(&Event::Recovered(RecoverEvent {
+    let state = host.state_mut();
+    let execute_after = ctx
+        .metadata()
+        .block_time()
+        .checked_add(state.recovery_delay)
+        .ok_or(Error::InvalidRecovery)?;
+    let previous = state
+        .pending_recoveries
+        .insert(lost_account, PendingRecovery {
+            new_account,
+            execute_after,
+        });
+    ensure!(previous.is_none(), Error::InvalidRecovery);
+    logger.log(&Event::RecoveryInitiated(RecoveryInitiatedEvent {
         lost_account,
         new_account,
+        execute_after,
     }))?;
 
     Ok(())
 }
 
+/// Updates the recovery of lost accounts.
+/// - `Execute` executes a pending recovery once the recovery delay has passed. The state of the lost account is
+///   moved to the new account.
+/// - `Cancel` cancels a pending recovery.
+/// - `SetDelay` sets the challenge window after a recovery is initiated before it can be executed. Applies to
+///   recoveries initiated afterwards.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not a `HolderRecovery` agent (once the governance is set, the
+/// contract executing a proposal), the lost account in case of a cancellation, or in case of setting the delay, does
+/// not match the owner or once the governance is set, is not the contract executing a proposal.
+/// Returns `Error::InvalidRecovery` if the recovery of the lost account is not pending.
+/// Returns `Error::RecoveryNotExecutable` if the recovery delay has not passed.
+/// Returns `Error::UnVerifiedIdentity` if the new account is no longer verified.
+#[receive(
+    contract = "security_sft_multi",
+    name = "updateRecovery",
+    mutable,
+    enable_logger,
+    parameter = "RecoveryUpdate",
+    error = "Error"
+)]
+pub fn update_recovery(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let update: RecoveryUpdate = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let is_recovery_agent = has_governed_role(ctx, state, AgentRole::HolderRecovery);
+    match update {
+        RecoveryUpdate::Execute(lost_account) => {
+            ensure!(is_recovery_agent, Error::Unauthorized);
+            let PendingRecovery {
+                new_account,
+                execute_after,
+            } = *state
+                .pending_recoveries
+                .get(&lost_account)
+                .ok_or(Error::InvalidRecovery)?;
+            ensure!(
+                ctx.metadata().block_time() >= execute_after,
+                Error::RecoveryNotExecutable
+            );
+
+            if let Some(security) = state.security {
+                ensure!(
+                    host.invoke_identity_registry_is_verified(
+                        &security.identity_registry,
+                        &new_account
+                    )?,
+                    Error::UnVerifiedIdentity
+                );
+            }
+
+            let state = host.state_mut();
+            state.pending_recoveries.remove(&lost_account);
+            state.recover(lost_account, new_account)?;
+            logger.log(&Event::Recovered(RecoverEvent {
+                lost_account,
+                new_account,
+            }))?;
+        }
+        RecoveryUpdate::Cancel(lost_account) => {
+            ensure!(
+                is_recovery_agent || ctx.sender().eq(&lost_account),
+                Error::Unauthorized
+            );
+            let PendingRecovery { new_account, .. } = host
+                .state_mut()
+                .pending_recoveries
+                .remove_and_get(&lost_account)
+                .ok_or(Error::InvalidRecovery)?;
+            logger.log(&Event::RecoveryCancelled(RecoverEvent {
+                lost_account,
+                new_account,
+            }))?;
+        }
+        RecoveryUpdate::SetDelay(recovery_delay) => {
+            ensure!(is_governor(ctx, state), Error::Unauthorized);
+            host.state_mut().recovery_delay = recovery_delay;
+            logger.log(&Event::RecoveryDelayUpdated(RecoveryDelayUpdatedEvent(
+                recovery_delay,
+            )))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the challenge window after a recovery is initiated before it can be
+/// executed and the pending recovery of a lost account, `None` if there is none.
+#[receive(
+    contract = "security_sft_multi",
+    name = "recovery",
+    parameter = "Address",
+    error = "Error",
+    return_value = "Recovery"
+)]
+pub fn recovery(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<Recovery> {
+    let lost_account: Address = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    Ok(Recovery {
+        delay:   state.recovery_delay,
+        pending: state
+            .pending_recoveries
+            .get(&lost_account)
+            .map(|recovery| *recovery),
+    })
+}
+
 #[receive(
     contract = "security_sft_multi",
     name = "recoveryAddress",
@@ -719,7 +890,16 @@ pub fn update_operator(
     logger: &mut Logger,
 ) -> ContractResult<()> {
     let UpdateOperatorParams { 0: updates }: UpdateOperatorParams = ctx.parameter_cursor().get()?;
-    let sender = ctx.sender();
+    update_operator_as(host, logger, ctx.sender(), updates)
+}
+
+/// Updates the operators of the `sender`.
+fn update_operator_as(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    sender: Address,
+    updates: Vec<UpdateOperator>,
+) -> ContractResult<()> {
     let (state, state_builder) = host.state_and_builder();
 
     for UpdateOperator { operator, update } in updates {
@@ -1047,19 +1227,30 @@ pub fn transfer(
 ) -> ContractResult<()> {
     let concordium_cis2::TransferParams::<TokenId, TokenAmount>(transfers) =
         ctx.parameter_cursor().get()?;
-    let self_address = ctx.self_address();
-    let sender = ctx.sender();
+    transfer_as(host, logger, ctx.self_address(), ctx.sender(), transfers)
+}
 
+/// Transfers the tokens on behalf of the `sender`.
+fn transfer_as(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    self_address: ContractAddress,
+    sender: Address,
+    transfers: Vec<Transfer<TokenId, TokenAmount>>,
+) -> ContractResult<()> {
     let state = host.state();
     let sender_address = state.addresses.get(&sender);
     let sender_is_operator_agent = sender_address
         .as_ref()
         .map(|s| s.is_agent(&[AgentRole::Operator]))
         .unwrap_or(false);
-    let sender_is_forced_transfer_agent = sender_address
-        .as_ref()
-        .map(|s| s.is_agent(&[AgentRole::ForcedTransfer]))
-        .unwrap_or(false);
+    let sender_is_forced_transfer_agent = match state.governance {
+        Some(_) => sender.matches_contract(&self_address),
+        None => sender_address
+            .as_ref()
+            .map(|s| s.is_agent(&[AgentRole::ForcedTransfer]))
+            .unwrap_or(false),
+    };
     let security = state.security;
 
     for Transfer {
@@ -1157,19 +1348,30 @@ pub fn burn(
     logger: &mut Logger,
 ) -> ContractResult<()> {
     let params: BurnParams = ctx.parameter_cursor().get()?;
-    let self_address = ctx.self_address();
-    let sender = ctx.sender();
+    burn_as(host, logger, ctx.self_address(), ctx.sender(), params)
+}
 
+/// Burns the tokens on behalf of the `sender`.
+fn burn_as(
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    self_address: ContractAddress,
+    sender: Address,
+    params: BurnParams,
+) -> ContractResult<()> {
     let state = host.state();
     let sender_address = state.addresses.get(&sender);
     let sender_is_operator_agent = sender_address
         .as_ref()
         .map(|s| s.is_agent(&[AgentRole::Operator]))
         .unwrap_or(false);
-    let sender_is_forced_burn_agent = sender_address
-        .as_ref()
-        .map(|s| s.is_agent(&[AgentRole::ForcedBurn]))
-        .unwrap_or(false);
+    let sender_is_forced_burn_agent = match state.governance {
+        Some(_) => sender.matches_contract(&self_address),
+        None => sender_address
+            .as_ref()
+            .map(|s| s.is_agent(&[AgentRole::ForcedBurn]))
+            .unwrap_or(false),
+    };
     let security = state.security;
 
     for Burn {
@@ -1249,3 +1451,824 @@ pub fn balance_of(
     }
     Ok(concordium_cis2::BalanceOfQueryResponse(res))
 }
+
+/// Takes a snapshot of the balances of all the tokens. The balances at the
+/// snapshot can be queried with `balanceOfAt`.
+///
+/// # Returns
+///
+/// Returns `ContractResult<u64>` containing the id of the snapshot.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not a snapshot agent.
+#[receive(
+    contract = "security_sft_multi",
+    name = "snapshot",
+    enable_logger,
+    mutable,
+    return_value = "u64",
+    error = "Error"
+)]
+pub fn snapshot(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<u64> {
+    let state = host.state_mut();
+    let is_authorized = state
+        .addresses
+        .get(&ctx.sender())
+        .is_some_and(|a| a.is_agent(&[AgentRole::Snapshot]));
+    ensure!(is_authorized, Error::Unauthorized);
+    state.snapshot_id += 1;
+    logger.log(&Event::Snapshot(SnapshotEvent {
+        snapshot_id: state.snapshot_id,
+    }))?;
+
+    Ok(state.snapshot_id)
+}
+
+/// Returns the id of the latest snapshot, `0` if no snapshot has been taken.
+#[receive(
+    contract = "security_sft_multi",
+    name = "snapshotId",
+    return_value = "u64"
+)]
+pub fn snapshot_id(_: &ReceiveContext, host: &Host<State>) -> ContractResult<u64> {
+    Ok(host.state().snapshot_id)
+}
+
+/// Queries the balances of the specified token IDs for the given addresses at
+/// a snapshot.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidSnapshot` if the snapshot has not been taken.
+/// Returns `Error::InvalidTokenId` if any of the tokens does not exist.
+#[receive(
+    contract = "security_sft_multi",
+    name = "balanceOfAt",
+    parameter = "BalanceOfAtQueryParams",
+    return_value = "BalanceOfQueryResponse",
+    error = "super::error::Error"
+)]
+pub fn balance_of_at(
+    ctx: &ReceiveContext,
+    host: &Host<State>,
+) -> ContractResult<BalanceOfQueryResponse> {
+    let BalanceOfAtQueryParams {
+        snapshot_id,
+        queries,
+    } = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    ensure!(
+        snapshot_id > 0 && snapshot_id <= state.snapshot_id,
+        Error::InvalidSnapshot
+    );
+    let mut res: Vec<TokenAmount> = Vec::with_capacity(queries.len());
+    for query in queries {
+        state
+            .tokens
+            .get(&query.token_id)
+            .ok_or(Error::InvalidTokenId)?;
+        res.push(state.balance_of_at(query.address, query.token_id, snapshot_id));
+    }
+    Ok(concordium_cis2::BalanceOfQueryResponse(res))
+}
+
+/// Queries the total supply of the specified token IDs.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTokenId` if any of the tokens does not exist.
+#[receive(
+    contract = "security_sft_multi",
+    name = "supplyOf",
+    parameter = "SupplyOfQueryParams",
+    return_value = "Vec<TokenAmount>",
+    error = "super::error::Error"
+)]
+pub fn supply_of(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<Vec<TokenAmount>> {
+    let SupplyOfQueryParams { tokens } = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let mut res: Vec<TokenAmount> = Vec::with_capacity(tokens.len());
+    for token_id in tokens {
+        let supply = state
+            .tokens
+            .get(&token_id)
+            .ok_or(Error::InvalidTokenId)?
+            .supply;
+        res.push(supply);
+    }
+    Ok(res)
+}
+
+/// Executes a `transfer`, `updateOperator` or `burn` signed by the `signer` account.
+/// This allows any account to sponsor the transaction of a signer holding no CCD.
+///
+/// # Returns
+///
+/// Returns `ContractResult<()>` indicating whether the operation was successful.
+///
+/// # Errors
+///
+/// - `Error::InvalidContractAddress`: If the message is signed for another contract.
+/// - `Error::PermitExpired`: If the message has expired.
+/// - `Error::InvalidSignature`: If the message is not signed by the `signer`.
+/// - `Error::InvalidNonce`: If the nonce of the message is not the signer's next nonce.
+/// - `Error::InvalidEntryPoint`: If the entrypoint does not support permits.
+#[receive(
+    contract = "security_sft_multi",
+    name = "permit",
+    parameter = "PermitParam",
+    error = "Error",
+    crypto_primitives,
+    mutable,
+    enable_logger
+)]
+pub fn permit(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+    crypto_primitives: &CryptoPrimitives,
+) -> ContractResult<()> {
+    let PermitParam {
+        signature,
+        signer,
+        message,
+    }: PermitParam = ctx.parameter_cursor().get()?;
+    let self_address = ctx.self_address();
+    ensure!(
+        message.contract_address.eq(&self_address),
+        Error::InvalidContractAddress
+    );
+    ensure!(
+        message.timestamp.gt(&ctx.metadata().slot_time()),
+        Error::PermitExpired
+    );
+    let hash = message.hash(signer, |data| crypto_primitives.hash_sha2_256(&data).0)?;
+    ensure!(
+        host.check_account_signature(signer, &signature, &hash)
+            .map_err(|_| Error::CheckSignature)?,
+        Error::InvalidSignature
+    );
+
+    {
+        let mut nonce = host.state_mut().nonces.entry(signer).or_insert(0);
+        ensure!(message.nonce.eq(&*nonce), Error::InvalidNonce);
+        *nonce += 1;
+    }
+    logger.log(&Event::Nonce(NonceEvent {
+        account: signer,
+        nonce:   message.nonce,
+    }))?;
+
+    let sender = Address::Account(signer);
+    let entry_point = message.entry_point.as_entrypoint_name();
+    if entry_point.eq(&TRANSFER_ENTRYPOINT) {
+        let TransferParams(transfers) = from_bytes(&message.payload)?;
+        transfer_as(host, logger, self_address, sender, transfers)
+    } else if entry_point.eq(&UPDATE_OPERATOR_ENTRYPOINT) {
+        let UpdateOperatorParams(updates) = from_bytes(&message.payload)?;
+        update_operator_as(host, logger, sender, updates)
+    } else if entry_point.eq(&BURN_ENTRYPOINT) {
+        let params: BurnParams = from_bytes(&message.payload)?;
+        burn_as(host, logger, self_address, sender, params)
+    } else {
+        bail!(Error::InvalidEntryPoint)
+    }
+}
+
+/// Returns the next permit nonce of the given accounts.
+///
+/// # Returns
+///
+/// Returns `ContractResult<NonceOfQueryResponse>` containing the nonce of each queried account.
+#[receive(
+    contract = "security_sft_multi",
+    name = "nonceOf",
+    parameter = "NonceOfQueryParams",
+    return_value = "NonceOfQueryResponse",
+    error = "Error"
+)]
+pub fn nonce_of(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<NonceOfQueryResponse> {
+    let NonceOfQueryParams { queries }: NonceOfQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let mut res = Vec::with_capacity(queries.len());
+    for account in queries {
+        res.push(state.nonces.get(&account).map_or(0, |nonce| *nonce));
+    }
+
+    Ok(NonceOfQueryResponse(res))
+}
+
+/// Determines whether the given entrypoints can be invoked with a permit.
+///
+/// # Returns
+///
+/// Returns `ContractResult<SupportsQueryResponse>` containing the support
+/// status for each queried entrypoint.
+#[receive(
+    contract = "security_sft_multi",
+    name = "supportsPermit",
+    parameter = "SupportsPermitQueryParams",
+    return_value = "SupportsQueryResponse",
+    error = "Error"
+)]
+pub fn supports_permit(
+    ctx: &ReceiveContext,
+    _: &Host<State>,
+) -> ContractResult<SupportsQueryResponse> {
+    let SupportsPermitQueryParams { queries }: SupportsPermitQueryParams =
+        ctx.parameter_cursor().get()?;
+    let mut response = Vec::with_capacity(queries.len());
+    for entry_point in queries {
+        if SUPPORTS_PERMIT_ENTRYPOINTS.contains(&entry_point.as_entrypoint_name()) {
+            response.push(SupportResult::Support);
+        } else {
+            response.push(SupportResult::NoSupport)
+        }
+    }
+
+    Ok(SupportsQueryResponse::from(response))
+}
+
+/// Creates a corporate action converting the balances of all the holders of
+/// `from_token_id` into `to_token_id` at a fixed rate. The `from_token_id` is
+/// paused until the corporate action is completed so that the balances cannot
+/// change while the holders are being converted.
+///
+/// # Returns
+///
+/// Returns `ContractResult<u64>` containing the id of the corporate action.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not a corporate action agent.
+/// Returns `Error::InvalidRate` if the rate is zero.
+/// Returns `Error::InvalidTokenId` if any of the tokens does not exist.
+/// Returns `Error::InvalidCorporateAction` if `from_token_id` already has a
+/// corporate action.
+#[receive(
+    contract = "security_sft_multi",
+    name = "createCorporateAction",
+    enable_logger,
+    mutable,
+    parameter = "CorporateAction",
+    return_value = "u64",
+    error = "Error"
+)]
+pub fn create_corporate_action(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<u64> {
+    let action: CorporateAction = ctx.parameter_cursor().get()?;
+    let (state, state_builder) = host.state_and_builder();
+    let is_authorized = state
+        .addresses
+        .get(&ctx.sender())
+        .is_some_and(|a| a.is_agent(&[AgentRole::CorporateAction]));
+    ensure!(is_authorized, Error::Unauthorized);
+    ensure!(action.rate.is_valid(), Error::InvalidRate);
+    ensure!(
+        state.tokens.get(&action.to_token_id).is_some(),
+        Error::InvalidTokenId
+    );
+    ensure!(
+        state
+            .corporate_actions
+            .iter()
+            .all(|(_, a)| a.action.from_token_id.ne(&action.from_token_id)),
+        Error::InvalidCorporateAction
+    );
+    // The supply of a paused token can only change by applying the corporate action.
+    let remaining = {
+        let mut from_token = state
+            .tokens
+            .get_mut(&action.from_token_id)
+            .ok_or(Error::InvalidTokenId)?;
+        from_token.pause();
+        from_token.supply
+    };
+    logger.log(&Event::Paused(Paused {
+        token_id: action.from_token_id,
+    }))?;
+
+    let action_id = state.next_corporate_action_id;
+    state.next_corporate_action_id += 1;
+    let _ = state
+        .corporate_actions
+        .insert(action_id, CorporateActionState {
+            action,
+            converted: state_builder.new_set(),
+            remaining,
+        });
+
+    Ok(action_id)
+}
+
+/// Applies the corporate action to a page of holders. The balance of
+/// `from_token_id` of every holder is burned and the converted balance of
+/// `to_token_id` is minted, frozen amounts stay frozen.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not a corporate action agent.
+/// Returns `Error::InvalidCorporateAction` if the corporate action does not
+/// exist.
+/// Returns `Error::InvalidRate` if a converted amount overflows.
+#[receive(
+    contract = "security_sft_multi",
+    name = "applyCorporateAction",
+    enable_logger,
+    mutable,
+    parameter = "ApplyCorporateActionParams",
+    error = "Error"
+)]
+pub fn apply_corporate_action(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let self_address = ctx.self_address();
+    let params: ApplyCorporateActionParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let is_authorized = state
+        .addresses
+        .get(&ctx.sender())
+        .is_some_and(|a| a.is_agent(&[AgentRole::CorporateAction]));
+    ensure!(is_authorized, Error::Unauthorized);
+    let action = state
+        .corporate_actions
+        .get(&params.action_id)
+        .map(|a| a.action)
+        .ok_or(Error::InvalidCorporateAction)?;
+    let security = state.security;
+
+    for owner in params.holders {
+        let (from, to) = match host
+            .state_mut()
+            .apply_corporate_action(params.action_id, owner)?
+        {
+            Some(converted) => converted,
+            None => continue,
+        };
+
+        if from.gt(&TokenAmount::zero()) {
+            if let Some(security) = security {
+                host.invoke_compiliance_burned(&security.compliance, &BurnedParam {
+                    token_id: TokenUId::new(action.from_token_id, self_address),
+                    amount: from.total(),
+                    owner,
+                })?;
+            }
+            if from.frozen.gt(&TokenAmount::zero()) {
+                logger.log(&Event::TokenUnFrozen(TokenFrozen {
+                    token_id: action.from_token_id,
+                    amount:   from.frozen,
+                    address:  owner,
+                }))?;
+            }
+            logger.log(&Event::Cis2(Cis2Event::Burn(BurnEvent {
+                amount: from.total(),
+                token_id: action.from_token_id,
+                owner,
+            })))?;
+        }
+
+        if to.gt(&TokenAmount::zero()) {
+            if let Some(security) = security {
+                host.invoke_compiliance_minted(&security.compliance, &MintedParam {
+                    token_id: TokenUId::new(action.to_token_id, self_address),
+                    amount: to.total(),
+                    owner,
+                })?;
+            }
+            logger.log(&Event::Cis2(Cis2Event::Mint(MintEvent {
+                token_id: action.to_token_id,
+                amount: to.total(),
+                owner,
+            })))?;
+            if to.frozen.gt(&TokenAmount::zero()) {
+                logger.log(&Event::TokenFrozen(TokenFrozen {
+                    token_id: action.to_token_id,
+                    amount:   to.frozen,
+                    address:  owner,
+                }))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Completes the corporate action and un pauses `from_token_id`.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not a corporate action agent.
+/// Returns `Error::InvalidCorporateAction` if the corporate action does not
+/// exist.
+/// Returns `Error::CorporateActionNotApplied` if the balances of some holders
+/// of `from_token_id` have not been converted yet.
+#[receive(
+    contract = "security_sft_multi",
+    name = "completeCorporateAction",
+    enable_logger,
+    mutable,
+    parameter = "u64",
+    error = "Error"
+)]
+pub fn complete_corporate_action(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let action_id: u64 = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    let is_authorized = state
+        .addresses
+        .get(&ctx.sender())
+        .is_some_and(|a| a.is_agent(&[AgentRole::CorporateAction]));
+    ensure!(is_authorized, Error::Unauthorized);
+
+    let action_state = state
+        .corporate_actions
+        .remove_and_get(&action_id)
+        .ok_or(Error::InvalidCorporateAction)?;
+    ensure!(
+        action_state.remaining.eq(&TokenAmount::zero()),
+        Error::CorporateActionNotApplied
+    );
+    let token_id = action_state.action.from_token_id;
+    action_state.delete();
+    // The token may have been removed after all its holders were converted.
+    if let Some(mut token) = state.tokens.get_mut(&token_id) {
+        token.un_pause();
+        logger.log(&Event::UnPaused(Paused { token_id }))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the corporate action.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidCorporateAction` if the corporate action does not
+/// exist.
+#[receive(
+    contract = "security_sft_multi",
+    name = "corporateAction",
+    parameter = "u64",
+    return_value = "CorporateAction",
+    error = "Error"
+)]
+pub fn corporate_action(
+    ctx: &ReceiveContext,
+    host: &Host<State>,
+) -> ContractResult<CorporateAction> {
+    let action_id: u64 = ctx.parameter_cursor().get()?;
+    host.state()
+        .corporate_actions
+        .get(&action_id)
+        .map(|a| a.action)
+        .ok_or(Error::InvalidCorporateAction)
+}
+
+/// Returns true if the sender can add / remove agents & set the governance.
+/// This is the owner until the governance is set, and from then on only the
+/// contract itself executing a proposal.
+fn is_governor(ctx: &ReceiveContext, state: &State) -> bool {
+    match state.governance {
+        Some(_) => ctx.sender().matches_contract(&ctx.self_address()),
+        None => ctx.sender().matches_account(&ctx.owner()),
+    }
+}
+
+/// Returns true if the sender can act with the sensitive `role` (`SetCompliance`,
+/// `HolderRecovery`, `ForcedTransfer` & `ForcedBurn`). This is an agent with the
+/// role until the governance is set, and from then on only the contract itself
+/// executing a proposal.
+fn has_governed_role(ctx: &ReceiveContext, state: &State, role: AgentRole) -> bool {
+    match state.governance {
+        Some(_) => ctx.sender().matches_contract(&ctx.self_address()),
+        None => state
+            .addresses
+            .get(&ctx.sender())
+            .is_some_and(|a| a.is_agent(&[role])),
+    }
+}
+
+/// Sets or removes the governance of the contract.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender does not match the owner, or
+/// once the governance is set, is not the contract executing a proposal.
+/// Returns `Error::InvalidGovernance` if the threshold can not be met by the
+/// approvers or there are duplicate approvers.
+#[receive(
+    contract = "security_sft_multi",
+    name = "setGovernance",
+    enable_logger,
+    mutable,
+    parameter = "Option<Governance>",
+    error = "Error"
+)]
+pub fn set_governance(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let governance: Option<Governance> = ctx.parameter_cursor().get()?;
+    ensure!(is_governor(ctx, host.state()), Error::Unauthorized);
+    ensure!(
+        governance.as_ref().is_none_or(|g| g.is_valid()),
+        Error::InvalidGovernance
+    );
+    host.state_mut().governance.clone_from(&governance);
+    logger.log(&Event::GovernanceUpdated(GovernanceUpdatedEvent {
+        governance,
+    }))?;
+
+    Ok(())
+}
+
+/// Returns the governance of the contract, `None` if it is not set.
+#[receive(
+    contract = "security_sft_multi",
+    name = "governance",
+    return_value = "Option<Governance>"
+)]
+pub fn governance(_: &ReceiveContext, host: &Host<State>) -> ContractResult<Option<Governance>> {
+    Ok(host.state().governance.clone())
+}
+
+/// Proposes to invoke an entrypoint of the contract. The proposal is approved
+/// by the proposer.
+///
+/// # Returns
+///
+/// Returns `ContractResult<u64>` containing the id of the proposal.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidGovernance` if the governance is not set.
+/// Returns `Error::Unauthorized` if the sender is not an approver.
+#[receive(
+    contract = "security_sft_multi",
+    name = "propose",
+    enable_logger,
+    mutable,
+    parameter = "ProposeParams",
+    return_value = "u64",
+    error = "Error"
+)]
+pub fn propose(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<u64> {
+    let ProposeParams {
+        entrypoint,
+        parameter,
+    }: ProposeParams = ctx.parameter_cursor().get()?;
+    let proposer = ctx.sender();
+    let (state, state_builder) = host.state_and_builder();
+    let governance = state.governance.as_ref().ok_or(Error::InvalidGovernance)?;
+    ensure!(governance.is_approver(&proposer), Error::Unauthorized);
+    let execute_after = ctx
+        .metadata()
+        .block_time()
+        .checked_add(governance.delay)
+        .ok_or(Error::InvalidGovernance)?;
+
+    let proposal_id = state.next_proposal_id;
+    state.next_proposal_id += 1;
+    let mut approvals = state_builder.new_set();
+    approvals.insert(proposer);
+    let _ = state.proposals.insert(proposal_id, ProposalState {
+        proposer,
+        entrypoint: entrypoint.clone(),
+        parameter,
+        execute_after,
+        approvals,
+    });
+    logger.log(&Event::ProposalCreated(ProposalCreatedEvent {
+        proposal_id,
+        proposer,
+        entrypoint,
+        execute_after,
+    }))?;
+    logger.log(&Event::ProposalApproved(ProposalEvent {
+        proposal_id,
+        approver: proposer,
+    }))?;
+
+    Ok(proposal_id)
+}
+
+/// Approves, executes or cancels a proposal.
+/// Executing the proposal invokes the proposed entrypoint of the contract with the contract itself as the sender of
+/// the invocation.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidGovernance` if the governance is not set.
+/// Returns `Error::Unauthorized` if the sender is not an approver.
+/// Returns `Error::InvalidProposal` if the proposal does not exist.
+/// Returns `Error::ProposalAlreadyApproved` if the sender has already approved
+/// the proposal.
+/// Returns `Error::ProposalNotExecutable` if the proposal is not approved by
+/// the threshold of the current approvers or is still time locked.
+/// Returns `Error::CallContractError` if the invocation fails.
+#[receive(
+    contract = "security_sft_multi",
+    name = "updateProposal",
+    enable_logger,
+    mutable,
+    parameter = "ProposalUpdate",
+    error = "Error"
+)]
+pub fn update_proposal(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let update: ProposalUpdate = ctx.parameter_cursor().get()?;
+    let approver = ctx.sender();
+    let state = host.state_mut();
+    let governance = state.governance.as_ref().ok_or(Error::InvalidGovernance)?;
+    ensure!(governance.is_approver(&approver), Error::Unauthorized);
+    match update {
+        ProposalUpdate::Approve(proposal_id) => {
+            let newly_approved = state
+                .proposals
+                .get_mut(&proposal_id)
+                .ok_or(Error::InvalidProposal)?
+                .approvals
+                .insert(approver);
+            ensure!(newly_approved, Error::ProposalAlreadyApproved);
+            logger.log(&Event::ProposalApproved(ProposalEvent {
+                proposal_id,
+                approver,
+            }))?;
+        }
+        ProposalUpdate::Execute(proposal_id) => {
+            let proposal = state
+                .proposals
+                .remove_and_get(&proposal_id)
+                .ok_or(Error::InvalidProposal)?;
+            ensure!(
+                proposal.approvals_count(governance) >= governance.threshold as usize,
+                Error::ProposalNotExecutable
+            );
+            ensure!(
+                ctx.metadata().block_time() >= proposal.execute_after,
+                Error::ProposalNotExecutable
+            );
+            let entrypoint = proposal.entrypoint.clone();
+            let parameter = proposal.parameter.clone();
+            proposal.delete();
+
+            host.invoke_contract_raw(
+                &ctx.self_address(),
+                Parameter::new_unchecked(&parameter),
+                entrypoint.as_entrypoint_name(),
+                Amount::zero(),
+            )?;
+            logger.log(&Event::ProposalExecuted(ProposalEvent {
+                proposal_id,
+                approver,
+            }))?;
+        }
+        ProposalUpdate::Cancel(proposal_id) => {
+            state
+                .proposals
+                .remove_and_get(&proposal_id)
+                .ok_or(Error::InvalidProposal)?
+                .delete();
+            logger.log(&Event::ProposalCancelled(ProposalEvent {
+                proposal_id,
+                approver,
+            }))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the proposal.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidProposal` if the proposal does not exist.
+#[receive(
+    contract = "security_sft_multi",
+    name = "proposal",
+    parameter = "u64",
+    return_value = "Proposal",
+    error = "Error"
+)]
+pub fn proposal(ctx: &ReceiveContext, host: &Host<State>) -> ContractResult<Proposal> {
+    let proposal_id: u64 = ctx.parameter_cursor().get()?;
+    host.state()
+        .proposals
+        .get(&proposal_id)
+        .map(|p| p.to_proposal())
+        .ok_or(Error::InvalidProposal)
+}
+
+/// Sets or removes a document of a token. Setting a document replaces the
+/// document with the same name.
+///
+/// # Errors
+///
+/// Returns `Error::Unauthorized` if the sender is not a `SetDocument` agent.
+/// Returns `Error::InvalidTokenId` if the token does not exist.
+/// Returns `Error::InvalidDocument` if the name of the document is empty or
+/// the removed document does not exist.
+#[receive(
+    contract = "security_sft_multi",
+    name = "updateDocument",
+    enable_logger,
+    mutable,
+    parameter = "DocumentUpdate",
+    error = "Error"
+)]
+pub fn update_document(
+    ctx: &ReceiveContext,
+    host: &mut Host<State>,
+    logger: &mut Logger,
+) -> ContractResult<()> {
+    let update: DocumentUpdate = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    let is_authorized = state
+        .addresses
+        .get(&ctx.sender())
+        .is_some_and(|a| a.is_agent(&[AgentRole::SetDocument]));
+    ensure!(is_authorized, Error::Unauthorized);
+    let (token_id, name, document) = match update {
+        DocumentUpdate::Set(SetDocumentParams {
+            token_id,
+            name,
+            uri,
+            content_hash,
+        }) => {
+            ensure!(state.tokens.get(&token_id).is_some(), Error::InvalidTokenId);
+            ensure!(!name.is_empty(), Error::InvalidDocument);
+            let document = Document {
+                uri,
+                content_hash,
+                timestamp: ctx.metadata().block_time(),
+            };
+            let _ = state
+                .documents
+                .insert((token_id, name.clone()), document.clone());
+            (token_id, name, Some(document))
+        }
+        DocumentUpdate::Remove(DocumentQuery { token_id, name }) => {
+            state
+                .documents
+                .remove_and_get(&(token_id, name.clone()))
+                .ok_or(Error::InvalidDocument)?;
+            (token_id, name, None)
+        }
+    };
+    logger.log(&Event::DocumentUpdated(DocumentUpdatedEvent {
+        token_id,
+        name,
+        document,
+    }))?;
+
+    Ok(())
+}
+
+/// Returns the names & the documents of a token.
+#[receive(
+    contract = "security_sft_multi",
+    name = "documents",
+    parameter = "TokenId",
+    return_value = "Vec<(String, Document)>",
+    error = "Error"
+)]
+pub fn documents(
+    ctx: &ReceiveContext,
+    host: &Host<State>,
+) -> ContractResult<Vec<(String, Document)>> {
+    let token_id: TokenId = ctx.parameter_cursor().get()?;
+    Ok(host
+        .state()
+        .documents
+        .iter()
+        .filter_map(|(key, document)| {
+            let (document_token_id, name) = &*key;
+            token_id
+                .eq(document_token_id)
+                .then(|| (name.clone(), document.clone()))
+        })
+        .collect())
+}
@@ -31,6 +31,40 @@ pub enum Error {
     InvalidAddress,
     RecoveredAddress,
     SecurityNotSet,
+    /// Triggered when the signature of a permit could not be checked.
+    CheckSignature,
+    /// Triggered when the signature of a permit is invalid.
+    InvalidSignature,
+    /// Triggered when the nonce of a permit is not the signer's next nonce.
+    InvalidNonce,
+    /// Triggered when a permit is signed for another contract.
+    InvalidContractAddress,
+    /// Triggered when a permit has expired.
+    PermitExpired,
+    /// Triggered when a permit is signed for an entrypoint which does not support permits.
+    InvalidEntryPoint,
+    /// Triggered when the rate of a corporate action is invalid or a converted amount overflows.
+    InvalidRate,
+    /// Triggered when the corporate action does not exist or the token already has one.
+    InvalidCorporateAction,
+    /// Triggered when the snapshot has not been taken.
+    InvalidSnapshot,
+    /// Triggered when the governance is not set or the threshold can not be met by the approvers.
+    InvalidGovernance,
+    /// Triggered when the proposal does not exist.
+    InvalidProposal,
+    /// Triggered when the approver has already approved the proposal.
+    ProposalAlreadyApproved,
+    /// Triggered when the proposal does not have enough approvals or is still time locked.
+    ProposalNotExecutable,
+    /// Triggered when the recovery is not pending or the lost account already has one.
+    InvalidRecovery,
+    /// Triggered when the challenge window of the recovery has not passed.
+    RecoveryNotExecutable,
+    /// Triggered when the document does not exist or its name is empty.
+    InvalidDocument,
+    /// Triggered when a corporate action is completed before all the holders have been converted.
+    CorporateActionNotApplied,
 }
 
 impl Error {
@@ -51,6 +85,23 @@ impl Error {
             Error::InvalidAddress => -8,
             Error::RecoveredAddress => -15,
             Error::SecurityNotSet => -16,
+            Error::CheckSignature => -17,
+            Error::InvalidSignature => -18,
+            Error::InvalidNonce => -19,
+            Error::InvalidContractAddress => -20,
+            Error::PermitExpired => -21,
+            Error::InvalidEntryPoint => -22,
+            Error::InvalidRate => -23,
+            Error::InvalidCorporateAction => -24,
+            Error::InvalidSnapshot => -25,
+            Error::InvalidGovernance => -26,
+            Error::InvalidProposal => -27,
+            Error::ProposalAlreadyApproved => -28,
+            Error::ProposalNotExecutable => -29,
+            Error::InvalidRecovery => -30,
+            Error::RecoveryNotExecutable => -31,
+            Error::InvalidDocument => -32,
+            Error::CorporateActionNotApplied => -33,
         })
         .unwrap()
     }
@@ -1,11 +1,13 @@
 use concordium_protocols::concordium_cis2_ext::IsTokenAmount;
 use concordium_protocols::concordium_cis2_security::contract_logic::{
-    Cis2SecurityState, SecurityTokenState,
+    BalanceCheckpoints, Cis2SecurityState, SecurityTokenState,
+};
+use concordium_protocols::concordium_cis2_security::{
+    Governance, PendingRecovery, Proposal, SecurityParams, TokenAmountSecurity,
 };
-use concordium_protocols::concordium_cis2_security::{SecurityParams, TokenAmountSecurity};
 use concordium_std::*;
 
-use super::types::{AgentRole, ContractResult, TokenAmount};
+use super::types::{AgentRole, ContractResult, CorporateAction, Document, TokenAmount};
 use crate::error::Error;
 use crate::types::TokenId;
 
@@ -187,15 +189,203 @@ impl HolderState {
             _ => None,
         }
     }
+
+    /// Converts the whole balance of `from_token_id` into `to_token_id` at the rate of the
+    /// corporate action. The frozen & un frozen amounts are converted separately.
+    /// Returns the converted balance & the balance it was converted into.
+    pub fn convert(
+        &mut self,
+        action: &CorporateAction,
+    ) -> Result<
+        (
+            TokenAmountSecurity<TokenAmount>,
+            TokenAmountSecurity<TokenAmount>,
+        ),
+        Error,
+    > {
+        match self {
+            HolderState::Active(holder) => {
+                let from = holder
+                    .balances
+                    .remove_and_get(&action.from_token_id)
+                    .unwrap_or_default();
+                let convert = |amount: TokenAmount| {
+                    action
+                        .rate
+                        .convert_token_amount_with_rem(&amount)
+                        .map(|(converted, _)| converted)
+                        .map_err(|_| Error::InvalidRate)
+                };
+                let to = TokenAmountSecurity {
+                    frozen:    convert(from.frozen)?,
+                    un_frozen: convert(from.un_frozen)?,
+                };
+                if to.gt(&TokenAmount::zero()) {
+                    holder
+                        .balances
+                        .entry(action.to_token_id)
+                        .or_insert_with(Default::default)
+                        .add_assign(to);
+                }
+                Ok((from, to))
+            }
+            _ => Ok((Default::default(), Default::default())),
+        }
+    }
 }
 
 #[derive(Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 /// Represents the state of the security NFT contract.
 pub struct State<S=StateApi> {
-    pub tokens:    StateMap<TokenId, SecurityTokenState<TokenAmount>, S>,
-    pub security:  Option<SecurityParams>,
-    pub addresses: StateMap<Address, HolderState<S>, S>,
+    pub tokens:                   StateMap<TokenId, SecurityTokenState<TokenAmount>, S>,
+    pub security:                 Option<SecurityParams>,
+    pub addresses:                StateMap<Address, HolderState<S>, S>,
+    /// The next CIS-3 permit nonce of each account.
+    pub nonces:                   StateMap<AccountAddress, u64, S>,
+    pub corporate_actions:        StateMap<u64, CorporateActionState<S>, S>,
+    pub next_corporate_action_id: u64,
+    /// The id of the latest snapshot, `0` if no snapshot has been taken.
+    pub snapshot_id:              u64,
+    /// The balances of the holders at the snapshots.
+    pub balance_checkpoints:      BalanceCheckpoints<(Address, TokenId), TokenAmount, S>,
+    /// The approvers of the proposals, `None` if the contract is not governed by proposals.
+    pub governance:               Option<Governance>,
+    pub proposals:                StateMap<u64, ProposalState<S>, S>,
+    pub next_proposal_id:         u64,
+    /// The challenge window after a recovery is initiated before it can be executed.
+    pub recovery_delay:           Duration,
+    /// The recoveries which have been initiated, keyed by the lost account.
+    pub pending_recoveries:       StateMap<Address, PendingRecovery, S>,
+    /// The documents of the tokens, keyed by the token & the name of the document.
+    pub documents:                StateMap<(TokenId, String), Document, S>,
+}
+
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+pub struct ProposalState<S=StateApi> {
+    pub proposer:      Address,
+    pub entrypoint:    OwnedEntrypointName,
+    pub parameter:     Vec<u8>,
+    pub execute_after: Timestamp,
+    /// The approvers which have approved the proposal.
+    pub approvals:     StateSet<Address, S>,
+}
+
+impl ProposalState {
+    /// Returns the number of approvals by the current approvers.
+    pub fn approvals_count(&self, governance: &Governance) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approver| governance.is_approver(approver))
+            .count()
+    }
+
+    pub fn to_proposal(&self) -> Proposal {
+        Proposal {
+            proposer:      self.proposer,
+            entrypoint:    self.entrypoint.clone(),
+            parameter:     self.parameter.clone(),
+            approvals:     self.approvals.iter().map(|a| *a).collect(),
+            execute_after: self.execute_after,
+        }
+    }
+}
+
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+pub struct CorporateActionState<S=StateApi> {
+    pub action:    CorporateAction,
+    /// The holders whose balances have been converted.
+    pub converted: StateSet<Address, S>,
+    /// The supply of `from_token_id` which has not been converted yet.
+    pub remaining: TokenAmount,
+}
+
+impl State {
+    /// Records the balance of the holder at the latest snapshot. This should be called before
+    /// the balance is updated.
+    pub fn checkpoint(&mut self, address: Address, token_id: TokenId) {
+        let snapshot_id = self.snapshot_id;
+        if snapshot_id == 0 {
+            return;
+        }
+        let balance = self
+            .addresses
+            .get(&address)
+            .map_or_else(TokenAmount::zero, |holder| holder.balance_total(token_id));
+        self.balance_checkpoints
+            .record((address, token_id), snapshot_id, balance);
+    }
+
+    /// Returns the balance of the holder at the snapshot.
+    pub fn balance_of_at(
+        &self,
+        address: Address,
+        token_id: TokenId,
+        snapshot_id: u64,
+    ) -> TokenAmount {
+        self.balance_checkpoints
+            .balance_at(&(address, token_id), snapshot_id)
+            .unwrap_or_else(|| {
+                self.addresses
+                    .get(&address)
+                    .map_or_else(TokenAmount::zero, |holder| holder.balance_total(token_id))
+            })
+    }
+
+    /// Converts the balance of the holder, unless it has already been converted by the corporate
+    /// action, and updates the supply of the tokens accordingly.
+    /// Returns the converted balance & the balance it was converted into.
+    pub fn apply_corporate_action(
+        &mut self,
+        action_id: u64,
+        holder: Address,
+    ) -> ContractResult<
+        Option<(
+            TokenAmountSecurity<TokenAmount>,
+            TokenAmountSecurity<TokenAmount>,
+        )>,
+    > {
+        let mut action_state = self
+            .corporate_actions
+            .get_mut(&action_id)
+            .ok_or(Error::InvalidCorporateAction)?;
+        if !action_state.converted.insert(holder) {
+            return Ok(None);
+        }
+        let action = action_state.action;
+        drop(action_state);
+        self.checkpoint(holder, action.from_token_id);
+        self.checkpoint(holder, action.to_token_id);
+        let (from, to) = match self.addresses.get_mut(&holder) {
+            Some(mut holder) => holder.convert(&action)?,
+            None => return Ok(None),
+        };
+        let mut action_state = self
+            .corporate_actions
+            .get_mut(&action_id)
+            .ok_or(Error::InvalidCorporateAction)?;
+        ensure!(
+            action_state.remaining >= from.total(),
+            Error::InsufficientFunds
+        );
+        action_state.remaining -= from.total();
+        drop(action_state);
+
+        let mut from_token = self
+            .tokens
+            .get_mut(&action.from_token_id)
+            .ok_or(Error::InvalidTokenId)?;
+        ensure!(from_token.supply >= from.total(), Error::InsufficientFunds);
+        from_token.supply -= from.total();
+        drop(from_token);
+        self.tokens
+            .get_mut(&action.to_token_id)
+            .ok_or(Error::InvalidTokenId)?
+            .supply += to.total();
+        Ok(Some((from, to)))
+    }
 }
 
 impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
@@ -211,6 +401,7 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
             .entry(token_id)
             .occupied_or(Error::InvalidTokenId)?
             .add_assign_supply(amount.total())?;
+        self.checkpoint(owner, token_id);
         self.addresses
             .entry(owner)
             .or_insert_with(|| HolderState::new_active(state_builder))
@@ -232,6 +423,8 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
             Error::InvalidTokenId
         );
         ensure!(amount.gt(&TokenAmount::zero()), Error::InvalidAmount);
+        self.checkpoint(from, token_id);
+        self.checkpoint(to, token_id);
         self.addresses
             .entry(to)
             .or_insert_with(|| HolderState::new_active(state_builder))
@@ -256,6 +449,7 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
             .entry(token_id)
             .occupied_or(Error::InvalidTokenId)?
             .modify(|t| t.sub_assign_supply(amount))?;
+        self.checkpoint(owner, token_id);
         let unfrozen_amount = self
             .addresses
             .entry(owner)
@@ -265,6 +459,17 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
     }
 
     fn recover(&mut self, lost_account: Address, new_account: Address) -> ContractResult<()> {
+        let token_ids: Vec<TokenId> = match self.addresses.get(&lost_account) {
+            Some(holder) => match &*holder {
+                HolderState::Active(holder) => holder.balances.iter().map(|(t, _)| *t).collect(),
+                HolderState::Recovered(_) => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        for token_id in token_ids {
+            self.checkpoint(lost_account, token_id);
+            self.checkpoint(new_account, token_id);
+        }
         let lost_holder = self
             .addresses
             .insert(lost_account, HolderState::Recovered(new_account));
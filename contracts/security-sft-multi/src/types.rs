@@ -1,7 +1,8 @@
 use concordium_cis2::TokenIdU64;
 use concordium_protocols::concordium_cis2_ext;
 use concordium_protocols::concordium_cis2_security::{self, Cis2SecurityEvent, SecurityParams};
-use concordium_std::{SchemaType, Serialize};
+use concordium_protocols::rate::Rate;
+use concordium_std::{Address, SchemaType, Serialize};
 
 use super::error::Error;
 pub type ContractResult<R> = Result<R, Error>;
@@ -34,6 +35,12 @@ pub enum AgentRole {
     Operator,
     RemoveToken,
     SetTokenMetadata,
+    /// The role to create, apply & complete corporate actions.
+    CorporateAction,
+    /// The role to take snapshots of the balances.
+    Snapshot,
+    /// The role to set & remove the documents of the tokens.
+    SetDocument,
 }
 
 impl AgentRole {
@@ -56,6 +63,9 @@ impl AgentRole {
             Self::Operator,
             Self::RemoveToken,
             Self::SetTokenMetadata,
+            Self::CorporateAction,
+            Self::Snapshot,
+            Self::SetDocument,
         ]
     }
 }
@@ -70,13 +80,40 @@ pub type PauseParam = concordium_cis2_security::PauseParam<TokenId>;
 pub type IsPausedResponse = concordium_cis2_security::IsPausedResponse;
 pub type BalanceOfQueryParams = concordium_cis2::BalanceOfQueryParams<TokenId>;
 pub type BalanceOfQueryResponse = concordium_cis2::BalanceOfQueryResponse<TokenAmount>;
+pub type BalanceOfAtQueryParams = concordium_cis2_security::BalanceOfAtQueryParams<TokenId>;
+pub type SupplyOfQueryParams = concordium_cis2_security::SupplyOfQueryParams<TokenId>;
+pub type SetDocumentParams = concordium_cis2_security::SetDocumentParams<TokenId>;
+pub type DocumentQuery = concordium_cis2_security::DocumentQuery<TokenId>;
+pub type DocumentUpdate = concordium_cis2_security::DocumentUpdate<TokenId>;
 pub type MintParams = concordium_cis2_security::MintParams<TokenId, TokenAmount>;
 pub type MintParam = concordium_cis2_security::MintParam<TokenAmount>;
 pub use concordium_cis2_ext::ContractMetadataUrl;
-pub use concordium_cis2_security::RecoverParam;
+pub use concordium_cis2_security::{
+    Document, PendingRecovery, ProposalUpdate, RecoverParam, Recovery, RecoveryUpdate,
+};
 
 #[derive(Serialize, SchemaType)]
 pub struct InitParam {
     pub security: Option<SecurityParams>,
     pub agents:   Vec<Agent>,
 }
+
+/// A conversion of the balances of all the holders of a token at a fixed rate.
+/// A split (e.g. 1 token becomes 10) converts a token into itself, a merge or a
+/// conversion converts a token into another token. Converted amounts are rounded
+/// down.
+#[derive(Serialize, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CorporateAction {
+    pub from_token_id: TokenId,
+    pub to_token_id:   TokenId,
+    /// The amount of `to_token_id` for every unit of `from_token_id`.
+    pub rate:          Rate,
+}
+
+#[derive(Serialize, SchemaType)]
+pub struct ApplyCorporateActionParams {
+    pub action_id: u64,
+    /// A page of the holders of `from_token_id` whose balances are converted.
+    /// Holders which have already been converted are skipped.
+    pub holders:   Vec<Address>,
+}
@@ -1,11 +1,14 @@
+use concordium_cis2::TokenIdUnit;
 use concordium_protocols::concordium_cis2_ext::IsTokenAmount;
 use concordium_protocols::concordium_cis2_security::contract_logic::{
-    Cis2SecurityState, SecurityTokenState,
+    BalanceCheckpoints, Cis2SecurityState, SecurityTokenState,
+};
+use concordium_protocols::concordium_cis2_security::{
+    Governance, PendingRecovery, Proposal, SecurityParams, TokenAmountSecurity,
 };
-use concordium_protocols::concordium_cis2_security::{SecurityParams, TokenAmountSecurity};
 use concordium_std::*;
 
-use super::types::{AgentRole, ContractResult, TokenAmount};
+use super::types::{AgentRole, ContractResult, Document, TokenAmount};
 use crate::error::Error;
 use crate::types::TokenId;
 
@@ -163,9 +166,88 @@ impl HolderState {
 #[derive(Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 pub struct State<S=StateApi> {
-    pub token:     SecurityTokenState<TokenAmount>,
-    pub security:  Option<SecurityParams>,
-    pub addresses: StateMap<Address, HolderState<S>, S>,
+    pub token:               SecurityTokenState<TokenAmount>,
+    pub security:            Option<SecurityParams>,
+    pub addresses:           StateMap<Address, HolderState<S>, S>,
+    /// The next CIS-3 permit nonce of each account.
+    pub nonces:              StateMap<AccountAddress, u64, S>,
+    /// The id of the latest snapshot, `0` if no snapshot has been taken.
+    pub snapshot_id:         u64,
+    /// The balances of the holders at the snapshots.
+    pub balance_checkpoints: BalanceCheckpoints<Address, TokenAmount, S>,
+    /// The approvers of the proposals, `None` if the contract is not governed by proposals.
+    pub governance:          Option<Governance>,
+    pub proposals:           StateMap<u64, ProposalState<S>, S>,
+    pub next_proposal_id:    u64,
+    /// The challenge window after a recovery is initiated before it can be executed.
+    pub recovery_delay:      Duration,
+    /// The recoveries which have been initiated, keyed by the lost account.
+    pub pending_recoveries:  StateMap<Address, PendingRecovery, S>,
+    /// The documents of the token, keyed by the name of the document.
+    pub documents:           StateMap<String, Document, S>,
+}
+
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+pub struct ProposalState<S=StateApi> {
+    pub proposer:      Address,
+    pub entrypoint:    OwnedEntrypointName,
+    pub parameter:     Vec<u8>,
+    pub execute_after: Timestamp,
+    /// The approvers which have approved the proposal.
+    pub approvals:     StateSet<Address, S>,
+}
+
+impl ProposalState {
+    /// Returns the number of approvals by the current approvers.
+    pub fn approvals_count(&self, governance: &Governance) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approver| governance.is_approver(approver))
+            .count()
+    }
+
+    pub fn to_proposal(&self) -> Proposal {
+        Proposal {
+            proposer:      self.proposer,
+            entrypoint:    self.entrypoint.clone(),
+            parameter:     self.parameter.clone(),
+            approvals:     self.approvals.iter().map(|a| *a).collect(),
+            execute_after: self.execute_after,
+        }
+    }
+}
+
+impl State {
+    /// Records the balance of the holder at the latest snapshot. This should be called before
+    /// the balance is updated.
+    pub fn checkpoint(&mut self, address: Address) {
+        let snapshot_id = self.snapshot_id;
+        if snapshot_id == 0 {
+            return;
+        }
+        let balance = self
+            .addresses
+            .get(&address)
+            .map_or_else(TokenAmount::zero, |holder| {
+                holder.balance_total(TokenIdUnit())
+            });
+        self.balance_checkpoints
+            .record(address, snapshot_id, balance);
+    }
+
+    /// Returns the balance of the holder at the snapshot.
+    pub fn balance_of_at(&self, address: Address, snapshot_id: u64) -> TokenAmount {
+        self.balance_checkpoints
+            .balance_at(&address, snapshot_id)
+            .unwrap_or_else(|| {
+                self.addresses
+                    .get(&address)
+                    .map_or_else(TokenAmount::zero, |holder| {
+                        holder.balance_total(TokenIdUnit())
+                    })
+            })
+    }
 }
 
 impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
@@ -178,6 +260,7 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
     ) -> ContractResult<()> {
         ensure!(amount.gt(&0.into()), Error::InvalidAmount);
         self.token.add_assign_supply(amount.total())?;
+        self.checkpoint(owner);
         self.addresses
             .entry(owner)
             .or_insert_with(|| HolderState::new_active(state_builder))
@@ -196,6 +279,8 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
     ) -> ContractResult<TokenAmount> {
         ensure!(!self.token.paused, Error::InvalidTokenId);
         ensure!(amount.gt(&TokenAmount::zero()), Error::InvalidAmount);
+        self.checkpoint(from);
+        self.checkpoint(to);
         self.addresses
             .entry(to)
             .or_insert_with(|| HolderState::new_active(state_builder))
@@ -217,6 +302,7 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
     ) -> ContractResult<TokenAmount> {
         ensure!(amount.gt(&TokenAmount::zero()), Error::InvalidAmount);
         self.token.sub_assign_supply(amount)?;
+        self.checkpoint(owner);
         let unfrozen_amount = self
             .addresses
             .entry(owner)
@@ -226,6 +312,8 @@ impl Cis2SecurityState<Error, TokenId, TokenAmount> for State {
     }
 
     fn recover(&mut self, lost_account: Address, new_account: Address) -> ContractResult<()> {
+        self.checkpoint(lost_account);
+        self.checkpoint(new_account);
         let lost_holder = self
             .addresses
             .insert(lost_account, HolderState::Recovered(new_account));
@@ -28,6 +28,10 @@ pub enum AgentRole {
     UnPause,
     Operator,
     SetTokenMetadata,
+    /// The role to take snapshots of the balances.
+    Snapshot,
+    /// The role to set & remove the documents of the token.
+    SetDocument,
 }
 
 impl AgentRole {
@@ -48,6 +52,8 @@ impl AgentRole {
             Self::UnPause,
             Self::Operator,
             Self::SetTokenMetadata,
+            Self::Snapshot,
+            Self::SetDocument,
         ]
     }
 }
@@ -62,6 +68,10 @@ pub type PauseParam = concordium_cis2_security::PauseParam<TokenId>;
 pub type IsPausedResponse = concordium_cis2_security::IsPausedResponse;
 pub type BalanceOfQueryParams = concordium_cis2::BalanceOfQueryParams<TokenId>;
 pub type BalanceOfQueryResponse = concordium_cis2::BalanceOfQueryResponse<TokenAmount>;
+pub type BalanceOfAtQueryParams = concordium_cis2_security::BalanceOfAtQueryParams<TokenId>;
+pub type SetDocumentParams = concordium_cis2_security::SetDocumentParams<TokenId>;
+pub type DocumentQuery = concordium_cis2_security::DocumentQuery<TokenId>;
+pub type DocumentUpdate = concordium_cis2_security::DocumentUpdate<TokenId>;
 pub type MintParams = concordium_cis2_security::MintParams<TokenId, TokenAmount>;
 pub type MintParam = concordium_cis2_security::MintParam<TokenAmount>;
 pub type SetTokenMetadataParams =
@@ -69,7 +79,9 @@ pub type SetTokenMetadataParams =
 pub type SetTokenMetadataParam =
     concordium_cis2_security::SetTokenMetadataParam<TokenId, ContractMetadataUrl>;
 pub use concordium_cis2_ext::ContractMetadataUrl;
-pub use concordium_cis2_security::RecoverParam;
+pub use concordium_cis2_security::{
+    Document, PendingRecovery, ProposalUpdate, RecoverParam, Recovery, RecoveryUpdate,
+};
 
 #[derive(Serialize, SchemaType)]
 pub struct InitParam {